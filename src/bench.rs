@@ -0,0 +1,106 @@
+//! `task_manager_cli bench`: times the core task queries against a throwaway
+//! in-memory database so query regressions show up as features are added.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::db::in_memory_pool;
+use crate::db::task_manager::operations::DbOperations;
+use crate::error::AppError;
+
+const BENCH_TOPIC: &str = "Bench";
+
+/// Per-call timings for one benchmarked operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpTimings {
+    pub name: &'static str,
+    pub samples: Vec<Duration>,
+}
+
+impl OpTimings {
+    fn new(name: &'static str, capacity: usize) -> Self {
+        Self {
+            name,
+            samples: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn time<T>(&mut self, op: impl FnOnce() -> Result<T, AppError>) -> Result<T, AppError> {
+        let started = Instant::now();
+        let value = op()?;
+        self.samples.push(started.elapsed());
+        Ok(value)
+    }
+
+    pub fn total(&self) -> Duration {
+        self.samples.iter().sum()
+    }
+
+    pub fn average(&self) -> Duration {
+        match u32::try_from(self.samples.len()) {
+            Ok(0) | Err(_) => Duration::ZERO,
+            Ok(count) => self.total() / count,
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples.iter().min().copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.iter().max().copied().unwrap_or_default()
+    }
+}
+
+/// One table row: name, total, average, min and max, in milliseconds.
+impl fmt::Display for OpTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        write!(
+            f,
+            "{:<10} {:>12.3} {:>12.3} {:>12.3} {:>12.3}",
+            self.name,
+            ms(self.total()),
+            ms(self.average()),
+            ms(self.min()),
+            ms(self.max())
+        )
+    }
+}
+
+fn in_memory_db() -> Result<DbOperations, AppError> {
+    Ok(DbOperations::new(
+        in_memory_pool()?,
+        crate::log_config::get_logger_or_discard(),
+    ))
+}
+
+/// Inserts `n` tasks, then loads the topic, toggles and deletes once per task.
+pub fn run_bench(n: usize) -> Result<Vec<OpTimings>, AppError> {
+    let db_ops = in_memory_db()?;
+    let topic = db_ops.add_topic(BENCH_TOPIC, "")?;
+
+    let mut insert = OpTimings::new("insert", n);
+    let mut task_ids = Vec::with_capacity(n);
+    for i in 0..n {
+        let task = insert.time(|| db_ops.add_task(topic.id, &format!("Task {i}"), ""))?;
+        task_ids.push(task.id);
+    }
+
+    let mut load = OpTimings::new("load", n);
+    for _ in 0..n {
+        load.time(|| db_ops.load_tasks(&topic))?;
+    }
+
+    let mut toggle = OpTimings::new("toggle", n);
+    for &id in &task_ids {
+        toggle.time(|| db_ops.toggle_task_completion(id))?;
+    }
+
+    let mut delete = OpTimings::new("delete", n);
+    for &id in &task_ids {
+        delete.time(|| db_ops.delete_task(id))?;
+    }
+
+    Ok(vec![insert, load, toggle, delete])
+}
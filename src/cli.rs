@@ -0,0 +1,1169 @@
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, IsTerminal, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use chrono::{Local, NaiveDate};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+
+use crate::db::task_manager::models::{MergeStrategy, TaskSource, TaskUpdate, TopicOrder};
+use crate::db::task_manager::operations::DbOperations;
+use crate::doctor::CheckStatus;
+use crate::error::AppError;
+use crate::export::{self, ImportFormat};
+use crate::task_manager::app::App;
+use crate::utils::format_bytes;
+
+/// Terminal task manager. Runs the TUI when no subcommand is given.
+#[derive(Debug, Parser)]
+#[command(name = "task_manager_cli", version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Open the database even if another instance appears to be running
+    #[arg(long, global = true)]
+    pub force: bool,
+    /// Keep logs off the terminal and print only each command's result or error
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Launch the interactive terminal UI (the default)
+    Tui,
+    /// Add a task to a topic, creating the topic if needed
+    Add {
+        /// Task name
+        name: String,
+        #[arg(short, long, default_value = "Default")]
+        topic: String,
+        #[arg(short, long, default_value = "")]
+        description: String,
+        /// Due date as YYYY-MM-DD
+        #[arg(long)]
+        due: Option<String>,
+    },
+    /// List tasks, optionally limited to one topic
+    List {
+        #[arg(short, long)]
+        topic: Option<String>,
+        #[arg(short, long, value_enum, default_value_t = ListFormat::Text)]
+        format: ListFormat,
+    },
+    /// List topics with their ids and descriptions
+    ListTopics {
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// Mark a task as completed
+    Complete { id: i32 },
+    /// Delete a task
+    Delete { id: i32 },
+    /// Export all tasks as JSON or CSV, to a file or stdout
+    Export {
+        /// Defaults to the file extension, or JSON when writing to stdout
+        #[arg(short, long, value_enum)]
+        format: Option<FileFormat>,
+        path: Option<PathBuf>,
+        /// Keep running and re-export whenever the database changes
+        #[arg(long, requires = "path")]
+        watch: bool,
+    },
+    /// Import tasks from a .json or .csv file, a Todoist JSON export or an Org file
+    Import {
+        path: PathBuf,
+        /// Defaults to the file extension
+        #[arg(short, long, value_enum)]
+        format: Option<ImportFileFormat>,
+        /// Topic that Todoist or Org tasks are imported into [default: Default];
+        /// JSON and CSV rows name their own topic
+        #[arg(short, long)]
+        topic: Option<String>,
+        /// Validate and print the summary without writing
+        #[arg(long)]
+        dry_run: bool,
+        /// What to do with JSON or CSV tasks whose name already exists in their topic
+        #[arg(long, value_enum, default_value_t = ImportMerge::Duplicate)]
+        merge: ImportMerge,
+    },
+    /// Write a Markdown digest of today's created, completed, overdue and due tasks
+    Digest { output: PathBuf },
+    /// Write tasks with a due date to an iCalendar (.ics) file as to-dos
+    ExportIcs { output: PathBuf },
+    /// Print task, topic and completion statistics for the whole database
+    Stats {
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// Print tasks as they are created, updated or deleted until Ctrl+C
+    WatchDb {
+        /// Milliseconds between polls of the database
+        #[arg(long, default_value_t = crate::watch::DEFAULT_INTERVAL_MS)]
+        interval: u64,
+    },
+    /// Run task commands typed at a prompt; `help` lists them
+    Repl,
+    /// Run migrations and verify the database is healthy
+    Check,
+    /// Diagnose common problems with the database, log directory and config
+    Doctor,
+    /// Apply pending migrations and exit without starting the UI
+    MigrateOnly,
+    /// Vacuum and analyze the database, reporting the size before and after
+    Maintain,
+    /// Time inserts, loads, toggles and deletes against an in-memory database
+    Bench {
+        /// Number of tasks to insert
+        #[arg(long, default_value_t = 1000)]
+        n: usize,
+        /// Exit with status 1 when the average insert takes longer than this
+        #[arg(long)]
+        max_insert_ms: Option<f64>,
+    },
+    /// Print a completion script for bash, zsh, fish or PowerShell
+    Completions {
+        #[arg(value_enum, required_unless_present = "list_topics")]
+        shell: Option<Shell>,
+        /// Print existing topic names, one per line (used by the scripts)
+        #[arg(long, conflicts_with = "shell")]
+        list_topics: bool,
+    },
+    /// Delete the task database and recreate it from the migrations
+    ResetDb {
+        /// Must be exactly `--yes`; values such as `--yes=true` are refused
+        #[arg(
+            long = "yes",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "yes"
+        )]
+        confirm: Option<String>,
+    },
+}
+
+impl Command {
+    /// Whether the command opens the task database or the TUI, and so stops
+    /// on the startup problems `config::preflight` finds. `doctor` reports
+    /// them itself; completions and `bench` never touch the database.
+    pub fn needs_preflight(&self) -> bool {
+        !matches!(
+            self,
+            Command::Doctor | Command::Completions { .. } | Command::Bench { .. }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListFormat {
+    Text,
+    Json,
+}
+
+/// Output of `list-topics` and `stats`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FileFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    #[value(name = "powershell")]
+    PowerShell,
+}
+
+impl From<Shell> for clap_complete::Shell {
+    fn from(shell: Shell) -> Self {
+        match shell {
+            Shell::Bash => clap_complete::Shell::Bash,
+            Shell::Zsh => clap_complete::Shell::Zsh,
+            Shell::Fish => clap_complete::Shell::Fish,
+            Shell::PowerShell => clap_complete::Shell::PowerShell,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportFileFormat {
+    Json,
+    Csv,
+    Todoist,
+    Org,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportMerge {
+    /// Keep the existing task
+    Skip,
+    /// Update the existing task's description and flags
+    Overwrite,
+    /// Always insert a new task
+    Duplicate,
+}
+
+impl From<ImportMerge> for MergeStrategy {
+    fn from(merge: ImportMerge) -> Self {
+        match merge {
+            ImportMerge::Skip => MergeStrategy::Skip,
+            ImportMerge::Overwrite => MergeStrategy::Overwrite,
+            ImportMerge::Duplicate => MergeStrategy::Duplicate,
+        }
+    }
+}
+
+impl From<FileFormat> for ImportFormat {
+    fn from(format: FileFormat) -> Self {
+        match format {
+            FileFormat::Json => ImportFormat::Json,
+            FileFormat::Csv => ImportFormat::Csv,
+        }
+    }
+}
+
+/// Opens the task database, running migrations and seeding built-in topics.
+/// The returned `App` holds the instance lock for as long as it lives.
+fn open_db(db_path: &str) -> Result<App, AppError> {
+    App::new(db_path)
+}
+
+/// The database for a dry-run import, opened without creating, migrating or
+/// seeding it: read-only when the file exists, otherwise an empty in-memory
+/// copy of the schema, so every topic counts as new.
+fn open_db_for_dry_run(db_path: &str) -> Result<DbOperations, AppError> {
+    let pool = if Path::new(db_path).exists() {
+        crate::db::read_only_pool(db_path)?
+    } else {
+        crate::db::in_memory_pool()?
+    };
+    Ok(DbOperations::new(
+        pool,
+        crate::log_config::get_logger_or_discard(),
+    ))
+}
+
+/// Runs a subcommand and returns the process exit code. With `quiet`, only
+/// the command's result (or error) is printed.
+pub fn run_command(command: Command, quiet: bool) -> Result<i32, AppError> {
+    let db_path = crate::task_manager::task_db_path()?;
+    let mut out = io::stdout();
+    match command {
+        Command::Tui => {
+            crate::run(quiet)?;
+            Ok(0)
+        }
+        Command::Add {
+            name,
+            topic,
+            description,
+            due,
+        } => add(
+            &open_db(&db_path)?.db_ops,
+            &mut out,
+            &topic,
+            &name,
+            &description,
+            due.as_deref(),
+        ),
+        Command::List { topic, format } => list(
+            &open_db(&db_path)?.db_ops,
+            &mut out,
+            topic.as_deref(),
+            format,
+        ),
+        Command::ListTopics { format } => match open_db(&db_path) {
+            Ok(app) => topics(&app.db_ops, &mut out, format),
+            Err(e @ AppError::AlreadyRunning) => Err(e),
+            Err(e) => {
+                eprintln!("error: cannot open database {db_path}: {e}");
+                Ok(1)
+            }
+        },
+        Command::Complete { id } => complete(&open_db(&db_path)?.db_ops, &mut out, id),
+        Command::Delete { id } => delete(&open_db(&db_path)?.db_ops, &mut out, id),
+        Command::Export {
+            format,
+            path: Some(path),
+            watch: true,
+        } => {
+            // Only borrow the connection pool: dropping the App releases the
+            // instance lock so the TUI can keep using the database.
+            let db_ops = open_db(&db_path)?.db_ops.clone();
+            let stop = Arc::new(AtomicBool::new(false));
+            #[cfg(unix)]
+            signal_hook::flag::register(signal_hook::consts::SIGINT, Arc::clone(&stop))?;
+            export_watch(
+                &db_ops,
+                &mut out,
+                format,
+                &path,
+                Path::new(&db_path),
+                &stop,
+                quiet,
+            )
+        }
+        Command::Export { format, path, .. } => export_tasks(
+            &open_db(&db_path)?.db_ops,
+            &mut out,
+            format,
+            path.as_deref(),
+        ),
+        Command::Import {
+            path,
+            format: Some(ImportFileFormat::Todoist),
+            topic,
+            dry_run,
+            ..
+        } if dry_run => import_todoist(
+            &open_db_for_dry_run(&db_path)?,
+            &mut out,
+            &path,
+            topic.as_deref().unwrap_or("Default"),
+            true,
+        ),
+        Command::Import {
+            path,
+            format: Some(ImportFileFormat::Todoist),
+            topic,
+            ..
+        } => import_todoist(
+            &open_db(&db_path)?.db_ops,
+            &mut out,
+            &path,
+            topic.as_deref().unwrap_or("Default"),
+            false,
+        ),
+        Command::Import {
+            path,
+            format: Some(ImportFileFormat::Org),
+            topic,
+            dry_run,
+            ..
+        } if dry_run => import_org(
+            &open_db_for_dry_run(&db_path)?,
+            &mut out,
+            &path,
+            topic.as_deref().unwrap_or("Default"),
+            true,
+        ),
+        Command::Import {
+            path,
+            format: Some(ImportFileFormat::Org),
+            topic,
+            ..
+        } => import_org(
+            &open_db(&db_path)?.db_ops,
+            &mut out,
+            &path,
+            topic.as_deref().unwrap_or("Default"),
+            false,
+        ),
+        Command::Import {
+            path,
+            format,
+            topic,
+            dry_run,
+            merge,
+        } => import(
+            &db_path,
+            &mut out,
+            &path,
+            format,
+            topic.as_deref(),
+            merge.into(),
+            dry_run,
+        ),
+        Command::Digest { output } => digest(
+            &open_db(&db_path)?.db_ops,
+            &mut out,
+            &output,
+            chrono::Local::now().date_naive(),
+        ),
+        Command::ExportIcs { output } => export_ics(&open_db(&db_path)?.db_ops, &mut out, &output),
+        Command::Stats { format } => stats(&open_db(&db_path)?.db_ops, &mut out, format),
+        Command::WatchDb { interval } => {
+            // As with `export --watch`, drop the App so the TUI can still
+            // open the database while this runs.
+            let db_ops = open_db(&db_path)?.db_ops.clone();
+            let stop = Arc::new(AtomicBool::new(false));
+            #[cfg(unix)]
+            signal_hook::flag::register(signal_hook::consts::SIGINT, Arc::clone(&stop))?;
+            crate::watch::run(&db_ops, &mut out, Duration::from_millis(interval), &stop)
+        }
+        Command::Repl => {
+            // Held for the whole session, like the TUI's instance lock.
+            let app = open_db(&db_path)?;
+            let stdin = io::stdin();
+            if stdin.is_terminal() {
+                crate::repl::run_interactive(&app.db_ops, &mut out)
+            } else {
+                crate::repl::run(&app.db_ops, stdin.lock(), &mut out, false)
+            }
+        }
+        Command::Check => check(&open_db(&db_path)?.db_ops, &mut out, &db_path),
+        Command::Completions {
+            list_topics: true, ..
+        } => list_topics(&db_path, &mut out),
+        Command::Completions { shell, .. } => match shell {
+            Some(shell) => completions(shell, &mut out),
+            None => Ok(0),
+        },
+        Command::ResetDb { confirm } => reset_db(&db_path, &mut out, confirm.as_deref()),
+        Command::Doctor => doctor(Path::new(&db_path), &mut out),
+        Command::MigrateOnly => migrate_only(&db_path, &mut out, quiet),
+        Command::Maintain => maintain(&open_db(&db_path)?.db_ops, &mut out, &db_path),
+        Command::Bench { n, max_insert_ms } => bench(&mut out, n, max_insert_ms),
+    }
+}
+
+pub fn add(
+    db_ops: &DbOperations,
+    out: &mut impl Write,
+    topic_name: &str,
+    name: &str,
+    description: &str,
+    due: Option<&str>,
+) -> Result<i32, AppError> {
+    let (topic_name, name) = (topic_name.trim(), name.trim());
+    if name.is_empty() {
+        return Err(
+            io::Error::new(io::ErrorKind::InvalidInput, "Task name cannot be empty").into(),
+        );
+    }
+    let due = due.map(str::trim).filter(|due| !due.is_empty());
+    if let Some(due) = due {
+        if chrono::NaiveDate::parse_from_str(due, crate::utils::DUE_DATE_FORMAT).is_err() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Due date must be YYYY-MM-DD, got '{due}'"),
+            )
+            .into());
+        }
+    }
+    if topic_name != "Default" && db_ops.is_special_topic(topic_name) {
+        return Err(AppError::ProtectedTopic(topic_name.to_string()));
+    }
+
+    let topic = db_ops.get_or_create_topic(topic_name)?;
+    let mut task = db_ops.add_task_from(topic.id, name, description.trim(), TaskSource::Cli)?;
+    if due.is_some() {
+        task = db_ops.set_task_due_date(task.id, due)?;
+    }
+    writeln!(
+        out,
+        "Added task {} to {}: {}",
+        task.id, topic.name, task.name
+    )?;
+    Ok(0)
+}
+
+/// Validates the file before opening the database so a bad file changes
+/// nothing. A dry run never writes to the database, nor creates it.
+/// `topic` is refused: JSON and CSV rows name their own topic.
+pub fn import(
+    db_path: &str,
+    out: &mut impl Write,
+    path: &Path,
+    format: Option<ImportFileFormat>,
+    topic: Option<&str>,
+    strategy: MergeStrategy,
+    dry_run: bool,
+) -> Result<i32, AppError> {
+    if topic.is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--topic only applies to --format todoist or org; JSON and CSV rows name their topic",
+        )
+        .into());
+    }
+    let (records, source) = match format {
+        Some(ImportFileFormat::Json) => (
+            export::parse_json(&fs::read_to_string(path)?)?,
+            TaskSource::ImportJson,
+        ),
+        Some(ImportFileFormat::Csv) => (
+            export::parse_csv(&fs::read_to_string(path)?)?,
+            TaskSource::ImportCsv,
+        ),
+        Some(ImportFileFormat::Todoist | ImportFileFormat::Org) | None => (
+            export::parse_import_file(path)?,
+            ImportFormat::from_path(path)
+                .map_or(TaskSource::ImportJson, |format| format.task_source()),
+        ),
+    };
+    let errors = export::validate_import(&records);
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("{error}");
+        }
+        return Ok(2);
+    }
+
+    let summary = if dry_run {
+        export::import_records(
+            &open_db_for_dry_run(db_path)?,
+            &records,
+            strategy,
+            source,
+            true,
+        )?
+    } else {
+        export::import_records(&open_db(db_path)?.db_ops, &records, strategy, source, false)?
+    };
+    write!(out, "{summary}")?;
+    Ok(0)
+}
+
+/// Imports a Todoist export into `topic_name`, creating the topic if needed
+/// (unless this is a dry run).
+pub fn import_todoist(
+    db_ops: &DbOperations,
+    out: &mut impl Write,
+    path: &Path,
+    topic_name: &str,
+    dry_run: bool,
+) -> Result<i32, AppError> {
+    let topic_name = topic_name.trim();
+    if topic_name != "Default" && db_ops.is_special_topic(topic_name) {
+        return Err(AppError::ProtectedTopic(topic_name.to_string()));
+    }
+    // A dry run only looks the topic up; a real import creates it if needed.
+    let existing = if dry_run {
+        db_ops
+            .load_topics(TopicOrder::Manual)?
+            .into_iter()
+            .find(|topic| topic.name.eq_ignore_ascii_case(topic_name))
+    } else {
+        Some(db_ops.get_or_create_topic(topic_name)?)
+    };
+    let summary = match existing {
+        Some(topic) => export::todoist::import_todoist(db_ops, path, topic.id, dry_run)?,
+        None => {
+            let todoist = export::todoist::parse_todoist(&fs::read_to_string(path)?)?;
+            let (tasks, skipped) = export::todoist::plan_todoist_import(&todoist, HashSet::new());
+            writeln!(out, "Would create topic {topic_name}")?;
+            export::todoist::TodoistImportSummary {
+                dry_run,
+                inserted: tasks.len(),
+                skipped,
+            }
+        }
+    };
+    write!(out, "{summary}")?;
+    Ok(0)
+}
+
+/// Imports an Org file into `topic_name`, creating the topic if needed
+/// (unless this is a dry run).
+pub fn import_org(
+    db_ops: &DbOperations,
+    out: &mut impl Write,
+    path: &Path,
+    topic_name: &str,
+    dry_run: bool,
+) -> Result<i32, AppError> {
+    let topic_name = topic_name.trim();
+    if topic_name != "Default" && db_ops.is_special_topic(topic_name) {
+        return Err(AppError::ProtectedTopic(topic_name.to_string()));
+    }
+    // A dry run only looks the topic up; a real import creates it if needed.
+    let existing = if dry_run {
+        db_ops
+            .load_topics(TopicOrder::Manual)?
+            .into_iter()
+            .find(|topic| topic.name.eq_ignore_ascii_case(topic_name))
+    } else {
+        Some(db_ops.get_or_create_topic(topic_name)?)
+    };
+    let summary = match existing {
+        Some(topic) => export::org::import_org(db_ops, path, topic.id, dry_run)?,
+        None => {
+            let org_tasks = export::org::parse_org(&fs::read_to_string(path)?);
+            let (tasks, skipped) = export::org::plan_org_import(&org_tasks, HashSet::new());
+            writeln!(out, "Would create topic {topic_name}")?;
+            export::org::OrgImportSummary {
+                dry_run,
+                inserted: tasks.len(),
+                subtasks: tasks.iter().map(|task| task.subtasks.len()).sum(),
+                skipped,
+            }
+        }
+    };
+    write!(out, "{summary}")?;
+    Ok(0)
+}
+
+pub fn list(
+    db_ops: &DbOperations,
+    out: &mut impl Write,
+    topic_name: Option<&str>,
+    format: ListFormat,
+) -> Result<i32, AppError> {
+    let topics = db_ops.load_topics(TopicOrder::Manual)?;
+    let topic_name = topic_name.unwrap_or("Default");
+    let Some(topic) = topics
+        .iter()
+        .find(|topic| topic.name.eq_ignore_ascii_case(topic_name))
+    else {
+        return Err(AppError::UnknownTopic(topic_name.to_string()));
+    };
+    let tasks = db_ops.load_tasks(topic)?;
+    let summary = db_ops.topic_summary(Some(topic.id))?;
+
+    match format {
+        ListFormat::Json => {
+            let json = serde_json::json!({
+                "meta": {
+                    "topic": topic.name,
+                    "total": summary.total,
+                    "completed": summary.completed,
+                    "pending": summary.pending(),
+                    "oldest_pending": summary.oldest_pending,
+                },
+                "tasks": tasks,
+            });
+            writeln!(out, "{}", serde_json::to_string_pretty(&json)?)?
+        }
+        ListFormat::Text => {
+            let header = format!(
+                "{}: {} task(s), {} completed, {} pending, oldest pending {}",
+                topic.name,
+                summary.total,
+                summary.completed,
+                summary.pending(),
+                summary.oldest_pending.as_deref().unwrap_or("-")
+            );
+            writeln!(out, "{header}")?;
+            writeln!(out, "{}", "─".repeat(header.chars().count()))?;
+            for task in &tasks {
+                let task_topic = topics
+                    .iter()
+                    .find(|topic| topic.id == task.topic_id)
+                    .map(|topic| topic.name.as_str())
+                    .unwrap_or("?");
+                writeln!(
+                    out,
+                    "{:>4}  [{}]{} {} ({})",
+                    task.id,
+                    if task.completed { "x" } else { " " },
+                    if task.favourite { " ★" } else { "" },
+                    task.name,
+                    task_topic
+                )?;
+            }
+        }
+    }
+    Ok(0)
+}
+
+/// Longest description shown in the `list-topics` table, `...` included.
+const TOPIC_DESCRIPTION_WIDTH: usize = 40;
+
+fn truncate_description(description: &str) -> String {
+    if description.chars().count() <= TOPIC_DESCRIPTION_WIDTH {
+        return description.to_string();
+    }
+    let kept: String = description
+        .chars()
+        .take(TOPIC_DESCRIPTION_WIDTH - 3)
+        .collect();
+    format!("{kept}...")
+}
+
+/// Prints every topic as a padded table, `{"topics": [...]}` JSON or CSV.
+pub fn topics(
+    db_ops: &DbOperations,
+    out: &mut impl Write,
+    format: OutputFormat,
+) -> Result<i32, AppError> {
+    let topics = db_ops.load_topics(TopicOrder::Manual)?;
+
+    match format {
+        OutputFormat::Json => writeln!(
+            out,
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({ "topics": topics }))?
+        )?,
+        OutputFormat::Csv => {
+            writeln!(out, "id,name,description")?;
+            for topic in &topics {
+                writeln!(
+                    out,
+                    "{},{},{}",
+                    topic.id,
+                    export::csv_field(&topic.name),
+                    export::csv_field(&topic.description)
+                )?;
+            }
+        }
+        OutputFormat::Table => {
+            let id_width = topics
+                .iter()
+                .map(|topic| topic.id.to_string().len())
+                .chain(["ID".len()])
+                .max()
+                .unwrap_or_default();
+            let name_width = topics
+                .iter()
+                .map(|topic| topic.name.chars().count())
+                .chain(["Name".len()])
+                .max()
+                .unwrap_or_default();
+            writeln!(
+                out,
+                "{:>id_width$}  {:<name_width$}  Description",
+                "ID", "Name"
+            )?;
+            for topic in &topics {
+                let line = format!(
+                    "{:>id_width$}  {:<name_width$}  {}",
+                    topic.id,
+                    topic.name,
+                    truncate_description(&topic.description)
+                );
+                writeln!(out, "{}", line.trim_end())?;
+            }
+        }
+    }
+    Ok(0)
+}
+
+pub fn complete(db_ops: &DbOperations, out: &mut impl Write, id: i32) -> Result<i32, AppError> {
+    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let task = db_ops.update_task(
+        id,
+        TaskUpdate {
+            name: None,
+            description: None,
+            completed: Some(true),
+            favourite: None,
+            pinned: None,
+            updated_at: &now,
+        },
+    )?;
+    writeln!(out, "Completed task {}: {}", task.id, task.name)?;
+    Ok(0)
+}
+
+pub fn delete(db_ops: &DbOperations, out: &mut impl Write, id: i32) -> Result<i32, AppError> {
+    if db_ops.delete_task(id)? == 0 {
+        return Err(AppError::TaskNotFound(id));
+    }
+    writeln!(out, "Deleted task {id}")?;
+    Ok(0)
+}
+
+/// The export file contents and the number of tasks in it.
+fn export_content(
+    db_ops: &DbOperations,
+    format: Option<FileFormat>,
+    path: Option<&Path>,
+) -> Result<(String, usize), AppError> {
+    let format = format
+        .map(ImportFormat::from)
+        .or_else(|| path.and_then(ImportFormat::from_path))
+        .unwrap_or(ImportFormat::Json);
+    let records = export::export_records(db_ops)?;
+    let content = match format {
+        ImportFormat::Json => export::to_json(&records)?,
+        ImportFormat::Csv => export::to_csv(&records),
+    };
+    Ok((content, records.len()))
+}
+
+pub fn export_tasks(
+    db_ops: &DbOperations,
+    out: &mut impl Write,
+    format: Option<FileFormat>,
+    path: Option<&Path>,
+) -> Result<i32, AppError> {
+    let (content, count) = export_content(db_ops, format, path)?;
+
+    match path {
+        Some(path) => {
+            fs::write(path, content)?;
+            writeln!(out, "Exported {count} task(s) to {}", path.display())?;
+        }
+        None => write!(out, "{content}")?,
+    }
+    Ok(0)
+}
+
+/// How long to wait after a database change before re-exporting, so a burst
+/// of writes produces one export.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Exports to `path`, then re-exports each time `db_path` is modified until
+/// `stop` is set (by SIGINT from the command line). `quiet` drops the
+/// per-re-export progress lines.
+pub fn export_watch(
+    db_ops: &DbOperations,
+    out: &mut impl Write,
+    format: Option<FileFormat>,
+    path: &Path,
+    db_path: &Path,
+    stop: &AtomicBool,
+    quiet: bool,
+) -> Result<i32, AppError> {
+    export_tasks(db_ops, out, format, Some(path))?;
+
+    let (sender, events) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    notify::Watcher::watch(&mut watcher, db_path, notify::RecursiveMode::NonRecursive)?;
+
+    while !stop.load(Ordering::Relaxed) {
+        match events.recv_timeout(WATCH_DEBOUNCE) {
+            Ok(Ok(event)) if matches!(event.kind, notify::EventKind::Modify(_)) => {
+                thread::sleep(WATCH_DEBOUNCE);
+                while events.try_recv().is_ok() {}
+                let (content, _) = export_content(db_ops, format, Some(path))?;
+                fs::write(path, content)?;
+                if !quiet {
+                    writeln!(
+                        out,
+                        "[{}] Re-exported to {}",
+                        Local::now().format("%Y-%m-%d %H:%M:%S"),
+                        path.display()
+                    )?;
+                }
+            }
+            Ok(Ok(_)) | Err(RecvTimeoutError::Timeout) => {}
+            Ok(Err(e)) => return Err(e.into()),
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    Ok(0)
+}
+
+pub fn digest(
+    db_ops: &DbOperations,
+    out: &mut impl Write,
+    output: &Path,
+    today: NaiveDate,
+) -> Result<i32, AppError> {
+    fs::write(output, export::generate_digest(db_ops, today)?)?;
+    writeln!(out, "Wrote digest for {today} to {}", output.display())?;
+    Ok(0)
+}
+
+pub fn export_ics(
+    db_ops: &DbOperations,
+    out: &mut impl Write,
+    output: &Path,
+) -> Result<i32, AppError> {
+    let tasks = db_ops.load_tasks_with_due_date()?;
+    fs::write(output, export::tasks_to_ics(&tasks))?;
+    writeln!(
+        out,
+        "Exported {} task(s) with due dates to {}",
+        tasks.len(),
+        output.display()
+    )?;
+    Ok(0)
+}
+
+/// Prints the whole-database statistics as a two-column table, JSON with
+/// ISO-8601 timestamps, or `statistic,value` CSV.
+pub fn stats(
+    db_ops: &DbOperations,
+    out: &mut impl Write,
+    format: OutputFormat,
+) -> Result<i32, AppError> {
+    let stats = db_ops.compute_global_stats()?;
+    // One decimal place is plenty for both averages, in every format.
+    let round = |value: f64| (value * 10.0).round() / 10.0;
+    let completion = round(stats.completion_percentage());
+    let average = round(stats.average_tasks_per_topic());
+
+    if format == OutputFormat::Json {
+        let json = serde_json::json!({
+            "total_tasks": stats.total_tasks,
+            "total_topics": stats.total_topics,
+            "completion_percentage": completion,
+            "overdue_tasks": stats.overdue_tasks,
+            "average_tasks_per_topic": average,
+            "most_productive_day": stats.most_productive_day.as_ref().map(|(day, count)| {
+                serde_json::json!({ "date": day, "tasks_created": count })
+            }),
+            "most_recently_updated_topic": stats.most_recent_topic.as_ref().map(|(name, at)| {
+                serde_json::json!({ "name": name, "updated_at": crate::utils::to_iso8601(at) })
+            }),
+        });
+        writeln!(out, "{}", serde_json::to_string_pretty(&json)?)?;
+        return Ok(0);
+    }
+
+    let rows = [
+        ("Total tasks", stats.total_tasks.to_string()),
+        ("Total topics", stats.total_topics.to_string()),
+        ("Completed", format!("{completion:.1}%")),
+        ("Overdue tasks", stats.overdue_tasks.to_string()),
+        ("Average tasks per topic", format!("{average:.1}")),
+        (
+            "Most productive day",
+            stats.most_productive_day.map_or_else(
+                || "-".to_string(),
+                |(day, count)| format!("{day} ({count} created)"),
+            ),
+        ),
+        (
+            "Most recently updated topic",
+            stats
+                .most_recent_topic
+                .map_or_else(|| "-".to_string(), |(name, at)| format!("{name} ({at})")),
+        ),
+    ];
+    if format == OutputFormat::Csv {
+        writeln!(out, "statistic,value")?;
+        for (label, value) in &rows {
+            writeln!(
+                out,
+                "{},{}",
+                export::csv_field(label),
+                export::csv_field(value)
+            )?;
+        }
+    } else {
+        let width = rows
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or_default();
+        for (label, value) in &rows {
+            writeln!(out, "{label:<width$}  {value}")?;
+        }
+    }
+    Ok(0)
+}
+
+pub fn check(db_ops: &DbOperations, out: &mut impl Write, db_path: &str) -> Result<i32, AppError> {
+    let topics = db_ops.load_topics(TopicOrder::Manual)?;
+    let summary = db_ops.topic_summary(None)?;
+    let problems = db_ops.integrity_check()?;
+    let healthy = problems.iter().all(|line| line == "ok");
+
+    writeln!(out, "Database: {db_path}")?;
+    match db_ops.pending_migration_count()? {
+        0 => writeln!(out, "Migrations: up to date")?,
+        pending => writeln!(out, "Migrations: {pending} pending")?,
+    }
+    writeln!(out, "Topics: {}", topics.len())?;
+    writeln!(
+        out,
+        "Tasks: {} ({} pending)",
+        summary.total,
+        summary.pending()
+    )?;
+    if healthy {
+        writeln!(out, "Integrity: ok")?;
+        Ok(0)
+    } else {
+        for problem in &problems {
+            writeln!(out, "Integrity: {problem}")?;
+        }
+        Ok(2)
+    }
+}
+
+/// Prints one line per self-check and a tally; exits 1 if any check failed.
+pub fn doctor(db_path: &Path, out: &mut impl Write) -> Result<i32, AppError> {
+    let results = crate::doctor::run_checks(db_path);
+    for result in &results {
+        writeln!(out, "{result}")?;
+    }
+    let count = |status: CheckStatus| {
+        results
+            .iter()
+            .filter(|result| result.status == status)
+            .count()
+    };
+    let failed = count(CheckStatus::Fail);
+    writeln!(
+        out,
+        "\n{} ok, {} warning(s), {} failed",
+        count(CheckStatus::Ok),
+        count(CheckStatus::Warn),
+        failed
+    )?;
+    Ok(if failed > 0 { 1 } else { 0 })
+}
+
+pub fn maintain(
+    db_ops: &DbOperations,
+    out: &mut impl Write,
+    db_path: &str,
+) -> Result<i32, AppError> {
+    let before = fs::metadata(db_path)?.len();
+    db_ops.vacuum()?;
+    db_ops.analyze()?;
+    let after = fs::metadata(db_path)?.len();
+
+    writeln!(out, "Database: {db_path}")?;
+    writeln!(out, "Size before: {}", format_bytes(before))?;
+    writeln!(out, "Size after: {}", format_bytes(after))?;
+    writeln!(out, "Freed: {}", format_bytes(before.saturating_sub(after)))?;
+    Ok(0)
+}
+
+pub fn bench(out: &mut impl Write, n: usize, max_insert_ms: Option<f64>) -> Result<i32, AppError> {
+    let timings = crate::bench::run_bench(n)?;
+    writeln!(
+        out,
+        "{:<10} {:>12} {:>12} {:>12} {:>12}",
+        "operation", "total (ms)", "avg (ms)", "min (ms)", "max (ms)"
+    )?;
+    for op in &timings {
+        writeln!(out, "{op}")?;
+    }
+
+    let Some(limit) = max_insert_ms else {
+        return Ok(0);
+    };
+    let insert_ms = timings
+        .iter()
+        .find(|op| op.name == "insert")
+        .map_or(0.0, |op| op.average().as_secs_f64() * 1000.0);
+    if insert_ms > limit {
+        writeln!(
+            out,
+            "\nAverage insert took {insert_ms:.3} ms, over the {limit} ms limit"
+        )?;
+        return Ok(1);
+    }
+    Ok(0)
+}
+
+const BIN_NAME: &str = "task_manager_cli";
+
+/// Completes `--topic`/`-t` values from the database, then defers to the
+/// generated `_task_manager_cli` function.
+const BASH_TOPIC_COMPLETION: &str = r#"
+_task_manager_cli_with_topics() {
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+    if [[ "$prev" == "--topic" || "$prev" == "-t" ]]; then
+        local IFS=$'\n'
+        COMPREPLY=( $(compgen -W "$(task_manager_cli completions --list-topics 2>/dev/null)" -- "${COMP_WORDS[COMP_CWORD]}") )
+        return 0
+    fi
+    _task_manager_cli "$@"
+}
+complete -F _task_manager_cli_with_topics -o bashdefault -o default task_manager_cli
+"#;
+
+const ZSH_TOPIC_COMPLETION: &str = r#"
+_task_manager_cli_with_topics() {
+    if [[ "${words[CURRENT-1]}" == (--topic|-t) ]]; then
+        local -a topics
+        topics=("${(@f)$(task_manager_cli completions --list-topics 2>/dev/null)}")
+        compadd -a topics
+        return
+    fi
+    _task_manager_cli "$@"
+}
+compdef _task_manager_cli_with_topics task_manager_cli
+"#;
+
+const FISH_TOPIC_COMPLETION: &str = r#"
+complete -c task_manager_cli -s t -l topic -r -f -a "(task_manager_cli completions --list-topics 2>/dev/null)"
+"#;
+
+/// Writes the completion script for `shell`. Bash, zsh and fish also
+/// complete `--topic` values by calling `completions --list-topics`.
+pub fn completions(shell: Shell, out: &mut impl Write) -> Result<i32, AppError> {
+    clap_complete::generate(
+        clap_complete::Shell::from(shell),
+        &mut Cli::command(),
+        BIN_NAME,
+        out,
+    );
+    let topic_completion = match shell {
+        Shell::Bash => BASH_TOPIC_COMPLETION,
+        Shell::Zsh => ZSH_TOPIC_COMPLETION,
+        Shell::Fish => FISH_TOPIC_COMPLETION,
+        Shell::PowerShell => "",
+    };
+    write!(out, "{topic_completion}")?;
+    Ok(0)
+}
+
+/// Prints topic names one per line for shell completion. Reads the database
+/// directly, without migrations or the instance lock, so it works while the
+/// TUI is open and prints nothing when there is no database yet.
+pub fn list_topics(db_path: &str, out: &mut impl Write) -> Result<i32, AppError> {
+    if !Path::new(db_path).exists() {
+        return Ok(0);
+    }
+    let pool = crate::db::establish_connection_pool(&format!("sqlite://{db_path}"))?;
+    let db_ops = DbOperations::new(pool, crate::log_config::get_logger_or_discard());
+    for topic in db_ops.load_topics(TopicOrder::Manual)? {
+        writeln!(out, "{}", topic.name)?;
+    }
+    Ok(0)
+}
+
+/// Applies pending migrations, listing each one unless `quiet`; exits 1 if
+/// they fail.
+pub fn migrate_only(db_path: &str, out: &mut impl Write, quiet: bool) -> Result<i32, AppError> {
+    let _lock = crate::task_manager::app::lock_database(Path::new(db_path))?;
+    let applied = crate::db::establish_connection_pool(&format!("sqlite://{db_path}"))
+        .and_then(|pool| Ok(pool.get()?))
+        .and_then(|mut conn| crate::db::run_migrations(&mut conn));
+    match applied {
+        Ok(applied) => {
+            for version in applied.iter().filter(|_| !quiet) {
+                writeln!(out, "Applied {version}")?;
+            }
+            writeln!(out, "Migrations applied successfully")?;
+            Ok(0)
+        }
+        Err(e) => {
+            eprintln!("Migrations failed: {e}");
+            Ok(1)
+        }
+    }
+}
+
+/// Deletes the database (and any SQLite side files) and recreates it. Only
+/// the exact confirmation `"yes"` is accepted.
+pub fn reset_db(
+    db_path: &str,
+    out: &mut impl Write,
+    confirm: Option<&str>,
+) -> Result<i32, AppError> {
+    if confirm != Some("yes") {
+        eprintln!("This will permanently delete all tasks and topics. Pass --yes to confirm.");
+        return Ok(1);
+    }
+
+    // Refuse to delete a database another instance is still using.
+    let lock = crate::task_manager::app::lock_database(Path::new(db_path))?;
+    for suffix in ["", "-wal", "-shm", "-journal"] {
+        match fs::remove_file(format!("{db_path}{suffix}")) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+    }
+    drop(lock);
+    open_db(db_path)?;
+    writeln!(out, "Database reset successfully.")?;
+    Ok(0)
+}
@@ -0,0 +1,22 @@
+/// Where yanked text goes. The TUI uses [`SystemClipboard`]; tests swap in
+/// their own so they never touch the desktop clipboard.
+pub trait Clipboard: Send {
+    fn copy_text(&mut self, text: &str) -> Result<(), arboard::Error>;
+    fn paste_text(&mut self) -> Result<String, arboard::Error>;
+}
+
+/// The desktop clipboard, opened per call.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClipboard;
+
+impl Clipboard for SystemClipboard {
+    fn copy_text(&mut self, text: &str) -> Result<(), arboard::Error> {
+        let mut clipboard = arboard::Clipboard::new()?;
+        clipboard.set_text(text.to_string())
+    }
+
+    fn paste_text(&mut self) -> Result<String, arboard::Error> {
+        let mut clipboard = arboard::Clipboard::new()?;
+        clipboard.get_text()
+    }
+}
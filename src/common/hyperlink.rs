@@ -0,0 +1,253 @@
+//! OSC 8 terminal hyperlinks. `tui` measures every cell it draws, so the
+//! escape sequences cannot go through it: once a frame is on screen, URLs
+//! found in its buffer are printed again over themselves, wrapped in a link.
+
+use crossterm::{
+    cursor::{MoveTo, RestorePosition, SavePosition},
+    queue,
+    style::{
+        Attribute, Color as CColor, Print, ResetColor, SetAttribute, SetBackgroundColor,
+        SetForegroundColor,
+    },
+};
+use std::io::{self, Write};
+use tui::{
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::{Color, Modifier},
+};
+
+/// Whether the terminal is likely to understand OSC 8, judged from `$TERM`,
+/// `$COLORTERM` and `$TERM_PROGRAM`.
+pub fn supports_hyperlinks() -> bool {
+    let var = |name: &str| std::env::var(name).ok();
+    supports_hyperlinks_for(
+        var("TERM").as_deref(),
+        var("COLORTERM").as_deref(),
+        var("TERM_PROGRAM").as_deref(),
+    )
+}
+
+/// A bare `xterm-256color` says nothing beyond xterm compatibility, so it
+/// only counts when `$COLORTERM` or `$TERM_PROGRAM` names a modern terminal.
+fn supports_hyperlinks_for(
+    term: Option<&str>,
+    colorterm: Option<&str>,
+    term_program: Option<&str>,
+) -> bool {
+    let term = term.unwrap_or_default();
+    if term.is_empty() || term == "dumb" || term == "linux" {
+        return false;
+    }
+    ["kitty", "wezterm", "alacritty", "foot"]
+        .iter()
+        .any(|name| term.contains(name))
+        || matches!(colorterm, Some("truecolor" | "24bit"))
+        || matches!(
+            term_program,
+            Some("iTerm.app" | "WezTerm" | "vscode" | "ghostty")
+        )
+}
+
+/// Ends the link opened by [`osc8_start`].
+const OSC8_END: &str = "\x1b]8;;\x1b\\";
+
+/// Starts a link to `url`; everything printed until [`OSC8_END`] is part of it.
+fn osc8_start(url: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\")
+}
+
+/// `(x, y)` of every place inside `area` where `url` is drawn in full on a
+/// single row.
+pub fn url_positions(buffer: &Buffer, area: Rect, url: &str) -> Vec<(u16, u16)> {
+    let chars: Vec<char> = url.chars().collect();
+    let area = area.intersection(buffer.area);
+    let Ok(len) = u16::try_from(chars.len()) else {
+        return Vec::new();
+    };
+    if len == 0 || len > area.width {
+        return Vec::new();
+    }
+    let mut positions = Vec::new();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..=area.right() - len {
+            let matches = chars.iter().zip(x..).all(|(ch, cx)| {
+                let symbol = &buffer.get(cx, y).symbol;
+                symbol.chars().eq(std::iter::once(*ch))
+            });
+            if matches {
+                positions.push((x, y));
+            }
+        }
+    }
+    positions
+}
+
+/// The crossterm colour `tui`'s crossterm backend draws for `color`.
+fn crossterm_color(color: Color) -> CColor {
+    match color {
+        Color::Reset => CColor::Reset,
+        Color::Black => CColor::Black,
+        Color::Red => CColor::DarkRed,
+        Color::Green => CColor::DarkGreen,
+        Color::Yellow => CColor::DarkYellow,
+        Color::Blue => CColor::DarkBlue,
+        Color::Magenta => CColor::DarkMagenta,
+        Color::Cyan => CColor::DarkCyan,
+        Color::Gray => CColor::Grey,
+        Color::DarkGray => CColor::DarkGrey,
+        Color::LightRed => CColor::Red,
+        Color::LightGreen => CColor::Green,
+        Color::LightYellow => CColor::Yellow,
+        Color::LightBlue => CColor::Blue,
+        Color::LightMagenta => CColor::Magenta,
+        Color::LightCyan => CColor::Cyan,
+        Color::White => CColor::White,
+        Color::Indexed(index) => CColor::AnsiValue(index),
+        Color::Rgb(r, g, b) => CColor::Rgb { r, g, b },
+    }
+}
+
+/// Prints `cell` again with the colours and modifiers the frame gave it.
+fn write_cell(out: &mut impl Write, cell: &Cell) -> io::Result<()> {
+    queue!(
+        out,
+        SetAttribute(Attribute::Reset),
+        SetForegroundColor(crossterm_color(cell.fg)),
+        SetBackgroundColor(crossterm_color(cell.bg))
+    )?;
+    let attributes = [
+        (Modifier::BOLD, Attribute::Bold),
+        (Modifier::DIM, Attribute::Dim),
+        (Modifier::ITALIC, Attribute::Italic),
+        (Modifier::UNDERLINED, Attribute::Underlined),
+        (Modifier::SLOW_BLINK, Attribute::SlowBlink),
+        (Modifier::RAPID_BLINK, Attribute::RapidBlink),
+        (Modifier::REVERSED, Attribute::Reverse),
+        (Modifier::HIDDEN, Attribute::Hidden),
+        (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+    ];
+    for (modifier, attribute) in attributes {
+        if cell.modifier.contains(modifier) {
+            queue!(out, SetAttribute(attribute))?;
+        }
+    }
+    queue!(out, Print(&cell.symbol))
+}
+
+/// Reprints each of `urls` where the frame shows it inside `area`, as a
+/// link drawn cell by cell in the frame's own style. The cursor is left
+/// where it was.
+pub fn write_hyperlinks(
+    out: &mut impl Write,
+    buffer: &Buffer,
+    area: Rect,
+    urls: &[&str],
+) -> io::Result<()> {
+    let mut wrote = false;
+    for url in urls {
+        let len = url.chars().count() as u16;
+        for (x, y) in url_positions(buffer, area, url) {
+            if !wrote {
+                queue!(out, SavePosition)?;
+                wrote = true;
+            }
+            queue!(out, MoveTo(x, y), Print(osc8_start(url)))?;
+            for cx in x..x + len {
+                write_cell(out, buffer.get(cx, y))?;
+            }
+            queue!(
+                out,
+                Print(OSC8_END),
+                SetAttribute(Attribute::Reset),
+                ResetColor
+            )?;
+        }
+    }
+    if wrote {
+        queue!(out, RestorePosition)?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{osc8_start, supports_hyperlinks_for, url_positions, write_hyperlinks, OSC8_END};
+    use tui::{
+        buffer::Buffer,
+        layout::Rect,
+        style::{Color, Modifier, Style},
+    };
+
+    #[test]
+    fn hyperlink_support_needs_more_than_plain_xterm() {
+        assert!(!supports_hyperlinks_for(None, Some("truecolor"), None));
+        assert!(!supports_hyperlinks_for(Some("dumb"), None, None));
+        assert!(!supports_hyperlinks_for(Some("xterm-256color"), None, None));
+        assert!(supports_hyperlinks_for(
+            Some("xterm-256color"),
+            Some("truecolor"),
+            None
+        ));
+        assert!(supports_hyperlinks_for(
+            Some("xterm-256color"),
+            None,
+            Some("iTerm.app")
+        ));
+        assert!(supports_hyperlinks_for(Some("xterm-kitty"), None, None));
+    }
+
+    #[test]
+    fn drawn_urls_are_found_and_wrapped_in_osc8() -> std::io::Result<()> {
+        let url = "https://example.com";
+        assert_eq!(
+            format!("{}site{OSC8_END}", osc8_start(url)),
+            "\x1b]8;;https://example.com\x1b\\site\x1b]8;;\x1b\\"
+        );
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 30, 3));
+        buffer.set_string(4, 1, url, Style::default());
+        buffer.set_string(0, 2, "https://example", Style::default());
+        assert_eq!(url_positions(&buffer, buffer.area, url), vec![(4, 1)]);
+        assert!(url_positions(&buffer, Rect::new(0, 0, 30, 1), url).is_empty());
+        assert!(url_positions(
+            &buffer,
+            buffer.area,
+            "https://a-much-longer-url.example.com"
+        )
+        .is_empty());
+
+        let mut out = Vec::new();
+        write_hyperlinks(&mut out, &buffer, buffer.area, &[url])?;
+        let written = String::from_utf8_lossy(&out);
+        assert!(written.starts_with(&format!("\x1b7\x1b[2;5H{}", osc8_start(url))));
+        assert!(written.contains(OSC8_END));
+
+        let mut out = Vec::new();
+        write_hyperlinks(&mut out, &buffer, buffer.area, &["https://missing.example"])?;
+        assert!(out.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn links_keep_the_colours_and_modifiers_of_each_cell() -> std::io::Result<()> {
+        let url = "https://a.example";
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 20, 1));
+        let style = Style::default()
+            .fg(Color::Yellow)
+            .bg(Color::Blue)
+            .add_modifier(Modifier::BOLD);
+        buffer.set_string(0, 0, url, style);
+        buffer.get_mut(8, 0).fg = Color::Rgb(1, 2, 3);
+
+        let mut out = Vec::new();
+        write_hyperlinks(&mut out, &buffer, buffer.area, &[url])?;
+        let written = String::from_utf8_lossy(&out);
+        // Dark yellow on dark blue, bold, and the one cell drawn in RGB.
+        assert!(written.contains("\x1b[38;5;3m\x1b[48;5;4m\x1b[1mh"));
+        assert!(written.contains("\x1b[38;2;1;2;3m\x1b[48;5;4m\x1b[1ma"));
+        assert!(!written.contains("\x1b[38;5;14m"));
+        Ok(())
+    }
+}
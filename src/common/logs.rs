@@ -1,4 +1,78 @@
-use chrono::Local;
+use chrono::{DateTime, Local};
+use std::fmt;
+
+/// Severity of a log panel entry, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum LogLevel {
+    #[default]
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Parses the level names used by `add_log`; anything unknown is `Info`.
+    pub fn from_label(level: &str) -> Self {
+        match level.trim().to_ascii_uppercase().as_str() {
+            "DEBUG" => LogLevel::Debug,
+            "WARN" | "WARNING" => LogLevel::Warn,
+            "ERROR" => LogLevel::Error,
+            _ => LogLevel::Info,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            LogLevel::Debug => LogLevel::Info,
+            LogLevel::Info => LogLevel::Warn,
+            LogLevel::Warn => LogLevel::Error,
+            LogLevel::Error => LogLevel::Debug,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Local>,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}] {}",
+            self.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            self.level.as_str(),
+            self.message
+        )
+    }
+}
+
+pub fn push_log_entry(
+    logs: &mut Vec<LogEntry>,
+    log_offset: &mut usize,
+    level: &str,
+    message: &str,
+) {
+    logs.push(LogEntry {
+        timestamp: Local::now(),
+        level: LogLevel::from_label(level),
+        message: message.to_string(),
+    });
+    *log_offset = 0;
+}
 
 pub fn push_timestamped_log(
     logs: &mut Vec<String>,
@@ -1,4 +1,6 @@
+pub mod clipboard;
 pub mod command_palette;
+pub mod hyperlink;
 pub mod logs;
 pub mod palette;
 pub mod tui;
@@ -1,42 +1,152 @@
-use crossterm::event::{self, Event, KeyEvent};
+use crossterm::{
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEvent, MouseEvent},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
 use std::{
     error::Error,
+    io,
+    path::Path,
+    process::{Command, ExitStatus},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
     time::{Duration, Instant},
 };
-use tui::{backend::Backend, Frame, Terminal};
+use tui::{backend::Backend, buffer::Buffer, layout::Rect, Frame, Terminal};
 
-pub fn run_event_loop_with_tick<B, State, Action, Draw, Handle, Process, Tick>(
+/// Tick interval for views without their own setting.
+pub const DEFAULT_TICK_RATE: Duration = Duration::from_millis(250);
+
+/// Set by the `SIGCONT` handler so the event loop can restore the terminal
+/// after the process was stopped and resumed.
+static RESUMED: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+
+fn resumed_flag() -> &'static Arc<AtomicBool> {
+    RESUMED.get_or_init(|| {
+        let flag = Arc::new(AtomicBool::new(false));
+        #[cfg(unix)]
+        let _ = signal_hook::flag::register(signal_hook::consts::SIGCONT, Arc::clone(&flag));
+        flag
+    })
+}
+
+/// Re-enters raw mode and the alternate screen and forces a full redraw.
+fn restore_terminal<B: Backend>(terminal: &mut Terminal<B>) -> Result<(), Box<dyn Error>> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+    Ok(())
+}
+
+/// Leaves raw mode and the alternate screen while `run` executes (e.g. an
+/// external editor), then restores the terminal even if `run` failed.
+pub fn with_suspended_terminal<B: Backend, T>(
+    terminal: &mut Terminal<B>,
+    run: impl FnOnce() -> io::Result<T>,
+) -> Result<T, Box<dyn Error>> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+    let result = run();
+    restore_terminal(terminal)?;
+    Ok(result?)
+}
+
+/// `$VISUAL`, then `$EDITOR`, then `vi`.
+pub fn editor_command(visual: Option<String>, editor: Option<String>) -> String {
+    visual
+        .into_iter()
+        .chain(editor)
+        .find(|command| !command.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Opens `path` in the user's editor and waits for it to exit. The editor
+/// command may carry arguments, e.g. `code -w`.
+pub fn open_in_editor(path: &Path) -> io::Result<ExitStatus> {
+    let command = editor_command(std::env::var("VISUAL").ok(), std::env::var("EDITOR").ok());
+    let mut parts = command.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    Command::new(program).args(parts).arg(path).status()
+}
+
+/// Hands the terminal back to the shell and stops the process (`SIGTSTP`),
+/// as `Ctrl+Z` would outside raw mode. Returns once resumed with `fg`.
+#[cfg(unix)]
+pub fn suspend_to_shell<B: Backend>(terminal: &mut Terminal<B>) -> Result<(), Box<dyn Error>> {
+    resumed_flag();
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+    signal_hook::low_level::raise(signal_hook::consts::SIGTSTP)?;
+    resumed_flag().store(false, Ordering::SeqCst);
+    restore_terminal(terminal)
+}
+
+#[cfg(not(unix))]
+pub fn suspend_to_shell<B: Backend>(_terminal: &mut Terminal<B>) -> Result<(), Box<dyn Error>> {
+    Ok(())
+}
+
+/// Like [`run_event_loop_with_tick`], also forwarding mouse events to
+/// `handle_mouse`. `after_draw` sees each frame once it is on screen, for
+/// output `tui` cannot express, such as hyperlinks.
+#[allow(clippy::too_many_arguments)]
+pub fn run_event_loop_with_mouse<B, State, Action, Draw, AfterDraw, Handle, Mouse, Process, Tick>(
     terminal: &mut Terminal<B>,
     state: &mut State,
+    tick_rate: Duration,
     mut draw: Draw,
+    mut after_draw: AfterDraw,
     mut handle_key: Handle,
+    mut handle_mouse: Mouse,
     mut process_action: Process,
     mut on_tick: Tick,
 ) -> Result<(), Box<dyn Error>>
 where
     B: Backend,
     Draw: FnMut(&mut Frame<B>, &mut State),
+    AfterDraw: FnMut(&Buffer, &State) -> io::Result<()>,
     Handle: FnMut(&mut State, KeyEvent) -> Result<Action, Box<dyn Error>>,
+    Mouse: FnMut(&mut State, MouseEvent),
     Process: FnMut(Action, &mut State, &mut Terminal<B>) -> Result<bool, Box<dyn Error>>,
     Tick: FnMut(&mut State) -> Result<(), Box<dyn Error>>,
 {
-    let tick_rate = Duration::from_millis(250);
     let mut last_tick = Instant::now();
+    let resumed = resumed_flag();
     terminal.clear()?;
 
     loop {
-        terminal.draw(|f| draw(f, state))?;
+        // Stopped from outside (e.g. `kill -TSTP`) and resumed: the shell has
+        // reset the terminal, so set it up again.
+        if resumed.swap(false, Ordering::SeqCst) {
+            restore_terminal(terminal)?;
+        }
+        let frame = terminal.draw(|f| draw(f, state))?;
+        after_draw(frame.buffer, state)?;
 
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
             .unwrap_or_else(|| Duration::from_secs(0));
 
         if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                let action = handle_key(state, key)?;
-                if process_action(action, state, terminal)? {
-                    break;
+            match event::read()? {
+                Event::Key(key) => {
+                    let action = handle_key(state, key)?;
+                    if process_action(action, state, terminal)? {
+                        break;
+                    }
+                }
+                Event::Resize(width, height) => {
+                    // Redraw straight away instead of waiting for the next tick.
+                    terminal.resize(Rect::new(0, 0, width, height))?;
+                    let frame = terminal.draw(|f| draw(f, state))?;
+                    after_draw(frame.buffer, state)?;
                 }
+                Event::Mouse(mouse) => handle_mouse(state, mouse),
+                _ => {}
             }
         }
 
@@ -49,6 +159,35 @@ where
     Ok(())
 }
 
+pub fn run_event_loop_with_tick<B, State, Action, Draw, Handle, Process, Tick>(
+    terminal: &mut Terminal<B>,
+    state: &mut State,
+    tick_rate: Duration,
+    draw: Draw,
+    handle_key: Handle,
+    process_action: Process,
+    on_tick: Tick,
+) -> Result<(), Box<dyn Error>>
+where
+    B: Backend,
+    Draw: FnMut(&mut Frame<B>, &mut State),
+    Handle: FnMut(&mut State, KeyEvent) -> Result<Action, Box<dyn Error>>,
+    Process: FnMut(Action, &mut State, &mut Terminal<B>) -> Result<bool, Box<dyn Error>>,
+    Tick: FnMut(&mut State) -> Result<(), Box<dyn Error>>,
+{
+    run_event_loop_with_mouse(
+        terminal,
+        state,
+        tick_rate,
+        draw,
+        |_, _| Ok(()),
+        handle_key,
+        |_, _| {},
+        process_action,
+        on_tick,
+    )
+}
+
 pub fn run_event_loop<B, State, Action, Draw, Handle, Process>(
     terminal: &mut Terminal<B>,
     state: &mut State,
@@ -65,9 +204,24 @@ where
     run_event_loop_with_tick(
         terminal,
         state,
+        DEFAULT_TICK_RATE,
         draw,
         handle_key,
         process_action,
         |_| Ok(()),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::editor_command;
+
+    #[test]
+    fn editor_command_prefers_visual_then_editor_then_vi() {
+        let some = |value: &str| Some(value.to_string());
+        assert_eq!(editor_command(some("code -w"), some("nano")), "code -w");
+        assert_eq!(editor_command(None, some("nano")), "nano");
+        assert_eq!(editor_command(some(" "), some("nano")), "nano");
+        assert_eq!(editor_command(None, None), "vi");
+    }
+}
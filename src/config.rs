@@ -0,0 +1,355 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::db::task_manager::models::TopicOrder;
+use crate::error::AppError;
+
+/// Set to `1` to silence the completion bell regardless of the config file.
+pub const NO_BELL_ENV: &str = "TASK_MANAGER_NO_BELL";
+
+/// Set to `1` to draw the UI with ASCII only, regardless of the config file.
+pub const ASCII_ENV: &str = "TASK_MANAGER_ASCII";
+
+/// Overrides `tick_rate_ms` from the config file.
+pub const TICK_RATE_ENV: &str = "TASK_MANAGER_TICK_RATE_MS";
+
+/// Set to `1` to leave the terminal window title alone.
+pub const NO_TITLE_ENV: &str = "TASK_MANAGER_NO_TITLE";
+
+pub const MIN_TICK_RATE_MS: u64 = 50;
+pub const MAX_TICK_RATE_MS: u64 = 1000;
+
+/// What Enter does on the Normal-mode task list; Space does the other.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EnterAction {
+    #[default]
+    ExpandTask,
+    ToggleComplete,
+}
+
+/// Where the task manager shows its topics.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutStyle {
+    /// A row of tabs above the task list.
+    #[default]
+    TabbedTop,
+    /// A scrolling list beside the task list; `h`/`l` move focus between them.
+    SidebarLeft,
+}
+
+/// User settings persisted next to the task database.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct Config {
+    /// Ring the terminal bell when a task is marked complete.
+    pub sound_enabled: bool,
+    /// Use plain ASCII markers (e.g. `[P]`) instead of emoji in the task list.
+    pub ascii_mode: bool,
+    /// How far ahead the "Due soon" view looks, in days.
+    pub due_soon_days: i64,
+    /// Interval between UI ticks, in milliseconds. Lower values keep live
+    /// timers smoother; higher values use less CPU.
+    pub tick_rate_ms: u64,
+    /// Serve Prometheus metrics on `127.0.0.1:<port>/metrics` while the task
+    /// manager is open.
+    pub metrics_port: Option<u16>,
+    /// Write a Markdown digest of the day to this path whenever the task
+    /// manager exits cleanly.
+    pub auto_digest: Option<String>,
+    /// Archive deleted tasks instead of removing them; they stay reachable
+    /// from the archive popup.
+    pub soft_delete: bool,
+    /// Tasks loaded into the list at a time; moving past either end of the
+    /// list loads the next or previous page.
+    pub page_size: usize,
+    /// Also write ERROR and CRITICAL records to `.logs/error.log`, rotated on
+    /// its own size limit (see `LOG_ERROR_MAX_BYTES`).
+    pub split_error_log: bool,
+    /// Whether Enter expands the selected task (Space toggles it done) or the
+    /// other way round.
+    pub enter_action: EnterAction,
+    /// Order of the topic tabs: `manual`, `name` or `created`.
+    pub topic_order: TopicOrder,
+    /// Make task URLs clickable in terminals that support OSC 8 links; the
+    /// bare URL is shown elsewhere.
+    pub hyperlinks: bool,
+    /// Topic tabs along the top (`tabbed_top`) or a topic list on the left
+    /// (`sidebar_left`).
+    pub layout: LayoutStyle,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            sound_enabled: true,
+            ascii_mode: false,
+            due_soon_days: 7,
+            tick_rate_ms: 250,
+            metrics_port: None,
+            auto_digest: None,
+            soft_delete: false,
+            page_size: 50,
+            split_error_log: false,
+            enter_action: EnterAction::ExpandTask,
+            topic_order: TopicOrder::Manual,
+            hyperlinks: true,
+            layout: LayoutStyle::TabbedTop,
+        }
+    }
+}
+
+/// A setting or path that stops the app from starting, found by
+/// [`Config::validate`] or [`preflight`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    DbDirNotWritable(PathBuf),
+    DbFilenameContainsPathSep(String),
+    LogDirNotWritable(PathBuf),
+    TickRateOutOfRange(u64),
+    PageSizeZero,
+    /// The config file exists but is not valid JSON for [`Config`].
+    InvalidConfigFile(PathBuf, String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::DbDirNotWritable(dir) => write!(
+                f,
+                "The database directory {} cannot be written to; point TASK_MANAGER_DB_DIR at a writable directory.",
+                dir.display()
+            ),
+            ConfigError::DbFilenameContainsPathSep(name) => write!(
+                f,
+                "TASK_MANAGER_DB_FILENAME must be a plain file name, but '{name}' contains a path separator; put the directory in TASK_MANAGER_DB_DIR instead."
+            ),
+            ConfigError::LogDirNotWritable(dir) => write!(
+                f,
+                "The log directory {} cannot be written to.",
+                dir.display()
+            ),
+            ConfigError::TickRateOutOfRange(value) => write!(
+                f,
+                "tick_rate_ms must be between {MIN_TICK_RATE_MS} and {MAX_TICK_RATE_MS}, got {value}."
+            ),
+            ConfigError::PageSizeZero => write!(f, "page_size must be at least 1."),
+            ConfigError::InvalidConfigFile(path, e) => {
+                write!(f, "The config file {} could not be read: {e}.", path.display())
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+impl Config {
+    pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+        if !(MIN_TICK_RATE_MS..=MAX_TICK_RATE_MS).contains(&self.tick_rate_ms) {
+            errors.push(ConfigError::TickRateOutOfRange(self.tick_rate_ms));
+        }
+        if self.page_size == 0 {
+            errors.push(ConfigError::PageSizeZero);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn tick_rate(&self) -> Duration {
+        Duration::from_millis(self.tick_rate_ms)
+    }
+}
+
+pub fn load_config(path: &Path) -> Result<Config, Box<dyn Error>> {
+    let config = read_config(path)?;
+    config.validate().map_err(|errors| {
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        AppError::Config(messages.join(" "))
+    })?;
+    Ok(config)
+}
+
+/// The config file as saved, without environment overrides.
+fn read_config_file(path: &Path) -> Result<Config, Box<dyn Error>> {
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+    let content = fs::read_to_string(path)?;
+    if content.trim().is_empty() {
+        Ok(Config::default())
+    } else {
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+/// The config file with environment overrides applied, not yet validated.
+fn read_config(path: &Path) -> Result<Config, Box<dyn Error>> {
+    let mut config = read_config_file(path)?;
+
+    if std::env::var(NO_BELL_ENV).is_ok_and(|value| value == "1") {
+        config.sound_enabled = false;
+    }
+    if ascii_env_enabled() {
+        config.ascii_mode = true;
+    }
+    if let Ok(value) = std::env::var(TICK_RATE_ENV) {
+        config.tick_rate_ms = value.trim().parse().map_err(|_| {
+            AppError::Config(format!("{TICK_RATE_ENV} must be a number, got '{value}'"))
+        })?;
+    }
+    Ok(config)
+}
+
+/// Directory holding the task database, from `TASK_MANAGER_DB_DIR`.
+fn env_db_dir() -> PathBuf {
+    PathBuf::from(
+        std::env::var("TASK_MANAGER_DB_DIR").unwrap_or_else(|_| ".task_manager".to_string()),
+    )
+}
+
+/// The config next to the task database chosen by the environment, or the
+/// defaults when it is missing or unreadable (`preflight` reports why).
+pub fn env_config() -> Config {
+    read_config(&env_db_dir().join("task_manager_config.json")).unwrap_or_default()
+}
+
+/// Startup checks for the task database location, the log directory and the
+/// config file, so `main` can report every problem at once instead of
+/// failing on the first `io::Error`.
+pub fn preflight() -> Result<(), Vec<ConfigError>> {
+    let db_dir = env_db_dir();
+    let db_filename =
+        std::env::var("TASK_MANAGER_DB_FILENAME").unwrap_or_else(|_| "task_manager.db".to_string());
+    check_startup_paths(
+        &db_dir,
+        &db_filename,
+        Path::new(crate::log_config::LOG_FILE),
+    )
+}
+
+/// [`preflight`] for explicit paths. Reads the config file from `db_dir` but
+/// creates nothing.
+pub fn check_startup_paths(
+    db_dir: &Path,
+    db_filename: &str,
+    log_file: &Path,
+) -> Result<(), Vec<ConfigError>> {
+    let mut errors = Vec::new();
+    if db_filename.contains(std::path::is_separator) {
+        errors.push(ConfigError::DbFilenameContainsPathSep(
+            db_filename.to_string(),
+        ));
+    }
+    if !dir_writable(db_dir) {
+        errors.push(ConfigError::DbDirNotWritable(db_dir.to_path_buf()));
+    }
+    let log_dir = log_file.parent().unwrap_or_else(|| Path::new("."));
+    if !dir_writable(log_dir) {
+        errors.push(ConfigError::LogDirNotWritable(log_dir.to_path_buf()));
+    }
+
+    let config_path = db_dir.join("task_manager_config.json");
+    match read_config(&config_path) {
+        Ok(config) => errors.extend(config.validate().err().unwrap_or_default()),
+        Err(e) => errors.push(ConfigError::InvalidConfigFile(config_path, e.to_string())),
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Whether a file can be created in `dir`, or in its nearest existing
+/// ancestor when `dir` has not been created yet. Nothing is written.
+fn dir_writable(dir: &Path) -> bool {
+    // A relative path's last ancestor is "", i.e. the current directory.
+    let Some(existing) = dir
+        .ancestors()
+        .map(|ancestor| {
+            if ancestor.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                ancestor
+            }
+        })
+        .find(|ancestor| ancestor.exists())
+    else {
+        return false;
+    };
+    if !existing.is_dir() {
+        return false;
+    }
+    can_create_in(existing)
+}
+
+#[cfg(unix)]
+fn can_create_in(dir: &Path) -> bool {
+    use rustix::fs::{access, Access};
+    access(dir, Access::WRITE_OK | Access::EXEC_OK).is_ok()
+}
+
+#[cfg(not(unix))]
+fn can_create_in(dir: &Path) -> bool {
+    fs::metadata(dir).is_ok_and(|metadata| !metadata.permissions().readonly())
+}
+
+/// Whether [`ASCII_ENV`] forces ASCII mode; also used before the config file
+/// is loaded, e.g. by the loading screen.
+pub fn ascii_env_enabled() -> bool {
+    std::env::var(ASCII_ENV).is_ok_and(|value| value == "1")
+}
+
+/// Re-reads the config file, applies `edit` and saves it. The in-memory
+/// config carries environment overrides such as [`NO_BELL_ENV`]; saving it
+/// whole would make them permanent.
+pub fn update_config(path: &Path, edit: impl FnOnce(&mut Config)) -> Result<(), Box<dyn Error>> {
+    let mut config = read_config_file(path)?;
+    edit(&mut config);
+    save_config(path, &config)
+}
+
+pub fn save_config(path: &Path, config: &Config) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let content = serde_json::to_string_pretty(config)?;
+    fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tick_rate_is_validated_against_allowed_range() {
+        let mut config = Config::default();
+        assert_eq!(config.tick_rate(), Duration::from_millis(250));
+        assert!(config.validate().is_ok());
+
+        config.tick_rate_ms = MIN_TICK_RATE_MS;
+        assert!(config.validate().is_ok());
+        config.tick_rate_ms = MAX_TICK_RATE_MS;
+        assert!(config.validate().is_ok());
+
+        config.tick_rate_ms = MIN_TICK_RATE_MS - 1;
+        assert_eq!(
+            config.validate(),
+            Err(vec![ConfigError::TickRateOutOfRange(MIN_TICK_RATE_MS - 1)])
+        );
+        config.tick_rate_ms = MAX_TICK_RATE_MS + 1;
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,2 @@
+-- Drop the topic settings table
+DROP TABLE IF EXISTS topic_settings;
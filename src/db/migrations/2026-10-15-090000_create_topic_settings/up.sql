@@ -0,0 +1,8 @@
+-- Persist per-topic list preferences
+CREATE TABLE IF NOT EXISTS topic_settings (
+    topic_id INTEGER PRIMARY KEY NOT NULL,
+    sort_mode TEXT NOT NULL DEFAULT 'created',
+    sort_ascending BOOLEAN NOT NULL DEFAULT 1,
+    completion_filter TEXT NOT NULL DEFAULT 'all',
+    FOREIGN KEY(topic_id) REFERENCES topic(id)
+);
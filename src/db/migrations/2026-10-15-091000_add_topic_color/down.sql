@@ -0,0 +1,2 @@
+-- Remove the topic colour column
+ALTER TABLE topic DROP COLUMN color;
@@ -0,0 +1,2 @@
+-- Optional display colour for topic tabs
+ALTER TABLE topic ADD COLUMN color TEXT;
@@ -0,0 +1,2 @@
+-- Remove the task url column
+ALTER TABLE task DROP COLUMN url;
@@ -0,0 +1,2 @@
+-- Optional link attached to a task
+ALTER TABLE task ADD COLUMN url TEXT;
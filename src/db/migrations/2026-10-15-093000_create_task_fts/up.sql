@@ -0,0 +1,28 @@
+-- Full-text index over task names and descriptions, kept in sync by triggers
+CREATE VIRTUAL TABLE IF NOT EXISTS task_fts USING fts5(
+    name,
+    description,
+    topic_id UNINDEXED,
+    content='task',
+    content_rowid='id'
+);
+
+INSERT INTO task_fts(rowid, name, description, topic_id)
+SELECT id, name, description, topic_id FROM task;
+
+CREATE TRIGGER IF NOT EXISTS task_fts_after_insert AFTER INSERT ON task BEGIN
+    INSERT INTO task_fts(rowid, name, description, topic_id)
+    VALUES (new.id, new.name, new.description, new.topic_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS task_fts_after_delete AFTER DELETE ON task BEGIN
+    INSERT INTO task_fts(task_fts, rowid, name, description, topic_id)
+    VALUES ('delete', old.id, old.name, old.description, old.topic_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS task_fts_after_update AFTER UPDATE ON task BEGIN
+    INSERT INTO task_fts(task_fts, rowid, name, description, topic_id)
+    VALUES ('delete', old.id, old.name, old.description, old.topic_id);
+    INSERT INTO task_fts(rowid, name, description, topic_id)
+    VALUES (new.id, new.name, new.description, new.topic_id);
+END;
@@ -0,0 +1,2 @@
+-- Remove the topic position column
+ALTER TABLE topic DROP COLUMN position;
@@ -0,0 +1,3 @@
+-- Explicit tab order for topics, backfilled from insertion order
+ALTER TABLE topic ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+UPDATE topic SET position = id;
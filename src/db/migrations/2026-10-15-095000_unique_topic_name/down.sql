@@ -0,0 +1,2 @@
+-- Allow duplicate topic names again
+DROP INDEX IF EXISTS idx_topic_name;
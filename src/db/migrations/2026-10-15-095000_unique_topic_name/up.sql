@@ -0,0 +1,6 @@
+-- Enforce unique topic names; rename any existing duplicates first
+UPDATE topic
+SET name = name || ' (' || id || ')'
+WHERE id NOT IN (SELECT MIN(id) FROM topic GROUP BY name);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_topic_name ON topic(name);
@@ -0,0 +1,2 @@
+-- Remove the task pinned column
+ALTER TABLE task DROP COLUMN pinned;
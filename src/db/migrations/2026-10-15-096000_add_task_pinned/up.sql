@@ -0,0 +1,2 @@
+-- Pinned tasks sort above everything else in their list
+ALTER TABLE task ADD COLUMN pinned BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,2 @@
+-- Remove the task due date column
+ALTER TABLE task DROP COLUMN due_date;
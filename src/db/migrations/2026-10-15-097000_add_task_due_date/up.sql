@@ -0,0 +1,2 @@
+-- Optional due date stored as YYYY-MM-DD
+ALTER TABLE task ADD COLUMN due_date TEXT;
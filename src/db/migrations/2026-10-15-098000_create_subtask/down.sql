@@ -0,0 +1,2 @@
+-- Drop subtasks
+DROP TABLE subtask;
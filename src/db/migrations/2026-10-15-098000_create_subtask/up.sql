@@ -0,0 +1,11 @@
+-- Checklist items that belong to a task
+CREATE TABLE IF NOT EXISTS subtask (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    parent_task_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    completed BOOLEAN NOT NULL DEFAULT 0,
+    position INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY(parent_task_id) REFERENCES task(id)
+);
+
+CREATE INDEX IF NOT EXISTS subtask_parent_task_id ON subtask(parent_task_id);
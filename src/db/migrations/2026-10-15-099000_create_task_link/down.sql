@@ -0,0 +1,2 @@
+-- Drop task links
+DROP TABLE task_link;
@@ -0,0 +1,12 @@
+-- Dependencies and relations between tasks
+CREATE TABLE IF NOT EXISTS task_link (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    source_task_id INTEGER NOT NULL,
+    target_task_id INTEGER NOT NULL,
+    link_type TEXT NOT NULL CHECK (link_type IN ('blocks', 'blocked_by', 'relates_to')),
+    FOREIGN KEY(source_task_id) REFERENCES task(id),
+    FOREIGN KEY(target_task_id) REFERENCES task(id)
+);
+
+CREATE INDEX IF NOT EXISTS task_link_source_task_id ON task_link(source_task_id);
+CREATE INDEX IF NOT EXISTS task_link_target_task_id ON task_link(target_task_id);
@@ -0,0 +1,18 @@
+-- Restore the subtask foreign key without the cascade
+CREATE TABLE subtask_old (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    parent_task_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    completed BOOLEAN NOT NULL DEFAULT 0,
+    position INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY(parent_task_id) REFERENCES task(id)
+);
+
+INSERT INTO subtask_old (id, parent_task_id, name, completed, position)
+SELECT id, parent_task_id, name, completed, position
+FROM subtask;
+
+DROP TABLE subtask;
+ALTER TABLE subtask_old RENAME TO subtask;
+
+CREATE INDEX IF NOT EXISTS subtask_parent_task_id ON subtask(parent_task_id);
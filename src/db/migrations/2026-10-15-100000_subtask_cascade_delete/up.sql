@@ -0,0 +1,19 @@
+-- Delete a task's subtasks along with it; SQLite can only add the cascade by rebuilding the table
+CREATE TABLE subtask_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    parent_task_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    completed BOOLEAN NOT NULL DEFAULT 0,
+    position INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY(parent_task_id) REFERENCES task(id) ON DELETE CASCADE
+);
+
+INSERT INTO subtask_new (id, parent_task_id, name, completed, position)
+SELECT id, parent_task_id, name, completed, position
+FROM subtask
+WHERE parent_task_id IN (SELECT id FROM task);
+
+DROP TABLE subtask;
+ALTER TABLE subtask_new RENAME TO subtask;
+
+CREATE INDEX IF NOT EXISTS subtask_parent_task_id ON subtask(parent_task_id);
@@ -0,0 +1,2 @@
+-- Remove the task sort_weight column
+ALTER TABLE task DROP COLUMN sort_weight;
@@ -0,0 +1,6 @@
+-- Manual task order; weights leave gaps of 10 so a task can move without renumbering
+ALTER TABLE task ADD COLUMN sort_weight INTEGER NOT NULL DEFAULT 0;
+UPDATE task SET sort_weight = 10 * (
+    SELECT COUNT(*) FROM task AS earlier
+    WHERE earlier.topic_id = task.topic_id AND earlier.id < task.id
+);
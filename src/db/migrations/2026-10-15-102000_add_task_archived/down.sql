@@ -0,0 +1,2 @@
+-- Remove the task archived column
+ALTER TABLE task DROP COLUMN archived;
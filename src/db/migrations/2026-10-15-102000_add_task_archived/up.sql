@@ -0,0 +1,2 @@
+-- Archived tasks are hidden from the task lists instead of being deleted
+ALTER TABLE task ADD COLUMN archived BOOLEAN NOT NULL DEFAULT FALSE;
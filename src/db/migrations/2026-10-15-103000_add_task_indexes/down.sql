@@ -0,0 +1,5 @@
+-- Remove the task indexes
+DROP INDEX IF EXISTS idx_task_topic_completed;
+DROP INDEX IF EXISTS idx_task_completed;
+DROP INDEX IF EXISTS idx_task_favourite;
+DROP INDEX IF EXISTS idx_task_topic_id;
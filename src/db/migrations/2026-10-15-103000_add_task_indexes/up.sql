@@ -0,0 +1,5 @@
+-- Indexes for the task list, favourite/completed views and per-topic counts
+CREATE INDEX IF NOT EXISTS idx_task_topic_id ON task(topic_id);
+CREATE INDEX IF NOT EXISTS idx_task_favourite ON task(favourite);
+CREATE INDEX IF NOT EXISTS idx_task_completed ON task(completed);
+CREATE INDEX IF NOT EXISTS idx_task_topic_completed ON task(topic_id, completed);
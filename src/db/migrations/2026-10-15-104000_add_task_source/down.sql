@@ -0,0 +1,2 @@
+-- Remove the task source column
+ALTER TABLE task DROP COLUMN source;
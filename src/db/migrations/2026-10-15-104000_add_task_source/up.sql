@@ -0,0 +1,2 @@
+-- Where each task was created: tui, cli, import_json, import_csv or import_org
+ALTER TABLE task ADD COLUMN source TEXT NOT NULL DEFAULT 'tui';
@@ -0,0 +1,2 @@
+-- Remove the topic alias column
+ALTER TABLE topic DROP COLUMN alias;
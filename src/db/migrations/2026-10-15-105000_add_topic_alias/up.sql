@@ -0,0 +1,2 @@
+-- Optional one-character key that jumps to the topic from Normal mode
+ALTER TABLE topic ADD COLUMN alias TEXT CHECK (alias IS NULL OR length(alias) = 1);
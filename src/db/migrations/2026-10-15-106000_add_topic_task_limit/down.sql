@@ -0,0 +1,2 @@
+-- Remove the topic task limit column
+ALTER TABLE topic DROP COLUMN task_limit;
@@ -0,0 +1,2 @@
+-- Optional cap on the number of tasks a topic can hold
+ALTER TABLE topic ADD COLUMN task_limit INTEGER CHECK (task_limit IS NULL OR task_limit > 0);
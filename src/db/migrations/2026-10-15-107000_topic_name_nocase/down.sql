@@ -0,0 +1,3 @@
+-- Compare topic names case-sensitively again
+DROP INDEX IF EXISTS idx_topic_name;
+CREATE UNIQUE INDEX idx_topic_name ON topic(name);
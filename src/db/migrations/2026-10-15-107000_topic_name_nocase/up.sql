@@ -0,0 +1,7 @@
+-- Make topic names unique regardless of case; rename case-folded duplicates first
+UPDATE topic
+SET name = name || ' (' || id || ')'
+WHERE id NOT IN (SELECT MIN(id) FROM topic GROUP BY name COLLATE NOCASE);
+
+DROP INDEX IF EXISTS idx_topic_name;
+CREATE UNIQUE INDEX idx_topic_name ON topic(name COLLATE NOCASE);
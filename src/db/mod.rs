@@ -2,28 +2,134 @@ pub mod notes;
 pub mod schema;
 pub mod task_manager;
 
+use diesel::migration::MigrationVersion;
 use diesel::prelude::*;
-use diesel::r2d2::{self, ConnectionManager};
+use diesel::r2d2::{self, ConnectionManager, CustomizeConnection};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
-use std::error::Error;
+use slog::{info, warn, Logger};
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::error::AppError;
 
 // Type alias for the database connection pool
 pub type DbPool = r2d2::Pool<ConnectionManager<SqliteConnection>>;
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("src/db/migrations");
 
+/// Milliseconds a `DbOperations` call may take before it is logged as slow.
+pub const SLOW_QUERY_MS_ENV: &str = "TASK_MANAGER_SLOW_QUERY_MS";
+const DEFAULT_SLOW_QUERY_MS: u64 = 100;
+
+/// [`SLOW_QUERY_MS_ENV`], or 100 ms when unset or not a number.
+pub fn slow_query_threshold() -> Duration {
+    let ms = std::env::var(SLOW_QUERY_MS_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(DEFAULT_SLOW_QUERY_MS);
+    Duration::from_millis(ms)
+}
+
+/// Started by [`timed_query!`]; warns when dropped if the method it covers
+/// ran for longer than the threshold.
+pub struct QueryTimer<'a> {
+    logger: &'a Logger,
+    method: &'static str,
+    threshold: Duration,
+    start: Instant,
+}
+
+impl<'a> QueryTimer<'a> {
+    pub fn start(logger: &'a Logger, method: &'static str, threshold: Duration) -> Self {
+        Self {
+            logger,
+            method,
+            threshold,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for QueryTimer<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        if elapsed > self.threshold {
+            warn!(self.logger, "Slow query detected";
+                "method" => self.method, "elapsed_ms" => elapsed.as_millis());
+        }
+    }
+}
+
+/// Times the rest of the enclosing `DbOperations` method, however it
+/// returns: `timed_query!(self, "load_topics");`
+macro_rules! timed_query {
+    ($ops:expr, $method:literal) => {
+        let _query_timer =
+            $crate::db::QueryTimer::start(&$ops.logger, $method, $ops.slow_query_threshold);
+    };
+}
+pub(crate) use timed_query;
+
+diesel::define_sql_function! {
+    /// Lowercases with Rust's Unicode rules. SQLite's own `lower()` only
+    /// folds ASCII, so "Über" would not match a search for "über".
+    fn unicode_lower(text: diesel::sql_types::Text) -> diesel::sql_types::Text;
+}
+
+/// Registers the Rust-backed SQL functions, such as [`unicode_lower`], on
+/// every pooled connection.
+#[derive(Debug)]
+struct SqlFunctions;
+
+impl CustomizeConnection<SqliteConnection, r2d2::Error> for SqlFunctions {
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), r2d2::Error> {
+        unicode_lower_utils::register_impl(conn, |text: String| text.to_lowercase())
+            .map_err(r2d2::Error::QueryError)
+    }
+}
+
 /// Initialize the database connection pool
-pub fn establish_connection_pool(database_url: &str) -> Result<DbPool, Box<dyn Error>> {
+pub fn establish_connection_pool(database_url: &str) -> Result<DbPool, AppError> {
     let manager = ConnectionManager::<SqliteConnection>::new(database_url);
-    let pool = r2d2::Pool::builder().build(manager)?;
+    let pool = r2d2::Pool::builder()
+        .connection_customizer(Box::new(SqlFunctions))
+        .build(manager)?;
+
+    Ok(pool)
+}
+
+/// A pool that only reads the database at `db_path`; SQLite refuses every
+/// write, and a missing file is an error rather than created.
+pub fn read_only_pool(db_path: &str) -> Result<DbPool, AppError> {
+    establish_connection_pool(&format!("sqlite://{db_path}?mode=ro"))
+}
 
+/// A fresh in-memory database with the migrations applied. The pool holds a
+/// single connection because every `:memory:` connection is its own database.
+pub fn in_memory_pool() -> Result<DbPool, AppError> {
+    let manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+    let pool = r2d2::Pool::builder()
+        .max_size(1)
+        .connection_customizer(Box::new(SqlFunctions))
+        .build(manager)?;
+    run_migrations(&mut *pool.get()?)?;
     Ok(pool)
 }
 
-pub fn run_migrations(conn: &mut SqliteConnection) -> Result<(), Box<dyn std::error::Error>> {
-    conn.run_pending_migrations(MIGRATIONS)
-        .map_err(|e| -> Box<dyn std::error::Error> { e })?;
-    Ok(())
+/// Applies pending migrations and returns the versions that were run.
+pub fn run_migrations(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<MigrationVersion<'static>>, AppError> {
+    let applied: Vec<MigrationVersion<'static>> = conn
+        .run_pending_migrations(MIGRATIONS)
+        .map_err(|e| AppError::Migration(e.to_string()))?
+        .iter()
+        .map(MigrationVersion::as_owned)
+        .collect();
+    let logger = crate::log_config::get_logger_or_discard();
+    for version in &applied {
+        info!(logger, "migration applied"; "version" => %version);
+    }
+    Ok(applied)
 }
 
 pub fn resolve_db_path(
@@ -12,6 +12,12 @@ diesel::table! {
         favourite -> Bool,
         created_at -> Text,
         updated_at -> Text,
+        url -> Nullable<Text>,
+        pinned -> Bool,
+        due_date -> Nullable<Text>,
+        sort_weight -> Integer,
+        archived -> Bool,
+        source -> Text,
     }
 }
 
@@ -24,6 +30,10 @@ diesel::table! {
         description -> Text,
         created_at -> Text,
         updated_at -> Text,
+        color -> Nullable<Text>,
+        position -> Integer,
+        alias -> Nullable<Text>,
+        task_limit -> Nullable<Integer>,
     }
 }
 
@@ -39,6 +49,49 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+
+    topic_settings (topic_id) {
+        topic_id -> Integer,
+        sort_mode -> Text,
+        sort_ascending -> Bool,
+        completion_filter -> Text,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    subtask (id) {
+        id -> Integer,
+        parent_task_id -> Integer,
+        name -> Text,
+        completed -> Bool,
+        position -> Integer,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    task_link (id) {
+        id -> Integer,
+        source_task_id -> Integer,
+        target_task_id -> Integer,
+        link_type -> Text,
+    }
+}
+
+diesel::joinable!(subtask -> task (parent_task_id));
 diesel::joinable!(task -> topic (topic_id));
+diesel::joinable!(topic_settings -> topic (topic_id));
 
-diesel::allow_tables_to_appear_in_same_query!(task, topic, note,);
+diesel::allow_tables_to_appear_in_same_query!(
+    task,
+    topic,
+    note,
+    topic_settings,
+    subtask,
+    task_link,
+);
@@ -1,9 +1,12 @@
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
-use crate::db::schema::{task, topic};
+use crate::db::schema::{subtask, task, task_link, topic, topic_settings};
 
-#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, Queryable, Selectable, Identifiable, Serialize, Deserialize,
+)]
 #[diesel(table_name = topic)]
 pub struct Topic {
     pub id: i32,
@@ -11,6 +14,19 @@ pub struct Topic {
     pub description: String,
     pub created_at: String,
     pub updated_at: String,
+    pub color: Option<String>,
+    /// Tab order; lower positions come first.
+    pub position: i32,
+    /// One-character key that jumps to this topic from Normal mode.
+    pub alias: Option<String>,
+    /// Most tasks the topic may hold; adding past it is refused.
+    pub task_limit: Option<i32>,
+}
+
+impl Topic {
+    pub fn alias_char(&self) -> Option<char> {
+        self.alias.as_deref().and_then(|alias| alias.chars().next())
+    }
 }
 
 #[derive(Debug, Clone, Insertable)]
@@ -20,10 +36,28 @@ pub struct NewTopic<'a> {
     pub description: &'a str,
     pub created_at: &'a str,
     pub updated_at: &'a str,
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, AsChangeset)]
+#[diesel(table_name = topic)]
+pub struct TopicUpdate<'a> {
+    pub name: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub color: Option<&'a str>,
+    pub updated_at: &'a str,
 }
 
 #[derive(
-    Debug, Clone, Queryable, Selectable, Identifiable, Associations, Serialize, Deserialize,
+    Debug,
+    Clone,
+    Queryable,
+    QueryableByName,
+    Selectable,
+    Identifiable,
+    Associations,
+    Serialize,
+    Deserialize,
 )]
 #[diesel(table_name = task)]
 #[diesel(belongs_to(Topic))]
@@ -36,6 +70,79 @@ pub struct Task {
     pub favourite: bool,
     pub created_at: String,
     pub updated_at: String,
+    pub url: Option<String>,
+    pub pinned: bool,
+    /// `YYYY-MM-DD`, if the task has a deadline.
+    pub due_date: Option<String>,
+    /// Position in the manual sort order; spaced by 10 within a topic.
+    pub sort_weight: i32,
+    /// Hidden from the task lists instead of deleted; see `Config::soft_delete`.
+    #[serde(default)]
+    pub archived: bool,
+    /// Which code path created the task; see [`TaskSource`].
+    #[serde(default = "default_task_source")]
+    pub source: String,
+}
+
+fn default_task_source() -> String {
+    TaskSource::Tui.as_str().to_string()
+}
+
+/// Where a task was created, stored in `task.source` for auditing imports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TaskSource {
+    #[default]
+    Tui,
+    Cli,
+    ImportJson,
+    ImportCsv,
+    ImportOrg,
+}
+
+impl TaskSource {
+    pub const ALL: [TaskSource; 5] = [
+        TaskSource::Tui,
+        TaskSource::Cli,
+        TaskSource::ImportJson,
+        TaskSource::ImportCsv,
+        TaskSource::ImportOrg,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskSource::Tui => "tui",
+            TaskSource::Cli => "cli",
+            TaskSource::ImportJson => "import_json",
+            TaskSource::ImportCsv => "import_csv",
+            TaskSource::ImportOrg => "import_org",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|source| source.as_str() == value)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaskSource::Tui => "TUI",
+            TaskSource::Cli => "CLI",
+            TaskSource::ImportJson => "JSON import",
+            TaskSource::ImportCsv => "CSV import",
+            TaskSource::ImportOrg => "Org import",
+        }
+    }
+}
+
+/// A full-text search hit: the task plus an FTS5 `snippet()` with matches
+/// wrapped in `<b>…</b>`.
+#[derive(Debug, Clone, QueryableByName)]
+pub struct TaskSearchHit {
+    #[diesel(embed)]
+    pub task: Task,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub snippet: String,
 }
 
 #[derive(Debug, Clone, Insertable)]
@@ -46,8 +153,13 @@ pub struct NewTask<'a> {
     pub description: &'a str,
     pub completed: bool,
     pub favourite: bool,
+    pub pinned: bool,
     pub created_at: &'a str,
     pub updated_at: &'a str,
+    pub sort_weight: i32,
+    /// One of the [`TaskSource`] strings.
+    pub source: &'a str,
+    pub url: Option<&'a str>,
 }
 
 #[derive(Debug, Clone, AsChangeset)]
@@ -57,5 +169,419 @@ pub struct TaskUpdate<'a> {
     pub description: Option<&'a str>,
     pub completed: Option<bool>,
     pub favourite: Option<bool>,
+    pub pinned: Option<bool>,
     pub updated_at: &'a str,
 }
+
+/// A checklist item under a task.
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Associations, Serialize)]
+#[diesel(table_name = subtask)]
+#[diesel(belongs_to(Task, foreign_key = parent_task_id))]
+pub struct Subtask {
+    pub id: i32,
+    pub parent_task_id: i32,
+    pub name: String,
+    pub completed: bool,
+    /// Order within the parent task; lower positions come first.
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = subtask)]
+pub struct NewSubtask<'a> {
+    pub parent_task_id: i32,
+    pub name: &'a str,
+    pub completed: bool,
+    pub position: i32,
+}
+
+/// How one task relates to another in `task_link`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LinkType {
+    #[default]
+    Blocks,
+    BlockedBy,
+    RelatesTo,
+}
+
+impl LinkType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkType::Blocks => "blocks",
+            LinkType::BlockedBy => "blocked_by",
+            LinkType::RelatesTo => "relates_to",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "blocks" => Some(LinkType::Blocks),
+            "blocked_by" => Some(LinkType::BlockedBy),
+            "relates_to" => Some(LinkType::RelatesTo),
+            _ => None,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            LinkType::Blocks => LinkType::BlockedBy,
+            LinkType::BlockedBy => LinkType::RelatesTo,
+            LinkType::RelatesTo => LinkType::Blocks,
+        }
+    }
+
+    /// The same link read from the other task's side.
+    pub fn inverse(&self) -> Self {
+        match self {
+            LinkType::Blocks => LinkType::BlockedBy,
+            LinkType::BlockedBy => LinkType::Blocks,
+            LinkType::RelatesTo => LinkType::RelatesTo,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LinkType::Blocks => "Blocks",
+            LinkType::BlockedBy => "Blocked by",
+            LinkType::RelatesTo => "Relates to",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize)]
+#[diesel(table_name = task_link)]
+pub struct TaskLink {
+    pub id: i32,
+    pub source_task_id: i32,
+    pub target_task_id: i32,
+    pub link_type: String,
+}
+
+impl TaskLink {
+    /// The link type and other task id as seen from `task_id`, or `None` if
+    /// the link does not involve it.
+    pub fn relative_to(&self, task_id: i32) -> Option<(LinkType, i32)> {
+        let link_type = LinkType::parse(&self.link_type)?;
+        if self.source_task_id == task_id {
+            Some((link_type, self.target_task_id))
+        } else if self.target_task_id == task_id {
+            Some((link_type.inverse(), self.source_task_id))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = task_link)]
+pub struct NewTaskLink<'a> {
+    pub source_task_id: i32,
+    pub target_task_id: i32,
+    pub link_type: &'a str,
+}
+
+/// A linked task as seen from one side of a `task_link`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkedTask {
+    pub link_id: i32,
+    pub link_type: LinkType,
+    pub task_id: i32,
+    pub name: String,
+    pub completed: bool,
+}
+
+/// How an import treats a record whose name matches a task already in the
+/// target topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// Leave the existing task alone.
+    Skip,
+    /// Replace the existing task's description and flags.
+    Overwrite,
+    /// Insert the record as a new task regardless.
+    #[default]
+    Duplicate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeOutcome {
+    Insert,
+    Update,
+    Skip,
+}
+
+impl MergeStrategy {
+    pub fn outcome(self, exists: bool) -> MergeOutcome {
+        match (self, exists) {
+            (MergeStrategy::Skip, true) => MergeOutcome::Skip,
+            (MergeStrategy::Overwrite, true) => MergeOutcome::Update,
+            _ => MergeOutcome::Insert,
+        }
+    }
+}
+
+/// Counts from [`DbOperations::import_with_strategy`].
+///
+/// [`DbOperations::import_with_strategy`]: crate::db::task_manager::operations::DbOperations::import_with_strategy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportResult {
+    pub inserted: usize,
+    pub updated: usize,
+    pub skipped: usize,
+}
+
+impl ImportResult {
+    pub fn record(&mut self, outcome: MergeOutcome) {
+        match outcome {
+            MergeOutcome::Insert => self.inserted += 1,
+            MergeOutcome::Update => self.updated += 1,
+            MergeOutcome::Skip => self.skipped += 1,
+        }
+    }
+}
+
+impl fmt::Display for ImportResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "inserted {}, updated {}, skipped {}",
+            self.inserted, self.updated, self.skipped
+        )
+    }
+}
+
+/// Summary of one topic for the statistics popup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicStats {
+    pub topic: Topic,
+    pub total: i64,
+    pub completed: i64,
+    pub in_progress: i64,
+    pub overdue: i64,
+    pub latest_created: Option<String>,
+    pub latest_completed: Option<String>,
+    /// `YYYY-MM-DD` with the most tasks last updated that day, and that count.
+    pub most_active_day: Option<(String, usize)>,
+}
+
+/// Header of `task_manager_cli list`: how many of the listed tasks are done.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TopicSummary {
+    pub total: i64,
+    pub completed: i64,
+    /// `YYYY-MM-DD` the oldest incomplete task was created.
+    pub oldest_pending: Option<String>,
+}
+
+impl TopicSummary {
+    pub fn pending(&self) -> i64 {
+        self.total - self.completed
+    }
+}
+
+/// Whole-database summary printed by `task_manager_cli stats`. Archived
+/// tasks and the Favourites and Completed views are left out.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GlobalStats {
+    pub total_tasks: i64,
+    pub completed_tasks: i64,
+    pub total_topics: i64,
+    pub overdue_tasks: i64,
+    /// `YYYY-MM-DD` on which the most tasks were created, and that count.
+    pub most_productive_day: Option<(String, usize)>,
+    /// Topic with the latest change to itself or one of its tasks, and when.
+    pub most_recent_topic: Option<(String, String)>,
+}
+
+impl GlobalStats {
+    pub fn completion_percentage(&self) -> f64 {
+        if self.total_tasks == 0 {
+            0.0
+        } else {
+            self.completed_tasks as f64 * 100.0 / self.total_tasks as f64
+        }
+    }
+
+    pub fn average_tasks_per_topic(&self) -> f64 {
+        if self.total_topics == 0 {
+            0.0
+        } else {
+            self.total_tasks as f64 / self.total_topics as f64
+        }
+    }
+}
+
+/// Ordering applied to the task list of a topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortMode {
+    #[default]
+    Created,
+    Name,
+    Updated,
+    Completed,
+    /// By `sort_weight`, as arranged with Shift+Up/Down.
+    Manual,
+}
+
+impl SortMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortMode::Created => "created",
+            SortMode::Name => "name",
+            SortMode::Updated => "updated",
+            SortMode::Completed => "completed",
+            SortMode::Manual => "manual",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "name" => SortMode::Name,
+            "updated" => SortMode::Updated,
+            "completed" => SortMode::Completed,
+            "manual" => SortMode::Manual,
+            _ => SortMode::Created,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            SortMode::Created => SortMode::Name,
+            SortMode::Name => SortMode::Updated,
+            SortMode::Updated => SortMode::Completed,
+            SortMode::Completed => SortMode::Manual,
+            SortMode::Manual => SortMode::Created,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortMode::Created => "Created",
+            SortMode::Name => "Name",
+            SortMode::Updated => "Updated",
+            SortMode::Completed => "Status",
+            SortMode::Manual => "Manual",
+        }
+    }
+}
+
+/// Order of the topic tabs, chosen with `Ctrl+O` and kept in the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TopicOrder {
+    /// By `position`, as arranged with Shift+Left/Right.
+    #[default]
+    Manual,
+    Name,
+    Created,
+}
+
+impl TopicOrder {
+    pub fn next(&self) -> Self {
+        match self {
+            TopicOrder::Manual => TopicOrder::Name,
+            TopicOrder::Name => TopicOrder::Created,
+            TopicOrder::Created => TopicOrder::Manual,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TopicOrder::Manual => "Manual",
+            TopicOrder::Name => "Name",
+            TopicOrder::Created => "Created",
+        }
+    }
+}
+
+/// Which tasks of a topic are shown based on their completion state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompletionFilter {
+    #[default]
+    All,
+    Open,
+    Done,
+}
+
+impl CompletionFilter {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompletionFilter::All => "all",
+            CompletionFilter::Open => "open",
+            CompletionFilter::Done => "done",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "open" => CompletionFilter::Open,
+            "done" => CompletionFilter::Done,
+            _ => CompletionFilter::All,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            CompletionFilter::All => CompletionFilter::Open,
+            CompletionFilter::Open => CompletionFilter::Done,
+            CompletionFilter::Done => CompletionFilter::All,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CompletionFilter::All => "All",
+            CompletionFilter::Open => "Open",
+            CompletionFilter::Done => "Done",
+        }
+    }
+
+    /// Pinned tasks match every filter so they never drop out of view.
+    pub fn matches(&self, task: &Task) -> bool {
+        if task.pinned {
+            return true;
+        }
+        match self {
+            CompletionFilter::All => true,
+            CompletionFilter::Open => !task.completed,
+            CompletionFilter::Done => task.completed,
+        }
+    }
+}
+
+/// Per-topic list preferences persisted in `topic_settings`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TopicSettings {
+    pub sort_mode: SortMode,
+    pub sort_ascending: bool,
+    pub completion_filter: CompletionFilter,
+}
+
+impl Default for TopicSettings {
+    fn default() -> Self {
+        Self {
+            sort_mode: SortMode::default(),
+            sort_ascending: true,
+            completion_filter: CompletionFilter::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Insertable)]
+#[diesel(table_name = topic_settings)]
+pub struct TopicSettingsRow {
+    pub topic_id: i32,
+    pub sort_mode: String,
+    pub sort_ascending: bool,
+    pub completion_filter: String,
+}
+
+impl From<TopicSettingsRow> for TopicSettings {
+    fn from(row: TopicSettingsRow) -> Self {
+        Self {
+            sort_mode: SortMode::parse(&row.sort_mode),
+            sort_ascending: row.sort_ascending,
+            completion_filter: CompletionFilter::parse(&row.completion_filter),
+        }
+    }
+}
@@ -1,28 +1,49 @@
 use diesel::prelude::*;
-use std::collections::HashSet;
-use std::error::Error;
+use diesel_migrations::MigrationHarness;
+use slog::{debug, info, Logger};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
-use crate::db::schema::{task, topic};
-use crate::db::task_manager::models::{NewTask, NewTopic, Task, TaskUpdate, Topic};
-use crate::db::DbPool;
+use crate::db::schema::{subtask, task, task_link, topic, topic_settings};
+use crate::db::task_manager::models::{
+    GlobalStats, ImportResult, LinkType, LinkedTask, MergeOutcome, MergeStrategy, NewSubtask,
+    NewTask, NewTaskLink, NewTopic, SortMode, Subtask, Task, TaskLink, TaskSearchHit, TaskSource,
+    TaskUpdate, Topic, TopicOrder, TopicSettings, TopicSettingsRow, TopicStats, TopicSummary,
+    TopicUpdate,
+};
+use crate::db::{timed_query, DbPool};
+use crate::error::AppError;
+use crate::export::ImportRecord;
 
+#[derive(Clone)]
 pub struct DbOperations {
     pub pool: DbPool,
+    logger: Logger,
     special_topics: HashSet<String>,
+    slow_query_threshold: Duration,
 }
 
 impl DbOperations {
-    pub fn new(pool: DbPool) -> Self {
+    pub fn new(pool: DbPool, logger: Logger) -> Self {
         let mut special_topics = HashSet::new();
         special_topics.insert("Favourites".to_string());
         special_topics.insert("Default".to_string());
+        special_topics.insert("Completed".to_string());
 
         Self {
             pool,
+            logger,
             special_topics,
+            slow_query_threshold: crate::db::slow_query_threshold(),
         }
     }
 
+    /// Overrides [`crate::db::SLOW_QUERY_MS_ENV`] for this handle.
+    pub fn with_slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_query_threshold = threshold;
+        self
+    }
+
     pub fn is_special_topic(&self, name: &str) -> bool {
         self.special_topics.contains(name)
     }
@@ -32,119 +53,1072 @@ impl DbOperations {
         &self,
     ) -> Result<
         diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<SqliteConnection>>,
-        Box<dyn Error>,
+        AppError,
     > {
         Ok(self.pool.get()?)
     }
 
-    pub fn load_topics(&self) -> Result<Vec<Topic>, Box<dyn Error>> {
+    pub fn load_topics(&self, order: TopicOrder) -> Result<Vec<Topic>, AppError> {
+        timed_query!(self, "load_topics");
+        let mut conn = self.get_conn()?;
+
+        let query = topic::table.into_boxed();
+        let query = match order {
+            TopicOrder::Manual => query.order_by((topic::position.asc(), topic::id.asc())),
+            TopicOrder::Name => query.order_by((
+                diesel::dsl::sql::<diesel::sql_types::Text>("name COLLATE NOCASE").asc(),
+                topic::id.asc(),
+            )),
+            TopicOrder::Created => query.order_by((topic::created_at.asc(), topic::id.asc())),
+        };
+        Ok(query.load::<Topic>(&mut conn)?)
+    }
+
+    /// Rewrites the manual order alphabetically, ignoring case. Built-in
+    /// topics keep their places at the front, as with Shift+Left/Right.
+    pub fn reorder_topics_by_name(&self) -> Result<(), AppError> {
+        timed_query!(self, "reorder_topics_by_name");
+        let (builtin, mut named): (Vec<Topic>, Vec<Topic>) = self
+            .load_topics(TopicOrder::Manual)?
+            .into_iter()
+            .partition(|topic| self.is_special_topic(&topic.name));
+        named.sort_by_cached_key(|topic| topic.name.to_lowercase());
+
+        let mut conn = self.get_conn()?;
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            for (position, topic) in (1..).zip(builtin.iter().chain(&named)) {
+                diesel::update(topic::table.find(topic.id))
+                    .set(topic::position.eq(position))
+                    .execute(conn)?;
+            }
+            Ok(())
+        })?;
+        info!(self.logger, "topics reordered by name"; "count" => builtin.len() + named.len());
+        Ok(())
+    }
+
+    /// Topics whose name or description contains `query`, ignoring ASCII
+    /// case, in tab order. An empty query matches every topic.
+    pub fn search_topics(&self, query: &str) -> Result<Vec<Topic>, AppError> {
+        timed_query!(self, "search_topics");
+        let mut conn = self.get_conn()?;
+        let escaped = query
+            .trim()
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        let pattern = format!("%{escaped}%");
+
+        Ok(topic::table
+            .filter(
+                topic::name
+                    .like(&pattern)
+                    .escape('\\')
+                    .or(topic::description.like(&pattern).escape('\\')),
+            )
+            .order_by((topic::position.asc(), topic::id.asc()))
+            .load::<Topic>(&mut conn)?)
+    }
+
+    pub fn add_topic(&self, name: &str, description: &str) -> Result<Topic, AppError> {
+        timed_query!(self, "add_topic");
         let mut conn = self.get_conn()?;
+        let topic = Self::insert_topic(&mut conn, name, description)?;
+        info!(self.logger, "topic added"; "topic_id" => topic.id, "name" => name);
+        Ok(topic)
+    }
 
-        Ok(topic::table.order_by(topic::id).load::<Topic>(&mut conn)?)
+    /// The topic called `name`, created at the end of the tab order if there
+    /// is none yet. The lookup and insert share an IMMEDIATE transaction,
+    /// which takes the write lock before the lookup, so two imports naming
+    /// the same new topic cannot both create it. Names match ignoring ASCII
+    /// case, as `idx_topic_name` compares them.
+    pub fn get_or_create_topic(&self, name: &str) -> Result<Topic, AppError> {
+        timed_query!(self, "get_or_create_topic");
+        let mut conn = self.get_conn()?;
+        let (topic, created) =
+            conn.immediate_transaction::<_, diesel::result::Error, _>(|conn| {
+                let existing = topic::table
+                    .filter(
+                        diesel::dsl::sql::<diesel::sql_types::Bool>("topic.name = ")
+                            .bind::<diesel::sql_types::Text, _>(name)
+                            .sql(" COLLATE NOCASE"),
+                    )
+                    .order_by(topic::id.asc())
+                    .first::<Topic>(conn)
+                    .optional()?;
+                match existing {
+                    Some(topic) => Ok((topic, false)),
+                    None => Ok((Self::insert_topic(conn, name, "")?, true)),
+                }
+            })?;
+        if created {
+            info!(self.logger, "topic added"; "topic_id" => topic.id, "name" => name);
+        }
+        Ok(topic)
     }
 
-    pub fn add_topic(&self, name: &str, description: &str) -> Result<Topic, Box<dyn Error>> {
+    fn insert_topic(
+        conn: &mut SqliteConnection,
+        name: &str,
+        description: &str,
+    ) -> Result<Topic, diesel::result::Error> {
         let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let last_position: Option<i32> = topic::table
+            .select(diesel::dsl::max(topic::position))
+            .first(conn)?;
         let new_topic = NewTopic {
             name,
             description,
             created_at: &now,
             updated_at: &now,
+            position: last_position.map_or(1, |position| position + 1),
         };
 
-        let mut conn = self.get_conn()?;
-
         diesel::insert_into(topic::table)
             .values(&new_topic)
-            .execute(&mut conn)?;
+            .execute(conn)?;
 
-        Ok(topic::table
+        topic::table
             .order_by(topic::id.desc())
             .limit(1)
-            .get_result::<Topic>(&mut conn)?)
+            .get_result::<Topic>(conn)
+    }
+
+    pub fn update_topic(&self, topic_id: i32, update: TopicUpdate) -> Result<Topic, AppError> {
+        timed_query!(self, "update_topic");
+        let mut conn = self.get_conn()?;
+
+        let current: Topic = topic::table
+            .find(topic_id)
+            .first(&mut conn)
+            .optional()?
+            .ok_or(AppError::TopicNotFound(topic_id))?;
+        if update.name.is_some_and(|name| name != current.name)
+            && self.is_special_topic(&current.name)
+        {
+            return Err(AppError::ProtectedTopic(current.name));
+        }
+
+        diesel::update(topic::table.find(topic_id))
+            .set(update)
+            .execute(&mut conn)?;
+
+        let topic = topic::table.find(topic_id).get_result::<Topic>(&mut conn)?;
+        info!(self.logger, "topic updated"; "topic_id" => topic_id, "name" => &topic.name);
+        Ok(topic)
+    }
+
+    /// Sets or clears the key that jumps to the topic.
+    pub fn set_topic_alias(&self, topic_id: i32, alias: Option<char>) -> Result<Topic, AppError> {
+        timed_query!(self, "set_topic_alias");
+        let mut conn = self.get_conn()?;
+        let alias = alias.map(String::from);
+
+        let updated = diesel::update(topic::table.find(topic_id))
+            .set(topic::alias.eq(&alias))
+            .execute(&mut conn)?;
+        if updated == 0 {
+            return Err(AppError::TopicNotFound(topic_id));
+        }
+
+        info!(self.logger, "topic alias set"; "topic_id" => topic_id, "alias" => &alias);
+        Ok(topic::table.find(topic_id).get_result::<Topic>(&mut conn)?)
     }
 
-    pub fn delete_topic(&self, topic_id: i32) -> Result<usize, Box<dyn Error>> {
+    /// Caps the number of tasks in the topic; `None` removes the cap.
+    pub fn set_topic_task_limit(
+        &self,
+        topic_id: i32,
+        limit: Option<i32>,
+    ) -> Result<Topic, AppError> {
+        timed_query!(self, "set_topic_task_limit");
+        let mut conn = self.get_conn()?;
+
+        let updated = diesel::update(topic::table.find(topic_id))
+            .set(topic::task_limit.eq(limit))
+            .execute(&mut conn)?;
+        if updated == 0 {
+            return Err(AppError::TopicNotFound(topic_id));
+        }
+
+        info!(self.logger, "topic task limit set"; "topic_id" => topic_id, "limit" => limit);
+        Ok(topic::table.find(topic_id).get_result::<Topic>(&mut conn)?)
+    }
+
+    /// Unarchived tasks in the topic, the number checked against its
+    /// `task_limit`.
+    pub fn count_tasks_in_topic(&self, topic_id: i32) -> Result<i64, AppError> {
+        timed_query!(self, "count_tasks_in_topic");
+        let mut conn = self.get_conn()?;
+
+        Ok(task::table
+            .filter(task::topic_id.eq(topic_id))
+            .filter(task::archived.eq(false))
+            .count()
+            .get_result::<i64>(&mut conn)?)
+    }
+
+    /// Swaps the topic with its left neighbour. Returns `false` when it is
+    /// already first or the neighbour is on the other side of the built-in group.
+    pub fn move_topic_left(&self, topic_id: i32) -> Result<bool, AppError> {
+        timed_query!(self, "move_topic_left");
+        self.move_topic(topic_id, -1)
+    }
+
+    /// Swaps the topic with its right neighbour; see [`Self::move_topic_left`].
+    pub fn move_topic_right(&self, topic_id: i32) -> Result<bool, AppError> {
+        timed_query!(self, "move_topic_right");
+        self.move_topic(topic_id, 1)
+    }
+
+    fn move_topic(&self, topic_id: i32, offset: isize) -> Result<bool, AppError> {
+        let topics = self.load_topics(TopicOrder::Manual)?;
+        let index = topics
+            .iter()
+            .position(|topic| topic.id == topic_id)
+            .ok_or(AppError::TopicNotFound(topic_id))?;
+        let Some(neighbour) = index
+            .checked_add_signed(offset)
+            .and_then(|neighbour| topics.get(neighbour))
+        else {
+            return Ok(false);
+        };
+        let current = &topics[index];
+        // Built-in topics stay grouped together: they only swap with each other.
+        if self.is_special_topic(&current.name) != self.is_special_topic(&neighbour.name) {
+            return Ok(false);
+        }
+
+        let mut conn = self.get_conn()?;
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            diesel::update(topic::table.find(current.id))
+                .set(topic::position.eq(neighbour.position))
+                .execute(conn)?;
+            diesel::update(topic::table.find(neighbour.id))
+                .set(topic::position.eq(current.position))
+                .execute(conn)?;
+            Ok(())
+        })?;
+        info!(self.logger, "topic moved";
+            "topic_id" => current.id, "position" => neighbour.position);
+        Ok(true)
+    }
+
+    pub fn delete_topic(&self, topic_id: i32) -> Result<usize, AppError> {
+        timed_query!(self, "delete_topic");
         let mut conn = self.get_conn()?;
 
         // First get the topic to check if it's a special topic
         let topic: Topic = topic::table
             .filter(topic::id.eq(topic_id))
-            .first(&mut conn)?;
+            .first(&mut conn)
+            .optional()?
+            .ok_or(AppError::TopicNotFound(topic_id))?;
 
         if self.is_special_topic(&topic.name) {
-            // Don't delete special topics
-            return Ok(0);
+            return Err(AppError::ProtectedTopic(topic.name));
         }
 
-        Ok(diesel::delete(topic::table.find(topic_id)).execute(&mut conn)?)
+        diesel::delete(topic_settings::table.find(topic_id)).execute(&mut conn)?;
+        let deleted = diesel::delete(topic::table.find(topic_id)).execute(&mut conn)?;
+        info!(self.logger, "topic deleted"; "topic_id" => topic_id, "name" => &topic.name);
+        Ok(deleted)
+    }
+
+    /// Moves every task from `source_id` into `target_id` and deletes the
+    /// source topic. Returns the number of tasks moved.
+    pub fn merge_topics(&self, source_id: i32, target_id: i32) -> Result<usize, AppError> {
+        timed_query!(self, "merge_topics");
+        if source_id == target_id {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Cannot merge a topic into itself",
+            )
+            .into());
+        }
+
+        let mut conn = self.get_conn()?;
+        for topic_id in [source_id, target_id] {
+            let topic: Topic = topic::table
+                .find(topic_id)
+                .first(&mut conn)
+                .optional()?
+                .ok_or(AppError::TopicNotFound(topic_id))?;
+            if self.is_special_topic(&topic.name) {
+                return Err(AppError::ProtectedTopic(topic.name));
+            }
+        }
+
+        let moved = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let moved = diesel::update(task::table.filter(task::topic_id.eq(source_id)))
+                .set(task::topic_id.eq(target_id))
+                .execute(conn)?;
+            diesel::delete(topic_settings::table.find(source_id)).execute(conn)?;
+            diesel::delete(topic::table.find(source_id)).execute(conn)?;
+            Ok(moved)
+        })?;
+        info!(self.logger, "topics merged";
+            "source_id" => source_id, "target_id" => target_id, "tasks_moved" => moved);
+        Ok(moved)
+    }
+
+    // Topic Settings Operations
+    pub fn load_topic_settings(&self, topic_id: i32) -> Result<TopicSettings, AppError> {
+        timed_query!(self, "load_topic_settings");
+        let mut conn = self.get_conn()?;
+
+        Ok(topic_settings::table
+            .find(topic_id)
+            .select(TopicSettingsRow::as_select())
+            .first(&mut conn)
+            .optional()?
+            .map(TopicSettings::from)
+            .unwrap_or_default())
+    }
+
+    pub fn save_topic_settings(
+        &self,
+        topic_id: i32,
+        settings: &TopicSettings,
+    ) -> Result<(), AppError> {
+        timed_query!(self, "save_topic_settings");
+        let mut conn = self.get_conn()?;
+        let row = TopicSettingsRow {
+            topic_id,
+            sort_mode: settings.sort_mode.as_str().to_string(),
+            sort_ascending: settings.sort_ascending,
+            completion_filter: settings.completion_filter.as_str().to_string(),
+        };
+
+        diesel::replace_into(topic_settings::table)
+            .values(&row)
+            .execute(&mut conn)?;
+        debug!(self.logger, "topic settings saved";
+            "topic_id" => topic_id, "sort_mode" => &row.sort_mode,
+            "completion_filter" => &row.completion_filter);
+        Ok(())
     }
 
     // Task Operations
-    pub fn load_tasks(&self, current_topic: &Topic) -> Result<Vec<Task>, Box<dyn Error>> {
+    pub fn load_tasks(&self, current_topic: &Topic) -> Result<Vec<Task>, AppError> {
+        timed_query!(self, "load_tasks");
+        self.load_tasks_sorted(current_topic, SortMode::Created, true)
+    }
+
+    pub fn load_tasks_sorted(
+        &self,
+        current_topic: &Topic,
+        sort_mode: SortMode,
+        ascending: bool,
+    ) -> Result<Vec<Task>, AppError> {
+        timed_query!(self, "load_tasks_sorted");
         let mut conn = self.get_conn()?;
 
-        Ok(match current_topic.name.as_str() {
-            "Favourites" => task::table
-                .filter(task::favourite.eq(true))
-                .order_by(task::id)
-                .load::<Task>(&mut conn)?,
-            "Completed" => task::table
-                .filter(task::completed.eq(true))
-                .order_by(task::id)
-                .load::<Task>(&mut conn)?,
-            "Default" => task::table.order_by(task::id).load::<Task>(&mut conn)?,
-            _ => task::table
-                .filter(task::topic_id.eq(current_topic.id))
-                .order_by(task::id)
+        Ok(sorted_tasks_query(current_topic, sort_mode, ascending).load::<Task>(&mut conn)?)
+    }
+
+    /// One page of [`load_tasks_sorted`](Self::load_tasks_sorted) narrowed by
+    /// `filter`: at most `page_size` tasks, skipping the first
+    /// `page * page_size` that pass it.
+    pub fn load_tasks_page(
+        &self,
+        current_topic: &Topic,
+        sort_mode: SortMode,
+        ascending: bool,
+        page: usize,
+        page_size: usize,
+        filter: impl FnOnce(TaskQuery) -> TaskQuery,
+    ) -> Result<Vec<Task>, AppError> {
+        timed_query!(self, "load_tasks_page");
+        let mut conn = self.get_conn()?;
+        let limit = i64::try_from(page_size).unwrap_or(i64::MAX);
+        let offset = i64::try_from(page.saturating_mul(page_size)).unwrap_or(i64::MAX);
+
+        Ok(
+            filter(sorted_tasks_query(current_topic, sort_mode, ascending))
+                .limit(limit)
+                .offset(offset)
                 .load::<Task>(&mut conn)?,
+        )
+    }
+
+    /// Tasks listed under `current_topic` that pass `filter`, i.e. the rows
+    /// [`load_tasks_page`](Self::load_tasks_page) pages through.
+    pub fn count_listed_tasks(
+        &self,
+        current_topic: &Topic,
+        filter: impl FnOnce(TaskQuery) -> TaskQuery,
+    ) -> Result<i64, AppError> {
+        timed_query!(self, "count_listed_tasks");
+        let mut conn = self.get_conn()?;
+
+        Ok(filter(topic_tasks_query(current_topic))
+            .count()
+            .get_result(&mut conn)?)
+    }
+
+    /// Where `task_id` falls in the sorted task list of `current_topic`
+    /// narrowed by `filter`, if it is listed there at all.
+    pub fn task_position(
+        &self,
+        current_topic: &Topic,
+        sort_mode: SortMode,
+        ascending: bool,
+        task_id: i32,
+        filter: impl FnOnce(TaskQuery) -> TaskQuery,
+    ) -> Result<Option<usize>, AppError> {
+        timed_query!(self, "task_position");
+        let mut conn = self.get_conn()?;
+
+        let ids = filter(sorted_tasks_query(current_topic, sort_mode, ascending))
+            .select(task::id)
+            .load::<i32>(&mut conn)?;
+        Ok(ids.iter().position(|id| *id == task_id))
+    }
+
+    /// Number of tasks in each topic, keyed by topic id. Topics without tasks
+    /// are absent from the map.
+    pub fn count_tasks_by_topic(&self) -> Result<HashMap<i32, i64>, AppError> {
+        timed_query!(self, "count_tasks_by_topic");
+        let mut conn = self.get_conn()?;
+
+        Ok(task::table
+            .filter(task::archived.eq(false))
+            .group_by(task::topic_id)
+            .select((task::topic_id, diesel::dsl::count_star()))
+            .load::<(i32, i64)>(&mut conn)?
+            .into_iter()
+            .collect())
+    }
+
+    /// Open tasks past their due date, per topic id. Topics without any are
+    /// left out.
+    pub fn count_overdue_tasks_per_topic(&self) -> Result<HashMap<i32, i64>, AppError> {
+        timed_query!(self, "count_overdue_tasks_per_topic");
+        let mut conn = self.get_conn()?;
+        let today = diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::Text>>(
+            "DATE('now', 'localtime')",
+        );
+
+        Ok(task::table
+            .filter(task::archived.eq(false))
+            .filter(task::completed.eq(false))
+            .filter(task::due_date.lt(today))
+            .group_by(task::topic_id)
+            .select((task::topic_id, diesel::dsl::count_star()))
+            .load::<(i32, i64)>(&mut conn)?
+            .into_iter()
+            .collect())
+    }
+
+    /// `(total, completed)` task counts across all topics; archived tasks
+    /// are left out.
+    pub fn count_tasks(&self) -> Result<(i64, i64), AppError> {
+        timed_query!(self, "count_tasks");
+        let mut conn = self.get_conn()?;
+
+        let total = task::table
+            .filter(task::archived.eq(false))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+        let completed = task::table
+            .filter(task::archived.eq(false))
+            .filter(task::completed.eq(true))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+        Ok((total, completed))
+    }
+
+    /// Task counts, latest tasks and the busiest day for one topic. Tasks have
+    /// no completion timestamp, so "latest completed" is the completed task
+    /// updated most recently. Archived tasks are left out.
+    pub fn topic_stats(&self, topic_id: i32) -> Result<TopicStats, AppError> {
+        timed_query!(self, "topic_stats");
+        let mut conn = self.get_conn()?;
+
+        let topic = topic::table
+            .find(topic_id)
+            .get_result::<Topic>(&mut conn)
+            .optional()?
+            .ok_or(AppError::TopicNotFound(topic_id))?;
+        let total = task::table
+            .filter(task::topic_id.eq(topic_id))
+            .filter(task::archived.eq(false))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+        let completed = task::table
+            .filter(task::topic_id.eq(topic_id))
+            .filter(task::archived.eq(false))
+            .filter(task::completed.eq(true))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+        let today = diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::Text>>(
+            "DATE('now', 'localtime')",
+        );
+        let overdue = task::table
+            .filter(task::topic_id.eq(topic_id))
+            .filter(task::archived.eq(false))
+            .filter(task::completed.eq(false))
+            .filter(task::due_date.lt(today))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+        let latest_created = task::table
+            .filter(task::topic_id.eq(topic_id))
+            .filter(task::archived.eq(false))
+            .order_by((task::created_at.desc(), task::id.desc()))
+            .select(task::name)
+            .first::<String>(&mut conn)
+            .optional()?;
+        let latest_completed = task::table
+            .filter(task::topic_id.eq(topic_id))
+            .filter(task::archived.eq(false))
+            .filter(task::completed.eq(true))
+            .order_by((task::updated_at.desc(), task::id.desc()))
+            .select(task::name)
+            .first::<String>(&mut conn)
+            .optional()?;
+        let updated_days = task::table
+            .filter(task::topic_id.eq(topic_id))
+            .filter(task::archived.eq(false))
+            .select(task::updated_at)
+            .load::<String>(&mut conn)?;
+
+        let mut per_day: HashMap<&str, usize> = HashMap::new();
+        for updated_at in &updated_days {
+            *per_day
+                .entry(updated_at.get(..10).unwrap_or(updated_at))
+                .or_default() += 1;
+        }
+        // Ties go to the most recent day.
+        let most_active_day = per_day
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)))
+            .map(|(day, count)| (day.to_string(), count));
+
+        Ok(TopicStats {
+            topic,
+            total,
+            completed,
+            in_progress: total - completed,
+            overdue,
+            latest_created,
+            latest_completed,
+            most_active_day,
         })
     }
 
-    pub fn add_task(
+    /// Counts for the `list` header, over the tasks the topic lists (every
+    /// unarchived task for `None`), in one aggregate query.
+    pub fn topic_summary(&self, topic_id: Option<i32>) -> Result<TopicSummary, AppError> {
+        timed_query!(self, "topic_summary");
+        let mut conn = self.get_conn()?;
+
+        let query = match topic_id {
+            Some(topic_id) => {
+                let topic = topic::table
+                    .find(topic_id)
+                    .get_result::<Topic>(&mut conn)
+                    .optional()?
+                    .ok_or(AppError::TopicNotFound(topic_id))?;
+                topic_tasks_query(&topic)
+            }
+            None => task::table.filter(task::archived.eq(false)).into_boxed(),
+        };
+        let (total, completed, oldest_pending) = query
+            .select((
+                diesel::dsl::count_star(),
+                diesel::dsl::sql::<diesel::sql_types::BigInt>("COALESCE(SUM(completed), 0)"),
+                diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::Text>>(
+                    "MIN(CASE WHEN completed = 0 THEN created_at END)",
+                ),
+            ))
+            .get_result::<(i64, i64, Option<String>)>(&mut conn)?;
+        Ok(TopicSummary {
+            total,
+            completed,
+            oldest_pending: oldest_pending
+                .map(|created_at| created_at.get(..10).unwrap_or(&created_at).to_string()),
+        })
+    }
+
+    /// Counts across every topic for `task_manager_cli stats`.
+    pub fn compute_global_stats(&self) -> Result<GlobalStats, AppError> {
+        timed_query!(self, "compute_global_stats");
+        let mut conn = self.get_conn()?;
+
+        let total_tasks = task::table
+            .filter(task::archived.eq(false))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+        let completed_tasks = task::table
+            .filter(task::archived.eq(false))
+            .filter(task::completed.eq(true))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+        let total_topics = topic::table
+            .filter(topic::name.ne_all(["Favourites", "Completed"]))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+        let today = diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::Text>>(
+            "DATE('now', 'localtime')",
+        );
+        let overdue_tasks = task::table
+            .filter(task::archived.eq(false))
+            .filter(task::completed.eq(false))
+            .filter(task::due_date.lt(today))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+        let created_days = task::table
+            .filter(task::archived.eq(false))
+            .select(task::created_at)
+            .load::<String>(&mut conn)?;
+        let latest_task_update = task::table
+            .inner_join(topic::table)
+            .filter(task::archived.eq(false))
+            .order_by((task::updated_at.desc(), task::id.desc()))
+            .select((topic::name, task::updated_at))
+            .first::<(String, String)>(&mut conn)
+            .optional()?;
+        let latest_topic_update = topic::table
+            .filter(topic::name.ne_all(["Favourites", "Completed"]))
+            .order_by((topic::updated_at.desc(), topic::id.desc()))
+            .select((topic::name, topic::updated_at))
+            .first::<(String, String)>(&mut conn)
+            .optional()?;
+
+        let mut per_day: HashMap<&str, usize> = HashMap::new();
+        for created_at in &created_days {
+            *per_day
+                .entry(created_at.get(..10).unwrap_or(created_at))
+                .or_default() += 1;
+        }
+        // Ties go to the most recent day, as in the topic statistics.
+        let most_productive_day = per_day
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)))
+            .map(|(day, count)| (day.to_string(), count));
+        let most_recent_topic = match (latest_task_update, latest_topic_update) {
+            (Some(task), Some(topic)) => Some(if topic.1 > task.1 { topic } else { task }),
+            (task, topic) => task.or(topic),
+        };
+
+        Ok(GlobalStats {
+            total_tasks,
+            completed_tasks,
+            total_topics,
+            overdue_tasks,
+            most_productive_day,
+            most_recent_topic,
+        })
+    }
+
+    pub fn count_topics(&self) -> Result<i64, AppError> {
+        timed_query!(self, "count_topics");
+        let mut conn = self.get_conn()?;
+
+        Ok(topic::table.count().get_result::<i64>(&mut conn)?)
+    }
+
+    /// Full-text search over task names and descriptions, best matches first.
+    /// Each term is matched as a prefix; `topic_id` narrows results to one topic.
+    pub fn fts_search_with_snippets(
+        &self,
+        query: &str,
+        topic_id: Option<i32>,
+    ) -> Result<Vec<(Task, String)>, AppError> {
+        timed_query!(self, "fts_search_with_snippets");
+        let match_query = query
+            .split_whitespace()
+            .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if match_query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.get_conn()?;
+        let hits = diesel::sql_query(
+            "SELECT task.*, snippet(task_fts, -1, '<b>', '</b>', '…', 12) AS snippet \
+             FROM task_fts JOIN task ON task.id = task_fts.rowid \
+             WHERE task_fts MATCH ? AND NOT task.archived \
+             AND (? IS NULL OR task.topic_id = ?) \
+             ORDER BY rank LIMIT 50",
+        )
+        .bind::<diesel::sql_types::Text, _>(&match_query)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(topic_id)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(topic_id)
+        .load::<TaskSearchHit>(&mut conn)?;
+        debug!(self.logger, "task search";
+            "query" => query, "topic_id" => topic_id, "hits" => hits.len());
+
+        Ok(hits
+            .into_iter()
+            .map(|hit| (hit.task, hit.snippet))
+            .collect())
+    }
+
+    pub fn add_task(&self, topic_id: i32, name: &str, description: &str) -> Result<Task, AppError> {
+        timed_query!(self, "add_task");
+        self.add_task_from(topic_id, name, description, TaskSource::Tui)
+    }
+
+    /// Adds a task, recording which code path created it.
+    pub fn add_task_from(
+        &self,
+        topic_id: i32,
+        name: &str,
+        description: &str,
+        source: TaskSource,
+    ) -> Result<Task, AppError> {
+        timed_query!(self, "add_task_from");
+        self.insert_task(topic_id, name, description, None, source)
+    }
+
+    /// Like [`Self::add_task`], with the URL set by the same insert.
+    pub fn add_task_with_url(
+        &self,
+        topic_id: i32,
+        name: &str,
+        description: &str,
+        url: Option<&str>,
+    ) -> Result<Task, AppError> {
+        timed_query!(self, "add_task_with_url");
+        self.insert_task(topic_id, name, description, url, TaskSource::Tui)
+    }
+
+    fn insert_task(
         &self,
         topic_id: i32,
         name: &str,
         description: &str,
-    ) -> Result<Task, Box<dyn Error>> {
+        url: Option<&str>,
+        source: TaskSource,
+    ) -> Result<Task, AppError> {
         let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-        let new_task = NewTask {
-            topic_id,
-            name,
-            description,
-            completed: false,
-            favourite: false,
-            created_at: &now,
-            updated_at: &now,
-        };
+        let mut conn = self.get_conn()?;
+
+        let task = conn.immediate_transaction::<_, diesel::result::Error, _>(|conn| {
+            let new_task = NewTask {
+                topic_id,
+                name,
+                description,
+                completed: false,
+                favourite: false,
+                pinned: false,
+                created_at: &now,
+                updated_at: &now,
+                sort_weight: next_sort_weight(conn, topic_id)?,
+                source: source.as_str(),
+                url,
+            };
+            diesel::insert_into(task::table)
+                .values(&new_task)
+                .execute(conn)?;
+            task::table
+                .order_by(task::id.desc())
+                .limit(1)
+                .get_result::<Task>(conn)
+        })?;
+        info!(self.logger, "task added";
+            "task_id" => task.id, "topic_id" => topic_id, "name" => name,
+            "source" => source.as_str());
+        Ok(task)
+    }
 
+    /// Inserts all tasks in one transaction; returns the number inserted.
+    pub fn bulk_insert_tasks(&self, tasks: &[NewTask]) -> Result<usize, AppError> {
+        timed_query!(self, "bulk_insert_tasks");
+        if tasks.is_empty() {
+            return Ok(0);
+        }
         let mut conn = self.get_conn()?;
 
-        diesel::insert_into(task::table)
-            .values(&new_task)
+        let inserted = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            diesel::insert_into(task::table).values(tasks).execute(conn)
+        })?;
+        info!(self.logger, "tasks bulk inserted"; "count" => inserted);
+        Ok(inserted)
+    }
+
+    /// Names of the tasks filed directly under `topic_id`.
+    pub fn task_names_in_topic(&self, topic_id: i32) -> Result<HashSet<String>, AppError> {
+        timed_query!(self, "task_names_in_topic");
+        let mut conn = self.get_conn()?;
+
+        Ok(task::table
+            .filter(task::topic_id.eq(topic_id))
+            .select(task::name)
+            .load::<String>(&mut conn)?
+            .into_iter()
+            .collect())
+    }
+
+    /// Imports `tasks` into `topic_id` in one transaction, resolving name
+    /// clashes with existing tasks (and earlier records) per `strategy`.
+    /// Inserted tasks are tagged with `source`.
+    pub fn import_with_strategy(
+        &self,
+        tasks: &[ImportRecord],
+        topic_id: i32,
+        strategy: MergeStrategy,
+        source: TaskSource,
+    ) -> Result<ImportResult, AppError> {
+        timed_query!(self, "import_with_strategy");
+        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let mut conn = self.get_conn()?;
+
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let mut existing: HashMap<String, i32> = task::table
+                .filter(task::topic_id.eq(topic_id))
+                .select((task::name, task::id))
+                .load::<(String, i32)>(conn)?
+                .into_iter()
+                .collect();
+            let mut result = ImportResult::default();
+            let mut sort_weight = next_sort_weight(conn, topic_id)?;
+
+            for record in tasks {
+                let name = record.name.trim();
+                let description = record.description.trim();
+                let outcome = strategy.outcome(existing.contains_key(name));
+                match outcome {
+                    MergeOutcome::Skip => {}
+                    MergeOutcome::Update => {
+                        diesel::update(task::table.find(existing[name]))
+                            .set(TaskUpdate {
+                                name: None,
+                                description: Some(description),
+                                completed: Some(record.completed),
+                                favourite: Some(record.favourite),
+                                pinned: None,
+                                updated_at: &now,
+                            })
+                            .execute(conn)?;
+                    }
+                    MergeOutcome::Insert => {
+                        diesel::insert_into(task::table)
+                            .values(&NewTask {
+                                topic_id,
+                                name,
+                                description,
+                                completed: record.completed,
+                                favourite: record.favourite,
+                                pinned: false,
+                                created_at: &now,
+                                updated_at: &now,
+                                sort_weight,
+                                source: source.as_str(),
+                                url: None,
+                            })
+                            .execute(conn)?;
+                        sort_weight += SORT_WEIGHT_STEP;
+                        let task_id = task::table
+                            .select(task::id)
+                            .order_by(task::id.desc())
+                            .first::<i32>(conn)?;
+                        existing.entry(name.to_string()).or_insert(task_id);
+                    }
+                }
+                result.record(outcome);
+            }
+            Ok(result)
+        })?;
+        info!(self.logger, "tasks imported";
+            "topic_id" => topic_id, "strategy" => ?strategy,
+            "inserted" => result.inserted, "updated" => result.updated,
+            "skipped" => result.skipped);
+        Ok(result)
+    }
+
+    /// Inserts each task followed by its `(name, completed)` subtasks, all in
+    /// one transaction; returns the number of tasks and subtasks inserted.
+    pub fn bulk_insert_tasks_with_subtasks(
+        &self,
+        tasks: &[(NewTask, Vec<(&str, bool)>)],
+    ) -> Result<(usize, usize), AppError> {
+        timed_query!(self, "bulk_insert_tasks_with_subtasks");
+        let mut conn = self.get_conn()?;
+
+        let counts = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let mut subtask_count = 0;
+            for (new_task, subtasks) in tasks {
+                diesel::insert_into(task::table)
+                    .values(new_task)
+                    .execute(conn)?;
+                let task_id = task::table
+                    .select(task::id)
+                    .order_by(task::id.desc())
+                    .first::<i32>(conn)?;
+                let new_subtasks: Vec<NewSubtask> = subtasks
+                    .iter()
+                    .zip(1..)
+                    .map(|((name, completed), position)| NewSubtask {
+                        parent_task_id: task_id,
+                        name,
+                        completed: *completed,
+                        position,
+                    })
+                    .collect();
+                subtask_count += diesel::insert_into(subtask::table)
+                    .values(&new_subtasks)
+                    .execute(conn)?;
+            }
+            Ok((tasks.len(), subtask_count))
+        })?;
+        info!(self.logger, "tasks with subtasks bulk inserted";
+            "tasks" => counts.0, "subtasks" => counts.1);
+        Ok(counts)
+    }
+
+    /// Sets or clears the due date (`YYYY-MM-DD`).
+    pub fn set_task_due_date(
+        &self,
+        task_id: i32,
+        due_date: Option<&str>,
+    ) -> Result<Task, AppError> {
+        timed_query!(self, "set_task_due_date");
+        let mut conn = self.get_conn()?;
+
+        let updated = diesel::update(task::table.find(task_id))
+            .set(task::due_date.eq(due_date))
             .execute(&mut conn)?;
+        if updated == 0 {
+            return Err(AppError::TaskNotFound(task_id));
+        }
+
+        info!(self.logger, "task due date set"; "task_id" => task_id, "due_date" => due_date);
+        Ok(task::table.find(task_id).get_result::<Task>(&mut conn)?)
+    }
+
+    /// Open tasks due today or within the next `days` days (overdue ones
+    /// included), soonest first.
+    pub fn load_tasks_due_within(&self, days: i64) -> Result<Vec<Task>, AppError> {
+        timed_query!(self, "load_tasks_due_within");
+        let mut conn = self.get_conn()?;
+        let cutoff = diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::Text>>(
+            "DATE('now', 'localtime', ",
+        )
+        .bind::<diesel::sql_types::Text, _>(format!("{days:+} days"))
+        .sql(")");
 
         Ok(task::table
-            .order_by(task::id.desc())
-            .limit(1)
-            .get_result::<Task>(&mut conn)?)
+            .filter(task::due_date.is_not_null())
+            .filter(task::due_date.le(cutoff))
+            .filter(task::completed.eq(false))
+            .filter(task::archived.eq(false))
+            .order_by((task::due_date.asc(), task::id.asc()))
+            .load::<Task>(&mut conn)?)
+    }
+
+    /// Every unarchived task with a due date, completed ones included, soonest
+    /// first.
+    pub fn load_tasks_with_due_date(&self) -> Result<Vec<Task>, AppError> {
+        timed_query!(self, "load_tasks_with_due_date");
+        let mut conn = self.get_conn()?;
+
+        Ok(task::table
+            .filter(task::due_date.is_not_null())
+            .filter(task::archived.eq(false))
+            .order_by((task::due_date.asc(), task::id.asc()))
+            .load::<Task>(&mut conn)?)
+    }
+
+    /// Open tasks whose due date is today.
+    pub fn count_tasks_due_today(&self) -> Result<usize, AppError> {
+        timed_query!(self, "count_tasks_due_today");
+        let mut conn = self.get_conn()?;
+        let today = diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::Text>>(
+            "DATE('now', 'localtime')",
+        );
+
+        let count = task::table
+            .filter(task::due_date.eq(today))
+            .filter(task::completed.eq(false))
+            .filter(task::archived.eq(false))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+        Ok(usize::try_from(count).unwrap_or_default())
+    }
+
+    /// The `limit` most recently created or updated tasks across all topics,
+    /// each with its topic.
+    pub fn recent_tasks(&self, limit: usize) -> Result<Vec<(Task, Topic)>, AppError> {
+        timed_query!(self, "recent_tasks");
+        let mut conn = self.get_conn()?;
+        let touched_at =
+            diesel::dsl::sql::<diesel::sql_types::Text>("MAX(task.created_at, task.updated_at)");
+
+        Ok(task::table
+            .inner_join(topic::table)
+            .filter(task::archived.eq(false))
+            .select((Task::as_select(), Topic::as_select()))
+            .order_by((touched_at.desc(), task::id.desc()))
+            .limit(i64::try_from(limit).unwrap_or(i64::MAX))
+            .load::<(Task, Topic)>(&mut conn)?)
+    }
+
+    pub fn set_task_url(&self, task_id: i32, url: Option<&str>) -> Result<Task, AppError> {
+        timed_query!(self, "set_task_url");
+        let mut conn = self.get_conn()?;
+
+        let updated = diesel::update(task::table.find(task_id))
+            .set(task::url.eq(url))
+            .execute(&mut conn)?;
+        if updated == 0 {
+            return Err(AppError::TaskNotFound(task_id));
+        }
+
+        info!(self.logger, "task url set"; "task_id" => task_id, "url" => url);
+        Ok(task::table.find(task_id).get_result::<Task>(&mut conn)?)
     }
 
-    pub fn update_task(&self, task_id: i32, update: TaskUpdate) -> Result<Task, Box<dyn Error>> {
+    pub fn rename_task(&self, task_id: i32, new_name: &str) -> Result<Task, AppError> {
+        timed_query!(self, "rename_task");
         let mut conn = self.get_conn()?;
 
+        let old_name = task::table
+            .find(task_id)
+            .select(task::name)
+            .get_result::<String>(&mut conn)
+            .optional()?
+            .ok_or(AppError::TaskNotFound(task_id))?;
+
+        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
         diesel::update(task::table.find(task_id))
+            .set((task::name.eq(new_name), task::updated_at.eq(&now)))
+            .execute(&mut conn)?;
+
+        info!(self.logger, "task renamed";
+            "task_id" => task_id, "old_name" => &old_name, "new_name" => new_name);
+        Ok(task::table.find(task_id).get_result::<Task>(&mut conn)?)
+    }
+
+    pub fn update_task(&self, task_id: i32, update: TaskUpdate) -> Result<Task, AppError> {
+        timed_query!(self, "update_task");
+        let mut conn = self.get_conn()?;
+
+        let updated = diesel::update(task::table.find(task_id))
             .set(update)
             .execute(&mut conn)?;
+        if updated == 0 {
+            return Err(AppError::TaskNotFound(task_id));
+        }
 
+        info!(self.logger, "task updated"; "task_id" => task_id);
         Ok(task::table.find(task_id).get_result::<Task>(&mut conn)?)
     }
 
-    pub fn toggle_task_completion(&self, task_id: i32) -> Result<Task, Box<dyn Error>> {
+    pub fn toggle_task_completion(&self, task_id: i32) -> Result<Task, AppError> {
+        timed_query!(self, "toggle_task_completion");
         let mut conn = self.get_conn()?;
 
         // Get current task
-        let current_task = task::table.find(task_id).get_result::<Task>(&mut conn)?;
+        let current_task = task::table
+            .find(task_id)
+            .get_result::<Task>(&mut conn)
+            .optional()?
+            .ok_or(AppError::TaskNotFound(task_id))?;
 
         // Create update with toggled completion
         let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
@@ -153,6 +1127,7 @@ impl DbOperations {
             description: None,
             completed: Some(!current_task.completed),
             favourite: None,
+            pinned: None,
             updated_at: &now,
         };
 
@@ -160,17 +1135,22 @@ impl DbOperations {
         diesel::update(task::table.find(task_id))
             .set(update)
             .execute(&mut conn)?;
+        info!(self.logger, "task completion toggled";
+            "task_id" => task_id, "completed" => !current_task.completed);
 
         Ok(task::table.find(task_id).get_result::<Task>(&mut conn)?)
     }
 
-    pub fn toggle_task_favourite(&self, task_id: i32) -> Result<Task, Box<dyn Error>> {
+    pub fn toggle_task_favourite(&self, task_id: i32) -> Result<Task, AppError> {
+        timed_query!(self, "toggle_task_favourite");
         let mut conn = self.get_conn()?;
 
         // Get current task
         let current_task = task::table
             .filter(task::id.eq(task_id))
-            .get_result::<Task>(&mut conn)?;
+            .get_result::<Task>(&mut conn)
+            .optional()?
+            .ok_or(AppError::TaskNotFound(task_id))?;
 
         // Create update with toggled favourite
         let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
@@ -179,6 +1159,7 @@ impl DbOperations {
             description: None,
             completed: None,
             favourite: Some(!current_task.favourite),
+            pinned: None,
             updated_at: &now,
         };
 
@@ -186,6 +1167,8 @@ impl DbOperations {
         diesel::update(task::table.find(task_id))
             .set(update)
             .execute(&mut conn)?;
+        info!(self.logger, "task favourite toggled";
+            "task_id" => task_id, "favourite" => !current_task.favourite);
 
         // task::table
         //     .filter(task::id.eq(task_id))
@@ -193,13 +1176,552 @@ impl DbOperations {
         Ok(task::table.filter(task::id.eq(task_id)).first(&mut conn)?)
     }
 
-    pub fn delete_task(&self, task_id: i32) -> Result<usize, Box<dyn Error>> {
+    pub fn pin_task(&self, task_id: i32) -> Result<Task, AppError> {
+        timed_query!(self, "pin_task");
+        self.set_task_pinned(task_id, true)
+    }
+
+    pub fn unpin_task(&self, task_id: i32) -> Result<Task, AppError> {
+        timed_query!(self, "unpin_task");
+        self.set_task_pinned(task_id, false)
+    }
+
+    fn set_task_pinned(&self, task_id: i32, pinned: bool) -> Result<Task, AppError> {
+        let mut conn = self.get_conn()?;
+
+        let updated = diesel::update(task::table.find(task_id))
+            .set(task::pinned.eq(pinned))
+            .execute(&mut conn)?;
+        if updated == 0 {
+            return Err(AppError::TaskNotFound(task_id));
+        }
+
+        info!(self.logger, "task pin changed"; "task_id" => task_id, "pinned" => pinned);
+        Ok(task::table.find(task_id).get_result::<Task>(&mut conn)?)
+    }
+
+    /// Weight for the next task appended to `topic_id`; bulk inserts add
+    /// multiples of 10 to it for each further task.
+    pub fn next_sort_weight(&self, topic_id: i32) -> Result<i32, AppError> {
+        timed_query!(self, "next_sort_weight");
+        let mut conn = self.get_conn()?;
+        Ok(next_sort_weight(&mut conn, topic_id)?)
+    }
+
+    /// Renumbers the topic's tasks `0, 10, 20, …` in their current manual
+    /// order; returns how many were updated.
+    pub fn reweight_tasks(&self, topic_id: i32) -> Result<usize, AppError> {
+        timed_query!(self, "reweight_tasks");
+        let mut conn = self.get_conn()?;
+
+        let count = conn.transaction(|conn| Self::reweight_topic_tasks(conn, topic_id))?;
+        info!(self.logger, "tasks reweighted"; "topic_id" => topic_id, "count" => count);
+        Ok(count)
+    }
+
+    /// Swaps the task's `sort_weight` with the task above it in its topic's
+    /// manual order. Returns `false` when it is already first.
+    pub fn move_task_up(&self, task_id: i32) -> Result<bool, AppError> {
+        timed_query!(self, "move_task_up");
+        self.move_task(task_id, -1)
+    }
+
+    /// Like [`Self::move_task_up`], towards the end of the order.
+    pub fn move_task_down(&self, task_id: i32) -> Result<bool, AppError> {
+        timed_query!(self, "move_task_down");
+        self.move_task(task_id, 1)
+    }
+
+    fn reweight_topic_tasks(
+        conn: &mut SqliteConnection,
+        topic_id: i32,
+    ) -> Result<usize, diesel::result::Error> {
+        let ids: Vec<i32> = task::table
+            .filter(task::topic_id.eq(topic_id))
+            .select(task::id)
+            .order_by((task::sort_weight.asc(), task::id.asc()))
+            .load(conn)?;
+        for (weight, id) in (0..).step_by(SORT_WEIGHT_STEP as usize).zip(&ids) {
+            diesel::update(task::table.find(id))
+                .set(task::sort_weight.eq(weight))
+                .execute(conn)?;
+        }
+        Ok(ids.len())
+    }
+
+    /// The lookup, any reweighting and the swap share an IMMEDIATE
+    /// transaction, so a concurrent move cannot change the order in between.
+    fn move_task(&self, task_id: i32, offset: isize) -> Result<bool, AppError> {
+        let mut conn = self.get_conn()?;
+        let moved = conn.immediate_transaction::<_, AppError, _>(|conn| {
+            let topic_id = task::table
+                .find(task_id)
+                .select(task::topic_id)
+                .first::<i32>(conn)
+                .optional()?
+                .ok_or(AppError::TaskNotFound(task_id))?;
+            let load_order = |conn: &mut SqliteConnection| {
+                task::table
+                    .filter(task::topic_id.eq(topic_id))
+                    .select((task::id, task::sort_weight))
+                    .order_by((task::sort_weight.asc(), task::id.asc()))
+                    .load::<(i32, i32)>(conn)
+            };
+
+            let mut order = load_order(conn)?;
+            let index = order
+                .iter()
+                .position(|(id, _)| *id == task_id)
+                .ok_or(AppError::TaskNotFound(task_id))?;
+            let Some(neighbour) = index
+                .checked_add_signed(offset)
+                .filter(|neighbour| *neighbour < order.len())
+            else {
+                return Ok(None);
+            };
+            // Equal weights would make the swap a no-op; spread them out first.
+            if order[index].1 == order[neighbour].1 {
+                Self::reweight_topic_tasks(conn, topic_id)?;
+                order = load_order(conn)?;
+            }
+            let ((current_id, current_weight), (neighbour_id, neighbour_weight)) =
+                (order[index], order[neighbour]);
+            diesel::update(task::table.find(current_id))
+                .set(task::sort_weight.eq(neighbour_weight))
+                .execute(conn)?;
+            diesel::update(task::table.find(neighbour_id))
+                .set(task::sort_weight.eq(current_weight))
+                .execute(conn)?;
+            Ok(Some(neighbour_weight))
+        })?;
+        let Some(sort_weight) = moved else {
+            return Ok(false);
+        };
+        info!(self.logger, "task moved"; "task_id" => task_id, "sort_weight" => sort_weight);
+        Ok(true)
+    }
+
+    /// Deletes the task and its subtasks. The subtask FK cascades too, but
+    /// SQLite only enforces it when `foreign_keys` is on, so the subtasks are
+    /// removed explicitly as well.
+    pub fn delete_task(&self, task_id: i32) -> Result<usize, AppError> {
+        timed_query!(self, "delete_task");
+        let mut conn = self.get_conn()?;
+
+        let (deleted, subtasks, links) =
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                delete_task_rows(conn, &[task_id])
+            })?;
+        info!(self.logger, "task deleted";
+            "task_id" => task_id, "rows" => deleted, "subtasks" => subtasks, "links" => links);
+        Ok(deleted)
+    }
+
+    /// [`Self::delete_task`] for several tasks in one transaction: either all
+    /// of them are deleted or none are. Returns the number of tasks deleted.
+    pub fn delete_tasks(&self, task_ids: &[i32]) -> Result<usize, AppError> {
+        timed_query!(self, "delete_tasks");
+        let mut conn = self.get_conn()?;
+
+        let (deleted, subtasks, links) = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| delete_task_rows(conn, task_ids))?;
+        info!(self.logger, "tasks deleted";
+            "tasks" => task_ids.len(), "rows" => deleted, "subtasks" => subtasks, "links" => links);
+        Ok(deleted)
+    }
+
+    /// Hides the task from every task list without deleting it.
+    pub fn archive_task(&self, task_id: i32) -> Result<Task, AppError> {
+        timed_query!(self, "archive_task");
+        self.set_task_archived(task_id, true)
+    }
+
+    /// [`Self::archive_task`] for several tasks in one statement. Returns the
+    /// number of tasks archived.
+    pub fn archive_tasks(&self, task_ids: &[i32]) -> Result<usize, AppError> {
+        timed_query!(self, "archive_tasks");
+        let mut conn = self.get_conn()?;
+
+        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let archived = diesel::update(task::table.filter(task::id.eq_any(task_ids)))
+            .set((task::archived.eq(true), task::updated_at.eq(&now)))
+            .execute(&mut conn)?;
+        info!(self.logger, "tasks archived"; "tasks" => task_ids.len(), "rows" => archived);
+        Ok(archived)
+    }
+
+    pub fn unarchive_task(&self, task_id: i32) -> Result<Task, AppError> {
+        timed_query!(self, "unarchive_task");
+        self.set_task_archived(task_id, false)
+    }
+
+    fn set_task_archived(&self, task_id: i32, archived: bool) -> Result<Task, AppError> {
+        let mut conn = self.get_conn()?;
+
+        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let updated = diesel::update(task::table.find(task_id))
+            .set((task::archived.eq(archived), task::updated_at.eq(&now)))
+            .execute(&mut conn)?;
+        if updated == 0 {
+            return Err(AppError::TaskNotFound(task_id));
+        }
+
+        info!(self.logger, "task archive state changed";
+            "task_id" => task_id, "archived" => archived);
+        Ok(task::table.find(task_id).get_result::<Task>(&mut conn)?)
+    }
+
+    /// Archived tasks, in one topic or across all of them, most recently
+    /// updated first.
+    pub fn load_archived_tasks(&self, topic_id: Option<i32>) -> Result<Vec<Task>, AppError> {
+        timed_query!(self, "load_archived_tasks");
+        let mut conn = self.get_conn()?;
+
+        let mut query = task::table.filter(task::archived.eq(true)).into_boxed();
+        if let Some(topic_id) = topic_id {
+            query = query.filter(task::topic_id.eq(topic_id));
+        }
+        Ok(query
+            .order_by((task::updated_at.desc(), task::id.desc()))
+            .load::<Task>(&mut conn)?)
+    }
+
+    /// Every task, archived or not, in id order.
+    pub fn load_all_tasks(&self) -> Result<Vec<Task>, AppError> {
+        timed_query!(self, "load_all_tasks");
+        let mut conn = self.get_conn()?;
+
+        Ok(task::table
+            .order_by(task::id.asc())
+            .load::<Task>(&mut conn)?)
+    }
+
+    // Subtask Operations
+    pub fn load_subtasks(&self, parent_id: i32) -> Result<Vec<Subtask>, AppError> {
+        timed_query!(self, "load_subtasks");
+        let mut conn = self.get_conn()?;
+
+        Ok(subtask::table
+            .filter(subtask::parent_task_id.eq(parent_id))
+            .order_by((subtask::position.asc(), subtask::id.asc()))
+            .load::<Subtask>(&mut conn)?)
+    }
+
+    /// Subtasks of every task in `parent_ids`, grouped by parent, in one query.
+    pub fn load_subtasks_for(
+        &self,
+        parent_ids: &[i32],
+    ) -> Result<HashMap<i32, Vec<Subtask>>, AppError> {
+        timed_query!(self, "load_subtasks_for");
+        let mut conn = self.get_conn()?;
+
+        let mut grouped: HashMap<i32, Vec<Subtask>> = HashMap::new();
+        for subtask in subtask::table
+            .filter(subtask::parent_task_id.eq_any(parent_ids))
+            .order_by((subtask::position.asc(), subtask::id.asc()))
+            .load::<Subtask>(&mut conn)?
+        {
+            grouped
+                .entry(subtask.parent_task_id)
+                .or_default()
+                .push(subtask);
+        }
+        Ok(grouped)
+    }
+
+    pub fn add_subtask(&self, parent_id: i32, name: &str) -> Result<Subtask, AppError> {
+        timed_query!(self, "add_subtask");
+        let mut conn = self.get_conn()?;
+        task::table
+            .find(parent_id)
+            .select(task::id)
+            .first::<i32>(&mut conn)
+            .optional()?
+            .ok_or(AppError::TaskNotFound(parent_id))?;
+        let last_position: Option<i32> = subtask::table
+            .filter(subtask::parent_task_id.eq(parent_id))
+            .select(diesel::dsl::max(subtask::position))
+            .first(&mut conn)?;
+
+        diesel::insert_into(subtask::table)
+            .values(&NewSubtask {
+                parent_task_id: parent_id,
+                name,
+                completed: false,
+                position: last_position.map_or(1, |position| position + 1),
+            })
+            .execute(&mut conn)?;
+
+        let subtask = subtask::table
+            .order_by(subtask::id.desc())
+            .limit(1)
+            .get_result::<Subtask>(&mut conn)?;
+        info!(self.logger, "subtask added";
+            "subtask_id" => subtask.id, "task_id" => parent_id, "name" => name);
+        Ok(subtask)
+    }
+
+    pub fn toggle_subtask(&self, subtask_id: i32) -> Result<Subtask, AppError> {
+        timed_query!(self, "toggle_subtask");
+        let mut conn = self.get_conn()?;
+
+        let current = subtask::table
+            .find(subtask_id)
+            .get_result::<Subtask>(&mut conn)
+            .optional()?
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("subtask {subtask_id} not found"),
+                )
+            })?;
+        diesel::update(subtask::table.find(subtask_id))
+            .set(subtask::completed.eq(!current.completed))
+            .execute(&mut conn)?;
+        info!(self.logger, "subtask toggled";
+            "subtask_id" => subtask_id, "completed" => !current.completed);
+
+        Ok(subtask::table
+            .find(subtask_id)
+            .get_result::<Subtask>(&mut conn)?)
+    }
+
+    pub fn delete_subtask(&self, subtask_id: i32) -> Result<usize, AppError> {
+        timed_query!(self, "delete_subtask");
+        let mut conn = self.get_conn()?;
+
+        let deleted = diesel::delete(subtask::table.find(subtask_id)).execute(&mut conn)?;
+        info!(self.logger, "subtask deleted"; "subtask_id" => subtask_id, "rows" => deleted);
+        Ok(deleted)
+    }
+
+    // Task Link Operations
+    pub fn add_link(
+        &self,
+        source_task_id: i32,
+        target_task_id: i32,
+        link_type: LinkType,
+    ) -> Result<TaskLink, AppError> {
+        timed_query!(self, "add_link");
+        if source_task_id == target_task_id {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "a task cannot be linked to itself",
+            )
+            .into());
+        }
+        let mut conn = self.get_conn()?;
+        for task_id in [source_task_id, target_task_id] {
+            task::table
+                .find(task_id)
+                .select(task::id)
+                .first::<i32>(&mut conn)
+                .optional()?
+                .ok_or(AppError::TaskNotFound(task_id))?;
+        }
+
+        diesel::insert_into(task_link::table)
+            .values(&NewTaskLink {
+                source_task_id,
+                target_task_id,
+                link_type: link_type.as_str(),
+            })
+            .execute(&mut conn)?;
+
+        let link = task_link::table
+            .order_by(task_link::id.desc())
+            .limit(1)
+            .get_result::<TaskLink>(&mut conn)?;
+        info!(self.logger, "task link added";
+            "link_id" => link.id, "source_task_id" => source_task_id,
+            "target_task_id" => target_task_id, "link_type" => link_type.as_str());
+        Ok(link)
+    }
+
+    pub fn remove_link(&self, link_id: i32) -> Result<usize, AppError> {
+        timed_query!(self, "remove_link");
+        let mut conn = self.get_conn()?;
+
+        let deleted = diesel::delete(task_link::table.find(link_id)).execute(&mut conn)?;
+        info!(self.logger, "task link removed"; "link_id" => link_id, "rows" => deleted);
+        Ok(deleted)
+    }
+
+    /// Links in which `task_id` is either the source or the target.
+    pub fn load_links(&self, task_id: i32) -> Result<Vec<TaskLink>, AppError> {
+        timed_query!(self, "load_links");
+        let mut conn = self.get_conn()?;
+
+        Ok(task_link::table
+            .filter(
+                task_link::source_task_id
+                    .eq(task_id)
+                    .or(task_link::target_task_id.eq(task_id)),
+            )
+            .order_by(task_link::id.asc())
+            .load::<TaskLink>(&mut conn)?)
+    }
+
+    /// Linked tasks of every task in `task_ids`, seen from that task's side.
+    /// Links to tasks that no longer exist are skipped.
+    pub fn load_linked_tasks_for(
+        &self,
+        task_ids: &[i32],
+    ) -> Result<HashMap<i32, Vec<LinkedTask>>, AppError> {
+        timed_query!(self, "load_linked_tasks_for");
+        let mut conn = self.get_conn()?;
+
+        let links = task_link::table
+            .filter(
+                task_link::source_task_id
+                    .eq_any(task_ids)
+                    .or(task_link::target_task_id.eq_any(task_ids)),
+            )
+            .order_by(task_link::id.asc())
+            .load::<TaskLink>(&mut conn)?;
+        let linked_ids: HashSet<i32> = links
+            .iter()
+            .flat_map(|link| [link.source_task_id, link.target_task_id])
+            .collect();
+        let tasks: HashMap<i32, (String, bool)> = task::table
+            .filter(task::id.eq_any(linked_ids))
+            .select((task::id, task::name, task::completed))
+            .load::<(i32, String, bool)>(&mut conn)?
+            .into_iter()
+            .map(|(id, name, completed)| (id, (name, completed)))
+            .collect();
+
+        let mut grouped: HashMap<i32, Vec<LinkedTask>> = HashMap::new();
+        for link in &links {
+            for task_id in [link.source_task_id, link.target_task_id] {
+                if !task_ids.contains(&task_id) {
+                    continue;
+                }
+                let Some((link_type, other_id)) = link.relative_to(task_id) else {
+                    continue;
+                };
+                if let Some((name, completed)) = tasks.get(&other_id) {
+                    grouped.entry(task_id).or_default().push(LinkedTask {
+                        link_id: link.id,
+                        link_type,
+                        task_id: other_id,
+                        name: name.clone(),
+                        completed: *completed,
+                    });
+                }
+            }
+        }
+        Ok(grouped)
+    }
+
+    /// Rebuilds the database file, returning free pages to the filesystem.
+    pub fn vacuum(&self) -> Result<(), AppError> {
+        timed_query!(self, "vacuum");
+        let mut conn = self.get_conn()?;
+
+        diesel::sql_query("VACUUM").execute(&mut conn)?;
+        info!(self.logger, "database vacuumed");
+        Ok(())
+    }
+
+    /// Refreshes the statistics SQLite's query planner uses.
+    pub fn analyze(&self) -> Result<(), AppError> {
+        timed_query!(self, "analyze");
+        let mut conn = self.get_conn()?;
+
+        diesel::sql_query("ANALYZE").execute(&mut conn)?;
+        info!(self.logger, "database analyzed");
+        Ok(())
+    }
+
+    /// Number of embedded migrations not yet applied to this database.
+    pub fn pending_migration_count(&self) -> Result<usize, AppError> {
+        timed_query!(self, "pending_migration_count");
         let mut conn = self.get_conn()?;
+        Ok(conn
+            .pending_migrations(crate::db::MIGRATIONS)
+            .map_err(|e| AppError::Migration(e.to_string()))?
+            .len())
+    }
 
-        Ok(diesel::delete(task::table.find(task_id)).execute(&mut conn)?)
+    /// SQLite's `EXPLAIN QUERY PLAN` for `sql`, one plan step per line
+    /// (e.g. `SEARCH task USING INDEX idx_task_topic_id (topic_id=?)`).
+    pub fn explain_query(&self, sql: &str) -> Result<String, AppError> {
+        timed_query!(self, "explain_query");
+        #[derive(QueryableByName)]
+        struct PlanRow {
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            detail: String,
+        }
+
+        let mut conn = self.get_conn()?;
+        let rows =
+            diesel::sql_query(format!("EXPLAIN QUERY PLAN {sql}")).load::<PlanRow>(&mut conn)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| row.detail)
+            .collect::<Vec<_>>()
+            .join("\n"))
     }
 
-    pub fn find_task(&self, task_id: i32) -> Result<Option<Task>, Box<dyn Error>> {
+    /// [`Self::explain_query`] for the exact query [`Self::load_tasks`] runs
+    /// for `current_topic`, built by diesel so it cannot drift from the code.
+    pub fn explain_task_list_query(
+        &self,
+        current_topic: &Topic,
+        sort_mode: SortMode,
+        ascending: bool,
+    ) -> Result<String, AppError> {
+        timed_query!(self, "explain_task_list_query");
+        let query = sorted_tasks_query(current_topic, sort_mode, ascending);
+        let sql = diesel::debug_query::<diesel::sqlite::Sqlite, _>(&query).to_string();
+        // Binds are listed after the SQL; EXPLAIN leaves the `?`s unbound.
+        let sql = sql.split(" -- binds:").next().unwrap_or(&sql);
+        self.explain_query(sql)
+    }
+
+    /// Runs SQLite's `PRAGMA integrity_check`; a healthy database returns `["ok"]`.
+    pub fn integrity_check(&self) -> Result<Vec<String>, AppError> {
+        timed_query!(self, "integrity_check");
+        #[derive(QueryableByName)]
+        struct IntegrityRow {
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            integrity_check: String,
+        }
+
+        let mut conn = self.get_conn()?;
+        let rows = diesel::sql_query("PRAGMA integrity_check").load::<IntegrityRow>(&mut conn)?;
+        Ok(rows.into_iter().map(|row| row.integrity_check).collect())
+    }
+
+    /// Tasks whose `topic_id` does not match any topic.
+    pub fn count_orphan_tasks(&self) -> Result<i64, AppError> {
+        timed_query!(self, "count_orphan_tasks");
+        let mut conn = self.get_conn()?;
+
+        Ok(task::table
+            .filter(diesel::dsl::not(diesel::dsl::exists(
+                topic::table.filter(topic::id.eq(task::topic_id)),
+            )))
+            .count()
+            .get_result(&mut conn)?)
+    }
+
+    /// Tasks with a missing or blank name, which the UI cannot show.
+    pub fn count_unnamed_tasks(&self) -> Result<i64, AppError> {
+        timed_query!(self, "count_unnamed_tasks");
+        let mut conn = self.get_conn()?;
+
+        Ok(task::table
+            .filter(diesel::dsl::sql::<diesel::sql_types::Bool>(
+                "name IS NULL OR TRIM(name) = ''",
+            ))
+            .count()
+            .get_result(&mut conn)?)
+    }
+
+    pub fn find_task(&self, task_id: i32) -> Result<Option<Task>, AppError> {
+        timed_query!(self, "find_task");
         let mut conn = self.get_conn()?;
 
         Ok(task::table
@@ -208,3 +1730,80 @@ impl DbOperations {
             .optional()?)
     }
 }
+
+/// Deletes `task_ids` with their subtasks and links; returns the task,
+/// subtask and link rows removed. Callers wrap it in a transaction.
+fn delete_task_rows(
+    conn: &mut SqliteConnection,
+    task_ids: &[i32],
+) -> QueryResult<(usize, usize, usize)> {
+    let subtasks = diesel::delete(subtask::table.filter(subtask::parent_task_id.eq_any(task_ids)))
+        .execute(conn)?;
+    let links = diesel::delete(
+        task_link::table.filter(
+            task_link::source_task_id
+                .eq_any(task_ids)
+                .or(task_link::target_task_id.eq_any(task_ids)),
+        ),
+    )
+    .execute(conn)?;
+    let deleted = diesel::delete(task::table.filter(task::id.eq_any(task_ids))).execute(conn)?;
+    Ok((deleted, subtasks, links))
+}
+
+/// A boxed query over `task`, as built for the task list.
+pub type TaskQuery = task::BoxedQuery<'static, diesel::sqlite::Sqlite>;
+
+/// Gap between neighbouring `sort_weight`s, so a move can usually be a swap.
+const SORT_WEIGHT_STEP: i32 = 10;
+
+/// The weight that puts a new task last in its topic's manual order.
+fn next_sort_weight(conn: &mut SqliteConnection, topic_id: i32) -> QueryResult<i32> {
+    let max: Option<i32> = task::table
+        .filter(task::topic_id.eq(topic_id))
+        .select(diesel::dsl::max(task::sort_weight))
+        .first(conn)?;
+    Ok(max.map_or(0, |max| max + SORT_WEIGHT_STEP))
+}
+
+/// Unarchived tasks listed under `current_topic`: the Default topic lists
+/// every task, Favourites and Completed are views across topics.
+fn topic_tasks_query(current_topic: &Topic) -> TaskQuery {
+    let query = task::table.filter(task::archived.eq(false)).into_boxed();
+    match current_topic.name.as_str() {
+        "Favourites" => query.filter(task::favourite.eq(true)),
+        "Completed" => query.filter(task::completed.eq(true)),
+        "Default" => query,
+        _ => query.filter(task::topic_id.eq(current_topic.id)),
+    }
+}
+
+/// The archived-free task query behind the task list, in display order.
+fn sorted_tasks_query(current_topic: &Topic, sort_mode: SortMode, ascending: bool) -> TaskQuery {
+    let mut query = topic_tasks_query(current_topic);
+    // Pinned tasks always come first, whatever the sort direction.
+    query = query.order_by(task::pinned.desc());
+    // Then open tasks before completed ones, across every page, unless the
+    // list is sorted by status itself.
+    if sort_mode != SortMode::Completed {
+        query = query.then_order_by(task::completed.asc());
+    }
+    query = match (sort_mode, ascending) {
+        (SortMode::Created, true) => query.then_order_by(task::created_at.asc()),
+        (SortMode::Created, false) => query.then_order_by(task::created_at.desc()),
+        (SortMode::Name, true) => query.then_order_by(task::name.asc()),
+        (SortMode::Name, false) => query.then_order_by(task::name.desc()),
+        (SortMode::Updated, true) => query.then_order_by(task::updated_at.asc()),
+        (SortMode::Updated, false) => query.then_order_by(task::updated_at.desc()),
+        (SortMode::Completed, true) => query.then_order_by(task::completed.asc()),
+        (SortMode::Completed, false) => query.then_order_by(task::completed.desc()),
+        (SortMode::Manual, true) => query.then_order_by(task::sort_weight.asc()),
+        (SortMode::Manual, false) => query.then_order_by(task::sort_weight.desc()),
+    };
+    // Tie-break on id so tasks with equal keys keep a stable order.
+    if ascending {
+        query.then_order_by(task::id.asc())
+    } else {
+        query.then_order_by(task::id.desc())
+    }
+}
@@ -0,0 +1,202 @@
+//! Self-checks for `task_manager_cli doctor`: each check inspects one thing
+//! that commonly stops the app from starting and reports OK, WARN or FAIL.
+
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::path::Path;
+
+use crate::config::load_config;
+use crate::db::task_manager::operations::DbOperations;
+use crate::log_config::LOG_FILE;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "[OK]",
+            CheckStatus::Warn => "[WARN]",
+            CheckStatus::Fail => "[FAIL]",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+impl CheckResult {
+    fn new(name: &str, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<7} {:<20} {}",
+            self.status.label(),
+            self.name,
+            self.message
+        )
+    }
+}
+
+/// Runs every check against the database at `db_path` and the config file
+/// next to it. The database is opened without migrations or the instance
+/// lock, and checks that need a connection are skipped if it cannot be made.
+pub fn run_checks(db_path: &Path) -> Vec<CheckResult> {
+    let mut results = vec![check_db_readable(db_path)];
+    if results[0].status == CheckStatus::Fail {
+        return results;
+    }
+    results.push(check_db_writable(db_path));
+
+    let db_ops = crate::db::establish_connection_pool(&format!("sqlite://{}", db_path.display()))
+        .map(|pool| DbOperations::new(pool, crate::log_config::get_logger_or_discard()));
+    match db_ops {
+        Ok(db_ops) => {
+            results.push(check_pool(&db_ops));
+            if results
+                .last()
+                .is_some_and(|result| result.status != CheckStatus::Fail)
+            {
+                results.push(check_schema_version(&db_ops));
+                results.push(check_orphan_tasks(&db_ops));
+                results.push(check_unnamed_tasks(&db_ops));
+            }
+        }
+        Err(e) => results.push(CheckResult::new(
+            "connection pool",
+            CheckStatus::Fail,
+            e.to_string(),
+        )),
+    }
+
+    results.push(check_log_dir(Path::new(LOG_FILE)));
+    let config_path = db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("task_manager_config.json");
+    results.push(check_config(&config_path));
+    results
+}
+
+pub fn check_db_readable(db_path: &Path) -> CheckResult {
+    const NAME: &str = "database readable";
+    if !db_path.exists() {
+        return CheckResult::new(
+            NAME,
+            CheckStatus::Fail,
+            format!(
+                "{} does not exist; start the app once to create it",
+                db_path.display()
+            ),
+        );
+    }
+    match File::open(db_path) {
+        Ok(_) => CheckResult::new(NAME, CheckStatus::Ok, db_path.display().to_string()),
+        Err(e) => CheckResult::new(NAME, CheckStatus::Fail, e.to_string()),
+    }
+}
+
+pub fn check_db_writable(db_path: &Path) -> CheckResult {
+    const NAME: &str = "database writable";
+    match OpenOptions::new().write(true).open(db_path) {
+        Ok(_) => CheckResult::new(NAME, CheckStatus::Ok, "yes"),
+        Err(e) => CheckResult::new(NAME, CheckStatus::Fail, e.to_string()),
+    }
+}
+
+pub fn check_pool(db_ops: &DbOperations) -> CheckResult {
+    const NAME: &str = "connection pool";
+    match db_ops.pool.get() {
+        Ok(_) => CheckResult::new(NAME, CheckStatus::Ok, "connection obtained"),
+        Err(e) => CheckResult::new(NAME, CheckStatus::Fail, e.to_string()),
+    }
+}
+
+pub fn check_schema_version(db_ops: &DbOperations) -> CheckResult {
+    const NAME: &str = "schema version";
+    match db_ops.pending_migration_count() {
+        Ok(0) => CheckResult::new(NAME, CheckStatus::Ok, "up to date"),
+        Ok(pending) => CheckResult::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("{pending} pending migration(s); run `migrate-only`"),
+        ),
+        Err(e) => CheckResult::new(NAME, CheckStatus::Fail, e.to_string()),
+    }
+}
+
+pub fn check_orphan_tasks(db_ops: &DbOperations) -> CheckResult {
+    const NAME: &str = "orphan tasks";
+    match db_ops.count_orphan_tasks() {
+        Ok(0) => CheckResult::new(NAME, CheckStatus::Ok, "none"),
+        Ok(count) => CheckResult::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("{count} task(s) point at a missing topic"),
+        ),
+        Err(e) => CheckResult::new(NAME, CheckStatus::Fail, e.to_string()),
+    }
+}
+
+pub fn check_unnamed_tasks(db_ops: &DbOperations) -> CheckResult {
+    const NAME: &str = "task names";
+    match db_ops.count_unnamed_tasks() {
+        Ok(0) => CheckResult::new(NAME, CheckStatus::Ok, "every task has a name"),
+        Ok(count) => CheckResult::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("{count} task(s) have no name"),
+        ),
+        Err(e) => CheckResult::new(NAME, CheckStatus::Fail, e.to_string()),
+    }
+}
+
+/// Opens the log file for appending, as the logger does, creating its
+/// directory if needed.
+pub fn check_log_dir(log_file: &Path) -> CheckResult {
+    const NAME: &str = "log directory";
+    let opened = log_file
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| OpenOptions::new().create(true).append(true).open(log_file));
+    match opened {
+        Ok(_) => CheckResult::new(NAME, CheckStatus::Ok, log_file.display().to_string()),
+        Err(e) => CheckResult::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{}: {e}", log_file.display()),
+        ),
+    }
+}
+
+pub fn check_config(config_path: &Path) -> CheckResult {
+    const NAME: &str = "config file";
+    if !config_path.exists() {
+        return CheckResult::new(NAME, CheckStatus::Ok, "not present, using defaults");
+    }
+    match load_config(config_path) {
+        Ok(_) => CheckResult::new(NAME, CheckStatus::Ok, config_path.display().to_string()),
+        Err(e) => CheckResult::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{}: {e}", config_path.display()),
+        ),
+    }
+}
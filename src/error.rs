@@ -0,0 +1,106 @@
+use std::{error::Error, fmt, io};
+
+/// Errors surfaced by the task manager's database and application layers.
+#[derive(Debug)]
+pub enum AppError {
+    Database(diesel::result::Error),
+    PoolTimeout(r2d2::Error),
+    Io(io::Error),
+    Config(String),
+    /// An import or other input file that could not be parsed.
+    Parse(String),
+    Migration(String),
+    TaskNotFound(i32),
+    TopicNotFound(i32),
+    /// A topic named on the command line that does not exist.
+    UnknownTopic(String),
+    ProtectedTopic(String),
+    DuplicateTopicName(String),
+    /// Topic name and its `task_limit`.
+    TopicTaskLimitReached(String, i32),
+    AlreadyRunning,
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Database(e) => write!(f, "database error: {e}"),
+            AppError::PoolTimeout(e) => write!(f, "database connection unavailable: {e}"),
+            AppError::Io(e) => write!(f, "{e}"),
+            AppError::Config(msg) => write!(f, "configuration error: {msg}"),
+            AppError::Parse(msg) => write!(f, "could not parse input: {msg}"),
+            AppError::Migration(msg) => write!(f, "migration failed: {msg}"),
+            AppError::TaskNotFound(id) => write!(f, "task {id} not found"),
+            AppError::TopicNotFound(id) => write!(f, "topic {id} not found"),
+            AppError::UnknownTopic(name) => write!(f, "unknown topic: {name}"),
+            AppError::ProtectedTopic(name) => write!(f, "topic '{name}' is protected"),
+            AppError::DuplicateTopicName(name) => {
+                write!(f, "a topic named '{name}' already exists")
+            }
+            AppError::TopicTaskLimitReached(name, limit) => write!(
+                f,
+                "topic '{name}' already holds its limit of {limit} tasks"
+            ),
+            AppError::AlreadyRunning => write!(
+                f,
+                "another task_manager_cli instance is using this database (pass --force to skip this check)"
+            ),
+        }
+    }
+}
+
+impl Error for AppError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AppError::Database(e) => Some(e),
+            AppError::PoolTimeout(e) => Some(e),
+            AppError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<diesel::result::Error> for AppError {
+    fn from(error: diesel::result::Error) -> Self {
+        AppError::Database(error)
+    }
+}
+
+impl From<r2d2::Error> for AppError {
+    fn from(error: r2d2::Error) -> Self {
+        AppError::PoolTimeout(error)
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(error: io::Error) -> Self {
+        AppError::Io(error)
+    }
+}
+
+impl From<notify::Error> for AppError {
+    fn from(error: notify::Error) -> Self {
+        AppError::Io(io::Error::other(error))
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(error: serde_json::Error) -> Self {
+        AppError::Parse(error.to_string())
+    }
+}
+
+/// Shared helpers (event loop, preset and palette stores) still return boxed
+/// errors; recover the concrete type where possible.
+impl From<Box<dyn Error>> for AppError {
+    fn from(error: Box<dyn Error>) -> Self {
+        let error = match error.downcast::<AppError>() {
+            Ok(error) => return *error,
+            Err(error) => error,
+        };
+        match error.downcast::<io::Error>() {
+            Ok(error) => AppError::Io(*error),
+            Err(error) => AppError::Config(error.to_string()),
+        }
+    }
+}
@@ -0,0 +1,857 @@
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt, fs, io, path::Path};
+
+use crate::db::task_manager::models::{ImportResult, MergeStrategy, Task, TaskSource, TopicOrder};
+use crate::db::task_manager::operations::DbOperations;
+use crate::error::AppError;
+use crate::utils::DUE_DATE_FORMAT;
+
+/// A single task row read from a JSON or CSV import file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportRecord {
+    pub topic: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub completed: bool,
+    #[serde(default)]
+    pub favourite: bool,
+    /// Line (CSV) or array position (JSON) the record came from, 1-based.
+    #[serde(skip)]
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportValidationError {
+    /// Index into the parsed records.
+    pub row: usize,
+    /// Source line reported to the user.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ImportValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Json,
+    Csv,
+}
+
+impl ImportFormat {
+    /// The `task.source` recorded for tasks imported from this format.
+    pub fn task_source(&self) -> TaskSource {
+        match self {
+            ImportFormat::Json => TaskSource::ImportJson,
+            ImportFormat::Csv => TaskSource::ImportCsv,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .as_deref()
+        {
+            Some("json") => Some(ImportFormat::Json),
+            Some("csv") => Some(ImportFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// What an import did, or would do when run with `--dry-run`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub dry_run: bool,
+    /// `(topic, task name)` pairs in file order.
+    pub tasks: Vec<(String, String)>,
+    pub new_topics: Vec<String>,
+    pub result: ImportResult,
+}
+
+impl fmt::Display for ImportSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let topics: HashSet<&str> = self.tasks.iter().map(|(topic, _)| topic.as_str()).collect();
+        writeln!(
+            f,
+            "{} {} task(s) into {} topic(s) ({} new)",
+            if self.dry_run {
+                "Would import"
+            } else {
+                "Imported"
+            },
+            self.tasks.len(),
+            topics.len(),
+            self.new_topics.len()
+        )?;
+        writeln!(f, "  {}", self.result)?;
+        for topic in &self.new_topics {
+            writeln!(f, "  new topic: {}", topic)?;
+        }
+        for (topic, name) in &self.tasks {
+            writeln!(f, "  {}: {}", topic, name)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_import_file(path: &Path) -> Result<Vec<ImportRecord>, AppError> {
+    let format = ImportFormat::from_path(path).ok_or_else(|| {
+        AppError::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Import file must have a .json or .csv extension",
+        ))
+    })?;
+    let content = fs::read_to_string(path)?;
+    match format {
+        ImportFormat::Json => parse_json(&content),
+        ImportFormat::Csv => parse_csv(&content),
+    }
+}
+
+pub fn parse_json(content: &str) -> Result<Vec<ImportRecord>, AppError> {
+    let mut records: Vec<ImportRecord> = serde_json::from_str(content)?;
+    for (index, record) in records.iter_mut().enumerate() {
+        record.line = index + 1;
+    }
+    Ok(records)
+}
+
+/// Parses `topic,name,description,completed,favourite` rows. The header row is
+/// required; only `topic` and `name` columns are mandatory.
+pub fn parse_csv(content: &str) -> Result<Vec<ImportRecord>, AppError> {
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let Some((_, header)) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let header: Vec<String> = split_csv_line(header)
+        .into_iter()
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+    let column = |name: &str| header.iter().position(|column| column == name);
+    let (Some(topic_col), Some(name_col)) = (column("topic"), column("name")) else {
+        return Err(AppError::Io(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "CSV header must include topic and name columns",
+        )));
+    };
+    let description_col = column("description");
+    let completed_col = column("completed");
+    let favourite_col = column("favourite").or_else(|| column("favorite"));
+
+    Ok(lines
+        .map(|(index, line)| {
+            let fields = split_csv_line(line);
+            let field = |col: Option<usize>| {
+                col.and_then(|col| fields.get(col))
+                    .map(|value| value.trim().to_string())
+                    .unwrap_or_default()
+            };
+            ImportRecord {
+                topic: field(Some(topic_col)),
+                name: field(Some(name_col)),
+                description: field(description_col),
+                completed: parse_csv_bool(&field(completed_col)),
+                favourite: parse_csv_bool(&field(favourite_col)),
+                line: index + 1,
+            }
+        })
+        .collect())
+}
+
+fn parse_csv_bool(value: &str) -> bool {
+    matches!(
+        value.to_lowercase().as_str(),
+        "true" | "yes" | "y" | "1" | "done"
+    )
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut current)),
+            _ => current.push(ch),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+pub fn validate_import(records: &[ImportRecord]) -> Vec<ImportValidationError> {
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (row, record) in records.iter().enumerate() {
+        let mut push = |message: String| {
+            errors.push(ImportValidationError {
+                row,
+                line: record.line,
+                message,
+            })
+        };
+        let topic = record.topic.trim();
+        let name = record.name.trim();
+
+        if topic.is_empty() {
+            push("topic cannot be empty".to_string());
+        } else if topic == "Favourites" || topic == "Completed" {
+            push(format!("cannot import into special topic '{}'", topic));
+        }
+        if name.is_empty() {
+            push("task name cannot be empty".to_string());
+        } else if !topic.is_empty() && !seen.insert((topic.to_lowercase(), name.to_lowercase())) {
+            push(format!("duplicate task '{}' in topic '{}'", name, topic));
+        }
+    }
+
+    errors
+}
+
+/// Inserts validated records, creating missing topics and resolving name
+/// clashes per `strategy`. With `dry_run` nothing is written and the summary
+/// describes what would have happened. New tasks are tagged with `source`.
+pub fn import_records(
+    db_ops: &DbOperations,
+    records: &[ImportRecord],
+    strategy: MergeStrategy,
+    source: TaskSource,
+    dry_run: bool,
+) -> Result<ImportSummary, AppError> {
+    let topics = db_ops.load_topics(TopicOrder::Manual)?;
+    let mut summary = ImportSummary {
+        dry_run,
+        ..ImportSummary::default()
+    };
+    // `(topic name, topic id, records)` in first-seen order; the id is `None`
+    // for topics a dry run would have created.
+    let mut groups: Vec<(String, Option<i32>, Vec<ImportRecord>)> = Vec::new();
+
+    for record in records {
+        let topic_name = record.topic.trim();
+        summary
+            .tasks
+            .push((topic_name.to_string(), record.name.trim().to_string()));
+        if let Some(group) = groups.iter_mut().find(|(name, _, _)| name == topic_name) {
+            group.2.push(record.clone());
+            continue;
+        }
+
+        let existing = topics
+            .iter()
+            .find(|topic| topic.name == topic_name)
+            .map(|topic| topic.id);
+        if existing.is_none() {
+            summary.new_topics.push(topic_name.to_string());
+        }
+        let topic_id = if dry_run {
+            existing
+        } else {
+            Some(db_ops.get_or_create_topic(topic_name)?.id)
+        };
+        groups.push((topic_name.to_string(), topic_id, vec![record.clone()]));
+    }
+
+    for (_, topic_id, records) in &groups {
+        match (topic_id, dry_run) {
+            (Some(topic_id), false) => {
+                let result = db_ops.import_with_strategy(records, *topic_id, strategy, source)?;
+                summary.result.inserted += result.inserted;
+                summary.result.updated += result.updated;
+                summary.result.skipped += result.skipped;
+            }
+            _ => {
+                let mut names = match topic_id {
+                    Some(topic_id) => db_ops.task_names_in_topic(*topic_id)?,
+                    None => HashSet::new(),
+                };
+                for record in records {
+                    let name = record.name.trim();
+                    summary
+                        .result
+                        .record(strategy.outcome(names.contains(name)));
+                    names.insert(name.to_string());
+                }
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Todoist import: the `tasks` of a Todoist JSON export become tasks of one
+/// topic.
+pub mod todoist {
+    use serde::Deserialize;
+    use std::{collections::HashSet, fmt, fs, path::Path};
+
+    use crate::db::task_manager::models::{NewTask, TaskSource, TopicOrder};
+    use crate::db::task_manager::operations::DbOperations;
+    use crate::error::AppError;
+
+    /// The parts of a Todoist JSON export we import; other fields are ignored.
+    #[derive(Debug, Clone, Deserialize)]
+    pub struct TodoistExport {
+        pub tasks: Vec<TodoistTask>,
+    }
+
+    #[derive(Debug, Clone, Deserialize)]
+    pub struct TodoistTask {
+        pub content: String,
+        #[serde(default)]
+        pub description: String,
+        #[serde(default, alias = "checked", alias = "is_completed")]
+        pub completed: bool,
+    }
+
+    /// What a Todoist import did, or would do with `--dry-run`.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct TodoistImportSummary {
+        pub dry_run: bool,
+        pub inserted: usize,
+        /// Tasks whose name already exists in the target topic, or repeats in the file.
+        pub skipped: usize,
+    }
+
+    impl fmt::Display for TodoistImportSummary {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            writeln!(
+                f,
+                "{} {} task(s) from Todoist ({} skipped as duplicates)",
+                if self.dry_run {
+                    "Would import"
+                } else {
+                    "Imported"
+                },
+                self.inserted,
+                self.skipped
+            )
+        }
+    }
+
+    pub fn parse_todoist(content: &str) -> Result<TodoistExport, AppError> {
+        Ok(serde_json::from_str(content)?)
+    }
+
+    /// Picks the Todoist tasks to insert and counts the rest: blank names, names
+    /// in `existing_names`, and repeats within the file.
+    pub fn plan_todoist_import(
+        export: &TodoistExport,
+        mut existing_names: HashSet<String>,
+    ) -> (Vec<&TodoistTask>, usize) {
+        let mut skipped = 0;
+        let tasks = export
+            .tasks
+            .iter()
+            .filter(|task| {
+                let name = task.content.trim();
+                let keep = !name.is_empty() && existing_names.insert(name.to_string());
+                if !keep {
+                    skipped += 1;
+                }
+                keep
+            })
+            .collect();
+        (tasks, skipped)
+    }
+
+    /// Imports a Todoist export into `target_topic_id`, skipping tasks whose name
+    /// is already used in that topic.
+    pub fn import_todoist(
+        db_ops: &DbOperations,
+        path: &Path,
+        target_topic_id: i32,
+        dry_run: bool,
+    ) -> Result<TodoistImportSummary, AppError> {
+        let export = parse_todoist(&fs::read_to_string(path)?)?;
+        let topics = db_ops.load_topics(TopicOrder::Manual)?;
+        let topic = topics
+            .iter()
+            .find(|topic| topic.id == target_topic_id)
+            .ok_or(AppError::TopicNotFound(target_topic_id))?;
+        if topic.name != "Default" && db_ops.is_special_topic(&topic.name) {
+            return Err(AppError::ProtectedTopic(topic.name.clone()));
+        }
+
+        let existing_names = db_ops
+            .load_tasks(topic)?
+            .into_iter()
+            .filter(|task| task.topic_id == target_topic_id)
+            .map(|task| task.name)
+            .collect();
+        let (tasks, skipped) = plan_todoist_import(&export, existing_names);
+        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let first_weight = db_ops.next_sort_weight(target_topic_id)?;
+        let new_tasks: Vec<NewTask> = tasks
+            .iter()
+            .zip((first_weight..).step_by(10))
+            .map(|(task, sort_weight)| NewTask {
+                topic_id: target_topic_id,
+                name: task.content.trim(),
+                description: task.description.trim(),
+                completed: task.completed,
+                favourite: false,
+                pinned: false,
+                created_at: &now,
+                updated_at: &now,
+                sort_weight,
+                source: TaskSource::ImportJson.as_str(),
+                url: None,
+            })
+            .collect();
+
+        Ok(TodoistImportSummary {
+            dry_run,
+            inserted: if dry_run {
+                new_tasks.len()
+            } else {
+                db_ops.bulk_insert_tasks(&new_tasks)?
+            },
+            skipped,
+        })
+    }
+}
+
+/// Emacs Org-mode import: top-level headings become tasks and deeper headings
+/// become subtasks of the task above them.
+pub mod org {
+    use std::{collections::HashSet, fmt, fs, path::Path};
+
+    use crate::db::task_manager::models::{NewTask, TaskSource, TopicOrder};
+    use crate::db::task_manager::operations::DbOperations;
+    use crate::error::AppError;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum OrgStatus {
+        #[default]
+        Todo,
+        Done,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct OrgTask {
+        pub name: String,
+        pub status: OrgStatus,
+        /// Text between this heading and the next one, trimmed.
+        pub body: String,
+        /// From a trailing `:tag1:tag2:` on the heading.
+        pub tags: Vec<String>,
+        /// Headings nested below this one (`**` and deeper).
+        pub subtasks: Vec<OrgTask>,
+    }
+
+    impl OrgTask {
+        pub fn completed(&self) -> bool {
+            self.status == OrgStatus::Done
+        }
+    }
+
+    /// What an Org import did, or would do with `--dry-run`.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct OrgImportSummary {
+        pub dry_run: bool,
+        pub inserted: usize,
+        pub subtasks: usize,
+        /// Headings whose name already exists in the target topic, or repeats in the file.
+        pub skipped: usize,
+    }
+
+    impl fmt::Display for OrgImportSummary {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            writeln!(
+                f,
+                "{} {} task(s) with {} subtask(s) from Org ({} skipped as duplicates)",
+                if self.dry_run {
+                    "Would import"
+                } else {
+                    "Imported"
+                },
+                self.inserted,
+                self.subtasks,
+                self.skipped
+            )
+        }
+    }
+
+    /// Splits `TODO Name :a:b:` into status, name and tags.
+    fn parse_heading(text: &str) -> OrgTask {
+        let (status, rest) = match text.split_once(' ') {
+            Some(("TODO", rest)) => (OrgStatus::Todo, rest),
+            Some(("DONE", rest)) => (OrgStatus::Done, rest),
+            _ if text == "TODO" => (OrgStatus::Todo, ""),
+            _ if text == "DONE" => (OrgStatus::Done, ""),
+            _ => (OrgStatus::Todo, text),
+        };
+        let rest = rest.trim();
+        let (name, tags) = match rest.rsplit_once(char::is_whitespace) {
+            Some((name, tags))
+                if tags.len() > 1 && tags.starts_with(':') && tags.ends_with(':') =>
+            {
+                (name.trim_end(), tags)
+            }
+            _ if rest.len() > 1 && rest.starts_with(':') && rest.ends_with(':') => ("", rest),
+            _ => (rest, ""),
+        };
+        OrgTask {
+            name: name.to_string(),
+            status,
+            body: String::new(),
+            tags: tags
+                .split(':')
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_string)
+                .collect(),
+            subtasks: Vec::new(),
+        }
+    }
+
+    /// Hands the collected body lines to the most recent heading.
+    fn flush_body(tasks: &mut [OrgTask], body: &mut Vec<&str>) {
+        let text = body.join("\n").trim().to_string();
+        body.clear();
+        let Some(task) = tasks.last_mut() else {
+            return;
+        };
+        match task.subtasks.last_mut() {
+            Some(subtask) => subtask.body = text,
+            None => task.body = text,
+        }
+    }
+
+    /// Parses the headings of an Org document. Text before the first heading
+    /// is ignored.
+    pub fn parse_org(content: &str) -> Vec<OrgTask> {
+        let mut tasks: Vec<OrgTask> = Vec::new();
+        let mut body: Vec<&str> = Vec::new();
+
+        for line in content.lines() {
+            let stars = line.chars().take_while(|c| *c == '*').count();
+            let is_heading = stars > 0 && line[stars..].starts_with(' ');
+            if !is_heading {
+                body.push(line.trim_end());
+                continue;
+            }
+            flush_body(&mut tasks, &mut body);
+            let heading = parse_heading(line[stars..].trim());
+            match tasks.last_mut() {
+                Some(parent) if stars > 1 => parent.subtasks.push(heading),
+                // Nested headings before any top-level one become tasks.
+                _ => tasks.push(heading),
+            }
+        }
+        flush_body(&mut tasks, &mut body);
+        tasks
+    }
+
+    /// Picks the tasks to insert and counts the rest: blank names, names in
+    /// `existing_names`, and repeats within the file.
+    pub fn plan_org_import(
+        tasks: &[OrgTask],
+        mut existing_names: HashSet<String>,
+    ) -> (Vec<&OrgTask>, usize) {
+        let mut skipped = 0;
+        let kept = tasks
+            .iter()
+            .filter(|task| {
+                let name = task.name.trim();
+                let keep = !name.is_empty() && existing_names.insert(name.to_string());
+                if !keep {
+                    skipped += 1;
+                }
+                keep
+            })
+            .collect();
+        (kept, skipped)
+    }
+
+    /// Imports an Org file into `target_topic_id`, skipping headings whose
+    /// name is already used in that topic. Tags are parsed but not stored.
+    pub fn import_org(
+        db_ops: &DbOperations,
+        path: &Path,
+        target_topic_id: i32,
+        dry_run: bool,
+    ) -> Result<OrgImportSummary, AppError> {
+        let org_tasks = parse_org(&fs::read_to_string(path)?);
+        let topics = db_ops.load_topics(TopicOrder::Manual)?;
+        let topic = topics
+            .iter()
+            .find(|topic| topic.id == target_topic_id)
+            .ok_or(AppError::TopicNotFound(target_topic_id))?;
+        if topic.name != "Default" && db_ops.is_special_topic(&topic.name) {
+            return Err(AppError::ProtectedTopic(topic.name.clone()));
+        }
+
+        let existing_names = db_ops
+            .load_tasks(topic)?
+            .into_iter()
+            .filter(|task| task.topic_id == target_topic_id)
+            .map(|task| task.name)
+            .collect();
+        let (tasks, skipped) = plan_org_import(&org_tasks, existing_names);
+        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let first_weight = db_ops.next_sort_weight(target_topic_id)?;
+        let new_tasks: Vec<(NewTask, Vec<(&str, bool)>)> = tasks
+            .iter()
+            .zip((first_weight..).step_by(10))
+            .map(|(task, sort_weight)| {
+                (
+                    NewTask {
+                        topic_id: target_topic_id,
+                        name: task.name.trim(),
+                        description: &task.body,
+                        completed: task.completed(),
+                        favourite: false,
+                        pinned: false,
+                        created_at: &now,
+                        updated_at: &now,
+                        sort_weight,
+                        source: TaskSource::ImportOrg.as_str(),
+                        url: None,
+                    },
+                    task.subtasks
+                        .iter()
+                        .filter(|subtask| !subtask.name.trim().is_empty())
+                        .map(|subtask| (subtask.name.trim(), subtask.completed()))
+                        .collect(),
+                )
+            })
+            .collect();
+
+        let (inserted, subtasks) = if dry_run {
+            (
+                new_tasks.len(),
+                new_tasks.iter().map(|(_, subtasks)| subtasks.len()).sum(),
+            )
+        } else {
+            db_ops.bulk_insert_tasks_with_subtasks(&new_tasks)?
+        };
+        Ok(OrgImportSummary {
+            dry_run,
+            inserted,
+            subtasks,
+            skipped,
+        })
+    }
+}
+
+/// Every task as an import-compatible record, so an export can be re-imported.
+pub fn export_records(db_ops: &DbOperations) -> Result<Vec<ImportRecord>, AppError> {
+    let topics = db_ops.load_topics(TopicOrder::Manual)?;
+    // The Default topic lists every task regardless of its topic.
+    let Some(all) = topics.iter().find(|topic| topic.name == "Default") else {
+        return Ok(Vec::new());
+    };
+    let tasks = db_ops.load_tasks(all)?;
+
+    Ok(tasks
+        .into_iter()
+        .enumerate()
+        .map(|(index, task)| ImportRecord {
+            topic: topics
+                .iter()
+                .find(|topic| topic.id == task.topic_id)
+                .map(|topic| topic.name.clone())
+                .unwrap_or_default(),
+            name: task.name,
+            description: task.description,
+            completed: task.completed,
+            favourite: task.favourite,
+            line: index + 1,
+        })
+        .collect())
+}
+
+/// Markdown summary of the day: tasks created, completed, overdue and due on
+/// `today`. Tasks have no completion timestamp, so "completed today" means
+/// completed tasks last updated today.
+pub fn generate_digest(db_ops: &DbOperations, today: NaiveDate) -> Result<String, AppError> {
+    let topics = db_ops.load_topics(TopicOrder::Manual)?;
+    let tasks = match topics.iter().find(|topic| topic.name == "Default") {
+        Some(all) => db_ops.load_tasks(all)?,
+        None => Vec::new(),
+    };
+    let today_str = today.format(DUE_DATE_FORMAT).to_string();
+    let due_on = |task: &Task| {
+        task.due_date
+            .as_deref()
+            .and_then(|due| NaiveDate::parse_from_str(due, DUE_DATE_FORMAT).ok())
+    };
+
+    let created: Vec<&Task> = tasks
+        .iter()
+        .filter(|task| task.created_at.starts_with(&today_str))
+        .collect();
+    let completed: Vec<&Task> = tasks
+        .iter()
+        .filter(|task| task.completed && task.updated_at.starts_with(&today_str))
+        .collect();
+    let overdue: Vec<&Task> = tasks
+        .iter()
+        .filter(|task| !task.completed && due_on(task).is_some_and(|due| due < today))
+        .collect();
+    let due_today: Vec<&Task> = tasks
+        .iter()
+        .filter(|task| !task.completed && due_on(task) == Some(today))
+        .collect();
+
+    let topic_name = |task: &Task| {
+        topics
+            .iter()
+            .find(|topic| topic.id == task.topic_id)
+            .map_or("", |topic| topic.name.as_str())
+    };
+    let mut out = format!("# Task digest for {today_str}\n");
+    for (heading, section) in [
+        ("Created today", &created),
+        ("Completed today", &completed),
+        ("Overdue", &overdue),
+        ("Due today", &due_today),
+    ] {
+        out.push_str(&format!("\n## {heading} ({})\n\n", section.len()));
+        if section.is_empty() {
+            out.push_str("_None_\n");
+        }
+        for task in section {
+            out.push_str(&format!("- {} ({})", task.name, topic_name(task)));
+            if let Some(due) = &task.due_date {
+                out.push_str(&format!(" — due {due}"));
+            }
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+/// Escapes an iCalendar TEXT value (RFC 5545 section 3.3.11).
+fn ics_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\n', '\r'], "\\n")
+}
+
+/// Folds a content line to 75 octets, continuing with CRLF and a space, and
+/// terminates it with CRLF.
+fn ics_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for ch in line.chars() {
+        if width + ch.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(ch);
+        width += ch.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// `DTSTAMP` for a task: its last update, converted from local time to UTC.
+fn ics_timestamp(updated_at: &str) -> String {
+    NaiveDateTime::parse_from_str(updated_at, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .and_then(|local| Local.from_local_datetime(&local).earliest())
+        .map_or_else(Utc::now, |local| local.with_timezone(&Utc))
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+/// One `VTODO` component, CRLF-terminated. `DUE` is omitted when the stored
+/// due date is not `YYYY-MM-DD`.
+pub fn task_to_vtodo(task: &Task) -> String {
+    let mut out = String::new();
+    let due = task
+        .due_date
+        .as_deref()
+        .and_then(|due| NaiveDate::parse_from_str(due, DUE_DATE_FORMAT).ok());
+    let status = if task.completed {
+        "COMPLETED"
+    } else {
+        "NEEDS-ACTION"
+    };
+    ics_line(&mut out, "BEGIN:VTODO");
+    ics_line(&mut out, &format!("UID:task-{}@task_manager_cli", task.id));
+    ics_line(
+        &mut out,
+        &format!("DTSTAMP:{}", ics_timestamp(&task.updated_at)),
+    );
+    ics_line(&mut out, &format!("SUMMARY:{}", ics_text(&task.name)));
+    ics_line(
+        &mut out,
+        &format!("DESCRIPTION:{}", ics_text(&task.description)),
+    );
+    if let Some(due) = due {
+        ics_line(
+            &mut out,
+            &format!("DUE;VALUE=DATE:{}", due.format("%Y%m%d")),
+        );
+    }
+    ics_line(&mut out, &format!("STATUS:{status}"));
+    ics_line(&mut out, "END:VTODO");
+    out
+}
+
+/// A `VCALENDAR` holding one `VTODO` per task.
+pub fn tasks_to_ics(tasks: &[Task]) -> String {
+    let mut out = String::new();
+    ics_line(&mut out, "BEGIN:VCALENDAR");
+    ics_line(&mut out, "VERSION:2.0");
+    ics_line(&mut out, "PRODID:-//task_manager_cli//Tasks//EN");
+    ics_line(&mut out, "CALSCALE:GREGORIAN");
+    for task in tasks {
+        out.push_str(&task_to_vtodo(task));
+    }
+    ics_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+pub fn to_json(records: &[ImportRecord]) -> Result<String, AppError> {
+    Ok(serde_json::to_string_pretty(records)?)
+}
+
+/// Writes records with the header [`parse_csv`] expects.
+pub fn to_csv(records: &[ImportRecord]) -> String {
+    let mut out = String::from("topic,name,description,completed,favourite\n");
+    for record in records {
+        let fields = [
+            csv_field(&record.topic),
+            csv_field(&record.name),
+            csv_field(&record.description),
+            record.completed.to_string(),
+            record.favourite.to_string(),
+        ];
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -8,13 +8,17 @@ use std::time::{Duration, Instant};
 use tui::backend::CrosstermBackend;
 use tui::Terminal;
 
+use crate::db::task_manager::operations::DbOperations;
+use crate::db::DbPool;
 use data::load_dashboard;
 pub use types::AppTool;
-use types::{handle_key, HomepageAction, HomepageDashboard};
+use types::{handle_key, HomepageAction, HomepageDashboard, LauncherState};
 
-/// Run the homepage (launcher) UI.
+/// Run the homepage (launcher) UI. `pool` is the task database, polled each
+/// tick for the header's due-today count.
 pub fn run_homepage(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    pool: &DbPool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let tools = [
         AppTool::TaskManager,
@@ -23,7 +27,7 @@ pub fn run_homepage(
         AppTool::DelegationTracker,
         AppTool::DecisionLog,
     ];
-    let mut selected = 0;
+    let mut launcher = LauncherState::default();
     let mut error_message: Option<String> = None;
     let mut dashboard = load_dashboard().unwrap_or_else(|err| {
         error_message = Some(err.to_string());
@@ -32,19 +36,29 @@ pub fn run_homepage(
             ..HomepageDashboard::default()
         }
     });
+    // `load_dashboard` has applied the task migrations by now.
+    let db_ops = DbOperations::new(pool.clone(), crate::log_config::get_logger_or_discard());
+    let mut due_today = db_ops.count_tasks_due_today().ok();
     let tick_rate = Duration::from_millis(250);
     let mut last_tick = Instant::now();
 
     loop {
         terminal.draw(|f| {
-            draw::draw_homepage(f, &tools, selected, &dashboard, error_message.as_deref());
+            draw::draw_homepage(
+                f,
+                &tools,
+                &launcher,
+                &dashboard,
+                due_today,
+                error_message.as_deref(),
+            );
         })?;
 
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
             .unwrap_or_else(|| Duration::from_secs(0));
         if event::poll(timeout)? {
-            let action = handle_key(event::read()?, &mut selected, tools.len());
+            let action = handle_key(event::read()?, &mut launcher, &tools);
             match action {
                 HomepageAction::Continue => {}
                 HomepageAction::Exit => break,
@@ -68,6 +82,8 @@ pub fn run_homepage(
             }
         }
         if last_tick.elapsed() >= tick_rate {
+            // Keep the last count if the database is briefly unavailable.
+            due_today = db_ops.count_tasks_due_today().ok().or(due_today);
             last_tick = Instant::now();
         }
     }
@@ -78,6 +94,9 @@ pub fn run_homepage(
 #[cfg(test)]
 mod tests {
     use super::data::{compact_text, scan_notes_tree};
+    use super::draw::draw_homepage;
+    use super::types::{handle_key, AppTool, HomepageAction, HomepageDashboard, LauncherState};
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
     use std::fs;
     use std::path::PathBuf;
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -121,4 +140,92 @@ mod tests {
         let _ = fs::remove_dir_all(root);
         Ok(())
     }
+
+    #[test]
+    fn launcher_search_filters_tools_and_launches_a_lone_match() {
+        let tools = [
+            AppTool::TaskManager,
+            AppTool::Notes,
+            AppTool::OneOnOneManager,
+            AppTool::DelegationTracker,
+            AppTool::DecisionLog,
+        ];
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let titles = |state: &LauncherState| -> Vec<&str> {
+            state
+                .matching(&tools)
+                .iter()
+                .map(|tool| tool.title())
+                .collect()
+        };
+        let mut state = LauncherState::default();
+
+        for c in "MAN".chars() {
+            handle_key(key(KeyCode::Char(c)), &mut state, &tools);
+        }
+        assert_eq!(titles(&state), vec!["Task Manager", "1:1 Manager"]);
+        handle_key(key(KeyCode::Down), &mut state, &tools);
+        assert_eq!(state.selected, 1);
+
+        handle_key(key(KeyCode::Char('1')), &mut state, &tools);
+        assert_eq!(state.selected, 0);
+        assert!(titles(&state).is_empty());
+        assert!(matches!(
+            handle_key(key(KeyCode::Enter), &mut state, &tools),
+            HomepageAction::Continue
+        ));
+
+        for _ in 0..4 {
+            handle_key(key(KeyCode::Backspace), &mut state, &tools);
+        }
+        assert_eq!(titles(&state).len(), tools.len());
+        for c in "tas".chars() {
+            handle_key(key(KeyCode::Char(c)), &mut state, &tools);
+        }
+        assert!(matches!(
+            handle_key(key(KeyCode::Enter), &mut state, &tools),
+            HomepageAction::Launch(AppTool::TaskManager)
+        ));
+
+        handle_key(key(KeyCode::Esc), &mut state, &tools);
+        assert_eq!(titles(&state).len(), tools.len());
+        // With an empty query the launcher keys keep working.
+        handle_key(key(KeyCode::Char('j')), &mut state, &tools);
+        assert_eq!((state.query.as_str(), state.selected), ("", 1));
+        assert!(matches!(
+            handle_key(key(KeyCode::Char('q')), &mut state, &tools),
+            HomepageAction::Exit
+        ));
+    }
+
+    #[test]
+    fn header_title_shows_tasks_due_today() -> Result<(), Box<dyn std::error::Error>> {
+        let tools = [AppTool::TaskManager, AppTool::Notes];
+        let dashboard = HomepageDashboard::default();
+        let mut terminal = tui::Terminal::new(tui::backend::TestBackend::new(120, 40))?;
+        let header = |terminal: &tui::Terminal<tui::backend::TestBackend>| -> String {
+            let buffer = terminal.backend().buffer();
+            (0..buffer.area.width)
+                .map(|x| buffer.get(x, 1).symbol.as_str())
+                .collect()
+        };
+
+        terminal.draw(|f| {
+            draw_homepage(
+                f,
+                &tools,
+                &LauncherState::default(),
+                &dashboard,
+                Some(3),
+                None,
+            )
+        })?;
+        assert!(header(&terminal).contains("Homepage | Tasks due today: 3"));
+
+        terminal.draw(|f| {
+            draw_homepage(f, &tools, &LauncherState::default(), &dashboard, None, None)
+        })?;
+        assert!(!header(&terminal).contains("due today"));
+        Ok(())
+    }
 }
@@ -1,4 +1,4 @@
-use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use tui::style::{Color, Modifier, Style};
 use tui::text::{Span, Spans};
 use tui::widgets::{Clear, List, ListItem, ListState, Paragraph, Wrap};
@@ -6,13 +6,15 @@ use tui::widgets::{Clear, List, ListItem, ListState, Paragraph, Wrap};
 use crate::ui_style::{self, Accent};
 
 use super::data::{support_lines_for_leadership, support_lines_for_notes, support_lines_for_tasks};
+use super::types::LauncherState;
 use super::{AppTool, HomepageDashboard};
 
 pub fn draw_homepage<B: tui::backend::Backend>(
     f: &mut tui::Frame<B>,
     tools: &[AppTool],
-    selected: usize,
+    launcher: &LauncherState,
     dashboard: &HomepageDashboard,
+    due_today: Option<usize>,
     error_message: Option<&str>,
 ) {
     let size = f.size();
@@ -21,20 +23,62 @@ pub fn draw_homepage<B: tui::backend::Backend>(
         .margin(1)
         .constraints([
             Constraint::Length(6),
+            Constraint::Length(3),
             Constraint::Min(20),
             Constraint::Length(4),
         ])
         .split(size);
 
-    draw_header(f, outer[0], dashboard, error_message);
-    draw_dashboard(f, outer[1], tools, selected, dashboard);
-    draw_footer(f, outer[2]);
+    draw_header(f, outer[0], dashboard, due_today, error_message);
+    draw_search_bar(f, outer[1], &launcher.query);
+    let matching = launcher.matching(tools);
+    match matching.get(launcher.selected) {
+        Some(selected_tool) => draw_dashboard(
+            f,
+            outer[2],
+            &matching,
+            launcher.selected,
+            *selected_tool,
+            dashboard,
+        ),
+        None => {
+            let empty = Paragraph::new(Span::styled(
+                format!(
+                    "No tools match \"{}\". Esc clears the search.",
+                    launcher.query
+                ),
+                ui_style::muted_style().add_modifier(Modifier::ITALIC),
+            ))
+            .block(ui_style::surface_block("Launcher", Accent::Primary));
+            f.render_widget(empty, outer[2]);
+        }
+    }
+    draw_footer(f, outer[3]);
 }
 
+fn draw_search_bar<B: tui::backend::Backend>(f: &mut tui::Frame<B>, area: Rect, query: &str) {
+    let text = if query.is_empty() {
+        Span::styled("Type to filter tools", ui_style::subtle_style())
+    } else {
+        Span::styled(query.to_string(), ui_style::body_style())
+    };
+    let search = Paragraph::new(Spans::from(vec![Span::raw("/ "), text]))
+        .block(ui_style::shell_block("Search"));
+    f.render_widget(search, area);
+    f.set_cursor(
+        (area.x + 3 + query.chars().count() as u16).min(area.right().saturating_sub(2)),
+        area.y + 1,
+    );
+}
+
+/// `HH:MM:SS`.
+const CLOCK_WIDTH: u16 = 8;
+
 fn draw_header<B: tui::backend::Backend>(
     f: &mut tui::Frame<B>,
     area: Rect,
     dashboard: &HomepageDashboard,
+    due_today: Option<usize>,
     error_message: Option<&str>,
 ) {
     let title_lines = vec![
@@ -44,8 +88,9 @@ fn draw_header<B: tui::backend::Backend>(
         )),
         Spans::from(Span::styled(
             format!(
-                "Tasks: {} total | Notes DB: {} | Note Files: {}",
+                "Tasks: {} | Topics: {} | Notes DB: {} | Note Files: {}",
                 dashboard.tasks.task_count,
+                dashboard.tasks.topic_count,
                 dashboard.notes.db_note_count,
                 dashboard.notes.file_count,
             ),
@@ -71,10 +116,27 @@ fn draw_header<B: tui::backend::Backend>(
         )),
     ];
 
-    let header = Paragraph::new(title_lines)
-        .block(ui_style::shell_block("Homepage"))
-        .wrap(Wrap { trim: false });
-    f.render_widget(header, area);
+    let title = match due_today {
+        Some(count) => format!("Homepage | Tasks due today: {count}"),
+        None => "Homepage".to_string(),
+    };
+    let block = ui_style::shell_block(&title);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(CLOCK_WIDTH)])
+        .split(inner);
+
+    let header = Paragraph::new(title_lines).wrap(Wrap { trim: false });
+    f.render_widget(header, columns[0]);
+    // Redrawn every tick, so the clock keeps running while the homepage idles.
+    let clock = Paragraph::new(Span::styled(
+        chrono::Local::now().format("%H:%M:%S").to_string(),
+        ui_style::title_style(Accent::Primary),
+    ))
+    .alignment(Alignment::Right);
+    f.render_widget(clock, columns[1]);
 }
 
 fn draw_dashboard<B: tui::backend::Backend>(
@@ -82,6 +144,7 @@ fn draw_dashboard<B: tui::backend::Backend>(
     area: Rect,
     tools: &[AppTool],
     selected: usize,
+    selected_tool: AppTool,
     dashboard: &HomepageDashboard,
 ) {
     let columns = Layout::default()
@@ -90,7 +153,7 @@ fn draw_dashboard<B: tui::backend::Backend>(
         .split(area);
 
     draw_tool_launcher(f, columns[0], tools, selected, dashboard);
-    draw_detail_panels(f, columns[1], tools[selected], dashboard);
+    draw_detail_panels(f, columns[1], selected_tool, dashboard);
 }
 
 fn draw_tool_launcher<B: tui::backend::Backend>(
@@ -524,6 +587,8 @@ fn draw_footer<B: tui::backend::Backend>(f: &mut tui::Frame<B>, area: Rect) {
     let footer = Paragraph::new(vec![
         ui_style::command_bar_spans(&[
             ("Enter", "launch selected app"),
+            ("type", "search"),
+            ("Esc", "clear search"),
             ("j/k", "move"),
             ("↑/↓", "move"),
             ("r", "refresh dashboard"),
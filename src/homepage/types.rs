@@ -1,13 +1,17 @@
 use std::io::Stdout;
 use std::path::PathBuf;
 
-use crossterm::event::Event;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
 use tui::{backend::CrosstermBackend, style::Color, Terminal};
 
 use crate::leadership_tools::{DashboardSnapshot, ToolKind as LeadershipTool};
 use crate::ui_style::{self, Accent};
 
 /// Enum representing a tool available from the homepage.
+///
+/// To add a tool: add a variant here, give it a title, subtitle and accent,
+/// start it from [`AppTool::run`], list it in `run_homepage`'s `tools`, and
+/// add its dashboard arms in `homepage/draw.rs`.
 #[derive(Clone, Copy)]
 pub enum AppTool {
     TaskManager,
@@ -53,7 +57,7 @@ impl AppTool {
         terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     ) -> Result<(), Box<dyn std::error::Error>> {
         match self {
-            AppTool::TaskManager => crate::task_manager::run_task_manager(terminal),
+            AppTool::TaskManager => Ok(crate::task_manager::run_task_manager(terminal)?),
             AppTool::Notes => crate::notes::run_notes_app(terminal),
             AppTool::OneOnOneManager => {
                 crate::leadership_tools::run_tool(LeadershipTool::OneOnOne, terminal)
@@ -114,33 +118,78 @@ pub enum HomepageAction {
     Launch(AppTool),
 }
 
-pub fn handle_key(key: Event, selected: &mut usize, tool_count: usize) -> HomepageAction {
+/// The launcher's search query and the selection within the tools that
+/// match it.
+#[derive(Default)]
+pub struct LauncherState {
+    pub query: String,
+    pub selected: usize,
+}
+
+impl LauncherState {
+    /// Tools whose title contains the query, case-insensitively, in order.
+    pub fn matching(&self, tools: &[AppTool]) -> Vec<AppTool> {
+        let query = self.query.trim().to_lowercase();
+        tools
+            .iter()
+            .filter(|tool| tool.title().to_lowercase().contains(&query))
+            .copied()
+            .collect()
+    }
+
+    fn move_down(&mut self, match_count: usize) -> HomepageAction {
+        if self.selected < match_count.saturating_sub(1) {
+            self.selected += 1;
+        }
+        HomepageAction::Continue
+    }
+
+    fn move_up(&mut self) -> HomepageAction {
+        self.selected = self.selected.saturating_sub(1);
+        HomepageAction::Continue
+    }
+
+    fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.selected = 0;
+    }
+}
+
+/// The search bar is always active, but while it is empty `q`, `j`, `k` and
+/// `r` keep their launcher meanings; any other character starts a search.
+pub fn handle_key(key: Event, state: &mut LauncherState, tools: &[AppTool]) -> HomepageAction {
     let Event::Key(key) = key else {
         return HomepageAction::Continue;
     };
+    let matching = state.matching(tools);
 
     match key.code {
-        crossterm::event::KeyCode::Char('q') => HomepageAction::Exit,
-        crossterm::event::KeyCode::Char('j') | crossterm::event::KeyCode::Down => {
-            if *selected < tool_count.saturating_sub(1) {
-                *selected += 1;
-            }
+        KeyCode::Char('q') if state.query.is_empty() => HomepageAction::Exit,
+        KeyCode::Char('r') if state.query.is_empty() => HomepageAction::Refresh,
+        KeyCode::Down => state.move_down(matching.len()),
+        KeyCode::Char('j') if state.query.is_empty() => state.move_down(matching.len()),
+        KeyCode::Up => state.move_up(),
+        KeyCode::Char('k') if state.query.is_empty() => state.move_up(),
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.set_query(format!("{}{c}", state.query));
             HomepageAction::Continue
         }
-        crossterm::event::KeyCode::Char('k') | crossterm::event::KeyCode::Up => {
-            if *selected > 0 {
-                *selected -= 1;
-            }
+        KeyCode::Backspace => {
+            let mut query = std::mem::take(&mut state.query);
+            query.pop();
+            state.set_query(query);
+            HomepageAction::Continue
+        }
+        KeyCode::Esc => {
+            state.set_query(String::new());
             HomepageAction::Continue
         }
-        crossterm::event::KeyCode::Char('r') => HomepageAction::Refresh,
-        crossterm::event::KeyCode::Enter => HomepageAction::Launch(match *selected {
-            0 => AppTool::TaskManager,
-            1 => AppTool::Notes,
-            2 => AppTool::OneOnOneManager,
-            3 => AppTool::DelegationTracker,
-            _ => AppTool::DecisionLog,
-        }),
+        // Editing the query resets the selection, so a lone match is selected.
+        KeyCode::Enter => matching
+            .get(state.selected)
+            .map_or(HomepageAction::Continue, |tool| {
+                HomepageAction::Launch(*tool)
+            }),
         _ => HomepageAction::Continue,
     }
 }
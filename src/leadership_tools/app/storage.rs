@@ -108,7 +108,10 @@ pub fn load_task_summary(task_id: i32) -> Result<Option<(String, String)>, Box<d
     );
     let db_url = format!("sqlite://{}", db_path.to_string_lossy());
     let pool = crate::db::establish_connection_pool(&db_url)?;
-    let ops = crate::db::task_manager::operations::DbOperations::new(pool);
+    let ops = crate::db::task_manager::operations::DbOperations::new(
+        pool,
+        crate::log_config::get_logger_or_discard(),
+    );
 
     Ok(ops.find_task(task_id)?.map(|task| {
         let status = if task.completed { "done" } else { "open" };
@@ -1,12 +1,21 @@
+pub mod bench;
+pub mod cli;
 mod common;
+pub mod config;
 pub mod db;
+pub mod doctor;
+pub mod error;
+pub mod export;
 mod filter_presets;
 mod homepage;
 pub mod leadership_tools;
 mod log_config;
 pub mod notes;
+pub mod repl;
 pub mod task_manager;
 mod ui_style;
+mod utils;
+pub mod watch;
 
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
@@ -17,9 +26,13 @@ use slog::info;
 use std::io;
 use tui::{backend::CrosstermBackend, Terminal};
 
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// Runs the TUI. `quiet` keeps log records off the terminal; they still go
+/// to the log file.
+pub fn run(quiet: bool) -> Result<(), Box<dyn std::error::Error>> {
     dotenv::dotenv().ok();
-    let log = log_config::init_logger();
+    let log = log_config::init_logger(quiet, config::env_config().split_error_log);
+    let db_path = task_manager::task_db_path()?;
+    let pool = db::establish_connection_pool(&format!("sqlite://{db_path}"))?;
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
@@ -27,7 +40,7 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
     let mut terminal = Terminal::new(backend)?;
 
     info!(log, "Starting Task Manager...");
-    let res = homepage::run_homepage(&mut terminal);
+    let res = homepage::run_homepage(&mut terminal, &pool);
 
     disable_raw_mode()?;
     execute!(
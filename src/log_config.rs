@@ -1,16 +1,31 @@
-use slog::{o, Drain, Logger};
+use slog::{o, Drain, Level, Logger};
 use slog_async;
 use slog_envlogger;
 use slog_envlogger::LogBuilder;
 use slog_json;
 use slog_term;
-use std::fs::{create_dir_all, OpenOptions};
-use std::path::Path;
+use std::fs::{self, create_dir_all, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 static LOGGER: OnceLock<Logger> = OnceLock::new();
 
-pub fn init_logger() -> &'static Logger {
+/// JSON log written by [`init_logger`].
+pub const LOG_FILE: &str = ".logs/app.log";
+
+/// ERROR and CRITICAL records only, when `Config::split_error_log` is set.
+pub const ERROR_LOG_FILE: &str = ".logs/error.log";
+
+/// Size in bytes at which [`ERROR_LOG_FILE`] is rotated to `error.log.1`.
+pub const LOG_ERROR_MAX_BYTES_ENV: &str = "LOG_ERROR_MAX_BYTES";
+
+const DEFAULT_ERROR_LOG_MAX_BYTES: u64 = 1024 * 1024;
+
+/// Logs to [`LOG_FILE`] and, unless `quiet` is set, to the terminal. With
+/// `split_error_log`, errors are also written to [`ERROR_LOG_FILE`]. Only the
+/// first call configures the logger; later calls return it unchanged.
+pub fn init_logger(quiet: bool, split_error_log: bool) -> &'static Logger {
     LOGGER.get_or_init(|| {
         // ✅ Make sure .logs/ exists
         let log_dir = ".logs";
@@ -18,31 +33,142 @@ pub fn init_logger() -> &'static Logger {
             create_dir_all(log_dir).expect("Failed to create logs directory");
         }
 
-        // Terminal drain
-        let decorator = slog_term::TermDecorator::new().build();
-        let term_drain = slog_term::CompactFormat::new(decorator).build().fuse();
-        let term_drain = slog_async::Async::new(term_drain).build().fuse();
+        let error_log = split_error_log.then(|| (Path::new(ERROR_LOG_FILE), error_log_max_bytes()));
+        build_logger(Path::new(LOG_FILE), error_log, quiet).expect("Cannot open log file")
+    })
+}
+
+/// [`LOG_ERROR_MAX_BYTES_ENV`], or 1 MB when unset or not a positive number.
+fn error_log_max_bytes() -> u64 {
+    std::env::var(LOG_ERROR_MAX_BYTES_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|&bytes| bytes > 0)
+        .unwrap_or(DEFAULT_ERROR_LOG_MAX_BYTES)
+}
+
+/// Builds the logger behind [`init_logger`]: every record goes to `log_file`,
+/// ERROR and above also to `error_log` (path, rotation size) when given, and
+/// to the terminal unless `quiet`.
+fn build_logger(
+    log_file: &Path,
+    error_log: Option<(&Path, u64)>,
+    quiet: bool,
+) -> io::Result<Logger> {
+    // File drain (JSON)
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_file)?;
+    let file_drain = slog_json::Json::default(file).fuse();
+    let file_drain = slog_async::Async::new(file_drain).build().fuse();
+
+    let error_drain = match error_log {
+        Some((path, max_bytes)) => {
+            // Flushing after every record lets the file rotate between records.
+            let errors = slog_json::Json::new(RotatingFile::open(path, max_bytes)?)
+                .add_default_keys()
+                .set_flush(true)
+                .build()
+                .fuse();
+            let errors = slog_async::Async::new(errors).build().fuse();
+            Some(slog::Filter::new(errors, |record: &slog::Record| {
+                record.level().is_at_least(Level::Error)
+            }))
+        }
+        None => None,
+    };
+
+    if quiet {
+        return Ok(match error_drain {
+            Some(errors) => root_logger(slog::Duplicate::new(file_drain, errors).fuse()),
+            None => root_logger(file_drain),
+        });
+    }
+
+    // Terminal drain
+    let decorator = slog_term::TermDecorator::new().build();
+    let term_drain = slog_term::CompactFormat::new(decorator).build().fuse();
+    let term_drain = slog_async::Async::new(term_drain).build().fuse();
+
+    // Combine
+    Ok(match error_drain {
+        Some(errors) => root_logger(
+            slog::Duplicate::new(term_drain, slog::Duplicate::new(file_drain, errors).fuse())
+                .fuse(),
+        ),
+        None => root_logger(slog::Duplicate::new(term_drain, file_drain).fuse()),
+    })
+}
+
+/// Appends to `path`, moving it to `<path>.1` (replacing any older copy) once
+/// a record would take it past `max_bytes`. Writes are buffered until
+/// `flush`, so a record is never split across the two files.
+struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    file: File,
+    written: u64,
+    pending: Vec<u8>,
+}
+
+impl RotatingFile {
+    fn open(path: &Path, max_bytes: u64) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            max_bytes,
+            file,
+            written,
+            pending: Vec::new(),
+        })
+    }
 
-        // File drain (JSON)
-        let file = OpenOptions::new()
+    fn rotate(&mut self) -> io::Result<()> {
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(".1");
+        fs::rename(&self.path, rotated)?;
+        self.file = OpenOptions::new()
             .create(true)
-            .write(true)
             .append(true)
-            .open(".logs/app.log")
-            .expect("Cannot open log file");
-        let file_drain = slog_json::Json::default(file).fuse();
-        let file_drain = slog_async::Async::new(file_drain).build().fuse();
-
-        // Combine and wrap with envlogger
-        let combined_drain = slog::Duplicate::new(term_drain, file_drain).fuse();
-
-        let env_drain = LogBuilder::new(combined_drain)
-            .parse(&std::env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()))
-            .build()
-            .fuse();
-        // Root logger
-        Logger::root(env_drain, o!("version" => env!("CARGO_PKG_VERSION")))
-    })
+            .open(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let len = self.pending.len() as u64;
+        if self.written > 0 && self.written + len > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(&self.pending)?;
+        self.pending.clear();
+        self.written += len;
+        self.file.flush()
+    }
+}
+
+/// Wraps `drain` with the `RUST_LOG` filter (default `info`).
+fn root_logger<D>(drain: D) -> Logger
+where
+    D: slog::SendSyncUnwindSafeDrain<Ok = (), Err = slog::Never>
+        + std::panic::RefUnwindSafe
+        + 'static,
+{
+    let env_drain = LogBuilder::new(drain)
+        .parse(&std::env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()))
+        .build()
+        .fuse();
+    // Root logger
+    Logger::root(env_drain, o!("version" => env!("CARGO_PKG_VERSION")))
 }
 
 pub fn get_logger() -> &'static Logger {
@@ -50,3 +176,56 @@ pub fn get_logger() -> &'static Logger {
         .get()
         .expect("Logger not initialized. Call init_logger() first.")
 }
+
+/// Like [`get_logger`], but falls back to a logger that discards everything
+/// when `init_logger()` was never called (CLI subcommands, tests).
+pub fn get_logger_or_discard() -> Logger {
+    LOGGER
+        .get()
+        .cloned()
+        .unwrap_or_else(|| Logger::root(slog::Discard, o!()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_logger;
+    use slog::{error, info, warn};
+    use std::fs;
+
+    #[test]
+    fn error_records_go_to_both_log_files_and_rotate() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir().join(format!(
+            "task_manager_cli_split_logs_{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir)?;
+        let log_file = dir.join("app.log");
+        let error_file = dir.join("error.log");
+
+        let logger = build_logger(&log_file, Some((&error_file, 100)), true)?;
+        info!(logger, "routine startup");
+        warn!(logger, "slow query");
+        error!(logger, "database is locked");
+        // Dropping the logger flushes the async drains.
+        drop(logger);
+
+        let all = fs::read_to_string(&log_file)?;
+        let errors = fs::read_to_string(&error_file)?;
+        for message in ["routine startup", "slow query", "database is locked"] {
+            assert!(all.contains(message), "{message} missing from app.log");
+        }
+        assert!(errors.contains("database is locked"));
+        assert!(!errors.contains("routine startup") && !errors.contains("slow query"));
+
+        let logger = build_logger(&log_file, Some((&error_file, 100)), true)?;
+        error!(logger, "disk full");
+        drop(logger);
+        let rotated = fs::read_to_string(dir.join("error.log.1"))?;
+        assert!(rotated.contains("database is locked"));
+        let errors = fs::read_to_string(&error_file)?;
+        assert!(errors.contains("disk full") && !errors.contains("database is locked"));
+
+        let _ = fs::remove_dir_all(dir);
+        Ok(())
+    }
+}
@@ -1,3 +1,33 @@
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    task_manager_cli::run()
+use clap::Parser;
+use task_manager_cli::cli::{self, Cli, Command};
+use task_manager_cli::error::AppError;
+use task_manager_cli::task_manager::app::App;
+
+fn main() {
+    dotenv::dotenv().ok();
+    let cli = Cli::parse();
+    if cli.force {
+        App::skip_instance_lock();
+    }
+    let command = cli.command.unwrap_or(Command::Tui);
+    if command.needs_preflight() {
+        if let Err(errors) = task_manager_cli::config::preflight() {
+            for error in &errors {
+                eprintln!("error: {error}");
+            }
+            std::process::exit(2);
+        }
+    }
+    let code = match cli::run_command(command, cli.quiet) {
+        Ok(code) => code,
+        Err(e @ AppError::AlreadyRunning) => {
+            eprintln!("error: {e}");
+            2
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            1
+        }
+    };
+    std::process::exit(code);
 }
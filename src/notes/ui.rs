@@ -14,6 +14,7 @@ pub fn run<B: Backend>(
     crate::common::tui::run_event_loop_with_tick(
         terminal,
         app,
+        crate::common::tui::DEFAULT_TICK_RATE,
         |f, app| draw::draw_ui(f, app),
         handle_key,
         |action, _, _| {
@@ -0,0 +1,306 @@
+//! `task_manager_cli repl`: one task command per line, without the TUI.
+//! Words are split on whitespace; wrap a name or description in quotes to
+//! keep its spaces. At a terminal, [`LineEditor`] reads the lines, with
+//! history and Tab completion of command and topic names.
+
+mod editor;
+
+use std::error::Error;
+use std::fmt;
+use std::io::{BufRead, Write};
+
+use crate::cli::{self, ListFormat, OutputFormat};
+use crate::db::task_manager::models::TopicOrder;
+use crate::db::task_manager::operations::DbOperations;
+use crate::error::AppError;
+
+pub use editor::{Edit, LineEditor};
+
+/// Command names, for Tab completion.
+pub const COMMANDS: [&str; 8] = [
+    "add",
+    "list",
+    "done",
+    "delete",
+    "favourite",
+    "topics",
+    "help",
+    "exit",
+];
+
+const HELP: &str = "\
+add <topic> <name> [description]  add a task, creating the topic if needed
+list [topic]                      list tasks (all of them by default)
+done <id>                         mark a task completed
+delete <id>                       delete a task
+favourite <id>                    toggle a task's favourite flag
+topics                            list topics
+exit                              leave (so does Ctrl+D)";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplCommand {
+    Add {
+        topic: String,
+        name: String,
+        description: String,
+    },
+    List {
+        topic: Option<String>,
+    },
+    Done(i32),
+    Delete(i32),
+    Favourite(i32),
+    Topics,
+    Help,
+    Exit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A blank line; the prompt is shown again.
+    Empty,
+    UnknownCommand(String),
+    MissingArgument {
+        command: &'static str,
+        argument: &'static str,
+    },
+    TooManyArguments(&'static str),
+    InvalidId(String),
+    UnterminatedQuote,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty command"),
+            ParseError::UnknownCommand(command) => {
+                write!(f, "unknown command '{command}'; try 'help'")
+            }
+            ParseError::MissingArgument { command, argument } => {
+                write!(f, "{command} needs a {argument}")
+            }
+            ParseError::TooManyArguments(command) => {
+                write!(
+                    f,
+                    "too many arguments for {command}; quote names with spaces"
+                )
+            }
+            ParseError::InvalidId(id) => write!(f, "'{id}' is not a task id"),
+            ParseError::UnterminatedQuote => write!(f, "unterminated quote"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Splits `line` on whitespace, keeping quoted (`"…"` or `'…'`) runs together.
+fn split_words(line: &str) -> Result<Vec<String>, ParseError> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    for ch in line.chars() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => word.get_or_insert_with(String::new).push(ch),
+            None if ch == '"' || ch == '\'' => {
+                quote = Some(ch);
+                word.get_or_insert_with(String::new);
+            }
+            None if ch.is_whitespace() => words.extend(word.take()),
+            None => word.get_or_insert_with(String::new).push(ch),
+        }
+    }
+    if quote.is_some() {
+        return Err(ParseError::UnterminatedQuote);
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Completions for the last word of `line`, the text before the cursor:
+/// command names for the first word, topic names for the second word of
+/// `add` and `list`. Returns the byte offset where that word starts and the
+/// candidates, quoted when they hold spaces.
+pub fn complete(line: &str, topics: &[String]) -> (usize, Vec<String>) {
+    let quotes = line.matches(['"', '\'']).count();
+    let start = match line.rfind(['"', '\'']) {
+        // Inside an open quote the word runs from the quote.
+        Some(quote) if quotes % 2 == 1 => quote,
+        _ => line.rfind(char::is_whitespace).map_or(0, |index| {
+            index + line[index..].chars().next().map_or(1, char::len_utf8)
+        }),
+    };
+    let word = line[start..].trim_start_matches(['"', '\'']).to_lowercase();
+    let previous: Vec<&str> = line[..start].split_whitespace().collect();
+    let names: Vec<&str> = match previous.as_slice() {
+        [] => COMMANDS.to_vec(),
+        ["add" | "list"] => topics.iter().map(String::as_str).collect(),
+        _ => Vec::new(),
+    };
+    let candidates = names
+        .into_iter()
+        .filter(|name| name.to_lowercase().starts_with(&word))
+        .map(|name| {
+            if name.contains(char::is_whitespace) {
+                format!("\"{name}\"")
+            } else {
+                name.to_string()
+            }
+        })
+        .collect();
+    (start, candidates)
+}
+
+pub fn parse_command(line: &str) -> Result<ReplCommand, ParseError> {
+    let words = split_words(line)?;
+    let Some((command, args)) = words.split_first() else {
+        return Err(ParseError::Empty);
+    };
+    let id = |command: &'static str| -> Result<i32, ParseError> {
+        match args {
+            [] => Err(ParseError::MissingArgument {
+                command,
+                argument: "task id",
+            }),
+            [id] => id.parse().map_err(|_| ParseError::InvalidId(id.clone())),
+            _ => Err(ParseError::TooManyArguments(command)),
+        }
+    };
+    let no_args = |command: &'static str, parsed: ReplCommand| {
+        if args.is_empty() {
+            Ok(parsed)
+        } else {
+            Err(ParseError::TooManyArguments(command))
+        }
+    };
+
+    match command.as_str() {
+        "add" => match args {
+            [] => Err(ParseError::MissingArgument {
+                command: "add",
+                argument: "topic",
+            }),
+            [_] => Err(ParseError::MissingArgument {
+                command: "add",
+                argument: "task name",
+            }),
+            [topic, name] | [topic, name, _] => Ok(ReplCommand::Add {
+                topic: topic.clone(),
+                name: name.clone(),
+                description: args.get(2).cloned().unwrap_or_default(),
+            }),
+            _ => Err(ParseError::TooManyArguments("add")),
+        },
+        "list" => match args {
+            [] => Ok(ReplCommand::List { topic: None }),
+            [topic] => Ok(ReplCommand::List {
+                topic: Some(topic.clone()),
+            }),
+            _ => Err(ParseError::TooManyArguments("list")),
+        },
+        "done" => id("done").map(ReplCommand::Done),
+        "delete" => id("delete").map(ReplCommand::Delete),
+        "favourite" => id("favourite").map(ReplCommand::Favourite),
+        "topics" => no_args("topics", ReplCommand::Topics),
+        "help" => no_args("help", ReplCommand::Help),
+        "exit" => no_args("exit", ReplCommand::Exit),
+        other => Err(ParseError::UnknownCommand(other.to_string())),
+    }
+}
+
+/// Reads commands from `input` until `exit` or end of input, writing each
+/// response to `out`. A failed command prints its error and the loop goes
+/// on. `prompt` shows `> ` before each line, for interactive use.
+pub fn run(
+    db_ops: &DbOperations,
+    mut input: impl BufRead,
+    out: &mut impl Write,
+    prompt: bool,
+) -> Result<i32, AppError> {
+    let mut line = String::new();
+    loop {
+        if prompt {
+            write!(out, "> ")?;
+            out.flush()?;
+        }
+        line.clear();
+        if input.read_line(&mut line)? == 0 || !run_line(db_ops, out, &line)? {
+            break;
+        }
+    }
+    Ok(0)
+}
+
+/// [`run`] at a terminal: lines come from a [`LineEditor`] that completes
+/// command names and the topics in the database.
+pub fn run_interactive(db_ops: &DbOperations, out: &mut impl Write) -> Result<i32, AppError> {
+    let mut editor = LineEditor::new();
+    loop {
+        let topics: Vec<String> = db_ops
+            .load_topics(TopicOrder::Manual)?
+            .into_iter()
+            .map(|topic| topic.name)
+            .collect();
+        match editor.read_line(out, "> ", &topics)? {
+            Some(line) if run_line(db_ops, out, &line)? => {}
+            _ => break,
+        }
+    }
+    Ok(0)
+}
+
+/// Parses and runs one line, printing any error. `false` once the line is
+/// `exit`.
+fn run_line(db_ops: &DbOperations, out: &mut impl Write, line: &str) -> Result<bool, AppError> {
+    match parse_command(line) {
+        Ok(ReplCommand::Exit) => return Ok(false),
+        Ok(command) => {
+            if let Err(e) = execute(db_ops, out, command) {
+                writeln!(out, "error: {e}")?;
+            }
+        }
+        Err(ParseError::Empty) => {}
+        Err(e) => writeln!(out, "error: {e}")?,
+    }
+    Ok(true)
+}
+
+fn execute(
+    db_ops: &DbOperations,
+    out: &mut impl Write,
+    command: ReplCommand,
+) -> Result<(), AppError> {
+    match command {
+        ReplCommand::Add {
+            topic,
+            name,
+            description,
+        } => {
+            cli::add(db_ops, out, &topic, &name, &description, None)?;
+        }
+        ReplCommand::List { topic } => {
+            cli::list(db_ops, out, topic.as_deref(), ListFormat::Text)?;
+        }
+        ReplCommand::Done(id) => {
+            cli::complete(db_ops, out, id)?;
+        }
+        ReplCommand::Delete(id) => {
+            cli::delete(db_ops, out, id)?;
+        }
+        ReplCommand::Favourite(id) => {
+            let task = db_ops.toggle_task_favourite(id)?;
+            let verb = if task.favourite {
+                "Favourited"
+            } else {
+                "Unfavourited"
+            };
+            writeln!(out, "{verb} task {}: {}", task.id, task.name)?;
+        }
+        ReplCommand::Topics => {
+            cli::topics(db_ops, out, OutputFormat::Table)?;
+        }
+        ReplCommand::Help => writeln!(out, "{HELP}")?,
+        ReplCommand::Exit => {}
+    }
+    Ok(())
+}
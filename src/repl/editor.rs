@@ -0,0 +1,235 @@
+//! The line editor behind the interactive repl: cursor movement, history and
+//! Tab completion on crossterm's raw mode. [`LineEditor::handle_key`] holds
+//! the editing rules so they can be tested without a terminal.
+
+use crossterm::{
+    cursor::MoveToColumn,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+    style::Print,
+    terminal::{self, Clear, ClearType},
+};
+use std::io::{self, Write};
+
+use super::complete;
+
+/// What a key did to the line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    /// The line changed, or nothing happened; redraw it.
+    Continue,
+    /// Enter: the finished line.
+    Submit(String),
+    /// Ctrl+D on an empty line.
+    Eof,
+    /// Tab found several completions sharing nothing more than what is typed.
+    Candidates(Vec<String>),
+}
+
+#[derive(Debug, Default)]
+pub struct LineEditor {
+    line: Vec<char>,
+    /// Position in `line`, in chars.
+    cursor: usize,
+    history: Vec<String>,
+    /// Entry of `history` shown by Up/Down; `None` while editing a new line.
+    history_index: Option<usize>,
+}
+
+impl LineEditor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn line(&self) -> String {
+        self.line.iter().collect()
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Applies `key`, completing against `topics` on Tab.
+    pub fn handle_key(&mut self, key: KeyEvent, topics: &[String]) -> Edit {
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Enter => {
+                let line = self.line();
+                if !line.trim().is_empty() && self.history.last() != Some(&line) {
+                    self.history.push(line.clone());
+                }
+                self.set_line(String::new());
+                self.history_index = None;
+                return Edit::Submit(line);
+            }
+            KeyCode::Char('d') if control => {
+                if self.line.is_empty() {
+                    return Edit::Eof;
+                }
+                if self.cursor < self.line.len() {
+                    self.line.remove(self.cursor);
+                }
+            }
+            KeyCode::Char('c') if control => {
+                self.set_line(String::new());
+                self.history_index = None;
+            }
+            KeyCode::Char('a') if control => self.cursor = 0,
+            KeyCode::Char('e') if control => self.cursor = self.line.len(),
+            KeyCode::Char('u') if control => {
+                self.line.drain(..self.cursor);
+                self.cursor = 0;
+            }
+            KeyCode::Char(c) if !control => {
+                self.line.insert(self.cursor, c);
+                self.cursor += 1;
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.line.remove(self.cursor);
+            }
+            KeyCode::Delete if self.cursor < self.line.len() => {
+                self.line.remove(self.cursor);
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.line.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.line.len(),
+            KeyCode::Up => {
+                let index = match self.history_index {
+                    Some(index) => index.saturating_sub(1),
+                    None => match self.history.len().checked_sub(1) {
+                        Some(last) => last,
+                        None => return Edit::Continue,
+                    },
+                };
+                self.history_index = Some(index);
+                self.set_line(self.history[index].clone());
+            }
+            KeyCode::Down => {
+                let Some(index) = self.history_index else {
+                    return Edit::Continue;
+                };
+                if index + 1 < self.history.len() {
+                    self.history_index = Some(index + 1);
+                    self.set_line(self.history[index + 1].clone());
+                } else {
+                    self.history_index = None;
+                    self.set_line(String::new());
+                }
+            }
+            KeyCode::Tab => return self.complete(topics),
+            _ => {}
+        }
+        Edit::Continue
+    }
+
+    /// Replaces the word before the cursor with its only completion, or with
+    /// the prefix every completion shares. Lists them when that adds nothing.
+    fn complete(&mut self, topics: &[String]) -> Edit {
+        let before: String = self.line[..self.cursor].iter().collect();
+        let (start, candidates) = complete(&before, topics);
+        let typed = &before[start..];
+        let replacement = match candidates.as_slice() {
+            [] => return Edit::Continue,
+            [only] => format!("{only} "),
+            [first, rest @ ..] => {
+                let shared = rest.iter().fold(first.as_str(), |shared, candidate| {
+                    let len = shared
+                        .char_indices()
+                        .zip(candidate.chars())
+                        .take_while(|((_, a), b)| a == b)
+                        .last()
+                        .map_or(0, |((index, a), _)| index + a.len_utf8());
+                    &shared[..len]
+                });
+                if shared.chars().count() <= typed.chars().count() {
+                    return Edit::Candidates(candidates);
+                }
+                shared.to_string()
+            }
+        };
+        let start = before[..start].chars().count();
+        self.line
+            .splice(start..self.cursor, replacement.chars())
+            .for_each(drop);
+        self.cursor = start + replacement.chars().count();
+        Edit::Continue
+    }
+
+    fn set_line(&mut self, line: String) {
+        self.line = line.chars().collect();
+        self.cursor = self.line.len();
+    }
+
+    /// Reads one line from the terminal after showing `prompt`. `None` on
+    /// Ctrl+D at an empty prompt.
+    pub fn read_line(
+        &mut self,
+        out: &mut impl Write,
+        prompt: &str,
+        topics: &[String],
+    ) -> io::Result<Option<String>> {
+        let _raw = RawMode::enable()?;
+        self.redraw(out, prompt)?;
+        loop {
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+            match self.handle_key(key, topics) {
+                Edit::Continue => {}
+                Edit::Submit(line) => {
+                    queue!(out, Print("\r\n"))?;
+                    out.flush()?;
+                    return Ok(Some(line));
+                }
+                Edit::Eof => {
+                    queue!(out, Print("\r\n"))?;
+                    out.flush()?;
+                    return Ok(None);
+                }
+                Edit::Candidates(candidates) => {
+                    queue!(
+                        out,
+                        Print("\r\n"),
+                        Print(candidates.join("  ")),
+                        Print("\r\n")
+                    )?;
+                }
+            }
+            self.redraw(out, prompt)?;
+        }
+    }
+
+    fn redraw(&self, out: &mut impl Write, prompt: &str) -> io::Result<()> {
+        let column = prompt.chars().count() + self.cursor;
+        queue!(
+            out,
+            MoveToColumn(0),
+            Clear(ClearType::UntilNewLine),
+            Print(prompt),
+            Print(self.line()),
+            MoveToColumn(u16::try_from(column).unwrap_or(u16::MAX)),
+        )?;
+        out.flush()
+    }
+}
+
+/// Raw mode for as long as it is held, so an error leaves the terminal usable.
+struct RawMode;
+
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
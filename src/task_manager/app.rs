@@ -1,9 +1,24 @@
+mod archive;
+mod bulk_delete;
+mod clipboard;
 mod core;
+mod due;
+mod editor;
 mod filters;
 mod helpers;
+mod links;
+mod paging;
+mod recent;
+mod search;
 mod special;
 mod tasks;
 mod types;
+mod worker;
 
+pub(crate) use self::core::lock_database;
+pub use crate::common::clipboard::{Clipboard, SystemClipboard};
+pub use due::window_title;
+pub use filters::{TaskFilter, TaskListFilter};
 pub(crate) use helpers::load_palette_history;
-pub use types::{App, InputMode};
+pub use types::{App, InputMode, LayoutRegions};
+pub use worker::{fetch_task_page, AppEvent, DbCommand, DbWorker, TaskPage};
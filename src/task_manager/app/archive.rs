@@ -0,0 +1,68 @@
+use crate::error::AppError;
+
+use super::{App, InputMode};
+
+impl App {
+    /// Opens the archive popup with the current topic's archived tasks, or
+    /// every archived task when a special topic is selected.
+    pub fn begin_archive(&mut self) -> Result<(), AppError> {
+        self.load_archived_tasks()?;
+        self.archived_selected = 0;
+        self.input_mode = InputMode::ViewingArchivedTasks;
+        Ok(())
+    }
+
+    pub fn close_archive(&mut self) {
+        self.archived_tasks.clear();
+        self.archived_selected = 0;
+        self.input_mode = InputMode::Normal;
+    }
+
+    fn load_archived_tasks(&mut self) -> Result<(), AppError> {
+        let topic_id = if self.current_topic_is_special() {
+            None
+        } else {
+            self.current_topic().map(|topic| topic.id)
+        };
+        self.archived_tasks = self.db_ops.load_archived_tasks(topic_id)?;
+        if self.archived_selected >= self.archived_tasks.len() {
+            self.archived_selected = self.archived_tasks.len().saturating_sub(1);
+        }
+        Ok(())
+    }
+
+    pub fn move_archive_selection(&mut self, delta: isize) {
+        let len = self.archived_tasks.len() as isize;
+        if len == 0 {
+            return;
+        }
+        self.archived_selected = (self.archived_selected as isize + delta).rem_euclid(len) as usize;
+    }
+
+    /// Restores the selected archived task to its topic's task list.
+    pub fn unarchive_selected_task(&mut self) -> Result<(), AppError> {
+        let Some(task_id) = self
+            .archived_tasks
+            .get(self.archived_selected)
+            .map(|t| t.id)
+        else {
+            return Ok(());
+        };
+        let task = self.db_ops.unarchive_task(task_id)?;
+        self.add_log("INFO", &format!("Restored task: {}", task.name));
+        self.load_archived_tasks()?;
+        self.load_tasks()?;
+        Ok(())
+    }
+
+    /// Permanently deletes the selected archived task and its subtasks.
+    pub fn purge_selected_archived_task(&mut self) -> Result<(), AppError> {
+        let Some(task) = self.archived_tasks.get(self.archived_selected).cloned() else {
+            return Ok(());
+        };
+        self.db_ops.delete_task(task.id)?;
+        self.add_log("INFO", &format!("Permanently deleted task: {}", task.name));
+        self.load_archived_tasks()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use crate::error::AppError;
+
+use super::worker::DbCommand;
+use super::{App, InputMode};
+
+impl App {
+    /// `Shift+D`: shows a checkbox beside every task so several can be
+    /// deleted at once. Tasks already in `selected_tasks` start checked.
+    pub fn begin_delete_multiple(&mut self) {
+        if self.tasks.is_empty() {
+            self.add_log("WARN", "No tasks to delete");
+            return;
+        }
+        // Marks left over from another topic or page would be deleted unseen.
+        let listed: Vec<i32> = self.tasks.iter().map(|task| task.id).collect();
+        self.selected_tasks.retain(|id| listed.contains(id));
+        self.input_mode = InputMode::DeletingMultipleTasks;
+    }
+
+    pub fn close_delete_multiple(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Checks or unchecks the task under the cursor.
+    pub fn toggle_delete_mark(&mut self) {
+        let Some(task_id) = self.current_task_id() else {
+            return;
+        };
+        if !self.selected_tasks.remove(&task_id) {
+            self.selected_tasks.insert(task_id);
+        }
+    }
+
+    /// Checks every task the current filter shows.
+    pub fn mark_all_for_delete(&mut self) {
+        for index in self.filtered_task_indices() {
+            self.selected_tasks.insert(self.tasks[index].id);
+        }
+    }
+
+    pub fn clear_delete_marks(&mut self) {
+        self.selected_tasks.clear();
+    }
+
+    /// Asks "Delete N tasks?" before anything is removed.
+    pub fn request_delete_marked(&mut self) {
+        if self.selected_tasks.is_empty() {
+            self.add_log("WARN", "No tasks checked for deletion");
+        } else {
+            self.input_mode = InputMode::ConfirmingDeleteMultipleTasks;
+        }
+    }
+
+    pub fn cancel_delete_marked(&mut self) {
+        self.input_mode = InputMode::DeletingMultipleTasks;
+    }
+
+    /// Deletes (or archives, with `soft_delete`) every checked task in one
+    /// transaction. Once that succeeds the checks are cleared and the list
+    /// returns to Normal mode; on failure nothing is removed and the checks
+    /// stay so the user can retry.
+    pub fn delete_marked_tasks(&mut self) -> Result<(), AppError> {
+        let mut task_ids: Vec<i32> = self.selected_tasks.iter().copied().collect();
+        task_ids.sort_unstable();
+        self.dispatch_db_command(DbCommand::DeleteTasks {
+            task_ids,
+            archive: self.config.soft_delete,
+        })
+    }
+}
@@ -0,0 +1,85 @@
+use crate::error::AppError;
+
+use super::{App, InputMode};
+
+/// Yanked task names kept for the `Ctrl+Y` picker.
+const CLIPBOARD_HISTORY_LIMIT: usize = 20;
+
+impl App {
+    /// Records a yanked name, newest first; yanking it again moves it back
+    /// to the top instead of listing it twice.
+    pub(super) fn push_clipboard_history(&mut self, text: &str) {
+        self.clipboard_history.retain(|entry| entry != text);
+        self.clipboard_history.insert(0, text.to_string());
+        self.clipboard_history.truncate(CLIPBOARD_HISTORY_LIMIT);
+    }
+
+    /// Opens the clipboard picker over the current mode, which Enter pastes
+    /// into and Esc returns to.
+    pub fn begin_clipboard_picker(&mut self) {
+        self.clipboard_return_mode = self.input_mode;
+        self.clipboard_selected = 0;
+        self.input_mode = InputMode::ChoosingClipboard;
+    }
+
+    pub fn close_clipboard_picker(&mut self) {
+        self.input_mode = self.clipboard_return_mode;
+    }
+
+    pub fn move_clipboard_selection(&mut self, delta: isize) {
+        if self.clipboard_history.is_empty() {
+            return;
+        }
+        let len = self.clipboard_history.len() as isize;
+        self.clipboard_selected =
+            (self.clipboard_selected as isize + delta).rem_euclid(len) as usize;
+    }
+
+    /// Makes the selected entry the internal clipboard, then pastes it into
+    /// the input the picker was opened from, or in Normal mode jumps to the
+    /// task with that name.
+    pub fn choose_clipboard_entry(&mut self) -> Result<(), AppError> {
+        let Some(text) = self.clipboard_history.get(self.clipboard_selected).cloned() else {
+            self.close_clipboard_picker();
+            return Ok(());
+        };
+        self.clipboard = text.clone();
+        self.close_clipboard_picker();
+        match self.input_mode {
+            InputMode::AddingTaskName | InputMode::EditingTaskName => {
+                self.task_name_input.push_str(&text)
+            }
+            InputMode::AddingTaskDescription => self.task_description_input.push_str(&text),
+            InputMode::AddingTaskUrl => self.task_url_input.push_str(&text),
+            InputMode::RenamingTask => self.rename_input.push_str(&text),
+            InputMode::AddingTopic
+            | InputMode::AddingTopicDescription
+            | InputMode::AddingSubtask => self.input.push_str(&text),
+            InputMode::Normal => self.focus_task_named(&text)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Searches every topic for `name`, focusing an exact match or leaving
+    /// the search popup open on the closest hits.
+    fn focus_task_named(&mut self, name: &str) -> Result<(), AppError> {
+        let exact = self
+            .db_ops
+            .fts_search_with_snippets(name, None)?
+            .into_iter()
+            .find(|(task, _)| task.name == name)
+            .map(|(task, _)| task.id);
+        match exact {
+            Some(task_id) => {
+                self.focus_task_by_id(task_id)?;
+            }
+            None => {
+                self.begin_search();
+                self.search_query = name.to_string();
+                self.run_search()?;
+            }
+        }
+        Ok(())
+    }
+}
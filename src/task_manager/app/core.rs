@@ -1,12 +1,72 @@
-use std::{collections::HashSet, error::Error, path::PathBuf};
+use crossterm::event::KeyCode;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use fs2::FileExt;
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, File},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+use tui::layout::Rect;
 
+use crate::common::clipboard::SystemClipboard;
+use crate::common::logs::{LogEntry, LogLevel};
+use crate::config::{load_config, update_config, LayoutStyle};
+use crate::db::task_manager::models::{
+    LinkType, SortMode, Topic, TopicOrder, TopicSettings, TopicUpdate,
+};
 use crate::db::task_manager::operations::DbOperations;
+use crate::error::AppError;
 use crate::filter_presets::load_presets;
+use crate::utils::format_bytes;
 
-use super::{load_palette_history, App, InputMode};
+use super::worker::{fetch_task_page, DbCommand};
+use super::{load_palette_history, App, InputMode, LayoutRegions, TaskFilter};
+
+/// How long the first key of a sequence such as `gg` waits for the second.
+const KEY_SEQUENCE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Keys Normal mode binds without modifiers, digits included for count
+/// prefixes. A topic alias on one of them would never fire, so
+/// `set_topic_alias` refuses them. Keep in sync with `handle_key`.
+pub const NORMAL_MODE_KEYS: &str = "q:Pyp/FvriWadDeRfH?tosSzcjkhlMNXgG0123456789";
+
+/// Set by `--force` to open the database even if another instance holds the lock.
+static SKIP_INSTANCE_LOCK: AtomicBool = AtomicBool::new(false);
+
+/// Takes an exclusive lock on `<db_dir>/.<db_stem>.lock` so two instances
+/// never write to the same database.
+pub(crate) fn lock_database(db_path: &Path) -> Result<Option<File>, AppError> {
+    if SKIP_INSTANCE_LOCK.load(Ordering::Relaxed) {
+        return Ok(None);
+    }
+
+    let stem = db_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("task_manager");
+    let lock_path = db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(format!(".{stem}.lock"));
+    if let Some(parent) = lock_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = File::create(lock_path)?;
+    file.try_lock_exclusive()
+        .map_err(|_| AppError::AlreadyRunning)?;
+    Ok(Some(file))
+}
 
 impl App {
-    pub fn new(db_path: &str) -> Result<App, Box<dyn Error>> {
+    /// Skips the single-instance lock for every `App` created afterwards.
+    pub fn skip_instance_lock() {
+        SKIP_INSTANCE_LOCK.store(true, Ordering::Relaxed);
+    }
+
+    pub fn new(db_path: &str) -> Result<App, AppError> {
+        let lock = lock_database(Path::new(db_path))?;
         let db_path_string = format!("sqlite://{}", db_path);
         let pool = crate::db::establish_connection_pool(&db_path_string)?;
         let preset_store_path = PathBuf::from(db_path)
@@ -17,25 +77,47 @@ impl App {
             .parent()
             .unwrap_or_else(|| std::path::Path::new("."))
             .join("task_palette_history.json");
+        let config_path = PathBuf::from(db_path)
+            .parent()
+            .unwrap_or_else(|| std::path::Path::new("."))
+            .join("task_manager_config.json");
 
-        {
+        let applied_migrations = {
             let mut conn = pool.get()?;
-            crate::db::run_migrations(&mut conn)?;
-        }
+            crate::db::run_migrations(&mut conn)?
+        };
 
-        let db_ops = DbOperations::new(pool);
+        let db_ops = DbOperations::new(pool, crate::log_config::get_logger_or_discard());
+        let config = load_config(&config_path)?;
         let mut app = App {
             db_ops,
             topics: Vec::new(),
             tasks: Vec::new(),
             task_filter: String::new(),
+            sort_mode: SortMode::default(),
+            sort_ascending: true,
+            filter: TaskFilter::default(),
             preset_selected: 0,
             custom_task_presets: load_presets(&preset_store_path)?,
             preset_store_path,
+            page_size: config.page_size,
+            page_offset: 0,
+            task_total: 0,
+            page_topic_id: None,
+            topic_switch_from: None,
+            config,
+            config_path,
+            db_path: PathBuf::from(db_path),
             palette_history_store_path: palette_history_store_path.clone(),
             selected: 0,
             selected_topic: 0,
             input_mode: InputMode::Normal,
+            pending_key: None,
+            count_prefix: None,
+            topic_name_input: String::new(),
+            delete_subtask_count: 0,
+            error_popup: None,
+            previous_mode: None,
             command_palette_return_mode: InputMode::Normal,
             command_palette_query: String::new(),
             command_palette_selected: 0,
@@ -43,19 +125,69 @@ impl App {
             input: String::new(),
             task_name_input: String::new(),
             task_description_input: String::new(),
+            ml_editor_lines: Vec::new(),
+            ml_editor_cursor: (0, 0),
+            task_url_input: String::new(),
             task_form_message: None,
+            rename_input: String::new(),
+            topic_setting_input: String::new(),
+            topic_setting_message: None,
+            search_query: String::new(),
+            search_results: Vec::new(),
+            search_selected: 0,
+            topic_search_query: String::new(),
+            topic_search_results: Vec::new(),
+            topic_search_selected: 0,
+            due_soon_tasks: Vec::new(),
+            topic_stats: None,
+            due_soon_selected: 0,
+            recent_cache: None,
+            recent_selected: 0,
+            last_title_count: None,
+            title_retry_at: None,
+            archived_tasks: Vec::new(),
+            archived_selected: 0,
+            log_panel_alert_until: None,
+            next_due_check_at: Instant::now(),
+            next_overdue_refresh_at: Instant::now(),
+            merge_target_selected: 0,
+            link_type_selected: LinkType::default(),
+            link_target_selected: 0,
+            system_clipboard: Box::new(SystemClipboard),
+            clipboard: String::new(),
+            clipboard_history: Vec::new(),
+            clipboard_selected: 0,
+            clipboard_return_mode: InputMode::Normal,
             preset_name_input: String::new(),
             preset_form_message: None,
+            terminal_too_small: false,
+            layout_regions: LayoutRegions::default(),
+            hovered_topic_idx: None,
+            mouse_column: 0,
+            log_panel_focused: false,
+            sidebar_focused: false,
+            task_counts: HashMap::new(),
+            overdue_counts: HashMap::new(),
+            db_worker: None,
+            task_load_seq: 0,
             logs: Vec::new(),
+            log_min_level: LogLevel::default(),
             log_offset: 0,
+            log_search_query: String::new(),
+            log_filtered: Vec::new(),
             expanded: HashSet::new(),
+            selected_tasks: HashSet::new(),
+            compact_mode: false,
+            subtasks: HashMap::new(),
+            task_links: HashMap::new(),
             special_tab_selected: 0,
             special_task_selected: 0,
             special_task_filter: String::new(),
             favourites_tasks: Vec::new(),
             completed_tasks: Vec::new(),
+            _lock: lock,
         };
-        let all_topics = app.db_ops.load_topics()?;
+        let all_topics = app.db_ops.load_topics(TopicOrder::Manual)?;
 
         if !all_topics.iter().any(|t| t.name == "Favourites") {
             app.add_topic("Favourites")?;
@@ -80,41 +212,661 @@ impl App {
         } else if !app.topics.is_empty() {
             app.selected_topic = 0;
         }
+        if applied_migrations.is_empty() {
+            app.add_log("INFO", "Database schema up to date");
+        }
+        for version in &applied_migrations {
+            app.add_log("INFO", &format!("Applied migration {version}"));
+        }
         app.load_tasks()?;
         app.add_log("INFO", "Tasks loaded");
+        // Only a warning; it is retried on the next due check.
+        if let Err(e) = app.check_due_within_day() {
+            app.add_log("ERROR", &format!("Failed to check due tasks: {e}"));
+        }
         app.add_log("INFO", "Application started");
         Ok(app)
     }
 
-    pub fn load_tasks(&mut self) -> Result<(), Box<dyn Error>> {
+    pub fn load_tasks(&mut self) -> Result<(), AppError> {
+        self.load_tasks_focusing(None)
+    }
+
+    /// [`App::load_tasks`], but loads the page holding `focus` and selects it
+    /// when that task is listed.
+    pub fn load_tasks_focusing(&mut self, focus: Option<i32>) -> Result<(), AppError> {
+        self.task_load_seq += 1;
         self.tasks.clear();
+        self.recent_cache = None;
         if self.topics.is_empty() {
+            self.task_counts = self.db_ops.count_tasks_by_topic()?;
+            self.refresh_overdue_counts()?;
             self.selected = 0;
             return Ok(());
         }
         if self.selected_topic >= self.topics.len() {
             self.selected_topic = 0;
         }
-        let current_topic = &self.topics[self.selected_topic];
-        self.tasks = self.db_ops.load_tasks(current_topic)?;
+        let Some(current_topic) = self.current_topic().cloned() else {
+            return Ok(());
+        };
+        let page = self.page_for(&current_topic);
+        let filter = self.task_list_filter();
+        let loaded = fetch_task_page(
+            &self.db_ops,
+            &current_topic,
+            page,
+            self.page_size,
+            &filter,
+            focus,
+        )?;
+        self.apply_task_page(loaded);
+        Ok(())
+    }
+
+    /// The page to load for `topic`: the current one, or the first when
+    /// switching topics.
+    pub(super) fn page_for(&mut self, topic: &Topic) -> usize {
+        if self.page_topic_id != Some(topic.id) {
+            self.page_topic_id = Some(topic.id);
+            self.page_offset = 0;
+        }
+        self.page_offset
+    }
+
+    pub fn topic_settings(&self) -> TopicSettings {
+        TopicSettings {
+            sort_mode: self.sort_mode,
+            sort_ascending: self.sort_ascending,
+            completion_filter: self.filter.completion,
+        }
+    }
+
+    fn save_topic_settings(&mut self) -> Result<(), AppError> {
+        let Some(topic_id) = self.current_topic().map(|topic| topic.id) else {
+            return Ok(());
+        };
+        let selected_id = self.current_task_id();
+        self.db_ops
+            .save_topic_settings(topic_id, &self.topic_settings())?;
+        self.load_tasks()?;
+        if let Some(id) = selected_id {
+            self.select_task_by_id(id)?;
+        }
         self.ensure_selected_visible();
         Ok(())
     }
 
-    pub fn load_topics(&mut self) -> Result<(), Box<dyn Error>> {
-        let all_topics = self.db_ops.load_topics()?;
+    pub fn cycle_sort_mode(&mut self) -> Result<(), AppError> {
+        self.sort_mode = self.sort_mode.next();
+        self.save_topic_settings()?;
+        self.add_log("INFO", &format!("Sort: {}", self.sort_mode.label()));
+        Ok(())
+    }
+
+    pub fn toggle_sort_direction(&mut self) -> Result<(), AppError> {
+        self.sort_ascending = !self.sort_ascending;
+        self.save_topic_settings()?;
+        let direction = if self.sort_ascending {
+            "ascending"
+        } else {
+            "descending"
+        };
+        self.add_log("INFO", &format!("Sort direction: {}", direction));
+        Ok(())
+    }
+
+    pub fn cycle_completion_filter(&mut self) -> Result<(), AppError> {
+        self.filter.completion = self.filter.completion.next();
+        self.save_topic_settings()?;
+        self.add_log(
+            "INFO",
+            &format!("Showing: {}", self.filter.completion.label()),
+        );
+        Ok(())
+    }
+
+    pub fn load_topics(&mut self) -> Result<(), AppError> {
+        let all_topics = self.db_ops.load_topics(self.config.topic_order)?;
+        self.apply_topics(all_topics);
+        self.task_counts = self.db_ops.count_tasks_by_topic()?;
+        self.refresh_overdue_counts()?;
+        Ok(())
+    }
+
+    /// Keeps `all_topics` minus the Favourites and Completed views, which
+    /// have their own popup, and warns about shared aliases. A clash is
+    /// warned once, when it first shows up, not on every reload.
+    pub(super) fn apply_topics(&mut self, all_topics: Vec<Topic>) {
+        let known = self.topic_alias_conflicts();
         self.topics = all_topics
             .into_iter()
             .filter(|t| t.name != "Favourites" && t.name != "Completed")
             .collect();
+        for (alias, names) in self.topic_alias_conflicts() {
+            if known.contains(&(alias, names.clone())) {
+                continue;
+            }
+            self.add_log(
+                "WARN",
+                &format!(
+                    "Alias '{}' is shared by {}; it jumps to {}",
+                    alias,
+                    names.join(", "),
+                    names[0]
+                ),
+            );
+        }
+    }
+
+    /// `(alias, topic names)` for every alias set on more than one topic, in
+    /// tab order. The first topic wins when the alias is pressed.
+    pub fn topic_alias_conflicts(&self) -> Vec<(char, Vec<String>)> {
+        let mut by_alias: Vec<(char, Vec<String>)> = Vec::new();
+        for topic in &self.topics {
+            let Some(alias) = topic.alias_char() else {
+                continue;
+            };
+            match by_alias.iter_mut().find(|(seen, _)| *seen == alias) {
+                Some((_, names)) => names.push(topic.name.clone()),
+                None => by_alias.push((alias, vec![topic.name.clone()])),
+            }
+        }
+        by_alias.retain(|(_, names)| names.len() > 1);
+        by_alias
+    }
+
+    /// Caps how many tasks the current topic may hold; `None` lifts the cap.
+    pub fn set_topic_task_limit(&mut self, limit: Option<i32>) -> Result<(), AppError> {
+        let Some(current) = self.current_topic().cloned() else {
+            return Ok(());
+        };
+        if limit.is_some_and(|limit| limit < 1) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Topic task limit must be at least 1",
+            )
+            .into());
+        }
+
+        self.db_ops.set_topic_task_limit(current.id, limit)?;
+        self.load_topics()?;
+        match limit {
+            Some(limit) => self.add_log(
+                "INFO",
+                &format!("{} now holds at most {} tasks", current.name, limit),
+            ),
+            None => self.add_log(
+                "INFO",
+                &format!("Removed the task limit for {}", current.name),
+            ),
+        }
+        Ok(())
+    }
+
+    /// Sets or clears the selected topic's jump alias. Keys that Normal mode
+    /// already binds are refused, since such an alias would never fire.
+    pub fn set_topic_alias(&mut self, alias: Option<char>) -> Result<(), AppError> {
+        let Some(current) = self.current_topic().cloned() else {
+            return Ok(());
+        };
+        if let Some(reason) = alias.and_then(topic_alias_problem) {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, reason).into());
+        }
+
+        self.db_ops.set_topic_alias(current.id, alias)?;
+        self.load_topics()?;
+        match alias {
+            Some(alias) => self.add_log(
+                "INFO",
+                &format!("Alias '{}' now jumps to {}", alias, current.name),
+            ),
+            None => self.add_log("INFO", &format!("Cleared alias for {}", current.name)),
+        }
+        Ok(())
+    }
+
+    /// Opens the alias popup, filled with the selected topic's alias.
+    pub fn begin_set_topic_alias(&mut self) {
+        let Some(alias) = self.current_topic().map(Topic::alias_char) else {
+            return;
+        };
+        self.topic_setting_input = alias.map(String::from).unwrap_or_default();
+        self.topic_setting_message = None;
+        self.input_mode = InputMode::SettingTopicAlias;
+    }
+
+    pub fn cancel_topic_setting(&mut self) {
+        self.topic_setting_input.clear();
+        self.topic_setting_message = None;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Saves `topic_setting_input` as the selected topic's alias; an empty
+    /// input clears it. A rejected alias keeps the popup open with the reason.
+    pub fn submit_topic_alias(&mut self) -> Result<(), AppError> {
+        let mut chars = self.topic_setting_input.trim().chars();
+        let alias = match (chars.next(), chars.next()) {
+            (alias, None) => alias,
+            _ => {
+                self.topic_setting_message = Some("An alias is a single key".to_string());
+                return Ok(());
+            }
+        };
+        if let Some(reason) = alias.and_then(topic_alias_problem) {
+            self.topic_setting_message = Some(reason);
+            return Ok(());
+        }
+        if let Err(e) = self.set_topic_alias(alias) {
+            self.topic_setting_message = Some(e.to_string());
+            return Err(e);
+        }
+        self.cancel_topic_setting();
+        Ok(())
+    }
+
+    /// Opens the task limit popup, filled with the selected topic's limit.
+    pub fn begin_set_topic_task_limit(&mut self) {
+        let Some(limit) = self.current_topic().map(|topic| topic.task_limit) else {
+            return;
+        };
+        self.topic_setting_input = limit.map(|limit| limit.to_string()).unwrap_or_default();
+        self.topic_setting_message = None;
+        self.input_mode = InputMode::SettingTopicTaskLimit;
+    }
+
+    /// Saves `topic_setting_input` as the selected topic's task limit; an
+    /// empty input lifts it. A rejected limit keeps the popup open with the
+    /// reason.
+    pub fn submit_topic_task_limit(&mut self) -> Result<(), AppError> {
+        let input = self.topic_setting_input.trim();
+        let limit = if input.is_empty() {
+            None
+        } else {
+            match input.parse::<i32>() {
+                Ok(limit) if limit >= 1 => Some(limit),
+                _ => {
+                    self.topic_setting_message =
+                        Some("The limit is a whole number of at least 1".to_string());
+                    return Ok(());
+                }
+            }
+        };
+        if let Err(e) = self.set_topic_task_limit(limit) {
+            self.topic_setting_message = Some(e.to_string());
+            return Err(e);
+        }
+        self.cancel_topic_setting();
+        Ok(())
+    }
+
+    /// Switches to the first topic whose alias is `key`. Returns `false` when
+    /// no topic uses it.
+    pub fn jump_to_topic_alias(&mut self, key: char) -> Result<bool, AppError> {
+        let Some(alias_topic) = self
+            .topics
+            .iter()
+            .position(|topic| topic.alias_char() == Some(key))
+        else {
+            return Ok(false);
+        };
+        if alias_topic != self.selected_topic {
+            let previous = self.selected_topic;
+            self.selected_topic = alias_topic;
+            if let Err(e) = self.load_tasks() {
+                self.selected_topic = previous;
+                return Err(e);
+            }
+            self.selected = 0;
+            self.ensure_selected_visible();
+        }
+        Ok(true)
+    }
+
+    /// Selects the topic `offset` places away and loads its tasks. Stays put
+    /// at either end, and on the old topic if its tasks fail to load.
+    pub fn step_topic(&mut self, offset: isize) -> Result<(), AppError> {
+        let previous = self.selected_topic;
+        let Some(next) = previous
+            .checked_add_signed(offset)
+            .filter(|next| *next < self.topics.len())
+        else {
+            return Ok(());
+        };
+        if self.db_worker.is_some() {
+            // Keys must not act on the old topic's tasks while the new page
+            // is on its way, so the list stays empty until it arrives.
+            self.topic_switch_from
+                .get_or_insert((previous, self.page_offset));
+            self.selected_topic = next;
+            self.tasks.clear();
+            self.subtasks.clear();
+            self.task_links.clear();
+            self.selected = 0;
+            if let Err(e) = self.request_tasks() {
+                self.roll_back_topic_switch()?;
+                return Err(e);
+            }
+            return Ok(());
+        }
+        self.selected_topic = next;
+        if let Err(e) = self.load_tasks() {
+            self.selected_topic = previous;
+            return Err(e);
+        }
+        self.selected = 0;
+        self.ensure_selected_visible();
+        Ok(())
+    }
+
+    /// Returns to the topic and page [`App::step_topic`] left, after its
+    /// page failed to load, and reloads them.
+    pub(super) fn roll_back_topic_switch(&mut self) -> Result<(), AppError> {
+        let Some((topic, page)) = self.topic_switch_from.take() else {
+            return Ok(());
+        };
+        self.selected_topic = topic;
+        self.page_topic_id = self.current_topic().map(|topic| topic.id);
+        self.page_offset = page;
+        self.request_tasks()
+    }
+
+    /// Whether `j`/`k` currently move through the topic sidebar.
+    pub fn sidebar_has_focus(&self) -> bool {
+        self.config.layout == LayoutStyle::SidebarLeft && self.sidebar_focused
+    }
+
+    /// Overdue tasks under `topic`, counted like [`App::topic_task_count`].
+    pub fn topic_overdue_count(&self, topic: &Topic) -> i64 {
+        if topic.name == "Default" {
+            self.overdue_counts.values().sum()
+        } else {
+            self.overdue_counts.get(&topic.id).copied().unwrap_or(0)
+        }
+    }
+
+    /// Tasks listed under `topic`; the Default topic lists every task.
+    pub fn topic_task_count(&self, topic: &Topic) -> i64 {
+        if topic.name == "Default" {
+            self.task_counts.values().sum()
+        } else {
+            self.task_counts.get(&topic.id).copied().unwrap_or(0)
+        }
+    }
+
+    /// Moves the selected topic one tab left (`offset < 0`) or right and keeps
+    /// it selected.
+    pub fn move_selected_topic(&mut self, offset: isize) -> Result<(), AppError> {
+        let Some(current) = self.current_topic().cloned() else {
+            return Ok(());
+        };
+        if self.config.topic_order != TopicOrder::Manual {
+            self.add_log(
+                "INFO",
+                "Switch to manual topic order (Ctrl+O) to move topics",
+            );
+            return Ok(());
+        }
+        let moved = if offset < 0 {
+            self.db_ops.move_topic_left(current.id)?
+        } else {
+            self.db_ops.move_topic_right(current.id)?
+        };
+        if !moved {
+            return Ok(());
+        }
+
+        self.load_topics()?;
+        if let Some(index) = self.topics.iter().position(|topic| topic.id == current.id) {
+            self.selected_topic = index;
+        }
+        self.add_log("INFO", &format!("Moved topic: {}", current.name));
+        Ok(())
+    }
+
+    /// Moves the selected task one place in the manual order. Only allowed
+    /// while the topic is sorted manually, where the move is visible.
+    pub fn move_selected_task(&mut self, offset: isize) -> Result<(), AppError> {
+        let Some(task) = self.current_task().cloned() else {
+            return Ok(());
+        };
+        if self.sort_mode != SortMode::Manual {
+            self.add_log("INFO", "Switch to manual sort (s) to reorder tasks");
+            return Ok(());
+        }
+        // The list shows the weights reversed when sorted descending.
+        self.dispatch_db_command(DbCommand::MoveTask {
+            task_id: task.id,
+            up: (offset < 0) == self.sort_ascending,
+        })
+    }
+
+    /// Topics the current topic can be merged into.
+    pub fn merge_candidates(&self) -> Vec<&Topic> {
+        let current_id = self.current_topic().map(|topic| topic.id);
+        self.topics
+            .iter()
+            .filter(|topic| Some(topic.id) != current_id)
+            .filter(|topic| !self.db_ops.is_special_topic(&topic.name))
+            .collect()
+    }
+
+    pub fn begin_merge_topic(&mut self) -> Result<(), AppError> {
+        let Some(current) = self.current_topic() else {
+            return Ok(());
+        };
+        if self.db_ops.is_special_topic(&current.name) {
+            return Err(AppError::ProtectedTopic(current.name.clone()));
+        }
+        if self.merge_candidates().is_empty() {
+            self.add_log("WARN", "No other topic to merge into");
+            return Ok(());
+        }
+        self.merge_target_selected = 0;
+        self.input_mode = InputMode::MergingTopic;
+        Ok(())
+    }
+
+    /// Merges the current topic into the selected candidate and switches to it.
+    pub fn confirm_merge_topic(&mut self) -> Result<(), AppError> {
+        self.input_mode = InputMode::Normal;
+        let Some(source) = self.current_topic().cloned() else {
+            return Ok(());
+        };
+        let Some(target) = self
+            .merge_candidates()
+            .get(self.merge_target_selected)
+            .map(|topic| (*topic).clone())
+        else {
+            return Ok(());
+        };
+
+        let moved = self.db_ops.merge_topics(source.id, target.id)?;
+        self.load_topics()?;
+        self.selected_topic = self
+            .topics
+            .iter()
+            .position(|topic| topic.id == target.id)
+            .unwrap_or(0);
+        self.selected = 0;
+        self.load_tasks()?;
+        self.add_log(
+            "INFO",
+            &format!(
+                "Merged '{}' into '{}' ({} task(s) moved)",
+                source.name, target.name, moved
+            ),
+        );
+        Ok(())
+    }
+
+    /// Flips the completion bell and persists the choice.
+    pub fn toggle_sound(&mut self) -> Result<(), AppError> {
+        let sound_enabled = !self.config.sound_enabled;
+        update_config(&self.config_path, |config| {
+            config.sound_enabled = sound_enabled;
+        })?;
+        self.config.sound_enabled = sound_enabled;
+        self.add_log(
+            "INFO",
+            if self.config.sound_enabled {
+                "Completion bell on"
+            } else {
+                "Completion bell off"
+            },
+        );
+        Ok(())
+    }
+
+    /// `Ctrl+O`: next topic order, saved to the config. The selected topic
+    /// stays selected wherever it lands.
+    pub fn cycle_topic_order(&mut self) -> Result<(), AppError> {
+        let topic_order = self.config.topic_order.next();
+        update_config(&self.config_path, |config| config.topic_order = topic_order)?;
+        self.config.topic_order = topic_order;
+        self.reload_topics_keeping_selection()?;
+        self.add_log(
+            "INFO",
+            &format!("Topic order: {}", self.config.topic_order.label()),
+        );
+        Ok(())
+    }
+
+    /// Rewrites the manual topic order alphabetically.
+    pub fn reorder_topics_by_name(&mut self) -> Result<(), AppError> {
+        if self.config.topic_order != TopicOrder::Manual {
+            self.add_log(
+                "INFO",
+                "Switch to manual topic order (Ctrl+O) to reorder topics",
+            );
+            return Ok(());
+        }
+        self.db_ops.reorder_topics_by_name()?;
+        self.reload_topics_keeping_selection()?;
+        self.add_log("INFO", "Topics reordered by name");
+        Ok(())
+    }
+
+    fn reload_topics_keeping_selection(&mut self) -> Result<(), AppError> {
+        if self.send_db_command(DbCommand::LoadTopics(self.config.topic_order)) {
+            return Ok(());
+        }
+        let current_id = self.current_topic().map(|topic| topic.id);
+        self.load_topics()?;
+        if let Some(index) =
+            current_id.and_then(|id| self.topics.iter().position(|topic| topic.id == id))
+        {
+            self.selected_topic = index;
+        }
         Ok(())
     }
 
     pub fn add_log(&mut self, level: &str, msg: &str) {
-        crate::common::logs::push_timestamped_log(&mut self.logs, &mut self.log_offset, level, msg);
+        crate::common::logs::push_log_entry(&mut self.logs, &mut self.log_offset, level, msg);
+        if self.log_search_active() {
+            self.refresh_log_search();
+        }
     }
 
-    pub fn add_topic<T: AsRef<str>>(&mut self, name: T) -> Result<(), Box<dyn Error>> {
+    /// Log entries at or above [`App::log_min_level`], oldest first. While a
+    /// log search is active only [`App::log_filtered`] entries are returned.
+    pub fn visible_logs(&self) -> Vec<&LogEntry> {
+        let entries: Box<dyn Iterator<Item = &LogEntry>> = if self.log_search_active() {
+            Box::new(self.log_filtered.iter().filter_map(|&i| self.logs.get(i)))
+        } else {
+            Box::new(self.logs.iter())
+        };
+        entries
+            .filter(|entry| entry.level >= self.log_min_level)
+            .collect()
+    }
+
+    /// Whether the log search filters anything; a blank query does not.
+    pub fn log_search_active(&self) -> bool {
+        !self.log_search_query.trim().is_empty()
+    }
+
+    pub fn begin_log_search(&mut self) {
+        self.input_mode = InputMode::SearchingLogs;
+        self.refresh_log_search();
+    }
+
+    pub fn push_log_search_char(&mut self, c: char) {
+        self.log_search_query.push(c);
+        self.refresh_log_search();
+    }
+
+    pub fn pop_log_search_char(&mut self) {
+        self.log_search_query.pop();
+        self.refresh_log_search();
+    }
+
+    /// Drops the log search and returns to the unfiltered, scrolled view.
+    pub fn clear_log_search(&mut self) {
+        self.log_search_query.clear();
+        self.log_filtered.clear();
+        self.log_offset = 0;
+        self.input_mode = InputMode::Normal;
+    }
+
+    fn refresh_log_search(&mut self) {
+        let query = self.log_search_query.trim().to_lowercase();
+        self.log_filtered = self
+            .logs
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| !query.is_empty() && entry.message.to_lowercase().contains(&query))
+            .map(|(i, _)| i)
+            .collect();
+        self.log_offset = 0;
+    }
+
+    pub fn cycle_log_level(&mut self) {
+        self.log_min_level = self.log_min_level.next();
+        self.log_offset = 0;
+    }
+
+    /// Vacuums the database, logging how long it took and the size change.
+    pub fn vacuum_database(&mut self) -> Result<(), AppError> {
+        let before = std::fs::metadata(&self.db_path)?.len();
+        let started = Instant::now();
+        self.db_ops.vacuum()?;
+        let elapsed = started.elapsed();
+        let after = std::fs::metadata(&self.db_path)?.len();
+        self.add_log(
+            "INFO",
+            &format!(
+                "Vacuumed database in {} ms: {} -> {} ({} freed)",
+                elapsed.as_millis(),
+                format_bytes(before),
+                format_bytes(after),
+                format_bytes(before.saturating_sub(after))
+            ),
+        );
+        Ok(())
+    }
+
+    pub fn toggle_compact_mode(&mut self) {
+        self.compact_mode = !self.compact_mode;
+        self.add_log(
+            "INFO",
+            if self.compact_mode {
+                "Compact view on"
+            } else {
+                "Compact view off"
+            },
+        );
+    }
+
+    pub fn add_topic<T: AsRef<str>>(&mut self, name: T) -> Result<(), AppError> {
+        self.add_topic_with_description(name, "")
+    }
+
+    pub fn add_topic_with_description<T: AsRef<str>>(
+        &mut self,
+        name: T,
+        description: &str,
+    ) -> Result<(), AppError> {
         let name_str = name.as_ref().trim();
         if name_str.is_empty() {
             return Err(std::io::Error::new(
@@ -123,12 +875,93 @@ impl App {
             )
             .into());
         }
-        self.db_ops.add_topic(name_str, "")?;
+        self.db_ops
+            .add_topic(name_str, description.trim())
+            .map_err(|e| duplicate_topic_name(e, name_str))?;
         self.load_topics()?;
         Ok(())
     }
 
-    pub fn focus_task_by_id(&mut self, task_id: i32) -> Result<bool, Box<dyn Error>> {
+    /// Opens the statistics popup for the selected topic.
+    pub fn begin_topic_stats(&mut self) -> Result<(), AppError> {
+        let Some(topic_id) = self.current_topic().map(|topic| topic.id) else {
+            self.add_log("WARN", "No topic selected");
+            return Ok(());
+        };
+        self.topic_stats = Some(self.db_ops.topic_stats(topic_id)?);
+        self.input_mode = InputMode::ViewingTopicStats;
+        Ok(())
+    }
+
+    pub fn close_topic_stats(&mut self) {
+        self.topic_stats = None;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Updates the selected topic's name, description and colour in one write.
+    /// `None` leaves a field unchanged; an empty colour clears the swatch.
+    pub fn update_topic(
+        &mut self,
+        name: Option<&str>,
+        description: Option<&str>,
+        color: Option<&str>,
+    ) -> Result<(), AppError> {
+        let Some(current) = self.current_topic().cloned() else {
+            return Ok(());
+        };
+
+        let name = name.map(str::trim);
+        if let Some(name) = name {
+            if name.is_empty() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "Topic name cannot be empty",
+                )
+                .into());
+            }
+            let taken = self
+                .db_ops
+                .load_topics(TopicOrder::Manual)?
+                .iter()
+                .any(|topic| topic.id != current.id && topic.name.eq_ignore_ascii_case(name));
+            if taken {
+                return Err(AppError::DuplicateTopicName(name.to_string()));
+            }
+        }
+
+        let color = color.map(str::trim);
+        if let Some(color) = color.filter(|color| !color.is_empty()) {
+            if crate::ui_style::parse_color(color).is_none() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Unknown colour '{}'", color),
+                )
+                .into());
+            }
+        }
+
+        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let updated = self
+            .db_ops
+            .update_topic(
+                current.id,
+                TopicUpdate {
+                    name,
+                    description: description.map(str::trim),
+                    color,
+                    updated_at: &now,
+                },
+            )
+            .map_err(|e| duplicate_topic_name(e, name.unwrap_or(&current.name)))?;
+        self.load_topics()?;
+        if let Some(index) = self.topics.iter().position(|topic| topic.id == updated.id) {
+            self.selected_topic = index;
+        }
+        self.add_log("INFO", &format!("Updated topic: {}", updated.name));
+        Ok(())
+    }
+
+    pub fn focus_task_by_id(&mut self, task_id: i32) -> Result<bool, AppError> {
         let Some(task) = self.db_ops.find_task(task_id)? else {
             return Ok(false);
         };
@@ -141,13 +974,7 @@ impl App {
             .or_else(|| self.topics.iter().position(|topic| topic.name == "Default"))
             .unwrap_or(0);
         self.load_tasks()?;
-
-        if let Some(index) = self
-            .tasks
-            .iter()
-            .position(|candidate| candidate.id == task_id)
-        {
-            self.selected = index;
+        if self.select_task_by_id(task_id)? {
             self.ensure_selected_visible();
         }
 
@@ -155,6 +982,112 @@ impl App {
         Ok(true)
     }
 
+    /// Starts a two-key sequence with `code`.
+    pub fn set_pending_key(&mut self, code: KeyCode) {
+        self.pending_key = Some((code, Instant::now()));
+    }
+
+    /// Ends the pending sequence, returning its first key unless it has
+    /// waited longer than [`KEY_SEQUENCE_TIMEOUT`].
+    pub fn take_pending_key(&mut self) -> Option<KeyCode> {
+        self.pending_key
+            .take()
+            .filter(|(_, pressed_at)| pressed_at.elapsed() < KEY_SEQUENCE_TIMEOUT)
+            .map(|(code, _)| code)
+    }
+
+    /// Called on every event loop tick; an unfinished key sequence expires
+    /// once it is older than [`KEY_SEQUENCE_TIMEOUT`].
+    pub fn on_tick(&mut self) {
+        if self
+            .pending_key
+            .is_some_and(|(_, pressed_at)| pressed_at.elapsed() >= KEY_SEQUENCE_TIMEOUT)
+        {
+            self.pending_key = None;
+        }
+        self.poll_db_events();
+        if Instant::now() >= self.next_due_check_at {
+            if let Err(e) = self.check_due_within_day() {
+                self.add_log("ERROR", &format!("Failed to check due tasks: {e}"));
+            }
+        }
+        if Instant::now() >= self.next_overdue_refresh_at {
+            if let Err(e) = self.refresh_overdue_counts() {
+                self.add_log("ERROR", &format!("Failed to count overdue tasks: {e}"));
+            }
+        }
+    }
+
+    /// Tracks which topic tab, if any, the mouse is over, and whether it is
+    /// over the log panel.
+    pub fn on_mouse_moved(&mut self, column: u16, row: u16) {
+        self.mouse_column = column;
+        self.hovered_topic_idx = self.layout_regions.topic_tabs.iter().position(|area| {
+            row == area.y && column >= area.x && column < area.x.saturating_add(area.width)
+        });
+        self.log_panel_focused = area_contains(self.layout_regions.chunks[4], column, row);
+    }
+
+    /// Scrolls the log panel one line per wheel notch when the mouse is over
+    /// it. Other panels ignore the wheel for now; the task list
+    /// (`layout_regions.chunks[1]`) would be gated the same way.
+    pub fn on_mouse_scroll(&mut self, column: u16, row: u16, up: bool) {
+        self.log_panel_focused = area_contains(self.layout_regions.chunks[4], column, row);
+        if !self.log_panel_focused {
+            return;
+        }
+        if up {
+            self.log_offset += 1;
+        } else {
+            self.log_offset = self.log_offset.saturating_sub(1);
+        }
+    }
+
+    pub fn has_unsaved_input(&self) -> bool {
+        match self.input_mode {
+            InputMode::AddingTaskName
+            | InputMode::AddingTaskDescription
+            | InputMode::AddingTaskUrl
+            | InputMode::EditingTaskName => {
+                !self.task_name_input.is_empty()
+                    || !self.task_description_input.is_empty()
+                    || !self.task_url_input.is_empty()
+            }
+            InputMode::MultilineEditDescription => {
+                !self.task_name_input.is_empty() || !self.ml_editor_text().is_empty()
+            }
+            InputMode::AddingTopic | InputMode::AddingSubtask => !self.input.is_empty(),
+            InputMode::AddingTopicDescription => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` when the app can exit right away; otherwise switches to
+    /// the exit confirmation so unsaved input is not silently discarded.
+    pub fn request_exit(&mut self) -> bool {
+        if !self.has_unsaved_input() {
+            return true;
+        }
+        self.previous_mode = Some(self.input_mode);
+        self.input_mode = InputMode::ConfirmingExit;
+        false
+    }
+
+    pub fn cancel_exit(&mut self) {
+        self.input_mode = self.previous_mode.take().unwrap_or(InputMode::Normal);
+    }
+
+    /// Shows `msg` in a popup over the UI until the next key press.
+    pub fn show_error_popup(&mut self, msg: String) {
+        self.error_popup = Some(msg);
+        self.input_mode = InputMode::ShowingError;
+    }
+
+    pub fn dismiss_error_popup(&mut self) {
+        self.error_popup = None;
+        self.input_mode = InputMode::Normal;
+    }
+
     pub fn begin_command_palette(&mut self) {
         crate::common::palette::begin_palette(
             &mut self.command_palette_query,
@@ -174,7 +1107,7 @@ impl App {
         );
     }
 
-    pub fn record_palette_command(&mut self, command_id: &str) -> Result<(), Box<dyn Error>> {
+    pub fn record_palette_command(&mut self, command_id: &str) -> Result<(), AppError> {
         crate::common::palette::record_recent_command(
             &self.palette_history_store_path,
             &mut self.recent_palette_commands,
@@ -184,3 +1117,31 @@ impl App {
         Ok(())
     }
 }
+
+/// Maps the `idx_topic_name` unique violation to a readable error.
+fn duplicate_topic_name(error: AppError, name: &str) -> AppError {
+    match error {
+        AppError::Database(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+            AppError::DuplicateTopicName(name.to_string())
+        }
+        other => other,
+    }
+}
+
+fn area_contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x
+        && column < area.x.saturating_add(area.width)
+        && row >= area.y
+        && row < area.y.saturating_add(area.height)
+}
+
+/// Why `alias` can't be a topic alias, if it can't.
+fn topic_alias_problem(alias: char) -> Option<String> {
+    if alias.is_whitespace() || alias.is_control() {
+        Some("Topic alias must be a visible character".to_string())
+    } else if NORMAL_MODE_KEYS.contains(alias) {
+        Some(format!("'{alias}' is already a Normal mode key"))
+    } else {
+        None
+    }
+}
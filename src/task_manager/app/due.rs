@@ -0,0 +1,117 @@
+use std::time::{Duration, Instant};
+
+use crate::error::AppError;
+
+use super::{App, InputMode};
+
+/// How long the log panel border stays red after a due-date warning.
+const LOG_PANEL_ALERT: Duration = Duration::from_secs(3);
+/// How often the tick re-checks for tasks due within 24 hours.
+const DUE_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// How often the tick recounts overdue tasks, so badges appear at midnight
+/// without any task changing.
+pub(super) const OVERDUE_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// `Task Manager — N due today`, or just `Task Manager` when nothing is due.
+pub fn window_title(due_today: usize) -> String {
+    if due_today == 0 {
+        "Task Manager".to_string()
+    } else {
+        format!("Task Manager — {due_today} due today")
+    }
+}
+
+impl App {
+    /// Warns about open tasks due within 24 hours (overdue ones included) and
+    /// flashes the log panel if there are any.
+    pub fn check_due_within_day(&mut self) -> Result<(), AppError> {
+        self.next_due_check_at = Instant::now() + DUE_CHECK_INTERVAL;
+        let due = self.db_ops.load_tasks_due_within(1)?;
+        for task in &due {
+            self.add_log(
+                "WARN",
+                &format!("Task '{}' is due within 24 hours!", task.name),
+            );
+        }
+        if !due.is_empty() {
+            self.log_panel_alert_until = Some(Instant::now() + LOG_PANEL_ALERT);
+        }
+        Ok(())
+    }
+
+    /// Recounts overdue tasks per topic for the tab badges.
+    pub fn refresh_overdue_counts(&mut self) -> Result<(), AppError> {
+        self.next_overdue_refresh_at = Instant::now() + OVERDUE_REFRESH_INTERVAL;
+        self.overdue_counts = self.db_ops.count_overdue_tasks_per_topic()?;
+        Ok(())
+    }
+
+    /// The terminal title to set, when the number of open tasks due today
+    /// has changed since the last call; `None` otherwise. After a failed
+    /// count it waits for the next due check instead of failing every tick.
+    pub fn window_title_update(&mut self) -> Result<Option<String>, AppError> {
+        if self
+            .title_retry_at
+            .is_some_and(|retry_at| Instant::now() < retry_at)
+        {
+            return Ok(None);
+        }
+        let count = match self.db_ops.count_tasks_due_today() {
+            Ok(count) => count,
+            Err(e) => {
+                self.title_retry_at = Some(self.next_due_check_at);
+                return Err(e);
+            }
+        };
+        self.title_retry_at = None;
+        if self.last_title_count == Some(count) {
+            return Ok(None);
+        }
+        self.last_title_count = Some(count);
+        Ok(Some(window_title(count)))
+    }
+
+    pub fn log_panel_alert_active(&self) -> bool {
+        self.log_panel_alert_until
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    /// Opens the "Due soon" popup with open tasks due within
+    /// `config.due_soon_days`.
+    pub fn begin_due_soon(&mut self) -> Result<(), AppError> {
+        self.due_soon_tasks = self
+            .db_ops
+            .load_tasks_due_within(self.config.due_soon_days)?;
+        self.due_soon_selected = 0;
+        self.input_mode = InputMode::ViewingDueSoon;
+        Ok(())
+    }
+
+    pub fn close_due_soon(&mut self) {
+        self.due_soon_tasks.clear();
+        self.due_soon_selected = 0;
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn move_due_soon_selection(&mut self, delta: isize) {
+        if self.due_soon_tasks.is_empty() {
+            return;
+        }
+        let len = self.due_soon_tasks.len() as isize;
+        self.due_soon_selected = (self.due_soon_selected as isize + delta).rem_euclid(len) as usize;
+    }
+
+    /// Jumps to the selected task in its topic and closes the popup.
+    pub fn open_selected_due_task(&mut self) -> Result<(), AppError> {
+        let Some(task_id) = self
+            .due_soon_tasks
+            .get(self.due_soon_selected)
+            .map(|task| task.id)
+        else {
+            return Ok(());
+        };
+        self.close_due_soon();
+        self.focus_task_by_id(task_id)?;
+        Ok(())
+    }
+}
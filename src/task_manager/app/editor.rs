@@ -0,0 +1,100 @@
+use super::{App, InputMode};
+
+/// Byte offset of the `col`th character of `line`, or its length past the end.
+fn byte_index(line: &str, col: usize) -> usize {
+    line.char_indices()
+        .nth(col)
+        .map_or(line.len(), |(index, _)| index)
+}
+
+impl App {
+    /// Switches the edit popup to the multi-line description editor, loading
+    /// `task_description_input` one line per row with the cursor at the end.
+    pub fn begin_multiline_description(&mut self) {
+        self.ml_editor_lines = self
+            .task_description_input
+            .split('\n')
+            .map(str::to_string)
+            .collect();
+        let row = self.ml_editor_lines.len() - 1;
+        self.ml_editor_cursor = (row, self.ml_editor_lines[row].chars().count());
+        self.input_mode = InputMode::MultilineEditDescription;
+    }
+
+    /// The editor buffer joined back into a single description.
+    pub fn ml_editor_text(&self) -> String {
+        self.ml_editor_lines.join("\n")
+    }
+
+    /// Copies the editor buffer back into `task_description_input`.
+    pub fn sync_multiline_description(&mut self) {
+        self.task_description_input = self.ml_editor_text();
+    }
+
+    pub fn ml_insert_char(&mut self, c: char) {
+        let (row, col) = self.ml_editor_cursor;
+        let line = &mut self.ml_editor_lines[row];
+        line.insert(byte_index(line, col), c);
+        self.ml_editor_cursor.1 += 1;
+    }
+
+    /// Splits the current line at the cursor.
+    pub fn ml_insert_newline(&mut self) {
+        let (row, col) = self.ml_editor_cursor;
+        let line = &mut self.ml_editor_lines[row];
+        let rest = line.split_off(byte_index(line, col));
+        self.ml_editor_lines.insert(row + 1, rest);
+        self.ml_editor_cursor = (row + 1, 0);
+    }
+
+    /// Deletes the character before the cursor, joining with the previous
+    /// line at the start of a line.
+    pub fn ml_backspace(&mut self) {
+        let (row, col) = self.ml_editor_cursor;
+        if col > 0 {
+            let line = &mut self.ml_editor_lines[row];
+            line.remove(byte_index(line, col - 1));
+            self.ml_editor_cursor.1 -= 1;
+        } else if row > 0 {
+            let line = self.ml_editor_lines.remove(row);
+            let previous = &mut self.ml_editor_lines[row - 1];
+            let previous_len = previous.chars().count();
+            previous.push_str(&line);
+            self.ml_editor_cursor = (row - 1, previous_len);
+        }
+    }
+
+    fn ml_line_len(&self, row: usize) -> usize {
+        self.ml_editor_lines[row].chars().count()
+    }
+
+    /// Left and right wrap onto the neighbouring line.
+    pub fn ml_move_horizontal(&mut self, delta: isize) {
+        let (row, col) = self.ml_editor_cursor;
+        self.ml_editor_cursor = if delta < 0 {
+            match (col, row) {
+                (0, 0) => (0, 0),
+                (0, _) => (row - 1, self.ml_line_len(row - 1)),
+                _ => (row, col - 1),
+            }
+        } else if col < self.ml_line_len(row) {
+            (row, col + 1)
+        } else if row + 1 < self.ml_editor_lines.len() {
+            (row + 1, 0)
+        } else {
+            (row, col)
+        };
+    }
+
+    /// Up and down keep the column where the target line is long enough.
+    pub fn ml_move_vertical(&mut self, delta: isize) {
+        let (row, col) = self.ml_editor_cursor;
+        let Some(row) = row
+            .checked_add_signed(delta)
+            .filter(|row| *row < self.ml_editor_lines.len())
+        else {
+            return;
+        };
+        self.ml_editor_cursor = (row, col.min(self.ml_line_len(row)));
+    }
+}
@@ -1,24 +1,163 @@
-use std::{error::Error, io};
-
-use crate::db::task_manager::models::Task;
+use diesel::prelude::*;
+use diesel::sql_types::{Bool, Text};
+use diesel::sqlite::Sqlite;
+use std::io;
+
+use crate::db::schema::task;
+use crate::db::task_manager::models::{CompletionFilter, Task, TaskSource};
+use crate::db::task_manager::operations::TaskQuery;
+use crate::error::AppError;
 use crate::filter_presets::{save_presets, SavedFilterPreset};
 
 use super::{App, InputMode};
 
+/// The structured filters applied to the task list. The free-text query
+/// typed after `/` is kept separately in `App::task_filter`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskFilter {
+    /// Persisted per topic; pinned tasks always pass it.
+    pub completion: CompletionFilter,
+}
+
+impl TaskFilter {
+    pub fn matches(&self, task: &Task) -> bool {
+        self.completion.matches(task)
+    }
+
+    /// Narrows `query` to the tasks that pass these filters.
+    pub fn filter_query(&self, query: TaskQuery) -> TaskQuery {
+        match self.completion {
+            CompletionFilter::All => query,
+            CompletionFilter::Open => query.filter(task::completed.eq(false).or(task::pinned)),
+            CompletionFilter::Done => query.filter(task::completed.eq(true).or(task::pinned)),
+        }
+    }
+
+    /// Short description for the task list title, e.g. `Open`.
+    pub fn summary(&self) -> String {
+        self.completion.label().to_string()
+    }
+}
+
+/// The structured filters plus the `/` query, resolved against the loaded
+/// topics so the database worker can page over the same tasks the list
+/// shows. Built by [`App::task_list_filter`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskListFilter {
+    pub filter: TaskFilter,
+    terms: Vec<FilterTerm>,
+}
+
+/// One token of the `/` query; `negated` for a leading `-`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FilterTerm {
+    negated: bool,
+    kind: FilterTermKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FilterTermKind {
+    /// Lowercase text found in the name, the description or the name of
+    /// one of `topic_ids`.
+    Text { needle: String, topic_ids: Vec<i32> },
+    /// `topic:`; the topics whose names contain the value.
+    Topics(Vec<i32>),
+    /// `status:`; `None` when the value was not understood.
+    Completed(Option<bool>),
+    /// `fav:`; `None` when the value was not understood.
+    Favourite(Option<bool>),
+    /// `source:`; `None` when the value was not understood.
+    Source(Option<TaskSource>),
+}
+
+/// A `/` query token, parsed but not yet matched against anything. Text is
+/// lowercased.
+enum FilterToken {
+    Topic(String),
+    Status(Option<bool>),
+    Favourite(Option<bool>),
+    Source(Option<TaskSource>),
+    Text(String),
+}
+
+type TermExpression = Box<dyn BoxableExpression<task::table, Sqlite, SqlType = Bool>>;
+
+impl FilterTerm {
+    fn to_expression(&self) -> TermExpression {
+        let matches: TermExpression = match &self.kind {
+            FilterTermKind::Text { needle, topic_ids } => Box::new(
+                contains_sql("name", needle)
+                    .or(contains_sql("description", needle))
+                    .or(task::topic_id.eq_any(topic_ids.clone())),
+            ),
+            FilterTermKind::Topics(topic_ids) => Box::new(task::topic_id.eq_any(topic_ids.clone())),
+            FilterTermKind::Completed(Some(expected)) => Box::new(task::completed.eq(*expected)),
+            FilterTermKind::Favourite(Some(expected)) => Box::new(task::favourite.eq(*expected)),
+            FilterTermKind::Source(Some(source)) => Box::new(task::source.eq(source.as_str())),
+            FilterTermKind::Completed(None)
+            | FilterTermKind::Favourite(None)
+            | FilterTermKind::Source(None) => Box::new(diesel::dsl::sql::<Bool>("0")),
+        };
+        if self.negated {
+            Box::new(diesel::dsl::not(matches))
+        } else {
+            matches
+        }
+    }
+}
+
+/// `column` contains the lowercase `needle`, folding case with the same
+/// Unicode rules as the in-memory filter.
+fn contains_sql(column: &str, needle: &str) -> TermExpression {
+    Box::new(
+        diesel::dsl::sql::<Bool>(&format!("instr(unicode_lower({column}), "))
+            .bind::<Text, _>(needle.to_string())
+            .sql(") > 0"),
+    )
+}
+
+impl TaskListFilter {
+    /// Narrows `query` to the tasks the list shows.
+    pub fn filter_query(&self, query: TaskQuery) -> TaskQuery {
+        self.terms
+            .iter()
+            .fold(self.filter.filter_query(query), |query, term| {
+                query.filter(term.to_expression())
+            })
+    }
+}
+
 impl App {
     pub fn has_task_filter(&self) -> bool {
         !self.task_filter.trim().is_empty()
     }
 
+    /// Indices of the tasks shown in the list, in display order. The
+    /// database already lists open tasks before completed ones unless the
+    /// list is sorted by status.
     pub fn filtered_task_indices(&self) -> Vec<usize> {
         self.tasks
             .iter()
             .enumerate()
+            .filter(|(_, task)| self.filter.matches(task))
             .filter(|(_, task)| self.task_matches_filter(task, &self.task_filter))
             .map(|(index, _)| index)
             .collect()
     }
 
+    /// Position in `filtered` of the first completed task, when open tasks
+    /// all come before it and both groups are non-empty. The list draws a
+    /// divider there.
+    pub fn completed_separator_position(&self, filtered: &[usize]) -> Option<usize> {
+        let position = filtered
+            .iter()
+            .position(|index| self.tasks[*index].completed)?;
+        let grouped = filtered[position..]
+            .iter()
+            .all(|index| self.tasks[*index].completed);
+        (position > 0 && grouped).then_some(position)
+    }
+
     fn topic_name_for_task(&self, topic_id: i32) -> Option<&str> {
         self.topics
             .iter()
@@ -44,6 +183,16 @@ impl App {
         }
     }
 
+    /// A source by its stored name (`import_csv`) or without the `import_`
+    /// prefix (`csv`).
+    fn parse_source_token(value: &str) -> Option<TaskSource> {
+        let value = value.trim().to_lowercase();
+        TaskSource::ALL.into_iter().find(|source| {
+            let name = source.as_str();
+            name == value || name.strip_prefix("import_") == Some(value.as_str())
+        })
+    }
+
     pub(super) fn filter_tokens(query: &str) -> Vec<String> {
         let mut tokens = Vec::new();
         let mut current = String::new();
@@ -82,30 +231,19 @@ impl App {
             .to_lowercase();
 
         Self::filter_tokens(trimmed).into_iter().all(|token| {
-            let (negated, token) = if let Some(token) = token.strip_prefix('-') {
-                (true, token)
-            } else {
-                (false, token.as_str())
-            };
-
-            let matches = if let Some(value) = token.strip_prefix("topic:") {
-                let value = value.to_lowercase();
-                !value.is_empty() && topic_name.contains(&value)
-            } else if let Some(value) = token
-                .strip_prefix("status:")
-                .or_else(|| token.strip_prefix("state:"))
-            {
-                Self::parse_status_token(value).is_some_and(|expected| task.completed == expected)
-            } else if let Some(value) = token
-                .strip_prefix("fav:")
-                .or_else(|| token.strip_prefix("favorite:"))
-                .or_else(|| token.strip_prefix("favourite:"))
-                .or_else(|| token.strip_prefix("star:"))
-            {
-                Self::parse_bool_token(value).is_some_and(|expected| task.favourite == expected)
-            } else {
-                let token = token.to_lowercase();
-                name.contains(&token) || description.contains(&token) || topic_name.contains(&token)
+            let (negated, token) = Self::parse_filter_token(&token);
+            let matches = match token {
+                FilterToken::Topic(value) => !value.is_empty() && topic_name.contains(&value),
+                FilterToken::Status(expected) => expected == Some(task.completed),
+                FilterToken::Favourite(expected) => expected == Some(task.favourite),
+                FilterToken::Source(expected) => {
+                    expected.is_some_and(|source| task.source == source.as_str())
+                }
+                FilterToken::Text(token) => {
+                    name.contains(&token)
+                        || description.contains(&token)
+                        || topic_name.contains(&token)
+                }
             };
 
             if negated {
@@ -116,6 +254,82 @@ impl App {
         })
     }
 
+    /// Splits a leading `-` off `token` and reads its `topic:`, `status:`,
+    /// `fav:` or `source:` prefix.
+    fn parse_filter_token(token: &str) -> (bool, FilterToken) {
+        let (negated, token) = match token.strip_prefix('-') {
+            Some(token) => (true, token),
+            None => (false, token),
+        };
+        let token = if let Some(value) = token.strip_prefix("topic:") {
+            FilterToken::Topic(value.to_lowercase())
+        } else if let Some(value) = token
+            .strip_prefix("status:")
+            .or_else(|| token.strip_prefix("state:"))
+        {
+            FilterToken::Status(Self::parse_status_token(value))
+        } else if let Some(value) = token
+            .strip_prefix("fav:")
+            .or_else(|| token.strip_prefix("favorite:"))
+            .or_else(|| token.strip_prefix("favourite:"))
+            .or_else(|| token.strip_prefix("star:"))
+        {
+            FilterToken::Favourite(Self::parse_bool_token(value))
+        } else if let Some(value) = token.strip_prefix("source:") {
+            FilterToken::Source(Self::parse_source_token(value))
+        } else {
+            FilterToken::Text(token.to_lowercase())
+        };
+        (negated, token)
+    }
+
+    /// Ids of the loaded topics whose names contain the lowercase `needle`.
+    fn topic_ids_matching(&self, needle: &str) -> Vec<i32> {
+        if needle.is_empty() {
+            return Vec::new();
+        }
+        self.topics
+            .iter()
+            .filter(|topic| topic.name.to_lowercase().contains(needle))
+            .map(|topic| topic.id)
+            .collect()
+    }
+
+    /// `filter` and `task_filter` in a form the database can apply, so
+    /// pages and totals cover only the tasks the list shows.
+    pub fn task_list_filter(&self) -> TaskListFilter {
+        let terms = Self::filter_tokens(self.task_filter.trim())
+            .iter()
+            .map(|token| {
+                let (negated, token) = Self::parse_filter_token(token);
+                let kind = match token {
+                    FilterToken::Topic(value) => {
+                        FilterTermKind::Topics(self.topic_ids_matching(&value))
+                    }
+                    FilterToken::Status(expected) => FilterTermKind::Completed(expected),
+                    FilterToken::Favourite(expected) => FilterTermKind::Favourite(expected),
+                    FilterToken::Source(expected) => FilterTermKind::Source(expected),
+                    FilterToken::Text(needle) => FilterTermKind::Text {
+                        topic_ids: self.topic_ids_matching(&needle),
+                        needle,
+                    },
+                };
+                FilterTerm { negated, kind }
+            })
+            .collect();
+        TaskListFilter {
+            filter: self.filter,
+            terms,
+        }
+    }
+
+    /// Starts the filtered list over from its first page.
+    fn refilter_tasks(&mut self) -> Result<(), AppError> {
+        self.page_offset = 0;
+        self.ensure_selected_visible();
+        self.request_tasks()
+    }
+
     pub fn ensure_selected_visible(&mut self) {
         let filtered = self.filtered_task_indices();
         if filtered.is_empty() {
@@ -150,23 +364,48 @@ impl App {
         }
     }
 
+    /// Selects the `row`-th visible task (1-based), clamped to the list.
+    pub fn jump_to_row(&mut self, row: u32) {
+        let filtered = self.filtered_task_indices();
+        if filtered.is_empty() {
+            return;
+        }
+        let position = (row.max(1) as usize - 1).min(filtered.len() - 1);
+        self.selected = filtered[position];
+    }
+
+    pub fn jump_to_last(&mut self) {
+        if let Some(last) = self.filtered_task_indices().last() {
+            self.selected = *last;
+        }
+    }
+
+    pub fn push_count_digit(&mut self, digit: u32) {
+        self.count_prefix = Some(
+            self.count_prefix
+                .unwrap_or(0)
+                .saturating_mul(10)
+                .saturating_add(digit),
+        );
+    }
+
     pub fn begin_task_filter(&mut self) {
         self.input_mode = InputMode::Filtering;
     }
 
-    pub fn append_task_filter_char(&mut self, c: char) {
+    pub fn append_task_filter_char(&mut self, c: char) -> Result<(), AppError> {
         self.task_filter.push(c);
-        self.ensure_selected_visible();
+        self.refilter_tasks()
     }
 
-    pub fn pop_task_filter_char(&mut self) {
+    pub fn pop_task_filter_char(&mut self) -> Result<(), AppError> {
         self.task_filter.pop();
-        self.ensure_selected_visible();
+        self.refilter_tasks()
     }
 
-    pub fn clear_task_filter(&mut self) {
+    pub fn clear_task_filter(&mut self) -> Result<(), AppError> {
         self.task_filter.clear();
-        self.ensure_selected_visible();
+        self.refilter_tasks()
     }
 
     pub fn task_filter_presets(&self) -> Vec<(&'static str, &'static str)> {
@@ -216,16 +455,17 @@ impl App {
         }
     }
 
-    pub fn apply_selected_task_preset(&mut self) {
+    pub fn apply_selected_task_preset(&mut self) -> Result<(), AppError> {
         if let Some((name, query, _)) = self
             .all_task_filter_presets()
             .get(self.preset_selected)
             .cloned()
         {
             self.task_filter = query;
-            self.ensure_selected_visible();
+            self.refilter_tasks()?;
             self.add_log("INFO", &format!("Applied preset: {}", name));
         }
+        Ok(())
     }
 
     pub fn apply_selected_special_task_preset(&mut self) {
@@ -263,7 +503,7 @@ impl App {
         self.input_mode = InputMode::SavingSpecialPreset;
     }
 
-    pub fn save_named_task_preset(&mut self, special: bool) -> Result<(), Box<dyn Error>> {
+    pub fn save_named_task_preset(&mut self, special: bool) -> Result<(), AppError> {
         let name = self.preset_name_input.trim();
         if name.is_empty() {
             return Err(
@@ -301,7 +541,7 @@ impl App {
         Ok(())
     }
 
-    pub fn delete_selected_task_preset(&mut self) -> Result<bool, Box<dyn Error>> {
+    pub fn delete_selected_task_preset(&mut self) -> Result<bool, AppError> {
         let builtin_len = self.task_filter_presets().len();
         if self.preset_selected < builtin_len {
             return Ok(false);
@@ -1,5 +1,7 @@
-use std::{error::Error, path::Path};
+use std::path::Path;
 
-pub(crate) fn load_palette_history(path: &Path) -> Result<Vec<String>, Box<dyn Error>> {
-    crate::common::palette::load_recent_commands(path)
+use crate::error::AppError;
+
+pub(crate) fn load_palette_history(path: &Path) -> Result<Vec<String>, AppError> {
+    Ok(crate::common::palette::load_recent_commands(path)?)
 }
@@ -0,0 +1,91 @@
+use std::io;
+
+use crate::db::task_manager::models::{LinkType, LinkedTask, Task};
+use crate::error::AppError;
+
+use super::{App, InputMode};
+
+impl App {
+    /// Opens the add-link popup for the selected task.
+    pub fn begin_add_link(&mut self) {
+        if self.current_task().is_none() {
+            self.add_log("WARN", "No task selected to link");
+            return;
+        }
+        if self.tasks.len() < 2 {
+            self.add_log("WARN", "No other task in this topic to link to");
+            return;
+        }
+        self.link_type_selected = LinkType::default();
+        self.link_target_selected = 0;
+        self.input_mode = InputMode::AddingLink;
+    }
+
+    pub fn cancel_add_link(&mut self) {
+        self.link_target_selected = 0;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Tasks the selected task can be linked to: every other loaded task.
+    pub fn link_candidates(&self) -> Vec<&Task> {
+        let selected_id = self.current_task_id();
+        self.tasks
+            .iter()
+            .filter(|task| Some(task.id) != selected_id)
+            .collect()
+    }
+
+    pub fn move_link_selection(&mut self, delta: isize) {
+        let len = self.link_candidates().len() as isize;
+        if len == 0 {
+            return;
+        }
+        self.link_target_selected =
+            (self.link_target_selected as isize + delta).rem_euclid(len) as usize;
+    }
+
+    pub fn cycle_link_type(&mut self) {
+        self.link_type_selected = self.link_type_selected.next();
+    }
+
+    /// Links the selected task to the highlighted candidate and closes the popup.
+    pub fn add_selected_link(&mut self) -> Result<(), AppError> {
+        let Some(source_id) = self.current_task_id() else {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "No task selected").into());
+        };
+        let Some(target_id) = self
+            .link_candidates()
+            .get(self.link_target_selected)
+            .map(|task| task.id)
+        else {
+            return Err(
+                io::Error::new(io::ErrorKind::InvalidInput, "No task chosen to link to").into(),
+            );
+        };
+        let link_type = self.link_type_selected;
+        self.db_ops.add_link(source_id, target_id, link_type)?;
+        self.add_log(
+            "INFO",
+            &format!(
+                "Linked task id {}: {} task id {}",
+                source_id,
+                link_type.label().to_lowercase(),
+                target_id
+            ),
+        );
+        self.cancel_add_link();
+        self.load_tasks()
+    }
+
+    /// Incomplete tasks that `task_id` is blocked by.
+    pub fn incomplete_blockers(&self, task_id: i32) -> Result<Vec<LinkedTask>, AppError> {
+        Ok(self
+            .db_ops
+            .load_linked_tasks_for(&[task_id])?
+            .remove(&task_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|linked| linked.link_type == LinkType::BlockedBy && !linked.completed)
+            .collect())
+    }
+}
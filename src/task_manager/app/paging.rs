@@ -0,0 +1,96 @@
+use crate::error::AppError;
+
+use super::App;
+
+impl App {
+    /// Pages in the current topic's filtered task list; at least one, even
+    /// when empty.
+    pub fn page_count(&self) -> usize {
+        self.task_total.div_ceil(self.page_size.max(1)).max(1)
+    }
+
+    /// Loads `page`, clamped to the topic, and selects its first visible task.
+    /// Returns `false` when already on that page.
+    pub fn go_to_page(&mut self, page: usize) -> Result<bool, AppError> {
+        let page = page.min(self.page_count() - 1);
+        if page == self.page_offset {
+            return Ok(false);
+        }
+        let previous = self.page_offset;
+        self.page_offset = page;
+        if let Err(e) = self.load_tasks() {
+            self.page_offset = previous;
+            return Err(e);
+        }
+        self.selected = 0;
+        self.ensure_selected_visible();
+        Ok(true)
+    }
+
+    pub fn next_page(&mut self) -> Result<bool, AppError> {
+        self.go_to_page(self.page_offset + 1)
+    }
+
+    pub fn previous_page(&mut self) -> Result<bool, AppError> {
+        match self.page_offset.checked_sub(1) {
+            Some(page) => self.go_to_page(page),
+            None => Ok(false),
+        }
+    }
+
+    /// `j`/Down: the next visible task, or the first one on the next page
+    /// when the selection is already on the last row or nothing is visible.
+    pub fn move_down_or_next_page(&mut self) -> Result<(), AppError> {
+        let at_bottom = self
+            .filtered_task_indices()
+            .last()
+            .is_none_or(|last| *last == self.selected);
+        if !at_bottom || !self.next_page()? {
+            self.move_selection_down();
+        }
+        Ok(())
+    }
+
+    /// `k`/Up: the previous visible task, or the last one on the previous
+    /// page when the selection is already on the first row.
+    pub fn move_up_or_previous_page(&mut self) -> Result<(), AppError> {
+        let at_top = self.filtered_task_indices().first() == Some(&self.selected);
+        if at_top && self.previous_page()? {
+            self.jump_to_last();
+        } else {
+            self.move_selection_up();
+        }
+        Ok(())
+    }
+
+    /// Loads the page of the current topic that holds `task_id` and selects
+    /// it. Returns `false` when the task is not listed in this topic.
+    pub fn select_task_by_id(&mut self, task_id: i32) -> Result<bool, AppError> {
+        let Some(topic) = self.current_topic().cloned() else {
+            return Ok(false);
+        };
+        let filter = self.task_list_filter();
+        let Some(position) = self.db_ops.task_position(
+            &topic,
+            self.sort_mode,
+            self.sort_ascending,
+            task_id,
+            |query| filter.filter_query(query),
+        )?
+        else {
+            return Ok(false);
+        };
+        let page = position / self.page_size.max(1);
+        if page != self.page_offset {
+            self.page_offset = page;
+            self.load_tasks()?;
+        }
+        match self.tasks.iter().position(|task| task.id == task_id) {
+            Some(index) => {
+                self.selected = index;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::db::task_manager::models::{Task, Topic};
+use crate::error::AppError;
+
+use super::{App, InputMode};
+
+/// How many tasks the recent tasks popup lists.
+const RECENT_TASK_LIMIT: usize = 10;
+
+impl App {
+    /// The most recently created or updated tasks with their topics, newest
+    /// first. Served from `recent_cache` until tasks are next reloaded.
+    pub fn recent_tasks(&mut self) -> Result<&[(Task, Topic)], AppError> {
+        if self.recent_cache.is_none() {
+            self.recent_cache = Some(self.db_ops.recent_tasks(RECENT_TASK_LIMIT)?);
+        }
+        Ok(self.recent_cache.as_deref().unwrap_or_default())
+    }
+
+    pub fn begin_recent(&mut self) -> Result<(), AppError> {
+        self.recent_tasks()?;
+        self.recent_selected = 0;
+        self.input_mode = InputMode::ViewingRecent;
+        Ok(())
+    }
+
+    pub fn close_recent(&mut self) {
+        self.recent_selected = 0;
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn move_recent_selection(&mut self, delta: isize) {
+        let len = self.recent_cache.as_ref().map_or(0, Vec::len) as isize;
+        if len == 0 {
+            return;
+        }
+        self.recent_selected = (self.recent_selected as isize + delta).rem_euclid(len) as usize;
+    }
+
+    /// Jumps to the selected task in its own topic and closes the popup.
+    pub fn open_selected_recent_task(&mut self) -> Result<(), AppError> {
+        let Some(task_id) = self
+            .recent_cache
+            .as_ref()
+            .and_then(|recent| recent.get(self.recent_selected))
+            .map(|(task, _)| task.id)
+        else {
+            return Ok(());
+        };
+        self.close_recent();
+        self.focus_task_by_id(task_id)?;
+        Ok(())
+    }
+}
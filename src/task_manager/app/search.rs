@@ -0,0 +1,113 @@
+use crate::error::AppError;
+
+use super::worker::DbCommand;
+use super::{App, InputMode};
+
+impl App {
+    pub fn begin_search(&mut self) {
+        self.search_query.clear();
+        self.search_results.clear();
+        self.search_selected = 0;
+        self.input_mode = InputMode::Searching;
+    }
+
+    pub fn close_search(&mut self) {
+        self.search_query.clear();
+        self.search_results.clear();
+        self.search_selected = 0;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Topic the search is limited to; the built-in topics search everything.
+    pub fn search_topic_id(&self) -> Option<i32> {
+        self.topics
+            .get(self.selected_topic)
+            .filter(|topic| !self.db_ops.is_special_topic(&topic.name))
+            .map(|topic| topic.id)
+    }
+
+    /// Re-runs the full-text search for the current query. With the worker
+    /// running, the results replace the current ones when they arrive.
+    pub fn run_search(&mut self) -> Result<(), AppError> {
+        self.dispatch_db_command(DbCommand::Search {
+            query: self.search_query.clone(),
+            topic_id: self.search_topic_id(),
+        })
+    }
+
+    /// Opens the topic search popup listing every topic tab.
+    pub fn begin_topic_search(&mut self) -> Result<(), AppError> {
+        self.topic_search_query.clear();
+        self.topic_search_selected = 0;
+        self.input_mode = InputMode::SearchingTopics;
+        self.run_topic_search()
+    }
+
+    pub fn close_topic_search(&mut self) {
+        self.topic_search_query.clear();
+        self.topic_search_results.clear();
+        self.topic_search_selected = 0;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Re-runs the topic search, keeping only topics shown as tabs.
+    pub fn run_topic_search(&mut self) -> Result<(), AppError> {
+        let matches = self.db_ops.search_topics(&self.topic_search_query)?;
+        self.topic_search_results = matches
+            .into_iter()
+            .filter(|topic| self.topics.iter().any(|tab| tab.id == topic.id))
+            .collect();
+        if self.topic_search_selected >= self.topic_search_results.len() {
+            self.topic_search_selected = self.topic_search_results.len().saturating_sub(1);
+        }
+        Ok(())
+    }
+
+    pub fn move_topic_search_selection(&mut self, delta: isize) {
+        if self.topic_search_results.is_empty() {
+            return;
+        }
+        let len = self.topic_search_results.len() as isize;
+        self.topic_search_selected =
+            (self.topic_search_selected as isize + delta).rem_euclid(len) as usize;
+    }
+
+    /// Switches to the selected topic and closes the popup.
+    pub fn open_selected_topic_search_result(&mut self) -> Result<(), AppError> {
+        let Some(index) = self
+            .topic_search_results
+            .get(self.topic_search_selected)
+            .and_then(|found| self.topics.iter().position(|tab| tab.id == found.id))
+        else {
+            return Ok(());
+        };
+        self.close_topic_search();
+        self.selected_topic = index;
+        self.selected = 0;
+        self.load_tasks()?;
+        self.ensure_selected_visible();
+        Ok(())
+    }
+
+    pub fn move_search_selection(&mut self, delta: isize) {
+        if self.search_results.is_empty() {
+            return;
+        }
+        let len = self.search_results.len() as isize;
+        self.search_selected = (self.search_selected as isize + delta).rem_euclid(len) as usize;
+    }
+
+    /// Jumps to the selected search hit and closes the popup.
+    pub fn open_selected_search_result(&mut self) -> Result<(), AppError> {
+        let Some(task_id) = self
+            .search_results
+            .get(self.search_selected)
+            .map(|(task, _)| task.id)
+        else {
+            return Ok(());
+        };
+        self.close_search();
+        self.focus_task_by_id(task_id)?;
+        Ok(())
+    }
+}
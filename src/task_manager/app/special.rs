@@ -1,6 +1,5 @@
-use std::error::Error;
-
 use crate::db::task_manager::models::{Task, Topic};
+use crate::error::AppError;
 
 use super::{App, InputMode};
 
@@ -13,13 +12,18 @@ impl App {
         self.input_mode = InputMode::DeleteSpecialTask;
     }
 
-    pub fn load_special_tasks(&mut self) -> Result<(), Box<dyn Error>> {
+    pub fn load_special_tasks(&mut self) -> Result<(), AppError> {
+        self.recent_cache = None;
         let fav_topic = Topic {
             id: -1,
             name: "Favourites".to_string(),
             description: String::new(),
             created_at: String::new(),
             updated_at: String::new(),
+            color: None,
+            position: 0,
+            alias: None,
+            task_limit: None,
         };
         self.favourites_tasks = self.db_ops.load_tasks(&fav_topic)?;
 
@@ -29,6 +33,10 @@ impl App {
             description: String::new(),
             created_at: String::new(),
             updated_at: String::new(),
+            color: None,
+            position: 0,
+            alias: None,
+            task_limit: None,
         };
         self.completed_tasks = self.db_ops.load_tasks(&completed_topic)?;
 
@@ -52,18 +60,17 @@ impl App {
         }
     }
 
-    pub fn toggle_special_task(&mut self) -> Result<(), Box<dyn Error>> {
+    pub fn toggle_special_task(&mut self) -> Result<(), AppError> {
         let tasks = self.get_current_special_tasks();
-        if let Some(task) = tasks.get(self.special_task_selected) {
-            self.db_ops.toggle_task_completion(task.id)?;
-            self.add_log("INFO", &format!("Toggled task id: {}", task.id));
+        if let Some(task_id) = tasks.get(self.special_task_selected).map(|task| task.id) {
+            self.toggle_completion(task_id)?;
             self.load_special_tasks()?;
             self.load_tasks()?;
         }
         Ok(())
     }
 
-    pub fn toggle_special_favourite(&mut self) -> Result<(), Box<dyn Error>> {
+    pub fn toggle_special_favourite(&mut self) -> Result<(), AppError> {
         let tasks = self.get_current_special_tasks();
         if let Some(task) = tasks.get(self.special_task_selected) {
             self.db_ops.toggle_task_favourite(task.id)?;
@@ -77,7 +84,7 @@ impl App {
         Ok(())
     }
 
-    pub fn delete_special_task(&mut self) -> Result<(), Box<dyn Error>> {
+    pub fn delete_special_task(&mut self) -> Result<(), AppError> {
         let tasks = self.get_current_special_tasks();
         if let Some(task) = tasks.get(self.special_task_selected) {
             self.db_ops.delete_task(task.id)?;
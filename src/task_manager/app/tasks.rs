@@ -1,12 +1,35 @@
-use chrono::Local;
-use std::{error::Error, io};
+use crossterm::{execute, style::Print};
+use std::io;
+use std::path::Path;
 
-use crate::db::task_manager::models::TaskUpdate;
+use crate::db::task_manager::models::{Task, Topic};
+use crate::error::AppError;
 
+use super::worker::DbCommand;
 use super::{App, InputMode};
 
 impl App {
-    pub fn add_task_with_details(&mut self, name: &str, desc: &str) -> Result<(), Box<dyn Error>> {
+    pub fn add_task_with_details(&mut self, name: &str, desc: &str) -> Result<(), AppError> {
+        self.add_task_with_url(name, desc, "")
+    }
+
+    /// Checks an optional task URL; an empty string means "no URL".
+    pub fn validate_task_url(url: &str) -> Result<Option<&str>, AppError> {
+        let url = url.trim();
+        if url.is_empty() {
+            return Ok(None);
+        }
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "URL must start with http:// or https://",
+            )
+            .into());
+        }
+        Ok(Some(url))
+    }
+
+    pub fn add_task_with_url(&mut self, name: &str, desc: &str, url: &str) -> Result<(), AppError> {
         let trimmed_name = name.trim();
         let trimmed_desc = desc.trim();
         if trimmed_name.is_empty() {
@@ -14,32 +37,62 @@ impl App {
                 io::Error::new(io::ErrorKind::InvalidInput, "Task name cannot be empty").into(),
             );
         }
+        let url = Self::validate_task_url(url)?;
 
-        let current_topic = &self.topics[self.selected_topic];
+        let Some(current_topic) = self.current_topic() else {
+            return Ok(());
+        };
         if current_topic.name == "Favourites" {
             return Ok(());
         }
-        self.db_ops
-            .add_task(current_topic.id, trimmed_name, trimmed_desc)?;
-        self.add_log(
-            "INFO",
-            &format!("Added task: {} - {}", trimmed_name, trimmed_desc),
-        );
-        self.load_tasks()
+        self.dispatch_db_command(DbCommand::AddTask {
+            topic: current_topic.clone(),
+            name: trimmed_name.to_string(),
+            description: trimmed_desc.to_string(),
+            url: url.map(str::to_string),
+        })
     }
 
-    pub fn toggle_task(&mut self) -> Result<(), Box<dyn Error>> {
-        if let Some(task) = self.tasks.get(self.selected) {
-            self.db_ops.toggle_task_completion(task.id)?;
-            self.add_log("INFO", &format!("Toggled task id: {}", task.id));
-            self.load_tasks()?;
-            self.load_special_tasks()?;
+    /// Toggles completion, ringing the terminal bell when the task becomes done.
+    pub(super) fn toggle_completion(&mut self, task_id: i32) -> Result<(), AppError> {
+        let task = self.db_ops.toggle_task_completion(task_id)?;
+        self.report_toggle(&task)
+    }
+
+    /// Rings the bell and logs a toggle, warning when a task is completed
+    /// while tasks blocking it are still open.
+    pub(super) fn report_toggle(&mut self, task: &Task) -> Result<(), AppError> {
+        let task_id = task.id;
+        if task.completed && self.config.sound_enabled {
+            execute!(io::stdout(), Print("\x07"))?;
+        }
+        self.add_log("INFO", &format!("Toggled task id: {}", task_id));
+        if task.completed {
+            let blockers = self.incomplete_blockers(task_id)?;
+            if !blockers.is_empty() {
+                let names: Vec<&str> = blockers.iter().map(|linked| linked.name.as_str()).collect();
+                self.add_log(
+                    "WARN",
+                    &format!(
+                        "Task '{}' was completed while still blocked by: {}",
+                        task.name,
+                        names.join(", ")
+                    ),
+                );
+            }
         }
         Ok(())
     }
 
-    pub fn toggle_favourite(&mut self) -> Result<(), Box<dyn Error>> {
-        if let Some(task) = self.tasks.get(self.selected) {
+    pub fn toggle_task(&mut self) -> Result<(), AppError> {
+        match self.current_task_id() {
+            Some(task_id) => self.dispatch_db_command(DbCommand::ToggleTask(task_id)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn toggle_favourite(&mut self) -> Result<(), AppError> {
+        if let Some(task) = self.current_task() {
             self.db_ops.toggle_task_favourite(task.id)?;
             self.add_log(
                 "INFO",
@@ -51,22 +104,58 @@ impl App {
         Ok(())
     }
 
-    pub fn delete_task(&mut self) -> Result<(), Box<dyn Error>> {
-        if let Some(task) = self.tasks.get(self.selected) {
-            self.db_ops.delete_task(task.id)?;
-            self.add_log("INFO", &format!("Deleted task id: {}", task.id));
+    pub fn toggle_pin(&mut self) -> Result<(), AppError> {
+        if let Some(task) = self.current_task() {
+            let task_id = task.id;
+            let task = if task.pinned {
+                self.db_ops.unpin_task(task_id)?
+            } else {
+                self.db_ops.pin_task(task_id)?
+            };
+            self.add_log(
+                "INFO",
+                &format!(
+                    "{} task id: {}",
+                    if task.pinned { "Pinned" } else { "Unpinned" },
+                    task_id
+                ),
+            );
             self.load_tasks()?;
-            if self.selected > 0 && self.selected >= self.tasks.len() {
-                self.selected -= 1;
-            }
+            // Follow the task to its new position in the list.
+            self.select_task_by_id(task_id)?;
+            self.ensure_selected_visible();
         }
         Ok(())
     }
 
-    pub fn delete_topic(&mut self) -> Result<(), Box<dyn Error>> {
-        let current_topic = &self.topics[self.selected_topic];
-        if current_topic.name == "Favourites" {
+    /// Imports an Emacs Org file into the current topic.
+    pub fn import_from_org_mode(&mut self, path: &Path) -> Result<(), AppError> {
+        let Some(topic_id) = self.current_topic().map(|topic| topic.id) else {
+            return Ok(());
+        };
+        self.dispatch_db_command(DbCommand::ImportOrg {
+            topic_id,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Deletes the selected task, or archives it when `soft_delete` is set.
+    pub fn delete_task(&mut self) -> Result<(), AppError> {
+        match self.current_task_id() {
+            Some(task_id) => self.dispatch_db_command(DbCommand::DeleteTasks {
+                task_ids: vec![task_id],
+                archive: self.config.soft_delete,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn delete_topic(&mut self) -> Result<(), AppError> {
+        let Some(current_topic) = self.current_topic() else {
             return Ok(());
+        };
+        if current_topic.name == "Favourites" {
+            return Err(AppError::ProtectedTopic(current_topic.name.clone()));
         }
         self.db_ops.delete_topic(current_topic.id)?;
         self.load_topics()?;
@@ -75,7 +164,7 @@ impl App {
         Ok(())
     }
 
-    pub fn edit_task(&mut self, name: &str, desc: &str) -> Result<(), Box<dyn Error>> {
+    pub fn edit_task(&mut self, name: &str, desc: &str) -> Result<(), AppError> {
         let trimmed_name = name.trim();
         let trimmed_desc = desc.trim();
         if trimmed_name.is_empty() {
@@ -83,38 +172,122 @@ impl App {
                 io::Error::new(io::ErrorKind::InvalidInput, "Task name cannot be empty").into(),
             );
         }
-        if let Some(task) = self.tasks.get(self.selected) {
-            let update = TaskUpdate {
-                name: Some(trimmed_name),
-                description: Some(trimmed_desc),
-                completed: Some(task.completed),
-                favourite: Some(task.favourite),
-                updated_at: &Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-            };
-            self.db_ops.update_task(task.id, update)?;
-            self.add_log(
-                "INFO",
-                &format!("Successfully edited task, with id: {}", task.id),
-            );
-            self.load_tasks()?;
-        } else {
-            self.add_log("WARN", "No task selected to edit");
+        match self.current_task_id() {
+            Some(task_id) => self.dispatch_db_command(DbCommand::EditTask {
+                task_id,
+                name: trimmed_name.to_string(),
+                description: trimmed_desc.to_string(),
+            }),
+            None => {
+                self.add_log("WARN", "No task selected to edit");
+                Ok(())
+            }
         }
-        Ok(())
     }
 
-    pub fn current_topic_is_special(&self) -> bool {
-        if self.topics.is_empty() {
-            false
-        } else {
-            let name = &self.topics[self.selected_topic].name;
-            name == "Favourites" || name == "Completed"
+    fn copy_to_clipboard(&mut self, text: &str, what: &str) {
+        self.clipboard = text.to_string();
+        match self.system_clipboard.copy_text(text) {
+            Ok(()) => self.add_log("INFO", &format!("Copied {} to clipboard", what)),
+            Err(e) => self.add_log(
+                "WARN",
+                &format!(
+                    "System clipboard unavailable ({}); copied {} to internal clipboard",
+                    e, what
+                ),
+            ),
+        }
+    }
+
+    pub fn yank_selected_task(&mut self) {
+        let Some(name) = self.current_task().map(|task| task.name.clone()) else {
+            self.add_log("WARN", "No task selected to copy");
+            return;
+        };
+        self.copy_to_clipboard(&name, "task name");
+        self.push_clipboard_history(&name);
+    }
+
+    pub fn copy_task_description(&mut self) {
+        let description = self.task_description_input.clone();
+        self.copy_to_clipboard(&description, "task description");
+    }
+
+    pub fn paste_task_name(&mut self) {
+        let text = match self.system_clipboard.paste_text() {
+            Ok(text) => text,
+            Err(e) => {
+                self.add_log(
+                    "WARN",
+                    &format!(
+                        "System clipboard unavailable ({}); pasting internal clipboard",
+                        e
+                    ),
+                );
+                self.clipboard.clone()
+            }
+        };
+
+        self.begin_add_task();
+        if self.input_mode == InputMode::AddingTaskName {
+            self.task_name_input = text.lines().next().unwrap_or_default().trim().to_string();
         }
     }
 
+    pub fn open_selected_task_url(&mut self) {
+        let Some(task) = self.current_task() else {
+            self.add_log("WARN", "No task selected");
+            return;
+        };
+        let Some(url) = task.url.clone().filter(|url| !url.trim().is_empty()) else {
+            self.add_log("WARN", "No URL set for this task");
+            return;
+        };
+        match open::that(&url) {
+            Ok(()) => self.add_log("INFO", &format!("Opened {}", url)),
+            Err(e) => self.add_log("ERROR", &format!("Failed to open {}: {}", url, e)),
+        }
+    }
+
+    /// URLs shown in full on screen: those of expanded tasks, and the
+    /// selected task's in the detail view. These become terminal links.
+    pub fn visible_task_urls(&self) -> Vec<&str> {
+        let viewing = (self.input_mode == InputMode::ViewingTask).then_some(self.selected);
+        self.tasks
+            .iter()
+            .enumerate()
+            .filter(|(index, task)| Some(*index) == viewing || self.expanded.contains(&task.id))
+            .filter_map(|(_, task)| task.url.as_deref())
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .collect()
+    }
+
+    /// The highlighted task, if the list has one.
+    pub fn current_task(&self) -> Option<&Task> {
+        self.tasks.get(self.selected)
+    }
+
+    pub fn current_task_id(&self) -> Option<i32> {
+        self.current_task().map(|task| task.id)
+    }
+
+    /// The selected topic; `None` while there are no topics.
+    pub fn current_topic(&self) -> Option<&Topic> {
+        self.topics.get(self.selected_topic)
+    }
+
+    pub fn current_topic_is_special(&self) -> bool {
+        self.current_topic()
+            .is_some_and(|topic| topic.name == "Favourites" || topic.name == "Completed")
+    }
+
     pub fn reset_task_inputs(&mut self) {
         self.task_name_input.clear();
         self.task_description_input.clear();
+        self.task_url_input.clear();
+        self.ml_editor_lines.clear();
+        self.ml_editor_cursor = (0, 0);
         self.task_form_message = None;
     }
 
@@ -141,7 +314,7 @@ impl App {
     }
 
     pub fn begin_edit_task(&mut self) {
-        if let Some(task) = self.tasks.get(self.selected) {
+        if let Some(task) = self.current_task().cloned() {
             self.task_name_input = task.name.clone();
             self.task_description_input = task.description.clone();
             self.input = task.description.clone();
@@ -151,16 +324,141 @@ impl App {
         }
     }
 
-    pub fn begin_delete_task(&mut self) {
-        if self.tasks.is_empty() {
+    /// Opens the rename popup with the selected task's name filled in.
+    pub fn begin_rename_task(&mut self) {
+        if let Some(task) = self.current_task() {
+            self.rename_input = task.name.clone();
+            self.clear_task_form_message();
+            self.input_mode = InputMode::RenamingTask;
+        } else {
+            self.add_log("WARN", "No task selected to rename");
+        }
+    }
+
+    pub fn cancel_rename_task(&mut self) {
+        self.rename_input.clear();
+        self.clear_task_form_message();
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Renames the selected task to `rename_input`. An empty name keeps the
+    /// popup open with an inline message instead.
+    pub fn submit_rename_task(&mut self) -> Result<(), AppError> {
+        let new_name = self.rename_input.trim().to_string();
+        if new_name.is_empty() {
+            self.set_task_form_message("Task name cannot be empty");
+            return Ok(());
+        }
+        let Some(task) = self.current_task() else {
+            self.add_log("WARN", "No task selected to rename");
+            self.cancel_rename_task();
+            return Ok(());
+        };
+        let task_id = task.id;
+        self.dispatch_db_command(DbCommand::RenameTask {
+            task_id,
+            name: new_name,
+        })?;
+        self.cancel_rename_task();
+        Ok(())
+    }
+
+    /// Opens the full-screen detail view for the selected task.
+    pub fn begin_view_task(&mut self) {
+        if self.current_task().is_some() {
+            self.input_mode = InputMode::ViewingTask;
+        } else {
+            self.add_log("WARN", "No task selected to view");
+        }
+    }
+
+    /// Asks for confirmation before deleting the selected task, naming how
+    /// many subtasks go with it when it has any.
+    pub fn begin_delete_task(&mut self) -> Result<(), AppError> {
+        let Some(task_id) = self.current_task_id() else {
             self.add_log("WARN", "No task selected to delete");
+            return Ok(());
+        };
+        self.delete_subtask_count = self.db_ops.load_subtasks(task_id)?.len();
+        self.input_mode = if self.delete_subtask_count > 0 {
+            InputMode::ConfirmingDeleteTask
+        } else {
+            InputMode::DeleteTask
+        };
+        Ok(())
+    }
+
+    pub fn begin_add_subtask(&mut self) {
+        if self.current_task().is_none() {
+            self.add_log("WARN", "No task selected to add a subtask to");
             return;
         }
-        self.input_mode = InputMode::DeleteTask;
+        self.input.clear();
+        self.input_mode = InputMode::AddingSubtask;
+    }
+
+    /// Adds a subtask named `name` to the selected task.
+    pub fn add_subtask(&mut self, name: &str) -> Result<(), AppError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Subtask name cannot be empty",
+            )
+            .into());
+        }
+        if let Some(task_id) = self.current_task_id() {
+            self.db_ops.add_subtask(task_id, name)?;
+            self.add_log(
+                "INFO",
+                &format!("Added subtask to task id {}: {}", task_id, name),
+            );
+            self.load_tasks()?;
+        }
+        Ok(())
+    }
+
+    /// `(completed, total)` subtasks of `task_id`.
+    pub fn subtask_progress(&self, task_id: i32) -> (usize, usize) {
+        self.subtasks.get(&task_id).map_or((0, 0), |subtasks| {
+            (
+                subtasks.iter().filter(|subtask| subtask.completed).count(),
+                subtasks.len(),
+            )
+        })
     }
 
     pub fn begin_add_topic(&mut self) {
         self.input.clear();
+        self.topic_name_input.clear();
         self.input_mode = InputMode::AddingTopic;
     }
+
+    /// Moves from the name step to the optional description step; an empty
+    /// name ends topic creation.
+    pub fn begin_topic_description(&mut self) {
+        if self.input.trim().is_empty() {
+            self.cancel_add_topic();
+            return;
+        }
+        self.topic_name_input = std::mem::take(&mut self.input);
+        self.input_mode = InputMode::AddingTopicDescription;
+    }
+
+    /// Creates the topic from both steps; an empty description is stored as
+    /// `""`.
+    pub fn submit_new_topic(&mut self) -> Result<(), AppError> {
+        let name = std::mem::take(&mut self.topic_name_input);
+        let description = std::mem::take(&mut self.input);
+        self.input_mode = InputMode::Normal;
+        self.add_topic_with_description(&name, &description)?;
+        self.add_log("INFO", &format!("Added topic: {}", name.trim()));
+        Ok(())
+    }
+
+    pub fn cancel_add_topic(&mut self) {
+        self.input.clear();
+        self.topic_name_input.clear();
+        self.input_mode = InputMode::Normal;
+    }
 }
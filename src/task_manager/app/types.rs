@@ -1,9 +1,35 @@
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    path::PathBuf,
+    time::Instant,
+};
 
-use crate::db::task_manager::models::{Task, Topic};
+use crossterm::event::KeyCode;
+use tui::layout::Rect;
+
+use crate::common::clipboard::Clipboard;
+use crate::common::logs::{LogEntry, LogLevel};
+use crate::config::Config;
+use crate::db::task_manager::models::{
+    LinkType, LinkedTask, SortMode, Subtask, Task, Topic, TopicStats,
+};
 use crate::db::task_manager::operations::DbOperations;
 use crate::filter_presets::SavedFilterPreset;
 
+use super::worker::DbWorker;
+use super::TaskFilter;
+
+/// Screen areas recorded while drawing, used to hit-test mouse events.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayoutRegions {
+    /// One single-row area per topic tab title, in tab order.
+    pub topic_tabs: Vec<Rect>,
+    /// The five main panels top to bottom: topic tabs, task list, command
+    /// bar, mode and logs.
+    pub chunks: [Rect; 5],
+}
+
 /// The mode of the application: either in normal navigation or adding a new task.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputMode {
@@ -17,19 +43,46 @@ pub enum InputMode {
     SavingSpecialPreset,
     AddingTaskName,
     AddingTaskDescription,
+    AddingTaskUrl,
     EditingTaskName,
-    EditingTaskDescription,
+    RenamingTask,
+    MultilineEditDescription,
     DeleteTask,
+    ConfirmingDeleteTask,
+    DeletingMultipleTasks,
+    ConfirmingDeleteMultipleTasks,
     DeleteSpecialTask,
     AddingTopic,
+    AddingTopicDescription,
+    SettingTopicAlias,
+    SettingTopicTaskLimit,
     Help,
     ViewingSpecialTopics,
+    ConfirmingExit,
+    Searching,
+    SearchingTopics,
+    MergingTopic,
+    ViewingTask,
+    ViewingDueSoon,
+    ViewingRecent,
+    ViewingArchivedTasks,
+    SearchingLogs,
+    AddingSubtask,
+    AddingLink,
+    ViewingTopicStats,
+    ShowingError,
+    ChoosingClipboard,
 }
 
 /// The overall application state.
 pub struct App {
     /// Database operations handler
     pub db_ops: DbOperations,
+    /// Thread running task loads and toggles while the TUI is up; `None`
+    /// runs them inline.
+    pub db_worker: Option<DbWorker>,
+    /// Bumped on every task load; worker pages from older loads are dropped.
+    pub task_load_seq: u64,
     /// Current list of Topics
     pub topics: Vec<Topic>,
     /// Current selected Topic
@@ -38,18 +91,45 @@ pub struct App {
     pub tasks: Vec<Task>,
     /// Current filter query for the visible task list.
     pub task_filter: String,
+    /// Sort order of the current topic, persisted per topic.
+    pub sort_mode: SortMode,
+    /// Whether the current topic is sorted ascending.
+    pub sort_ascending: bool,
+    /// Structured task list filters; the completion state is persisted per
+    /// topic.
+    pub filter: TaskFilter,
     /// Current selected preset in the presets popup.
     pub preset_selected: usize,
     /// User-defined presets persisted to disk.
     pub custom_task_presets: Vec<SavedFilterPreset>,
     /// Preset storage path.
     pub preset_store_path: PathBuf,
+    /// User settings loaded from the config file.
+    pub config: Config,
+    /// Config file path.
+    pub config_path: PathBuf,
+    /// The SQLite database file.
+    pub db_path: PathBuf,
     /// Palette history storage path.
     pub palette_history_store_path: PathBuf,
     /// Currently selected index in the task list.
     pub selected: usize,
     /// The current input mode.
     pub input_mode: InputMode,
+    /// First key of a two-key Normal-mode sequence such as `gg`, and when
+    /// it was pressed.
+    pub pending_key: Option<(KeyCode, Instant)>,
+    /// Numeric prefix typed before a jump, e.g. the `10` in `10G`.
+    pub count_prefix: Option<u32>,
+    /// Name entered in the first step of topic creation, kept while the
+    /// description is typed.
+    pub topic_name_input: String,
+    /// Subtasks that will be deleted with the task being confirmed.
+    pub delete_subtask_count: usize,
+    /// Error shown in a popup until the next key press.
+    pub error_popup: Option<String>,
+    /// The mode to return to when an exit confirmation is cancelled.
+    pub previous_mode: Option<InputMode>,
     /// The mode to return to after closing the command palette.
     pub command_palette_return_mode: InputMode,
     /// Palette query text.
@@ -64,18 +144,128 @@ pub struct App {
     pub task_name_input: String,
     /// Buffer for task description (when creating a new task)
     pub task_description_input: String,
+    /// Description being edited in the multi-line editor, one entry per line.
+    pub ml_editor_lines: Vec<String>,
+    /// `(row, column)` of the multi-line editor cursor, in characters.
+    pub ml_editor_cursor: (usize, usize),
+    /// Buffer for the optional task URL (when creating a new task)
+    pub task_url_input: String,
     /// Inline feedback shown inside the task form popup.
     pub task_form_message: Option<String>,
+    /// Buffer for the `R` rename popup, pre-filled with the task's name.
+    pub rename_input: String,
+    /// Buffer for the topic setting popups, pre-filled with the current value.
+    pub topic_setting_input: String,
+    /// Why the last topic setting was rejected, shown in its popup.
+    pub topic_setting_message: Option<String>,
+    /// Full-text search query.
+    pub search_query: String,
+    /// Matching tasks with their highlighted snippets, best match first.
+    pub search_results: Vec<(Task, String)>,
+    /// Selected row in the search popup.
+    pub search_selected: usize,
+    /// Query typed into the `Ctrl+F` topic search popup.
+    pub topic_search_query: String,
+    /// Tabs whose name or description matches the topic search query.
+    pub topic_search_results: Vec<Topic>,
+    /// Selected row in the topic search popup.
+    pub topic_search_selected: usize,
+    /// Statistics for the topic shown in the `i` popup.
+    pub topic_stats: Option<TopicStats>,
+    /// Open tasks shown in the "Due soon" popup, soonest first.
+    pub due_soon_tasks: Vec<Task>,
+    /// Selected row in the "Due soon" popup.
+    pub due_soon_selected: usize,
+    /// Most recently touched tasks for the `r` popup; cleared whenever tasks
+    /// are reloaded so the next `r` queries the database again.
+    pub recent_cache: Option<Vec<(Task, Topic)>>,
+    /// Selected row in the recent tasks popup.
+    pub recent_selected: usize,
+    /// Due-today count shown in the terminal title, once it has been set.
+    pub last_title_count: Option<usize>,
+    /// Set after the due-today count fails; the title is not recounted
+    /// before then.
+    pub title_retry_at: Option<Instant>,
+    /// Archived tasks listed in the archive popup.
+    pub archived_tasks: Vec<Task>,
+    /// Selected row in the archive popup.
+    pub archived_selected: usize,
+    /// Tasks per page of the task list, from `Config::page_size`.
+    pub page_size: usize,
+    /// Zero-based page of the current topic held in `tasks`.
+    pub page_offset: usize,
+    /// Tasks in the current topic that pass the filters, across every page.
+    pub task_total: usize,
+    /// Topic the page offset belongs to; switching topics starts at page 0.
+    pub page_topic_id: Option<i32>,
+    /// While [`App::step_topic`] waits on the worker for a page: the topic
+    /// index and page that were on screen, restored if the load fails.
+    pub topic_switch_from: Option<(usize, usize)>,
+    /// The log panel border is drawn red until this instant.
+    pub log_panel_alert_until: Option<Instant>,
+    /// When the tick next re-checks for tasks due within 24 hours.
+    pub next_due_check_at: Instant,
+    /// When the tick next refreshes [`App::overdue_counts`].
+    pub next_overdue_refresh_at: Instant,
+    /// Selected row in the merge-topic popup.
+    pub merge_target_selected: usize,
+    /// Link type chosen in the add-link popup.
+    pub link_type_selected: LinkType,
+    /// Selected row among the link candidates.
+    pub link_target_selected: usize,
+    /// Clipboard that yanks copy to and pastes read from.
+    pub system_clipboard: Box<dyn Clipboard>,
+    /// Internal clipboard used when the system clipboard is unavailable.
+    pub clipboard: String,
+    /// Task names yanked this session, newest first.
+    pub clipboard_history: Vec<String>,
+    /// Selected row in the clipboard picker.
+    pub clipboard_selected: usize,
+    /// Mode the clipboard picker was opened from and pastes into.
+    pub clipboard_return_mode: InputMode,
     /// Buffer for naming a saved preset.
     pub preset_name_input: String,
     /// Inline feedback shown inside the preset popup.
     pub preset_form_message: Option<String>,
+    /// Set while the terminal is below the minimum supported size.
+    pub terminal_too_small: bool,
+    /// Areas from the last frame, for mouse hit-testing.
+    pub layout_regions: LayoutRegions,
+    /// Topic tab under the mouse cursor, if any.
+    pub hovered_topic_idx: Option<usize>,
+    /// Column of the last mouse move; anchors the topic tooltip.
+    pub mouse_column: u16,
+    /// Set while the mouse is over the log panel, which then takes the
+    /// scroll wheel.
+    pub log_panel_focused: bool,
+    /// In the sidebar layout, set while `j`/`k` move through topics rather
+    /// than tasks.
+    pub sidebar_focused: bool,
+    /// Task count per topic id, refreshed whenever topics or tasks reload.
+    pub task_counts: HashMap<i32, i64>,
+    /// Overdue open tasks per topic id, for the `[!N]` tab badges. Refreshed
+    /// with `task_counts` and once a minute, as tasks fall overdue.
+    pub overdue_counts: HashMap<i32, i64>,
     /// Log storage.
-    pub logs: Vec<String>,
+    pub logs: Vec<LogEntry>,
+    /// Entries below this level are hidden from the log panel.
+    pub log_min_level: LogLevel,
     /// Scroll offset to be displayed.
     pub log_offset: usize,
+    /// Case-insensitive text the log panel is narrowed to; empty shows all.
+    pub log_search_query: String,
+    /// Indices into [`App::logs`] matching [`App::log_search_query`].
+    pub log_filtered: Vec<usize>,
     /// Set task IDs that are expanded
     pub expanded: HashSet<i32>,
+    /// Tasks checked in the `Shift+D` mass-delete list.
+    pub selected_tasks: HashSet<i32>,
+    /// Two tasks per row, names only; expanded tasks still get a full row.
+    pub compact_mode: bool,
+    /// Subtasks of the loaded tasks, keyed by parent task id.
+    pub subtasks: HashMap<i32, Vec<Subtask>>,
+    /// Linked tasks of the loaded tasks, keyed by task id.
+    pub task_links: HashMap<i32, Vec<LinkedTask>>,
     /// Current tab in special tasks popup. `0 = favourites`, `1 = completed`.
     pub special_tab_selected: usize,
     /// Selected task in special popup.
@@ -86,4 +276,6 @@ pub struct App {
     pub favourites_tasks: Vec<Task>,
     /// Cached completed tasks.
     pub completed_tasks: Vec<Task>,
+    /// Exclusive lock on the database's lock file, released on drop.
+    pub(super) _lock: Option<File>,
 }
@@ -0,0 +1,486 @@
+//! Database work off the render loop. Key handlers send a [`DbCommand`] to a
+//! worker thread holding its own [`DbOperations`]; its answer comes back as
+//! an [`AppEvent`] that [`App::poll_db_events`] applies before the next
+//! frame is drawn.
+//! Without a worker (tests, or if the thread has stopped) the same work runs
+//! inline.
+
+use chrono::Local;
+use std::{
+    collections::HashMap,
+    io,
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Instant,
+};
+
+use crate::db::task_manager::models::{
+    LinkedTask, Subtask, Task, TaskUpdate, Topic, TopicOrder, TopicSettings,
+};
+use crate::db::task_manager::operations::DbOperations;
+use crate::error::AppError;
+
+use super::{App, InputMode, TaskListFilter};
+
+/// Work for the database thread.
+#[derive(Debug, Clone)]
+pub enum DbCommand {
+    LoadTopics(TopicOrder),
+    /// Loads `page` of the tasks in `topic` that pass `filter`, clamped to
+    /// the pages there are, or the page holding `focus` when it is listed.
+    /// `seq` comes back with the page so stale loads can be dropped.
+    LoadTasks {
+        seq: u64,
+        topic: Topic,
+        page: usize,
+        page_size: usize,
+        filter: Box<TaskListFilter>,
+        focus: Option<i32>,
+    },
+    ToggleTask(i32),
+    /// Adds a task to `topic`, refused once the topic's task limit is reached.
+    AddTask {
+        topic: Topic,
+        name: String,
+        description: String,
+        url: Option<String>,
+    },
+    EditTask {
+        task_id: i32,
+        name: String,
+        description: String,
+    },
+    RenameTask {
+        task_id: i32,
+        name: String,
+    },
+    /// Deletes the tasks in one transaction, or archives them when `archive`
+    /// is set.
+    DeleteTasks {
+        task_ids: Vec<i32>,
+        archive: bool,
+    },
+    /// Swaps the task with its neighbour in the manual order.
+    MoveTask {
+        task_id: i32,
+        up: bool,
+    },
+    ImportOrg {
+        topic_id: i32,
+        path: PathBuf,
+    },
+    /// Full-text search, limited to `topic_id` when set.
+    Search {
+        query: String,
+        topic_id: Option<i32>,
+    },
+}
+
+/// The database thread's answer to one [`DbCommand`].
+#[derive(Debug)]
+pub enum AppEvent {
+    /// Every topic, built-in views included, in the requested order.
+    TopicsLoaded(Vec<Topic>),
+    TasksLoaded {
+        seq: u64,
+        page: Box<TaskPage>,
+    },
+    /// A write finished.
+    MutationComplete(Mutation),
+    /// Results for `query`, dropped if the query has changed since.
+    SearchResults {
+        query: String,
+        results: Vec<(Task, String)>,
+    },
+    Error(AppError),
+}
+
+/// What a finished write changed, for the log and the reload that follows.
+#[derive(Debug)]
+pub enum Mutation {
+    Toggled(Task),
+    Added(Task),
+    Edited(Task),
+    Renamed(Task),
+    Deleted {
+        task_ids: Vec<i32>,
+        archived: bool,
+    },
+    /// `moved` is `false` when the task was already at that end.
+    Moved {
+        task_id: i32,
+        moved: bool,
+    },
+    /// The import summary, ready to log.
+    Imported(String),
+}
+
+/// One page of a topic's tasks with everything drawn alongside them.
+#[derive(Debug, Clone)]
+pub struct TaskPage {
+    pub topic_id: i32,
+    pub page: usize,
+    /// Tasks in the topic that pass the filter, across every page.
+    pub total: usize,
+    /// Row of the task the load was asked to focus, when it is listed.
+    pub selected: Option<usize>,
+    pub settings: TopicSettings,
+    pub tasks: Vec<Task>,
+    pub subtasks: HashMap<i32, Vec<Subtask>>,
+    pub task_links: HashMap<i32, Vec<LinkedTask>>,
+    pub task_counts: HashMap<i32, i64>,
+    pub overdue_counts: HashMap<i32, i64>,
+}
+
+/// Reads `page` of the tasks in `topic` that pass `filter` (clamped to the
+/// last page) in the topic's saved sort order, or the page holding `focus`
+/// when that task is listed. The topic's saved completion filter replaces
+/// the one in `filter`. Shared by the worker and [`App::load_tasks`].
+pub fn fetch_task_page(
+    db_ops: &DbOperations,
+    topic: &Topic,
+    page: usize,
+    page_size: usize,
+    filter: &TaskListFilter,
+    focus: Option<i32>,
+) -> Result<TaskPage, AppError> {
+    let task_counts = db_ops.count_tasks_by_topic()?;
+    let overdue_counts = db_ops.count_overdue_tasks_per_topic()?;
+    let settings = db_ops.load_topic_settings(topic.id)?;
+    let mut filter = filter.clone();
+    filter.filter.completion = settings.completion_filter;
+    let total = db_ops.count_listed_tasks(topic, |query| filter.filter_query(query))?;
+    let total = usize::try_from(total).unwrap_or_default();
+    let pages = total.div_ceil(page_size.max(1)).max(1);
+    let position = match focus {
+        Some(task_id) => db_ops.task_position(
+            topic,
+            settings.sort_mode,
+            settings.sort_ascending,
+            task_id,
+            |query| filter.filter_query(query),
+        )?,
+        None => None,
+    };
+    let page = match position {
+        Some(position) => position / page_size.max(1),
+        None => page.min(pages - 1),
+    };
+    let tasks = db_ops.load_tasks_page(
+        topic,
+        settings.sort_mode,
+        settings.sort_ascending,
+        page,
+        page_size,
+        |query| filter.filter_query(query),
+    )?;
+    let task_ids: Vec<i32> = tasks.iter().map(|task| task.id).collect();
+    Ok(TaskPage {
+        topic_id: topic.id,
+        page,
+        total,
+        selected: focus.and_then(|focus| task_ids.iter().position(|id| *id == focus)),
+        settings,
+        subtasks: db_ops.load_subtasks_for(&task_ids)?,
+        task_links: db_ops.load_linked_tasks_for(&task_ids)?,
+        tasks,
+        task_counts,
+        overdue_counts,
+    })
+}
+
+fn run_command(db_ops: &DbOperations, command: DbCommand) -> Result<AppEvent, AppError> {
+    Ok(match command {
+        DbCommand::LoadTopics(order) => AppEvent::TopicsLoaded(db_ops.load_topics(order)?),
+        DbCommand::LoadTasks {
+            seq,
+            topic,
+            page,
+            page_size,
+            filter,
+            focus,
+        } => AppEvent::TasksLoaded {
+            seq,
+            page: Box::new(fetch_task_page(
+                db_ops, &topic, page, page_size, &filter, focus,
+            )?),
+        },
+        DbCommand::ToggleTask(task_id) => {
+            AppEvent::MutationComplete(Mutation::Toggled(db_ops.toggle_task_completion(task_id)?))
+        }
+        DbCommand::AddTask {
+            topic,
+            name,
+            description,
+            url,
+        } => {
+            if let Some(limit) = topic.task_limit {
+                if db_ops.count_tasks_in_topic(topic.id)? >= i64::from(limit) {
+                    return Err(AppError::TopicTaskLimitReached(topic.name, limit));
+                }
+            }
+            let task = db_ops.add_task_with_url(topic.id, &name, &description, url.as_deref())?;
+            AppEvent::MutationComplete(Mutation::Added(task))
+        }
+        DbCommand::EditTask {
+            task_id,
+            name,
+            description,
+        } => {
+            let update = TaskUpdate {
+                name: Some(&name),
+                description: Some(&description),
+                completed: None,
+                favourite: None,
+                pinned: None,
+                updated_at: &Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            };
+            AppEvent::MutationComplete(Mutation::Edited(db_ops.update_task(task_id, update)?))
+        }
+        DbCommand::RenameTask { task_id, name } => {
+            AppEvent::MutationComplete(Mutation::Renamed(db_ops.rename_task(task_id, &name)?))
+        }
+        DbCommand::DeleteTasks { task_ids, archive } => {
+            if archive {
+                db_ops.archive_tasks(&task_ids)?;
+            } else {
+                db_ops.delete_tasks(&task_ids)?;
+            }
+            AppEvent::MutationComplete(Mutation::Deleted {
+                task_ids,
+                archived: archive,
+            })
+        }
+        DbCommand::MoveTask { task_id, up } => {
+            let moved = if up {
+                db_ops.move_task_up(task_id)?
+            } else {
+                db_ops.move_task_down(task_id)?
+            };
+            AppEvent::MutationComplete(Mutation::Moved { task_id, moved })
+        }
+        DbCommand::ImportOrg { topic_id, path } => {
+            let summary = crate::export::org::import_org(db_ops, &path, topic_id, false)?;
+            AppEvent::MutationComplete(Mutation::Imported(summary.to_string()))
+        }
+        DbCommand::Search { query, topic_id } => AppEvent::SearchResults {
+            results: db_ops.fts_search_with_snippets(&query, topic_id)?,
+            query,
+        },
+    })
+}
+
+/// Handle to the database thread. The thread exits once this is dropped.
+pub struct DbWorker {
+    commands: Sender<DbCommand>,
+    events: Receiver<AppEvent>,
+}
+
+impl DbWorker {
+    pub fn spawn(db_ops: DbOperations) -> io::Result<Self> {
+        let (commands, command_rx) = mpsc::channel::<DbCommand>();
+        let (event_tx, events) = mpsc::channel();
+        thread::Builder::new()
+            .name("db-worker".to_string())
+            .spawn(move || {
+                for command in command_rx {
+                    let event = run_command(&db_ops, command).unwrap_or_else(AppEvent::Error);
+                    if event_tx.send(event).is_err() {
+                        break;
+                    }
+                }
+            })?;
+        Ok(Self { commands, events })
+    }
+
+    /// Queues `command`; `false` if the thread has stopped.
+    pub fn send(&self, command: DbCommand) -> bool {
+        self.commands.send(command).is_ok()
+    }
+
+    pub fn try_recv(&self) -> Option<AppEvent> {
+        self.events.try_recv().ok()
+    }
+}
+
+impl App {
+    /// Moves task loading, topic reloads, task writes and search onto a
+    /// worker thread. Called once the TUI starts.
+    pub fn start_db_worker(&mut self) -> Result<(), AppError> {
+        self.db_worker = Some(DbWorker::spawn(self.db_ops.clone())?);
+        Ok(())
+    }
+
+    /// Sends `command` to the worker. Returns `false` when there is none, in
+    /// which case the caller does the work inline.
+    pub(super) fn send_db_command(&mut self, command: DbCommand) -> bool {
+        let Some(worker) = &self.db_worker else {
+            return false;
+        };
+        if worker.send(command) {
+            return true;
+        }
+        self.db_worker = None;
+        self.add_log("WARN", "Database worker stopped; running queries inline");
+        false
+    }
+
+    /// Runs `command` on the worker, or inline when there is none. Inline
+    /// errors go back to the caller; the worker's are logged when they
+    /// arrive.
+    pub(super) fn dispatch_db_command(&mut self, command: DbCommand) -> Result<(), AppError> {
+        if self.send_db_command(command.clone()) {
+            return Ok(());
+        }
+        let event = run_command(&self.db_ops, command)?;
+        self.apply_db_event(event)
+    }
+
+    /// Applies every event the worker has sent since the last call.
+    pub fn poll_db_events(&mut self) {
+        while let Some(event) = self.db_worker.as_ref().and_then(DbWorker::try_recv) {
+            if let Err(e) = self.apply_db_event(event) {
+                self.add_log("ERROR", &format!("Database error: {e}"));
+            }
+        }
+    }
+
+    fn apply_db_event(&mut self, event: AppEvent) -> Result<(), AppError> {
+        match event {
+            AppEvent::TopicsLoaded(topics) => {
+                let current_id = self.current_topic().map(|topic| topic.id);
+                self.apply_topics(topics);
+                if let Some(index) =
+                    current_id.and_then(|id| self.topics.iter().position(|topic| topic.id == id))
+                {
+                    self.selected_topic = index;
+                }
+            }
+            AppEvent::TasksLoaded { seq, page } => {
+                // Tasks have been loaded again since, here or on the worker.
+                if seq == self.task_load_seq {
+                    self.apply_task_page(*page);
+                }
+            }
+            AppEvent::MutationComplete(mutation) => self.apply_mutation(mutation)?,
+            AppEvent::SearchResults { query, results } => {
+                if query == self.search_query {
+                    self.search_results = results;
+                    if self.search_selected >= self.search_results.len() {
+                        self.search_selected = self.search_results.len().saturating_sub(1);
+                    }
+                }
+            }
+            AppEvent::Error(e) => {
+                if let Err(reload) = self.roll_back_topic_switch() {
+                    self.add_log("ERROR", &format!("Database error: {reload}"));
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Logs a finished write and reloads the tasks it touched.
+    fn apply_mutation(&mut self, mutation: Mutation) -> Result<(), AppError> {
+        let mut focus = None;
+        match mutation {
+            Mutation::Toggled(task) => {
+                self.report_toggle(&task)?;
+                self.load_special_tasks()?;
+            }
+            Mutation::Added(task) => self.add_log(
+                "INFO",
+                &format!("Added task: {} - {}", task.name, task.description),
+            ),
+            Mutation::Edited(task) => self.add_log(
+                "INFO",
+                &format!("Successfully edited task, with id: {}", task.id),
+            ),
+            Mutation::Renamed(task) => {
+                self.add_log(
+                    "INFO",
+                    &format!("Renamed task id {} to '{}'", task.id, task.name),
+                );
+                // Renaming can move the task when the list is sorted by name.
+                focus = Some(task.id);
+            }
+            Mutation::Deleted { task_ids, archived } => {
+                self.selected_tasks.retain(|id| !task_ids.contains(id));
+                for task_id in &task_ids {
+                    self.expanded.remove(task_id);
+                }
+                if self.input_mode == InputMode::ConfirmingDeleteMultipleTasks {
+                    self.input_mode = InputMode::Normal;
+                }
+                let verb = if archived { "Archived" } else { "Deleted" };
+                match task_ids.as_slice() {
+                    [task_id] => self.add_log("INFO", &format!("{} task id: {}", verb, task_id)),
+                    _ => self.add_log("INFO", &format!("{} {} task(s)", verb, task_ids.len())),
+                }
+            }
+            Mutation::Moved { task_id, moved } => {
+                if !moved {
+                    return Ok(());
+                }
+                self.add_log("INFO", &format!("Moved task id: {}", task_id));
+                focus = Some(task_id);
+            }
+            Mutation::Imported(summary) => self.add_log("INFO", summary.trim_end()),
+        }
+        self.request_tasks_focusing(focus)
+    }
+
+    /// Reloads the current page of tasks, on the worker when there is one.
+    pub fn request_tasks(&mut self) -> Result<(), AppError> {
+        self.request_tasks_focusing(None)
+    }
+
+    /// [`App::request_tasks`], but loads the page holding `focus` and selects
+    /// it when that task is listed.
+    pub fn request_tasks_focusing(&mut self, focus: Option<i32>) -> Result<(), AppError> {
+        let Some(topic) = self.current_topic().cloned() else {
+            return self.load_tasks();
+        };
+        let page = self.page_for(&topic);
+        self.task_load_seq += 1;
+        let command = DbCommand::LoadTasks {
+            seq: self.task_load_seq,
+            topic,
+            page,
+            page_size: self.page_size,
+            filter: Box::new(self.task_list_filter()),
+            focus,
+        };
+        if !self.send_db_command(command) {
+            self.load_tasks_focusing(focus)?;
+        }
+        Ok(())
+    }
+
+    /// Stores a page fetched by [`fetch_task_page`].
+    pub(super) fn apply_task_page(&mut self, page: TaskPage) {
+        self.recent_cache = None;
+        self.task_counts = page.task_counts;
+        self.overdue_counts = page.overdue_counts;
+        self.next_overdue_refresh_at = Instant::now() + super::due::OVERDUE_REFRESH_INTERVAL;
+        self.sort_mode = page.settings.sort_mode;
+        self.sort_ascending = page.settings.sort_ascending;
+        self.filter.completion = page.settings.completion_filter;
+        self.page_offset = page.page;
+        self.topic_switch_from = None;
+        self.task_total = page.total;
+        self.tasks = page.tasks;
+        self.subtasks = page.subtasks;
+        self.task_links = page.task_links;
+        match page.selected {
+            Some(row) => self.selected = row,
+            None if self.selected >= self.tasks.len() => {
+                self.selected = self.tasks.len().saturating_sub(1);
+            }
+            None => {}
+        }
+        self.ensure_selected_visible();
+    }
+}
@@ -0,0 +1,135 @@
+//! Minimal Prometheus exposition for the task database, served from a
+//! background thread while the task manager UI is open.
+
+use slog::{info, warn};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::db::task_manager::operations::DbOperations;
+use crate::error::AppError;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+pub struct MetricsServer {
+    addr: SocketAddr,
+    shutdown: Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl MetricsServer {
+    /// Binds `127.0.0.1:<port>` and serves `GET /metrics` until [`shutdown`]
+    /// is called. Port `0` picks a free port; see [`local_addr`].
+    ///
+    /// [`shutdown`]: MetricsServer::shutdown
+    /// [`local_addr`]: MetricsServer::local_addr
+    pub fn start(port: u16, db_ops: DbOperations) -> Result<Self, AppError> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let (shutdown, receiver) = mpsc::channel();
+        let handle = thread::spawn(move || serve(listener, db_ops, receiver));
+        info!(crate::log_config::get_logger_or_discard(), "metrics server started"; "addr" => %addr);
+        Ok(Self {
+            addr,
+            shutdown,
+            handle,
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Signals the server thread to stop and waits for it to exit.
+    pub fn shutdown(self) {
+        let _ = self.shutdown.send(());
+        let _ = self.handle.join();
+    }
+}
+
+fn serve(listener: TcpListener, db_ops: DbOperations, shutdown: Receiver<()>) {
+    let logger = crate::log_config::get_logger_or_discard();
+    loop {
+        match shutdown.try_recv() {
+            Ok(()) | Err(TryRecvError::Disconnected) => break,
+            Err(TryRecvError::Empty) => {}
+        }
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Err(e) = handle_connection(stream, &db_ops) {
+                    warn!(logger, "metrics request failed"; "error" => %e);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                warn!(logger, "metrics accept failed"; "error" => %e);
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, db_ops: &DbOperations) -> Result<(), AppError> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => match render_metrics(db_ops) {
+            Ok(body) => ("200 OK", body),
+            Err(e) => ("500 Internal Server Error", format!("{e}\n")),
+        },
+        _ => ("404 Not Found", "not found\n".to_string()),
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Current counts in the Prometheus text exposition format.
+pub fn render_metrics(db_ops: &DbOperations) -> Result<String, AppError> {
+    let (total_tasks, completed_tasks) = db_ops.count_tasks()?;
+    let total_topics = db_ops.count_topics()?;
+    let pool_state = db_ops.pool.state();
+    let active_connections = pool_state.connections - pool_state.idle_connections;
+
+    let mut output = String::new();
+    for (name, help, value) in [
+        (
+            "task_manager_total_tasks",
+            "Number of tasks across all topics.",
+            total_tasks,
+        ),
+        (
+            "task_manager_completed_tasks",
+            "Number of completed tasks.",
+            completed_tasks,
+        ),
+        (
+            "task_manager_total_topics",
+            "Number of topics.",
+            total_topics,
+        ),
+        (
+            "task_manager_db_pool_connections_active",
+            "Database pool connections currently checked out.",
+            i64::from(active_connections),
+        ),
+    ] {
+        output.push_str(&format!(
+            "# HELP {name} {help}\n# TYPE {name} gauge\n{name} {value}\n"
+        ));
+    }
+    Ok(output)
+}
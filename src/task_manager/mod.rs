@@ -1,7 +1,9 @@
 pub mod app;
+pub mod metrics;
 pub mod ui;
+use crate::error::AppError;
 use crate::log_config;
-use slog::info;
+use slog::{info, warn};
 use std::fs;
 use std::io;
 use std::io::Stdout;
@@ -22,12 +24,9 @@ fn task_manager_session_state() -> &'static Mutex<TaskManagerSessionState> {
     TASK_MANAGER_SESSION_STATE.get_or_init(|| Mutex::new(TaskManagerSessionState::default()))
 }
 
-pub fn run_task_manager(
-    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let log = log_config::get_logger();
-    info!(log, "Starting Task Manager, inside task manager...");
-
+/// Resolves the task database path from the environment and makes sure its
+/// directory exists.
+pub fn task_db_path() -> Result<String, AppError> {
     let db_path = crate::db::resolve_db_path(
         "TASK_MANAGER_DB_DIR",
         ".task_manager",
@@ -35,41 +34,72 @@ pub fn run_task_manager(
         "task_manager.db",
     );
     if let Some(db_dir) = db_path.parent() {
-        info!(log, "DB_DIR: {:?}", db_dir);
         fs::create_dir_all(db_dir)?;
     }
 
-    info!(log, "DB_PATH: {:?}", db_path);
-    let db_path_str = db_path
+    Ok(db_path
         .to_str()
         .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Path contains invalid Unicode"))?
-        .to_string();
+        .to_string())
+}
+
+pub fn run_task_manager(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<(), AppError> {
+    let log = log_config::get_logger();
+    info!(log, "Starting Task Manager, inside task manager...");
+
+    let db_path_str = task_db_path()?;
     info!(log, "DB_PATH_STR: {}", db_path_str);
 
-    let mut app = app::App::new(&db_path_str)?;
+    let mut app = ui::load_with_splash(terminal, db_path_str)?;
     let mut focused_task_id = None;
     if let Ok(mut state) = task_manager_session_state().lock() {
         app.task_filter = state.task_filter.clone();
         app.special_task_filter = state.special_task_filter.clone();
         focused_task_id = state.focused_task_id.take();
+    }
+    if app.has_task_filter() {
+        app.load_tasks()?;
         app.ensure_selected_visible();
     }
     if let Some(task_id) = focused_task_id {
         let _ = app.focus_task_by_id(task_id)?;
     }
 
+    let metrics_server = match app.config.metrics_port {
+        Some(port) => match metrics::MetricsServer::start(port, app.db_ops.clone()) {
+            Ok(server) => Some(server),
+            Err(e) => {
+                warn!(log, "metrics server not started"; "port" => port, "error" => %e);
+                app.add_log("WARN", &format!("Metrics server not started: {e}"));
+                None
+            }
+        },
+        None => None,
+    };
+
     let result = ui::run(&mut app, terminal);
+    if let Some(server) = metrics_server {
+        server.shutdown();
+    }
     if let Ok(mut state) = task_manager_session_state().lock() {
         state.task_filter = app.task_filter.clone();
         state.special_task_filter = app.special_task_filter.clone();
     }
+    if let (Ok(()), Some(path)) = (&result, &app.config.auto_digest) {
+        let digest = crate::export::generate_digest(&app.db_ops, chrono::Local::now().date_naive())
+            .and_then(|digest| Ok(std::fs::write(path, digest)?));
+        match digest {
+            Ok(()) => info!(log, "digest written"; "path" => path),
+            Err(e) => warn!(log, "digest not written"; "path" => path, "error" => %e),
+        }
+    }
     result
 }
 
 pub fn run_task_manager_with_focus(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     task_id: i32,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<(), AppError> {
     if let Ok(mut state) = task_manager_session_state().lock() {
         state.focused_task_id = Some(task_id);
     }
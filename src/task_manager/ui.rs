@@ -1,24 +1,524 @@
 mod draw;
 mod events;
 
-use std::error::Error;
-use tui::{backend::Backend, Terminal};
+use crossterm::{execute, terminal::SetTitle};
+use slog::debug;
+use std::{
+    io,
+    path::Path,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::Paragraph,
+    Frame, Terminal,
+};
 
-use crate::task_manager::app::App;
+use crate::db::task_manager::models::Topic;
+use crate::error::AppError;
+use crate::task_manager::app::{window_title, App, InputMode};
+use crate::ui_style::{self, Accent};
 
-use events::{handle_key, UiAction};
+use events::{handle_key, handle_mouse, UiAction};
 
-pub fn run<B: Backend>(app: &mut App, terminal: &mut Terminal<B>) -> Result<(), Box<dyn Error>> {
-    crate::common::tui::run_event_loop(
+pub fn run<B: Backend>(app: &mut App, terminal: &mut Terminal<B>) -> Result<(), AppError> {
+    app.start_db_worker()?;
+    let tick_rate = app.config.tick_rate();
+    let set_title = !std::env::var(crate::config::NO_TITLE_ENV).is_ok_and(|value| value == "1");
+    let hyperlinks = app.config.hyperlinks && crate::common::hyperlink::supports_hyperlinks();
+    debug!(crate::log_config::get_logger_or_discard(), "task manager tick rate";
+        "tick_rate_ms" => app.config.tick_rate_ms);
+    crate::common::tui::run_event_loop_with_mouse(
         terminal,
         app,
-        |f, app| draw::draw_ui(f, app),
-        handle_key,
-        |action, _, _| {
+        tick_rate,
+        |f, app| {
+            // Worker replies are applied every loop turn, not just on ticks.
+            app.poll_db_events();
+            draw::draw_ui(f, app)
+        },
+        |buffer, app| {
+            if !hyperlinks {
+                return Ok(());
+            }
+            let urls = app.visible_task_urls();
+            // The task detail covers the whole frame; otherwise links only
+            // appear in expanded rows of the task list.
+            let area = if app.input_mode == InputMode::ViewingTask {
+                buffer.area
+            } else {
+                app.layout_regions.chunks[1]
+            };
+            crate::common::hyperlink::write_hyperlinks(&mut io::stdout(), buffer, area, &urls)
+        },
+        |app, key| {
+            let action = handle_key(app, key)?;
+            app.poll_db_events();
+            Ok(action)
+        },
+        handle_mouse,
+        |action, app, terminal| {
             Ok(match action {
                 UiAction::Continue => false,
                 UiAction::Exit => true,
+                UiAction::Suspend => {
+                    crate::common::tui::suspend_to_shell(terminal)?;
+                    false
+                }
+                UiAction::OpenLogFile => {
+                    open_log_file(app, terminal)?;
+                    false
+                }
             })
         },
-    )
+        |app| {
+            app.on_tick();
+            if set_title {
+                update_window_title(app)?;
+            }
+            Ok(())
+        },
+    )?;
+    if app.last_title_count.take().is_some() {
+        execute!(io::stdout(), SetTitle(window_title(0)))?;
+    }
+    Ok(())
+}
+
+/// Shows the due-today count in the terminal title, writing only when it
+/// changes. Like the other terminal commands this goes straight to stdout.
+fn update_window_title(app: &mut App) -> io::Result<()> {
+    match app.window_title_update() {
+        Ok(Some(title)) => execute!(io::stdout(), SetTitle(title)),
+        Ok(None) => Ok(()),
+        Err(e) => {
+            app.add_log("ERROR", &format!("Failed to count tasks due today: {e}"));
+            Ok(())
+        }
+    }
+}
+
+/// Shows the log file in the user's editor; editor failures are logged rather
+/// than ending the session.
+fn open_log_file<B: Backend>(app: &mut App, terminal: &mut Terminal<B>) -> Result<(), AppError> {
+    let path = Path::new(crate::log_config::LOG_FILE);
+    if !path.exists() {
+        app.add_log("WARN", &format!("No log file at {}", path.display()));
+        return Ok(());
+    }
+    match crate::common::tui::with_suspended_terminal(terminal, || {
+        crate::common::tui::open_in_editor(path)
+    }) {
+        Ok(status) if status.success() => {}
+        Ok(status) => app.add_log("WARN", &format!("Editor exited with {status}")),
+        Err(e) => {
+            let message = format!("Failed to open log file: {e}");
+            app.add_log("ERROR", &message);
+            app.show_error_popup(message);
+        }
+    }
+    Ok(())
+}
+
+/// Builds the [`App`] on a background thread, animating a splash screen until
+/// the pool and migrations are ready so slow storage never shows a blank screen.
+pub fn load_with_splash<B: Backend>(
+    terminal: &mut Terminal<B>,
+    db_path: String,
+) -> Result<App, AppError> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(App::new(&db_path));
+    });
+
+    let ascii = crate::config::ascii_env_enabled();
+    let mut frame = 0;
+    loop {
+        terminal.draw(|f| draw::draw_splash(f, frame, ascii))?;
+        match receiver.recv_timeout(Duration::from_millis(100)) {
+            Ok(result) => return result,
+            Err(RecvTimeoutError::Timeout) => frame += 1,
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(io::Error::other("task manager failed to start").into())
+            }
+        }
+    }
+}
+
+/// Column and row of the end of `text` once word-wrapped to `width` columns,
+/// matching the popup's `Wrap { trim: true }` rendering. Words longer than a
+/// line are split across lines.
+pub(crate) fn compute_wrapped_cursor(text: &str, width: u16) -> (u16, u16) {
+    if width == 0 {
+        return (0, 0);
+    }
+    let width = width as usize;
+    let (mut col, mut row) = (0, 0);
+
+    for (index, word) in text.split(' ').enumerate() {
+        if index > 0 {
+            if col < width {
+                col += 1;
+            } else {
+                // The separating space starts a new line and is trimmed.
+                row += 1;
+                col = 0;
+            }
+        }
+        let mut len = word.chars().count();
+        if len == 0 {
+            continue;
+        }
+        if col > 0 && col + len > width {
+            row += 1;
+            col = 0;
+        }
+        while col + len > width {
+            len -= width - col;
+            row += 1;
+            col = 0;
+        }
+        col += len;
+    }
+
+    if col >= width {
+        row += 1;
+        col = 0;
+    }
+    (col as u16, row as u16)
+}
+
+/// Style for a topic tab: the topic's own colour when it has a valid one,
+/// otherwise the tasks accent for the active tab and gray for the rest. The
+/// active tab is always bold.
+pub(crate) fn topic_style(topic: &Topic, is_active: bool) -> Style {
+    let style = match topic.color.as_deref().and_then(ui_style::parse_color) {
+        Some(color) => Style::default().fg(color),
+        None if is_active => ui_style::title_style(Accent::Tasks),
+        None => ui_style::muted_style(),
+    };
+    if is_active {
+        style.add_modifier(Modifier::BOLD)
+    } else {
+        style
+    }
+}
+
+/// Short name of `mode` for the status bar.
+pub(crate) fn mode_label(mode: InputMode) -> &'static str {
+    match mode {
+        InputMode::Normal => "Normal",
+        InputMode::CommandPalette => "Command Palette",
+        InputMode::Filtering => "Filtering Tasks",
+        InputMode::AddingTaskName => "Adding Task - Name Input",
+        InputMode::AddingTaskDescription => "Adding Task - Description Input",
+        InputMode::AddingTaskUrl => "Adding Task - URL Input",
+        InputMode::EditingTaskName => "Editing Task - Name Input",
+        InputMode::RenamingTask => "Renaming Task",
+        InputMode::SettingTopicAlias => "Setting Topic Alias",
+        InputMode::SettingTopicTaskLimit => "Setting Topic Task Limit",
+        InputMode::MultilineEditDescription => "Editing Task - Description Input",
+        InputMode::PresetFilters => "Task Presets",
+        InputMode::PresetSpecialFilters => "Special Task Presets",
+        InputMode::SavingPreset => "Saving Task Preset",
+        InputMode::SavingSpecialPreset => "Saving Special Preset",
+        InputMode::DeleteTask | InputMode::ConfirmingDeleteTask => "Delete Task",
+        InputMode::DeleteSpecialTask => "Delete Task",
+        InputMode::DeletingMultipleTasks => "Deleting Multiple Tasks",
+        InputMode::ConfirmingDeleteMultipleTasks => "Delete Tasks",
+        InputMode::AddingTopic => "Adding Topic",
+        InputMode::AddingTopicDescription => "Adding Topic - Description Input",
+        InputMode::AddingSubtask => "Adding Subtask",
+        InputMode::Help => "Viewing Help",
+        InputMode::ViewingSpecialTopics => "Viewing Special Topics",
+        InputMode::FilteringSpecial => "Filtering Special Tasks",
+        InputMode::ConfirmingExit => "Confirm Exit",
+        InputMode::Searching => "Searching Tasks",
+        InputMode::SearchingTopics => "Searching Topics",
+        InputMode::ChoosingClipboard => "Clipboard History",
+        InputMode::MergingTopic => "Merging Topic",
+        InputMode::ViewingTask => "Viewing Task",
+        InputMode::ViewingDueSoon => "Tasks Due Soon",
+        InputMode::ViewingRecent => "Recent Tasks",
+        InputMode::SearchingLogs => "Searching Logs",
+        InputMode::ViewingArchivedTasks => "Archived Tasks",
+        InputMode::AddingLink => "Linking Task",
+        InputMode::ShowingError => "Error",
+        InputMode::ViewingTopicStats => "Topic Statistics",
+    }
+}
+
+/// The three most useful `(key, description)` pairs in `mode`, shown in the
+/// Commands panel; the help popup (`H`) lists everything else.
+pub(crate) fn context_shortcuts(mode: &InputMode) -> &'static [(&'static str, &'static str)] {
+    match mode {
+        InputMode::Normal => &[("a", "add task"), ("d", "delete"), ("t", "toggle")],
+        InputMode::CommandPalette => &[
+            ("Enter", "run command"),
+            ("Up/Down", "move"),
+            ("Esc", "close"),
+        ],
+        InputMode::Filtering | InputMode::FilteringSpecial => &[
+            ("Enter", "keep filter"),
+            ("Esc", "clear"),
+            ("status:", "done/open"),
+        ],
+        InputMode::PresetFilters | InputMode::PresetSpecialFilters => {
+            &[("Enter", "apply"), ("S", "save current"), ("Esc", "close")]
+        }
+        InputMode::SavingPreset | InputMode::SavingSpecialPreset => {
+            &[("Enter", "save preset"), ("Esc", "cancel")]
+        }
+        InputMode::AddingTaskName => &[
+            ("Enter", "next field"),
+            ("Esc", "cancel"),
+            ("Tab", "description"),
+        ],
+        InputMode::AddingTaskDescription => {
+            &[("Enter", "next field"), ("Esc", "cancel"), ("Tab", "name")]
+        }
+        InputMode::AddingTaskUrl => &[("Enter", "create"), ("Esc", "cancel"), ("Tab", "name")],
+        InputMode::EditingTaskName => {
+            &[("Enter", "save"), ("Esc", "cancel"), ("Tab", "description")]
+        }
+        InputMode::RenamingTask => &[("Enter", "rename"), ("Esc", "cancel")],
+        InputMode::SettingTopicAlias | InputMode::SettingTopicTaskLimit => {
+            &[("Enter", "save"), ("Esc", "cancel")]
+        }
+        InputMode::MultilineEditDescription => {
+            &[("Ctrl+S", "save"), ("Esc", "cancel"), ("Tab", "name")]
+        }
+        InputMode::DeleteTask | InputMode::DeleteSpecialTask => {
+            &[("y", "confirm delete"), ("n", "cancel")]
+        }
+        InputMode::ConfirmingDeleteTask => &[("y", "delete with subtasks"), ("n", "cancel")],
+        InputMode::DeletingMultipleTasks => {
+            &[("Space", "check"), ("a/n", "all/none"), ("Enter", "delete")]
+        }
+        InputMode::ConfirmingDeleteMultipleTasks => &[("y", "delete checked"), ("n", "back")],
+        InputMode::AddingTopic => &[("Enter", "next"), ("Esc", "cancel")],
+        InputMode::AddingTopicDescription => &[("Enter", "create"), ("Esc", "cancel")],
+        InputMode::AddingSubtask => &[("Enter", "add"), ("Esc", "cancel")],
+        InputMode::Help => &[("Esc/?", "close help")],
+        InputMode::ViewingSpecialTopics => &[
+            ("Tab", "switch tab"),
+            ("/", "filter"),
+            ("P", "presets"),
+            ("Esc", "close"),
+        ],
+        InputMode::ConfirmingExit => &[("y", "discard and quit"), ("n", "keep editing")],
+        InputMode::Searching => &[
+            ("Enter", "go to task"),
+            ("Up/Down", "move"),
+            ("Esc", "close"),
+        ],
+        InputMode::ChoosingClipboard => &[("Enter", "paste"), ("j/k", "move"), ("Esc", "close")],
+        InputMode::SearchingTopics => &[
+            ("Enter", "go to topic"),
+            ("Up/Down", "move"),
+            ("Esc", "close"),
+        ],
+        InputMode::MergingTopic => &[
+            ("Enter", "merge into topic"),
+            ("j/k", "move"),
+            ("Esc", "cancel"),
+        ],
+        InputMode::ViewingTask => &[("e", "edit"), ("t", "toggle done"), ("Esc", "close")],
+        InputMode::ViewingDueSoon | InputMode::ViewingRecent => {
+            &[("Enter", "go to task"), ("j/k", "move"), ("Esc", "close")]
+        }
+        InputMode::ViewingArchivedTasks => {
+            &[("u", "restore"), ("D", "delete forever"), ("Esc", "close")]
+        }
+        InputMode::SearchingLogs => &[
+            ("type", "filter logs"),
+            ("Enter", "keep filter"),
+            ("Esc", "clear"),
+        ],
+        InputMode::AddingLink => &[("Enter", "link"), ("Tab", "link type"), ("Esc", "cancel")],
+        InputMode::ViewingTopicStats => &[("Any key", "close")],
+        InputMode::ShowingError => &[("Any key", "dismiss")],
+    }
+}
+
+/// The status bar's mode, filter and count sections, e.g. `Normal`,
+/// `Filter: Open` and `5/12 tasks` (passing the filters on every page / in
+/// the topic).
+pub(crate) fn status_bar_sections(app: &App) -> [String; 3] {
+    let mut filter = format!("Filter: {}", app.filter.summary());
+    if app.has_task_filter() {
+        filter.push_str(&format!(" \"{}\"", app.task_filter.trim()));
+    }
+    filter.push_str(&format!(" | Topics: {}", app.config.topic_order.label()));
+    let topic_total = app
+        .current_topic()
+        .map_or(0, |topic| app.topic_task_count(topic));
+    let mut counts = format!("{}/{} tasks", app.task_total, topic_total);
+    let pages = app.page_count();
+    if pages > 1 {
+        counts.push_str(&format!(" | Page {} of {}", app.page_offset + 1, pages));
+    }
+    [mode_label(app.input_mode).to_string(), filter, counts]
+}
+
+/// One-line status bar replacing the old "Mode" panel: mode on the left,
+/// active filters in the centre and task counts on the right.
+pub(crate) fn render_status_bar<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let parts = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 3); 3].as_ref())
+        .split(area);
+    let [mode, filter, counts] = status_bar_sections(app);
+    let sections = [
+        (mode, ui_style::title_style(Accent::Tasks), Alignment::Left),
+        (filter, ui_style::body_style(), Alignment::Center),
+        (counts, ui_style::muted_style(), Alignment::Right),
+    ];
+    for ((text, style, alignment), part) in sections.into_iter().zip(parts) {
+        let section = Paragraph::new(Spans::from(Span::styled(text, style))).alignment(alignment);
+        f.render_widget(section, part);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        compute_wrapped_cursor, context_shortcuts, load_with_splash, status_bar_sections,
+        topic_style,
+    };
+    use crate::db::task_manager::models::Topic;
+    use crate::db::task_manager::models::{CompletionFilter, TopicOrder};
+    use crate::task_manager::app::{App, InputMode};
+    use tui::{
+        backend::TestBackend,
+        style::{Color, Modifier},
+        Terminal,
+    };
+
+    #[test]
+    fn splash_hands_back_the_loaded_app() -> Result<(), Box<dyn std::error::Error>> {
+        let db_path =
+            std::env::temp_dir().join(format!("task_manager_cli_splash_{}.db", std::process::id()));
+        let mut terminal = Terminal::new(TestBackend::new(80, 24))?;
+
+        let app = load_with_splash(&mut terminal, db_path.to_string_lossy().to_string())?;
+        assert!(app.topics.iter().any(|topic| topic.name == "Default"));
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn context_shortcuts_list_at_most_three_keys_per_mode() {
+        assert_eq!(
+            context_shortcuts(&InputMode::Normal),
+            &[("a", "add task"), ("d", "delete"), ("t", "toggle")]
+        );
+        assert_eq!(
+            context_shortcuts(&InputMode::AddingTaskName),
+            &[
+                ("Enter", "next field"),
+                ("Esc", "cancel"),
+                ("Tab", "description")
+            ]
+        );
+        for mode in [
+            InputMode::Filtering,
+            InputMode::ViewingTask,
+            InputMode::MultilineEditDescription,
+            InputMode::ShowingError,
+        ] {
+            let shortcuts = context_shortcuts(&mode);
+            assert!((1..=3).contains(&shortcuts.len()), "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn status_bar_shows_mode_filter_and_visible_counts() -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = std::env::temp_dir().join(format!(
+            "task_manager_cli_status_bar_{}.db",
+            std::process::id()
+        ));
+        let mut app = App::new(&db_path.to_string_lossy())?;
+        app.selected_topic = app
+            .topics
+            .iter()
+            .position(|topic| topic.name == "Default")
+            .expect("default topic should exist");
+        app.add_task_with_details("Alpha", "")?;
+        app.add_task_with_details("Beta", "")?;
+        app.add_task_with_details("Gamma", "")?;
+        app.db_ops.toggle_task_completion(app.tasks[0].id)?;
+        app.load_tasks()?;
+
+        assert_eq!(
+            status_bar_sections(&app),
+            ["Normal", "Filter: All | Topics: Manual", "3/3 tasks"]
+        );
+
+        app.task_filter = "bet".to_string();
+        app.cycle_completion_filter()?;
+        assert_eq!(app.filter.completion, CompletionFilter::Open);
+        app.config.topic_order = TopicOrder::Name;
+        assert_eq!(
+            status_bar_sections(&app),
+            ["Normal", "Filter: Open \"bet\" | Topics: Name", "1/3 tasks"]
+        );
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn topic_style_uses_the_topic_colour_or_falls_back() {
+        let mut topic = Topic {
+            id: 1,
+            name: "Work".to_string(),
+            description: String::new(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            color: Some("#1e90ff".to_string()),
+            position: 0,
+            alias: None,
+            task_limit: None,
+        };
+        let active = topic_style(&topic, true);
+        assert_eq!(active.fg, Some(Color::Rgb(0x1e, 0x90, 0xff)));
+        assert!(active.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(
+            topic_style(&topic, false).fg,
+            Some(Color::Rgb(30, 144, 255))
+        );
+
+        topic.color = Some("#12345".to_string());
+        assert_eq!(topic_style(&topic, true).fg, Some(Color::LightYellow));
+        topic.color = None;
+        assert_eq!(topic_style(&topic, false).fg, Some(Color::Gray));
+    }
+
+    #[test]
+    fn wrapped_cursor_follows_word_wrapping() {
+        let text = "aaaa bbbb cccc dddd eeee ffff gggg hhhh";
+        assert_eq!(text.len(), 39);
+        assert_eq!(compute_wrapped_cursor(text, 20), (19, 1));
+        assert_eq!(compute_wrapped_cursor("short", 20), (5, 0));
+        assert_eq!(compute_wrapped_cursor("", 20), (0, 0));
+        assert_eq!(compute_wrapped_cursor("anything", 0), (0, 0));
+    }
+
+    #[test]
+    fn wrapped_cursor_splits_words_longer_than_the_field() {
+        let text = "x".repeat(40);
+        assert_eq!(compute_wrapped_cursor(&text, 20), (0, 2));
+        assert_eq!(compute_wrapped_cursor(&"y".repeat(45), 20), (5, 2));
+        assert_eq!(
+            compute_wrapped_cursor("ab cdefghijklmnopqrstuvwxyz", 10),
+            (4, 3)
+        );
+    }
 }
@@ -1,7 +1,12 @@
 use crate::common::command_palette;
+use crate::common::logs::{LogEntry, LogLevel};
 use crate::common::widgets;
+use crate::config::{EnterAction, LayoutStyle};
+use crate::db::task_manager::models::{LinkType, LinkedTask, Task, TaskSource, TopicStats};
 use crate::task_manager::app::{App, InputMode};
 use crate::ui_style::{self, Accent, PopupSize};
+use crate::utils::{format_age, format_due, reading_minutes, word_count, DUE_DATE_FORMAT};
+use chrono::NaiveDate;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
@@ -12,6 +17,7 @@ use tui::{
 };
 
 use super::events::visible_task_palette_commands;
+use super::{compute_wrapped_cursor, topic_style};
 
 fn highlighted_spans(text: &str, query: &str, base: Style, highlight: Style) -> Spans<'static> {
     let trimmed = query.trim();
@@ -41,6 +47,31 @@ fn highlighted_spans(text: &str, query: &str, base: Style, highlight: Style) ->
     Spans::from(spans)
 }
 
+/// Renders an FTS5 snippet, drawing the `<b>…</b>` matches in bold yellow.
+pub(crate) fn snippet_spans(snippet: &str, base: Style) -> Spans<'static> {
+    let highlight = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    let mut rest = snippet;
+
+    while let Some(start) = rest.find("<b>") {
+        if start > 0 {
+            spans.push(Span::styled(rest[..start].to_string(), base));
+        }
+        rest = &rest[start + "<b>".len()..];
+        let end = rest.find("</b>").unwrap_or(rest.len());
+        spans.push(Span::styled(rest[..end].to_string(), highlight));
+        rest = rest.get(end + "</b>".len()..).unwrap_or("");
+    }
+
+    if !rest.is_empty() {
+        spans.push(Span::styled(rest.to_string(), base));
+    }
+
+    Spans::from(spans)
+}
+
 fn task_status_spans(task: &crate::db::task_manager::models::Task) -> Spans<'static> {
     let mut spans = vec![Span::styled(
         if task.completed { "DONE " } else { "OPEN " },
@@ -60,44 +91,542 @@ fn task_status_spans(task: &crate::db::task_manager::models::Task) -> Spans<'sta
         ));
     }
 
+    if let Some(due) = task.due_date.as_deref().filter(|_| !task.completed) {
+        let today = chrono::Local::now().date_naive();
+        spans.push(Span::styled(
+            format!("{} ", format_due(due, today).to_uppercase()),
+            due_style(due, today),
+        ));
+    }
+
     spans.push(Span::styled(
-        format!("Updated {}", task.updated_at),
+        format!("Updated {}", format_age(&task.updated_at)),
         ui_style::subtle_style(),
     ));
 
     Spans::from(spans)
 }
 
-pub fn draw_ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+/// Red once the due date has passed, yellow until then.
+fn due_style(due_date: &str, today: NaiveDate) -> Style {
+    let overdue = NaiveDate::parse_from_str(due_date, DUE_DATE_FORMAT).is_ok_and(|due| due < today);
+    if overdue {
+        ui_style::danger_style()
+    } else {
+        ui_style::warning_style()
+    }
+}
+
+/// One log panel row; parts of the message matching `query` are drawn in
+/// yellow.
+fn log_entry_spans(entry: &LogEntry, query: &str) -> Spans<'static> {
+    let level_color = match entry.level {
+        LogLevel::Debug => Color::Gray,
+        LogLevel::Info => Color::Cyan,
+        LogLevel::Warn => Color::Yellow,
+        LogLevel::Error => Color::Red,
+    };
+    let mut spans = vec![
+        Span::raw(format!("{} ", entry.timestamp.format("%Y-%m-%d %H:%M:%S"))),
+        Span::styled(
+            format!("[{}]", entry.level.as_str()),
+            Style::default().fg(level_color),
+        ),
+        Span::raw(" "),
+    ];
+    spans.extend(
+        highlighted_spans(
+            &entry.message,
+            query,
+            Style::default(),
+            Style::default().fg(Color::Yellow),
+        )
+        .0,
+    );
+    Spans::from(spans)
+}
+
+const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+
+/// Pinned, completed and open markers drawn in ASCII mode.
+const ASCII_PINNED: &str = "[P] ";
+const ASCII_DONE: &str = "[X]";
+const ASCII_OPEN: &str = "[ ]";
+const ASCII_LINK: &str = "[L] ";
+const ASCII_SWATCH: &str = "# ";
+
+/// Single-cell glyphs the UI draws (borders, swatches, punctuation) and the
+/// ASCII character each becomes in ASCII mode.
+const ASCII_SYMBOLS: [(&str, &str); 24] = [
+    ("─", "-"),
+    ("━", "-"),
+    ("═", "="),
+    ("│", "|"),
+    ("┃", "|"),
+    ("║", "|"),
+    ("┌", "+"),
+    ("┐", "+"),
+    ("└", "+"),
+    ("┘", "+"),
+    ("╭", "+"),
+    ("╮", "+"),
+    ("╰", "+"),
+    ("╯", "+"),
+    ("╔", "+"),
+    ("╗", "+"),
+    ("╚", "+"),
+    ("╝", "+"),
+    ("├", "+"),
+    ("┤", "+"),
+    ("█", "#"),
+    ("…", "."),
+    ("—", "-"),
+    ("×", "x"),
+];
+
+const fn is_ascii(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] > 0x7f {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const _: () = {
+    assert!(is_ascii(ASCII_PINNED));
+    assert!(is_ascii(ASCII_DONE));
+    assert!(is_ascii(ASCII_OPEN));
+    assert!(is_ascii(ASCII_LINK));
+    assert!(is_ascii(ASCII_SWATCH));
+    let mut i = 0;
+    while i < ASCII_SYMBOLS.len() {
+        assert!(is_ascii(ASCII_SYMBOLS[i].1));
+        i += 1;
+    }
+    let mut i = 0;
+    while i < SPINNER.len() {
+        assert!(SPINNER[i].is_ascii());
+        i += 1;
+    }
+};
+
+/// Final render pass for ASCII mode: swaps the box-drawing borders tui draws
+/// (it has no ASCII border set) and other UI glyphs for ASCII characters.
+struct AsciiSymbols;
+
+impl tui::widgets::Widget for AsciiSymbols {
+    fn render(self, area: Rect, buf: &mut tui::buffer::Buffer) {
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                if let Some((_, ascii)) = ASCII_SYMBOLS
+                    .iter()
+                    .find(|(symbol, _)| *symbol == cell.symbol)
+                {
+                    cell.set_symbol(ascii);
+                }
+            }
+        }
+    }
+}
+
+/// Loading screen shown while the app is initialised; `frame` advances the spinner.
+pub fn draw_splash<B: Backend>(f: &mut Frame<B>, frame: usize, ascii: bool) {
     let size = f.size();
-    let chunks = Layout::default()
+    let rows = Layout::default()
         .direction(Direction::Vertical)
-        .margin(0)
-        .constraints(
-            [
-                Constraint::Length(3),
-                Constraint::Min(5),
-                Constraint::Length(5),
-                Constraint::Length(3),
-                Constraint::Length(15),
-            ]
-            .as_ref(),
-        )
+        .constraints([
+            Constraint::Percentage(50),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
         .split(size);
+    let text = format!(
+        "Loading task_manager_cli… {}",
+        SPINNER[frame % SPINNER.len()]
+    );
+    let splash = Paragraph::new(text)
+        .style(ui_style::title_style(Accent::Tasks))
+        .alignment(tui::layout::Alignment::Center);
+    f.render_widget(splash, rows[1]);
+    if ascii {
+        f.render_widget(AsciiSymbols, size);
+    }
+}
 
-    let titles: Vec<Spans> = if app.topics.is_empty() {
-        vec![Spans::from(Span::styled(
-            "No topics. Press 'N' to add a topic.",
-            Style::default()
-                .fg(Color::Gray)
-                .add_modifier(Modifier::ITALIC),
-        ))]
+fn word_count_spans(text: &str) -> Spans<'static> {
+    let words = word_count(text);
+    Spans::from(Span::styled(
+        format!(
+            "Words: {} | Est. read: ~{} min",
+            words,
+            reading_minutes(words)
+        ),
+        Style::default().fg(Color::Gray),
+    ))
+}
+
+/// Title for the task list block: the topic description (if any) followed by
+/// counts, sort settings and the active filter.
+pub(crate) fn task_list_title(app: &App, shown: usize) -> String {
+    let description = app
+        .topics
+        .get(app.selected_topic)
+        .map(|topic| topic.description.trim())
+        .unwrap_or_default();
+    let heading = if description.is_empty() {
+        "Tasks".to_string()
+    } else if description.chars().count() > 40 {
+        let truncated: String = description.chars().take(39).collect();
+        format!("Tasks — {}…", truncated.trim_end())
+    } else {
+        format!("Tasks — {}", description)
+    };
+
+    let mut title = format!(
+        "{} [shown {} / total {}] | Sort: {} {} | Show: {}",
+        heading,
+        shown,
+        app.tasks.len(),
+        app.sort_mode.label(),
+        if app.sort_ascending { "asc" } else { "desc" },
+        app.filter.summary()
+    );
+    if app.has_task_filter() {
+        title.push_str(&format!(" | Filter: {}", app.task_filter));
+    }
+    // Counted like the limit check: the topic's own tasks, even for Default.
+    if let Some(topic) = app.current_topic() {
+        if let Some(limit) = topic.task_limit {
+            let count = app.task_counts.get(&topic.id).copied().unwrap_or(0);
+            title.push_str(&format!(" | {count}/{limit} tasks"));
+        }
+    }
+    title
+}
+
+/// A task row in the regular (non-compact) list: title, summary and status,
+/// plus details, links and subtasks when expanded.
+fn task_list_item(app: &App, task: &Task) -> ListItem<'static> {
+    let title_style = if task.completed {
+        Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::CROSSED_OUT)
+    } else {
+        Style::default()
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD)
+    };
+    let summary = if task.description.trim().is_empty() {
+        "No description".to_string()
+    } else if task.description.len() > 72 {
+        format!("{}...", &task.description[..72])
     } else {
-        app.topics
+        task.description.clone()
+    };
+    let has_url = task
+        .url
+        .as_deref()
+        .is_some_and(|url| !url.trim().is_empty());
+    let mut title_line = highlighted_spans(
+        &task.name,
+        &app.task_filter,
+        title_style,
+        ui_style::focused_inline_style(),
+    );
+    if has_url {
+        let marker = if app.config.ascii_mode {
+            ASCII_LINK
+        } else {
+            "[🔗] "
+        };
+        title_line.0.insert(0, Span::raw(marker));
+    }
+    if task.pinned {
+        let marker = if app.config.ascii_mode {
+            ASCII_PINNED
+        } else {
+            "📌 "
+        };
+        title_line.0.insert(0, Span::raw(marker));
+    }
+    if let Some(checkbox) = delete_checkbox(app, task.id) {
+        title_line
+            .0
+            .insert(0, Span::styled(checkbox, ui_style::info_style()));
+    }
+    let (done, total) = app.subtask_progress(task.id);
+    if total > 0 {
+        title_line.0.push(Span::styled(
+            format!(" [{done}/{total}]"),
+            ui_style::muted_style(),
+        ));
+    }
+    let lines = if app.expanded.contains(&task.id) {
+        let mut lines = vec![
+            title_line,
+            highlighted_spans(
+                &summary,
+                &app.task_filter,
+                ui_style::info_style(),
+                ui_style::focused_inline_style(),
+            ),
+            task_status_spans(task),
+            word_count_spans(&task.description),
+            Spans::from(Span::styled(
+                format!(
+                    "ID {} | Created {} | Topic {} | Source {}",
+                    task.id,
+                    format_age(&task.created_at),
+                    task.topic_id,
+                    TaskSource::parse(&task.source)
+                        .map_or(task.source.as_str(), |source| { source.label() })
+                ),
+                ui_style::muted_style(),
+            )),
+        ];
+        if let Some(url) = task.url.as_deref().filter(|_| has_url) {
+            lines.push(Spans::from(Span::styled(
+                url.to_string(),
+                Style::default().fg(Color::Cyan),
+            )));
+        }
+        if let Some(links) = app.task_links.get(&task.id) {
+            lines.extend(task_link_lines(links));
+        }
+        for subtask in app.subtasks.get(&task.id).into_iter().flatten() {
+            let (mark, style) = if subtask.completed {
+                (
+                    "x",
+                    ui_style::muted_style().add_modifier(Modifier::CROSSED_OUT),
+                )
+            } else {
+                (" ", Style::default().fg(Color::White))
+            };
+            lines.push(Spans::from(Span::styled(
+                format!("    [{mark}] {}", subtask.name),
+                style,
+            )));
+        }
+        lines
+    } else {
+        vec![
+            title_line,
+            highlighted_spans(
+                &summary,
+                &app.task_filter,
+                ui_style::info_style(),
+                ui_style::focused_inline_style(),
+            ),
+            task_status_spans(task),
+        ]
+    };
+    ListItem::new(lines)
+}
+
+/// `[x] ` or `[ ] ` while picking tasks to delete with `Shift+D`.
+fn delete_checkbox(app: &App, task_id: i32) -> Option<&'static str> {
+    let picking = matches!(
+        app.input_mode,
+        InputMode::DeletingMultipleTasks | InputMode::ConfirmingDeleteMultipleTasks
+    );
+    match (picking, app.selected_tasks.contains(&task_id)) {
+        (false, _) => None,
+        (true, true) => Some("[x] "),
+        (true, false) => Some("[ ] "),
+    }
+}
+
+const COMPLETED_SEPARATOR: &str = "─── Completed ─────────────────";
+
+/// List row the completed divider is drawn at, counted in compact rows when
+/// compact mode is on.
+fn completed_separator_row(app: &App, filtered_indices: &[usize]) -> Option<usize> {
+    let position = app.completed_separator_position(filtered_indices)?;
+    if app.compact_mode {
+        compact_row_groups(app, filtered_indices)
             .iter()
-            .map(|t| Spans::from(Span::raw(&t.name)))
+            .position(|group| group[0] == filtered_indices[position])
+    } else {
+        Some(position)
+    }
+}
+
+/// Task indices per visual row in compact mode: pairs of collapsed tasks with
+/// the same completion state, and expanded tasks on a row of their own.
+pub(crate) fn compact_row_groups(app: &App, filtered_indices: &[usize]) -> Vec<Vec<usize>> {
+    let is_expanded = |index: usize| app.expanded.contains(&app.tasks[index].id);
+    let same_state = |a: usize, b: usize| app.tasks[a].completed == app.tasks[b].completed;
+    let mut groups = Vec::new();
+    let mut rest = filtered_indices;
+    while let Some((&first, tail)) = rest.split_first() {
+        match tail.first() {
+            Some(&second)
+                if !is_expanded(first) && !is_expanded(second) && same_state(first, second) =>
+            {
+                groups.push(vec![first, second]);
+                rest = &tail[1..];
+            }
+            _ => {
+                groups.push(vec![first]);
+                rest = tail;
+            }
+        }
+    }
+    groups
+}
+
+/// One compact cell: a completion marker and the name, cut to leave a
+/// two-column gap and padded to `width`.
+pub(crate) fn compact_cell_text(name: &str, completed: bool, width: u16, ascii: bool) -> String {
+    let marker = match (completed, ascii) {
+        (true, false) => "✓",
+        (false, false) => "○",
+        (true, true) => ASCII_DONE,
+        (false, true) => ASCII_OPEN,
+    };
+    let cap = usize::from(width)
+        .saturating_sub(marker.chars().count() + 3)
+        .max(1);
+    let name = if name.chars().count() > cap {
+        let cut: String = name.chars().take(cap.saturating_sub(1)).collect();
+        format!("{cut}…")
+    } else {
+        name.to_string()
+    };
+    let text = format!("{marker} {name}");
+    format!("{text:<width$}", width = usize::from(width))
+}
+
+fn compact_row_item(app: &App, group: &[usize], half_width: u16) -> ListItem<'static> {
+    let spans: Vec<Span> = group
+        .iter()
+        .map(|index| {
+            let task = &app.tasks[*index];
+            let style = if *index == app.selected {
+                ui_style::selected_style()
+            } else if task.completed {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let name = match delete_checkbox(app, task.id) {
+                Some(checkbox) => format!("{checkbox}{}", task.name),
+                None => task.name.clone(),
+            };
+            Span::styled(
+                compact_cell_text(&name, task.completed, half_width, app.config.ascii_mode),
+                style,
+            )
+        })
+        .collect();
+    ListItem::new(Spans::from(spans))
+}
+
+/// Where `Tabs` places each title inside a bordered `area`: one column of
+/// padding either side of every title and a one-column divider between them.
+fn topic_tab_regions(area: Rect, titles: &[Spans]) -> Vec<Rect> {
+    let inner_right = area.right().saturating_sub(1);
+    let row = area.y + 1;
+    let mut x = area.x + 1;
+    let mut regions = Vec::new();
+    for title in titles {
+        x = x.saturating_add(1);
+        if x >= inner_right {
+            break;
+        }
+        let width = (title.width() as u16).min(inner_right - x);
+        regions.push(Rect::new(x, row, width, 1));
+        x = x.saturating_add(width).saturating_add(2);
+    }
+    regions
+}
+
+/// First 60 characters of the hovered topic's description, just below the tabs.
+fn draw_topic_tooltip<B: Backend>(f: &mut Frame<B>, app: &App, tabs_area: Rect) {
+    let Some(topic) = app
+        .hovered_topic_idx
+        .and_then(|index| app.topics.get(index))
+    else {
+        return;
+    };
+    let description = topic.description.trim();
+    if description.is_empty() {
+        return;
+    }
+    let text: String = description.chars().take(60).collect();
+    let size = f.size();
+    let x = app.mouse_column.min(size.right().saturating_sub(1));
+    let y = tabs_area.bottom();
+    if y >= size.bottom() {
+        return;
+    }
+    let width = (text.chars().count() as u16 + 2).min(size.right() - x);
+    let area = Rect::new(x, y, width, 1);
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(format!(" {text} "))
+            .style(Style::default().fg(Color::Black).bg(Color::Yellow)),
+        area,
+    );
+}
+
+const MIN_WIDTH: u16 = 80;
+const MIN_HEIGHT: u16 = 24;
+
+/// `name (count)`, after a colour swatch when the topic has a colour and
+/// followed by a red `[!N]` when N of its tasks are overdue.
+fn topic_title(app: &App, index: usize) -> Spans<'static> {
+    let topic = &app.topics[index];
+    let mut spans = Vec::new();
+    if let Some(color) = topic.color.as_deref().and_then(ui_style::parse_color) {
+        let swatch = if app.config.ascii_mode {
+            ASCII_SWATCH
+        } else {
+            "█ "
+        };
+        spans.push(Span::styled(swatch, Style::default().fg(color)));
+    }
+    spans.push(Span::styled(
+        format!("{} ({})", topic.name, app.topic_task_count(topic)),
+        topic_style(topic, index == app.selected_topic),
+    ));
+    let overdue = app.topic_overdue_count(topic);
+    if overdue > 0 {
+        spans.push(Span::styled(
+            format!(" [!{overdue}]"),
+            ui_style::danger_style(),
+        ));
+    }
+    Spans::from(spans)
+}
+
+fn no_topics_hint() -> Spans<'static> {
+    Spans::from(Span::styled(
+        "No topics. Press 'N' to add a topic.",
+        Style::default()
+            .fg(Color::Gray)
+            .add_modifier(Modifier::ITALIC),
+    ))
+}
+
+fn draw_topic_tabs<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let titles: Vec<Spans> = if app.topics.is_empty() {
+        vec![no_topics_hint()]
+    } else {
+        (0..app.topics.len())
+            .map(|index| topic_title(app, index))
             .collect()
     };
+    app.layout_regions.topic_tabs = if app.topics.is_empty() {
+        Vec::new()
+    } else {
+        topic_tab_regions(area, &titles)
+    };
     let topic_title = format!("Topics [{}]", app.topics.len());
     let tabs = Tabs::new(titles)
         .select(if app.topics.is_empty() {
@@ -106,14 +635,114 @@ pub fn draw_ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
             app.selected_topic
         })
         .block(ui_style::surface_block(&topic_title, Accent::Tasks))
-        .highlight_style(ui_style::title_style(Accent::Tasks))
+        // Titles carry their own styles; a highlight colour would hide the
+        // active topic's colour.
+        .highlight_style(Style::default())
         .divider(Span::raw("|"));
-    f.render_widget(tabs, chunks[0]);
+    f.render_widget(tabs, area);
+}
+
+/// One topic per row for [`LayoutStyle::SidebarLeft`]. The border dims while
+/// the task list has focus.
+fn draw_topic_sidebar<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let items: Vec<ListItem> = if app.topics.is_empty() {
+        vec![ListItem::new(no_topics_hint())]
+    } else {
+        (0..app.topics.len())
+            .map(|index| ListItem::new(topic_title(app, index)))
+            .collect()
+    };
+    let title = format!("Topics [{}]", app.topics.len());
+    let mut block = ui_style::surface_block(&title, Accent::Tasks);
+    if !app.sidebar_focused {
+        block = block.border_style(ui_style::muted_style());
+    }
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(if app.sidebar_focused {
+            ui_style::selected_style()
+        } else {
+            Style::default()
+        })
+        .highlight_symbol("> ");
+    let mut state = ListState::default();
+    if !app.topics.is_empty() {
+        state.select(Some(app.selected_topic));
+    }
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+pub fn draw_ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    draw_screen(f, app);
+    if app.config.ascii_mode {
+        f.render_widget(AsciiSymbols, f.size());
+    }
+}
+
+fn draw_screen<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let size = f.size();
+    app.terminal_too_small = size.width < MIN_WIDTH || size.height < MIN_HEIGHT;
+    if app.terminal_too_small {
+        app.layout_regions = Default::default();
+        app.hovered_topic_idx = None;
+        let warning = Paragraph::new(format!(
+            "Terminal too small (need {}×{})",
+            MIN_WIDTH, MIN_HEIGHT
+        ))
+        .style(Style::default().fg(Color::White).bg(Color::Red))
+        .wrap(Wrap { trim: true });
+        f.render_widget(warning, size);
+        return;
+    }
+    let sidebar = app.config.layout == LayoutStyle::SidebarLeft;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(0)
+        .constraints(
+            [
+                // The sidebar layout draws topics beside the tasks instead.
+                Constraint::Length(if sidebar { 0 } else { 3 }),
+                Constraint::Min(5),
+                Constraint::Length(4),
+                Constraint::Length(1),
+                // The log search bar borrows three extra rows.
+                Constraint::Length(if app.input_mode == InputMode::SearchingLogs {
+                    18
+                } else {
+                    15
+                }),
+            ]
+            .as_ref(),
+        )
+        .split(size);
+    let task_area = if sidebar {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(20), Constraint::Percentage(80)].as_ref())
+            .split(chunks[1]);
+        draw_topic_sidebar(f, app, columns[0]);
+        columns[1]
+    } else {
+        chunks[1]
+    };
+    app.layout_regions.chunks = [chunks[0], task_area, chunks[2], chunks[3], chunks[4]];
+
+    if sidebar {
+        app.layout_regions.topic_tabs = Vec::new();
+        app.hovered_topic_idx = None;
+    } else {
+        draw_topic_tabs(f, app, chunks[0]);
+    }
 
     let filtered_indices = app.filtered_task_indices();
     let items: Vec<ListItem> = if app.tasks.is_empty() {
+        let hint = if app.topic_switch_from.is_some() {
+            "Loading tasks..."
+        } else {
+            "No tasks in this topic. Press 'a' to add a task."
+        };
         vec![ListItem::new(vec![Spans::from(Span::styled(
-            "No tasks in this topic. Press 'a' to add a task.",
+            hint,
             Style::default()
                 .fg(Color::Gray)
                 .add_modifier(Modifier::ITALIC),
@@ -126,255 +755,146 @@ pub fn draw_ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
                 .add_modifier(Modifier::ITALIC),
         ))])]
     } else {
-        filtered_indices
+        let groups: Vec<Vec<usize>> = if app.compact_mode {
+            compact_row_groups(app, &filtered_indices)
+        } else {
+            filtered_indices.iter().map(|index| vec![*index]).collect()
+        };
+        let half_width = task_area.width.saturating_sub(5) / 2;
+        let mut items: Vec<ListItem> = groups
             .iter()
-            .map(|task_index| {
-                let task = &app.tasks[*task_index];
-                let title_style = if task.completed {
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::CROSSED_OUT)
-                } else {
-                    Style::default()
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD)
-                };
-                let summary = if task.description.trim().is_empty() {
-                    "No description".to_string()
-                } else if task.description.len() > 72 {
-                    format!("{}...", &task.description[..72])
-                } else {
-                    task.description.clone()
-                };
-                let lines = if app.expanded.contains(&task.id) {
-                    vec![
-                        highlighted_spans(
-                            &task.name,
-                            &app.task_filter,
-                            title_style,
-                            ui_style::focused_inline_style(),
-                        ),
-                        highlighted_spans(
-                            &summary,
-                            &app.task_filter,
-                            ui_style::info_style(),
-                            ui_style::focused_inline_style(),
-                        ),
-                        task_status_spans(task),
-                        Spans::from(Span::styled(
-                            format!(
-                                "ID {} | Created {} | Topic {}",
-                                task.id, task.created_at, task.topic_id
-                            ),
-                            ui_style::muted_style(),
-                        )),
-                    ]
-                } else {
-                    vec![
-                        highlighted_spans(
-                            &task.name,
-                            &app.task_filter,
-                            title_style,
-                            ui_style::focused_inline_style(),
-                        ),
-                        highlighted_spans(
-                            &summary,
-                            &app.task_filter,
-                            ui_style::info_style(),
-                            ui_style::focused_inline_style(),
-                        ),
-                        task_status_spans(task),
-                    ]
-                };
-                ListItem::new(lines)
+            .map(|group| match group.as_slice() {
+                [index] if !app.compact_mode || app.expanded.contains(&app.tasks[*index].id) => {
+                    task_list_item(app, &app.tasks[*index])
+                }
+                _ => compact_row_item(app, group, half_width),
             })
-            .collect()
+            .collect();
+        if let Some(row) = completed_separator_row(app, &filtered_indices) {
+            items.insert(
+                row,
+                ListItem::new(Span::styled(
+                    COMPLETED_SEPARATOR,
+                    Style::default().fg(Color::DarkGray),
+                )),
+            );
+        }
+        items
     };
 
-    let tasks_title = if app.has_task_filter() {
-        format!(
-            "Tasks [shown {} / total {}] | Filter: {}",
-            filtered_indices.len(),
-            app.tasks.len(),
-            app.task_filter
-        )
-    } else {
-        format!(
-            "Tasks [shown {} / total {}]",
-            filtered_indices.len(),
-            app.tasks.len()
-        )
-    };
+    // Compact rows style the selected half themselves.
+    let compact_selection = app.compact_mode
+        && app
+            .current_task()
+            .is_some_and(|task| !app.expanded.contains(&task.id));
+    let tasks_title = task_list_title(app, filtered_indices.len());
     let tasks_list = List::new(items)
         .block(ui_style::surface_block(&tasks_title, Accent::Tasks))
-        .highlight_style(ui_style::selected_style())
+        .highlight_style(if compact_selection {
+            Style::default()
+        } else {
+            ui_style::selected_style()
+        })
         .highlight_symbol("=> ");
 
     let mut list_state = ListState::default();
     if !filtered_indices.is_empty() {
+        let separator = completed_separator_row(app, &filtered_indices);
         list_state.select(
-            filtered_indices
-                .iter()
-                .position(|index| *index == app.selected),
+            if app.compact_mode {
+                compact_row_groups(app, &filtered_indices)
+                    .iter()
+                    .position(|group| group.contains(&app.selected))
+            } else {
+                filtered_indices
+                    .iter()
+                    .position(|index| *index == app.selected)
+            }
+            .map(|row| row + usize::from(separator.is_some_and(|separator| row >= separator))),
         );
     }
-    f.render_stateful_widget(tasks_list, chunks[1], &mut list_state);
-
-    let command_lines =
-        match app.input_mode {
-            InputMode::Normal => vec![
-                ui_style::command_bar_spans(&[
-                    ("a", "add task"),
-                    ("A", "add topic"),
-                    ("e", "edit"),
-                    ("d", "delete"),
-                ]),
-                ui_style::command_bar_spans(&[
-                    ("Enter", "expand"),
-                    ("Space", "done"),
-                    ("/", "filter"),
-                    ("p", "presets"),
-                    (":", "palette"),
-                ]),
-                ui_style::command_bar_spans(&[
-                    ("S", "special"),
-                    ("f", "favorite"),
-                    ("H", "help"),
-                    ("q", "quit"),
-                ]),
-            ],
-            InputMode::Filtering => vec![
-                Spans::from(vec![
-                    Span::raw("Query "),
-                    Span::styled(
-                        app.task_filter.clone(),
-                        ui_style::title_style(Accent::Tasks),
-                    ),
-                ]),
-                ui_style::command_bar_spans(&[
-                    ("Enter", "keep filter"),
-                    ("Esc", "clear"),
-                    ("status:", "done/open"),
-                    ("topic:", "topic"),
-                    ("fav:", "favorite"),
-                ]),
-            ],
-            InputMode::CommandPalette => vec![
-                Spans::from(vec![
-                    Span::raw("Palette "),
-                    Span::styled(
-                        app.command_palette_query.clone(),
-                        ui_style::title_style(Accent::Tasks),
-                    ),
-                ]),
-                ui_style::command_bar_spans(&[
-                    ("Enter", "run command"),
-                    ("j/k", "move"),
-                    ("Backspace", "edit query"),
-                    ("Esc", "close"),
-                ]),
-            ],
-            InputMode::AddingTopic => vec![
-                Spans::from(vec![
-                    Span::raw("Topic "),
-                    Span::styled(app.input.clone(), ui_style::title_style(Accent::Tasks)),
-                ]),
-                ui_style::command_bar_spans(&[("Enter", "create"), ("Esc", "cancel")]),
-            ],
-            InputMode::ViewingSpecialTopics => vec![
-                ui_style::command_bar_spans(&[
-                    ("Tab", "switch tab"),
-                    ("/", "filter"),
-                    ("p", "presets"),
-                    ("d", "delete"),
-                    ("f", "favorite"),
-                ]),
-                ui_style::command_bar_spans(&[(":", "palette"), ("Esc", "close"), ("H", "help")]),
-            ],
-            InputMode::FilteringSpecial => vec![
-                Spans::from(vec![
-                    Span::raw("Special "),
-                    Span::styled(
-                        app.special_task_filter.clone(),
-                        ui_style::title_style(Accent::Tasks),
-                    ),
-                ]),
-                ui_style::command_bar_spans(&[
-                    ("Enter", "keep filter"),
-                    ("Esc", "clear"),
-                    ("status:", "done/open"),
-                    ("fav:", "favorite"),
-                ]),
-            ],
-            InputMode::PresetFilters | InputMode::PresetSpecialFilters => {
-                vec![ui_style::command_bar_spans(&[
-                    ("Enter", "apply"),
-                    ("S", "save current"),
-                    ("x", "delete saved"),
-                    ("Esc", "close"),
-                ])]
-            }
-            InputMode::SavingPreset | InputMode::SavingSpecialPreset => vec![
-                ui_style::command_bar_spans(&[("Enter", "save preset"), ("Esc", "cancel")]),
-            ],
-            InputMode::DeleteTask | InputMode::DeleteSpecialTask => vec![
-                ui_style::command_bar_spans(&[("y", "confirm delete"), ("n", "cancel")]),
-            ],
-            InputMode::Help => vec![ui_style::command_bar_spans(&[("Esc", "close help")])],
-            InputMode::AddingTaskName
-            | InputMode::AddingTaskDescription
-            | InputMode::EditingTaskName
-            | InputMode::EditingTaskDescription => vec![ui_style::command_bar_spans(&[
-                ("Tab", "switch field"),
-                ("Enter", "save"),
-                ("Esc", "cancel"),
-            ])],
-        };
+    f.render_stateful_widget(tasks_list, task_area, &mut list_state);
+
+    // Text-entry modes echo what has been typed above their shortcuts.
+    let typed = match app.input_mode {
+        InputMode::Filtering => Some(("Query ", &app.task_filter)),
+        InputMode::FilteringSpecial => Some(("Special ", &app.special_task_filter)),
+        InputMode::Searching => Some(("Search ", &app.search_query)),
+        InputMode::SearchingTopics => Some(("Topics ", &app.topic_search_query)),
+        InputMode::CommandPalette => Some(("Palette ", &app.command_palette_query)),
+        InputMode::AddingTopic => Some(("Topic ", &app.input)),
+        InputMode::AddingSubtask => Some(("Subtask ", &app.input)),
+        _ => None,
+    };
+    let mut command_lines = Vec::new();
+    if let Some((label, text)) = typed {
+        command_lines.push(Spans::from(vec![
+            Span::raw(label),
+            Span::styled(text.clone(), ui_style::title_style(Accent::Tasks)),
+        ]));
+    }
+    command_lines.push(ui_style::command_bar_spans(super::context_shortcuts(
+        &app.input_mode,
+    )));
     let help_message = Paragraph::new(command_lines)
         .style(ui_style::info_style())
-        .block(ui_style::command_bar_block("Commands"));
+        .block(ui_style::command_bar_block(
+            if app.input_mode == InputMode::Normal {
+                "Commands - H for help"
+            } else {
+                "Commands"
+            },
+        ));
     f.render_widget(help_message, chunks[2]);
 
-    let mode_text = match app.input_mode {
-        InputMode::Normal => "Normal Mode",
-        InputMode::CommandPalette => "Command Palette",
-        InputMode::Filtering => "Filtering Tasks",
-        InputMode::AddingTaskName => "Adding Task - Name Input",
-        InputMode::AddingTaskDescription => "Adding Task - Description Input",
-        InputMode::EditingTaskName => "Editing Task - Name Input",
-        InputMode::EditingTaskDescription => "Editing Task - Description Input",
-        InputMode::PresetFilters => "Task Presets",
-        InputMode::PresetSpecialFilters => "Special Task Presets",
-        InputMode::SavingPreset => "Saving Task Preset",
-        InputMode::SavingSpecialPreset => "Saving Special Preset",
-        InputMode::DeleteTask => "Delete Task",
-        InputMode::DeleteSpecialTask => "Delete Task",
-        InputMode::AddingTopic => "Adding Topic",
-        InputMode::Help => "Viewing Help",
-        InputMode::ViewingSpecialTopics => "Viewing Special Topics",
-        InputMode::FilteringSpecial => "Filtering Special Tasks",
-    };
-    let mode = Paragraph::new(mode_text)
-        .style(ui_style::body_style())
-        .block(ui_style::shell_block("Mode"));
-    f.render_widget(mode, chunks[3]);
+    super::render_status_bar(f, app, chunks[3]);
 
-    let log_area_height = chunks[4].height as usize;
-    let total_logs = app.logs.len();
+    let (log_area, log_search_area) = if app.input_mode == InputMode::SearchingLogs {
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(3)].as_ref())
+            .split(chunks[4]);
+        (parts[0], Some(parts[1]))
+    } else {
+        (chunks[4], None)
+    };
+    let log_area_height = log_area.height as usize;
+    let logs = app.visible_logs();
+    let total_logs = logs.len();
     let start = if total_logs > log_area_height + app.log_offset {
         total_logs - log_area_height - app.log_offset
     } else {
         0
     };
-    let visible_logs: Vec<ListItem> = app.logs[start..]
+    let visible_logs: Vec<ListItem> = logs[start..]
         .iter()
-        .map(|line| ListItem::new(Span::raw(line)))
+        .map(|entry| ListItem::new(log_entry_spans(entry, &app.log_search_query)))
         .collect();
-    let logs_list = List::new(visible_logs).block(ui_style::shell_block("Logs"));
-    f.render_widget(logs_list, chunks[4]);
+    let mut logs_title = if app.log_min_level == LogLevel::Debug {
+        "Logs".to_string()
+    } else {
+        format!("Logs (>= {})", app.log_min_level.as_str())
+    };
+    if app.log_search_active() {
+        logs_title.push_str(&format!(" - {} match(es)", total_logs));
+    }
+    let mut logs_block = ui_style::shell_block(&logs_title);
+    if app.log_panel_alert_active() {
+        logs_block = logs_block.border_style(Style::default().fg(Color::Red));
+    }
+    let logs_list = List::new(visible_logs).block(logs_block);
+    f.render_widget(logs_list, log_area);
+    if let Some(area) = log_search_area {
+        let search_bar = Paragraph::new(format!("/{}", app.log_search_query))
+            .block(ui_style::shell_block("Search logs"));
+        f.render_widget(search_bar, area);
+    }
+
+    draw_topic_tooltip(f, app, chunks[0]);
 
     if app.input_mode == InputMode::Help {
-        let help_text = get_help_text();
+        let mut help_text = get_help_text(app.config.enter_action);
+        help_text.extend(topic_alias_help_lines(app));
         let help_paragraph = Paragraph::new(help_text)
             .block(ui_style::popup_block("Help", Accent::Tasks))
             .style(Style::default().fg(Color::White).bg(Color::Black))
@@ -388,15 +908,34 @@ pub fn draw_ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
         app.input_mode,
         InputMode::AddingTaskName
             | InputMode::AddingTaskDescription
+            | InputMode::AddingTaskUrl
             | InputMode::EditingTaskName
-            | InputMode::EditingTaskDescription
+            | InputMode::MultilineEditDescription
     ) {
         draw_add_task_popup(f, app);
     }
 
-    if app.input_mode == InputMode::DeleteTask {
+    if app.input_mode == InputMode::AddingTopicDescription {
+        draw_add_topic_popup(f, app);
+    }
+
+    if matches!(
+        app.input_mode,
+        InputMode::DeleteTask | InputMode::ConfirmingDeleteTask
+    ) {
         draw_delete_popup(f, app);
     }
+    if app.input_mode == InputMode::ConfirmingDeleteMultipleTasks {
+        widgets::draw_confirmation_popup(
+            f,
+            f.size(),
+            Accent::Tasks,
+            "Delete Tasks",
+            "Delete Confirmation",
+            &format!("Delete {} tasks? y/n", app.selected_tasks.len()),
+            "Press [Y] to confirm deletion or [N] to go back",
+        );
+    }
 
     if matches!(
         app.input_mode,
@@ -412,16 +951,574 @@ pub fn draw_ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
         draw_task_presets_popup(f, app, false);
     }
 
-    if matches!(
-        app.input_mode,
-        InputMode::SavingPreset | InputMode::SavingSpecialPreset
-    ) {
-        draw_save_task_preset_popup(f, app);
-    }
+    if matches!(
+        app.input_mode,
+        InputMode::SavingPreset | InputMode::SavingSpecialPreset
+    ) {
+        draw_save_task_preset_popup(f, app);
+    }
+
+    if app.input_mode == InputMode::Searching {
+        draw_search_popup(f, app, size);
+    }
+    if app.input_mode == InputMode::SearchingTopics {
+        draw_topic_search_popup(f, app, size);
+    }
+    if app.input_mode == InputMode::RenamingTask {
+        draw_rename_task_popup(f, app);
+    }
+    if app.input_mode == InputMode::SettingTopicAlias {
+        draw_topic_alias_popup(f, app);
+    }
+    if app.input_mode == InputMode::SettingTopicTaskLimit {
+        draw_topic_task_limit_popup(f, app);
+    }
+    if app.input_mode == InputMode::ChoosingClipboard {
+        draw_clipboard_popup(f, app, size);
+    }
+    if app.input_mode == InputMode::MergingTopic {
+        draw_merge_topic_popup(f, app, size);
+    }
+    if app.input_mode == InputMode::ViewingTask {
+        draw_task_detail(f, app, size);
+    }
+    if app.input_mode == InputMode::ViewingDueSoon {
+        draw_due_soon_popup(f, app, size);
+    }
+    if app.input_mode == InputMode::ViewingRecent {
+        draw_recent_popup(f, app, size);
+    }
+    if app.input_mode == InputMode::ViewingArchivedTasks {
+        draw_archive_popup(f, app, size);
+    }
+    if app.input_mode == InputMode::AddingLink {
+        draw_add_link_popup(f, app, size);
+    }
+    if app.input_mode == InputMode::ViewingTopicStats {
+        if let Some(stats) = &app.topic_stats {
+            draw_topic_stats_popup(f, stats, size);
+        }
+    }
+
+    if app.input_mode == InputMode::CommandPalette {
+        draw_command_palette_popup(f, app, size);
+    }
+    if app.input_mode == InputMode::ConfirmingExit {
+        widgets::draw_confirmation_popup(
+            f,
+            size,
+            Accent::Tasks,
+            "Quit",
+            "Unsaved Input",
+            "You have unsaved task input. Discard and quit?",
+            "Press [Y] to discard and quit or [N] to keep editing",
+        );
+    }
+    if let Some(message) = app.error_popup.as_deref() {
+        draw_error_popup(f, message, size);
+    }
+}
+
+/// Red-bordered popup for an error the user needs to see; any key closes it.
+fn draw_error_popup<B: Backend>(f: &mut Frame<B>, message: &str, size: Rect) {
+    let popup_area = ui_style::popup_rect(PopupSize::Standard, size);
+    f.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Spans::from(Span::styled(message.to_string(), ui_style::body_style())),
+        Spans::default(),
+        Spans::from(Span::styled(
+            "Press any key to dismiss",
+            ui_style::muted_style(),
+        )),
+    ];
+    let popup = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        ui_style::popup_block("Error", Accent::Tasks)
+            .border_style(Style::default().fg(Color::LightRed)),
+    );
+    f.render_widget(popup, popup_area);
+}
+
+/// Full-screen view of every field on the selected task.
+fn draw_task_detail<B: Backend>(f: &mut Frame<B>, app: &App, size: Rect) {
+    let Some(task) = app.current_task() else {
+        return;
+    };
+    let field = |label: &str, value: String| {
+        Spans::from(vec![
+            Span::styled(format!("{label:<10}"), ui_style::muted_style()),
+            Span::styled(value, ui_style::body_style()),
+        ])
+    };
+    let topic = app
+        .topics
+        .iter()
+        .find(|topic| topic.id == task.topic_id)
+        .map_or_else(|| task.topic_id.to_string(), |topic| topic.name.clone());
+
+    let mut lines = vec![
+        Spans::from(Span::styled(
+            task.name.clone(),
+            ui_style::title_style(Accent::Tasks).add_modifier(Modifier::BOLD),
+        )),
+        task_status_spans(task),
+        Spans::default(),
+        field("ID", task.id.to_string()),
+        field("Topic", topic),
+        field("Pinned", if task.pinned { "Yes" } else { "No" }.to_string()),
+        field(
+            "Due",
+            task.due_date.as_deref().map_or_else(
+                || "-".to_string(),
+                |due| {
+                    format!(
+                        "{} ({})",
+                        due,
+                        format_due(due, chrono::Local::now().date_naive())
+                    )
+                },
+            ),
+        ),
+        field(
+            "URL",
+            task.url
+                .clone()
+                .filter(|url| !url.trim().is_empty())
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+        field(
+            "Created",
+            format!("{} ({})", task.created_at, format_age(&task.created_at)),
+        ),
+        field(
+            "Updated",
+            format!("{} ({})", task.updated_at, format_age(&task.updated_at)),
+        ),
+        word_count_spans(&task.description),
+        Spans::default(),
+    ];
+    if task.description.trim().is_empty() {
+        lines.push(Spans::from(Span::styled(
+            "No description",
+            ui_style::muted_style(),
+        )));
+    } else {
+        lines.extend(
+            task.description
+                .lines()
+                .map(|line| Spans::from(Span::styled(line.to_string(), ui_style::body_style()))),
+        );
+    }
+
+    lines.push(Spans::default());
+    lines.push(ui_style::command_bar_spans(&[
+        ("e", "edit"),
+        ("d", "delete"),
+        ("t", "toggle done"),
+        ("f", "favourite"),
+        ("j/k", "next/prev"),
+        ("Esc", "close"),
+    ]));
+
+    let detail = Paragraph::new(lines)
+        .block(ui_style::popup_block("Task", Accent::Tasks))
+        .wrap(Wrap { trim: false });
+    f.render_widget(Clear, size);
+    f.render_widget(detail, size);
+}
+
+fn draw_due_soon_popup<B: Backend>(f: &mut Frame<B>, app: &mut App, size: Rect) {
+    let today = chrono::Local::now().date_naive();
+    let items: Vec<ListItem> = if app.due_soon_tasks.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "Nothing due. Enjoy the quiet.",
+            ui_style::muted_style(),
+        ))]
+    } else {
+        app.due_soon_tasks
+            .iter()
+            .map(|task| {
+                let topic = app
+                    .topics
+                    .iter()
+                    .find(|topic| topic.id == task.topic_id)
+                    .map_or("?", |topic| topic.name.as_str());
+                let due = task.due_date.as_deref().unwrap_or_default();
+                ListItem::new(vec![
+                    Spans::from(vec![
+                        Span::styled(format!("[{topic}] "), ui_style::muted_style()),
+                        Span::styled(task.name.clone(), ui_style::title_style(Accent::Tasks)),
+                    ]),
+                    Spans::from(Span::styled(
+                        format!("{} ({})", due, format_due(due, today)),
+                        due_style(due, today),
+                    )),
+                ])
+            })
+            .collect()
+    };
+    let title = format!("Tasks due within {} days", app.config.due_soon_days);
+    let selected = (!app.due_soon_tasks.is_empty()).then_some(app.due_soon_selected);
+    widgets::draw_list_popup(
+        f,
+        size,
+        PopupSize::Wide,
+        Accent::Tasks,
+        &title,
+        items,
+        selected,
+    );
+}
+
+fn draw_recent_popup<B: Backend>(f: &mut Frame<B>, app: &mut App, size: Rect) {
+    let recent = app.recent_cache.as_deref().unwrap_or_default();
+    let items: Vec<ListItem> = if recent.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "No tasks yet.",
+            ui_style::muted_style(),
+        ))]
+    } else {
+        recent
+            .iter()
+            .map(|(task, topic)| {
+                ListItem::new(vec![
+                    Spans::from(vec![
+                        Span::styled(format!("[{}] ", topic.name), ui_style::muted_style()),
+                        Span::styled(task.name.clone(), ui_style::title_style(Accent::Tasks)),
+                    ]),
+                    Spans::from(Span::styled(
+                        format!(
+                            "Updated {}",
+                            task.updated_at.as_str().max(task.created_at.as_str())
+                        ),
+                        ui_style::muted_style(),
+                    )),
+                ])
+            })
+            .collect()
+    };
+    let selected = (!recent.is_empty()).then_some(app.recent_selected);
+    widgets::draw_list_popup(
+        f,
+        size,
+        PopupSize::Wide,
+        Accent::Tasks,
+        "Recently touched",
+        items,
+        selected,
+    );
+}
+
+fn draw_archive_popup<B: Backend>(f: &mut Frame<B>, app: &mut App, size: Rect) {
+    let items: Vec<ListItem> = if app.archived_tasks.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "No archived tasks.",
+            ui_style::muted_style(),
+        ))]
+    } else {
+        app.archived_tasks
+            .iter()
+            .map(|task| {
+                let topic_name = app
+                    .topics
+                    .iter()
+                    .find(|topic| topic.id == task.topic_id)
+                    .map_or("?", |topic| topic.name.as_str());
+                ListItem::new(vec![
+                    Spans::from(vec![
+                        Span::styled(format!("[{topic_name}] "), ui_style::muted_style()),
+                        Span::styled(task.name.clone(), ui_style::title_style(Accent::Tasks)),
+                    ]),
+                    Spans::from(Span::styled(
+                        format!("Archived {}", task.updated_at),
+                        ui_style::muted_style(),
+                    )),
+                ])
+            })
+            .collect()
+    };
+    let selected = (!app.archived_tasks.is_empty()).then_some(app.archived_selected);
+    widgets::draw_list_popup(
+        f,
+        size,
+        PopupSize::Wide,
+        Accent::Tasks,
+        "Archived tasks",
+        items,
+        selected,
+    );
+}
+
+/// Label/value rows for the topic statistics popup.
+fn topic_stats_rows(stats: &TopicStats) -> Vec<(&'static str, String)> {
+    let none = || "-".to_string();
+    let description = if stats.topic.description.is_empty() {
+        none()
+    } else {
+        stats.topic.description.clone()
+    };
+    vec![
+        ("Topic", stats.topic.name.clone()),
+        ("Description", description),
+        (
+            "Created",
+            stats
+                .topic
+                .created_at
+                .get(..10)
+                .unwrap_or(&stats.topic.created_at)
+                .to_string(),
+        ),
+        ("Tasks", stats.total.to_string()),
+        ("Completed", stats.completed.to_string()),
+        ("In progress", stats.in_progress.to_string()),
+        ("Overdue", stats.overdue.to_string()),
+        (
+            "Latest created",
+            stats.latest_created.clone().unwrap_or_else(none),
+        ),
+        (
+            "Latest completed",
+            stats.latest_completed.clone().unwrap_or_else(none),
+        ),
+        (
+            "Most active day",
+            stats
+                .most_active_day
+                .as_ref()
+                .map_or_else(none, |(day, count)| format!("{day} ({count} updates)")),
+        ),
+    ]
+}
+
+/// Two-column table of topic statistics with alternating row shading.
+fn draw_topic_stats_popup<B: Backend>(f: &mut Frame<B>, stats: &TopicStats, size: Rect) {
+    let area = ui_style::popup_rect(PopupSize::Tall, size);
+    f.render_widget(Clear, area);
+
+    let row_width = area.width.saturating_sub(2) as usize;
+    let lines: Vec<Spans> = topic_stats_rows(stats)
+        .into_iter()
+        .enumerate()
+        .map(|(index, (label, value))| {
+            let background = if index % 2 == 0 {
+                Color::Rgb(48, 48, 48)
+            } else {
+                Color::Rgb(28, 28, 28)
+            };
+            let label = format!(" {label:<18}");
+            let value = format!(
+                "{value:<width$}",
+                width = row_width.saturating_sub(label.len())
+            );
+            Spans::from(vec![
+                Span::styled(label, ui_style::muted_style().bg(background)),
+                Span::styled(value, ui_style::body_style().bg(background)),
+            ])
+        })
+        .collect();
+
+    let title = format!("Topic Statistics: {}", stats.topic.name);
+    let popup = Paragraph::new(lines).block(ui_style::popup_block(&title, Accent::Tasks));
+    f.render_widget(popup, area);
+}
+
+fn draw_add_link_popup<B: Backend>(f: &mut Frame<B>, app: &mut App, size: Rect) {
+    let source = app
+        .current_task()
+        .map(|task| task.name.clone())
+        .unwrap_or_default();
+    let items: Vec<ListItem> = app
+        .link_candidates()
+        .iter()
+        .map(|task| {
+            ListItem::new(Span::styled(
+                task.name.clone(),
+                ui_style::title_style(Accent::Tasks),
+            ))
+        })
+        .collect();
+    widgets::draw_list_popup(
+        f,
+        size,
+        PopupSize::Standard,
+        Accent::Tasks,
+        &format!(
+            "'{}' {}… (Tab to change)",
+            source,
+            app.link_type_selected.label().to_lowercase()
+        ),
+        items,
+        Some(app.link_target_selected),
+    );
+}
+
+/// "Blocked by: A, B" style lines for the expanded task view, one per link type.
+fn task_link_lines(links: &[LinkedTask]) -> Vec<Spans<'static>> {
+    [LinkType::BlockedBy, LinkType::Blocks, LinkType::RelatesTo]
+        .into_iter()
+        .filter_map(|link_type| {
+            let names: Vec<&str> = links
+                .iter()
+                .filter(|linked| linked.link_type == link_type)
+                .map(|linked| linked.name.as_str())
+                .collect();
+            if names.is_empty() {
+                return None;
+            }
+            let blocked = link_type == LinkType::BlockedBy
+                && links
+                    .iter()
+                    .any(|linked| linked.link_type == link_type && !linked.completed);
+            Some(Spans::from(vec![
+                Span::styled(
+                    format!("{}: ", link_type.label()),
+                    if blocked {
+                        ui_style::warning_style()
+                    } else {
+                        ui_style::muted_style()
+                    },
+                ),
+                Span::raw(names.join(", ")),
+            ]))
+        })
+        .collect()
+}
+
+fn draw_merge_topic_popup<B: Backend>(f: &mut Frame<B>, app: &mut App, size: Rect) {
+    let source = app
+        .topics
+        .get(app.selected_topic)
+        .map(|topic| topic.name.clone())
+        .unwrap_or_default();
+    let items: Vec<ListItem> = app
+        .merge_candidates()
+        .iter()
+        .map(|topic| {
+            ListItem::new(Span::styled(
+                topic.name.clone(),
+                ui_style::title_style(Accent::Tasks),
+            ))
+        })
+        .collect();
+    widgets::draw_list_popup(
+        f,
+        size,
+        PopupSize::Standard,
+        Accent::Tasks,
+        &format!("Merge '{}' into… (Enter merge, Esc cancel)", source),
+        items,
+        Some(app.merge_target_selected),
+    );
+}
+
+fn draw_search_popup<B: Backend>(f: &mut Frame<B>, app: &mut App, size: Rect) {
+    let items: Vec<ListItem> = if app.search_results.is_empty() {
+        let message = if app.search_query.trim().is_empty() {
+            "Type to search task names and descriptions"
+        } else {
+            "No matching tasks"
+        };
+        vec![ListItem::new(Span::styled(
+            message,
+            ui_style::muted_style(),
+        ))]
+    } else {
+        app.search_results
+            .iter()
+            .map(|(task, snippet)| {
+                ListItem::new(vec![
+                    Spans::from(Span::styled(
+                        task.name.clone(),
+                        ui_style::title_style(Accent::Tasks),
+                    )),
+                    snippet_spans(snippet, ui_style::muted_style()),
+                ])
+            })
+            .collect()
+    };
+    let scope = if app.search_topic_id().is_some() {
+        "this topic"
+    } else {
+        "all topics"
+    };
+    let title = format!("Search {}: {}", scope, app.search_query);
+    let selected = (!app.search_results.is_empty()).then_some(app.search_selected);
+    widgets::draw_list_popup(
+        f,
+        size,
+        PopupSize::Wide,
+        Accent::Tasks,
+        &title,
+        items,
+        selected,
+    );
+}
+
+/// `Ctrl+Y`: task names yanked this session, newest first.
+fn draw_clipboard_popup<B: Backend>(f: &mut Frame<B>, app: &App, size: Rect) {
+    let items: Vec<ListItem> = if app.clipboard_history.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "Nothing yanked yet; 'y' copies the selected task name",
+            ui_style::muted_style(),
+        ))]
+    } else {
+        app.clipboard_history
+            .iter()
+            .map(|entry| ListItem::new(Span::styled(entry.clone(), ui_style::body_style())))
+            .collect()
+    };
+    let selected = (!app.clipboard_history.is_empty()).then_some(app.clipboard_selected);
+    widgets::draw_list_popup(
+        f,
+        size,
+        PopupSize::Standard,
+        Accent::Tasks,
+        "Clipboard History",
+        items,
+        selected,
+    );
+}
 
-    if app.input_mode == InputMode::CommandPalette {
-        draw_command_palette_popup(f, app, size);
-    }
+/// `Ctrl+F`: topics matching the query, each with its task count.
+fn draw_topic_search_popup<B: Backend>(f: &mut Frame<B>, app: &App, size: Rect) {
+    let items: Vec<ListItem> = if app.topic_search_results.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "No matching topics",
+            ui_style::muted_style(),
+        ))]
+    } else {
+        app.topic_search_results
+            .iter()
+            .map(|topic| {
+                let mut lines = vec![Spans::from(vec![
+                    Span::styled(topic.name.clone(), ui_style::title_style(Accent::Tasks)),
+                    Span::styled(
+                        format!("  {} tasks", app.topic_task_count(topic)),
+                        ui_style::muted_style(),
+                    ),
+                ])];
+                if !topic.description.is_empty() {
+                    lines.push(Spans::from(Span::styled(
+                        topic.description.clone(),
+                        ui_style::muted_style(),
+                    )));
+                }
+                ListItem::new(lines)
+            })
+            .collect()
+    };
+    let title = format!("Search topics: {}", app.topic_search_query);
+    let selected = (!app.topic_search_results.is_empty()).then_some(app.topic_search_selected);
+    widgets::draw_list_popup(
+        f,
+        size,
+        PopupSize::Wide,
+        Accent::Tasks,
+        &title,
+        items,
+        selected,
+    );
 }
 
 fn draw_command_palette_popup<B: Backend>(f: &mut Frame<B>, app: &mut App, size: Rect) {
@@ -436,6 +1533,57 @@ fn draw_command_palette_popup<B: Backend>(f: &mut Frame<B>, app: &mut App, size:
     );
 }
 
+/// Second step of topic creation: the name is fixed, the description is being
+/// typed.
+fn draw_add_topic_popup<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let size = f.size();
+    let popup_area = ui_style::popup_rect(PopupSize::Standard, size);
+    f.render_widget(Clear, popup_area);
+
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(3),
+        ])
+        .split(popup_area);
+
+    let popup_title = Paragraph::new("Create New Topic")
+        .style(ui_style::title_style(Accent::Tasks))
+        .block(ui_style::popup_block("Topic Editor", Accent::Tasks));
+    f.render_widget(popup_title, popup_layout[0]);
+
+    let name_input = Paragraph::new(app.topic_name_input.as_str())
+        .style(ui_style::muted_style())
+        .block(ui_style::popup_block("Topic Name", Accent::Tasks));
+    f.render_widget(name_input, popup_layout[1]);
+
+    let desc_input = Paragraph::new(app.input.as_str())
+        .style(ui_style::title_style(Accent::Tasks))
+        .block(ui_style::popup_block(
+            "Topic Description (optional)",
+            Accent::Tasks,
+        ))
+        .wrap(Wrap { trim: true });
+    f.render_widget(desc_input, popup_layout[2]);
+
+    let instructions = Paragraph::new(
+        "Enter a description or leave empty, then press Enter to create. (Esc to cancel)",
+    )
+    .style(ui_style::body_style())
+    .block(ui_style::popup_block("Instructions", Accent::Tasks));
+    f.render_widget(instructions, popup_layout[3]);
+
+    let (cursor_x, cursor_y) =
+        compute_wrapped_cursor(&app.input, popup_layout[2].width.saturating_sub(2));
+    f.set_cursor(
+        popup_layout[2].x + 1 + cursor_x,
+        popup_layout[2].y + 1 + cursor_y,
+    );
+}
+
 fn draw_add_task_popup<B: Backend>(f: &mut Frame<B>, app: &mut App) {
     let size = f.size();
     let popup_area = ui_style::popup_rect(PopupSize::Standard, size);
@@ -450,13 +1598,14 @@ fn draw_add_task_popup<B: Backend>(f: &mut Frame<B>, app: &mut App) {
             Constraint::Min(5),
             Constraint::Length(3),
             Constraint::Length(3),
+            Constraint::Length(3),
         ])
         .split(popup_area);
 
     let popup_title = Paragraph::new(
         if matches!(
             app.input_mode,
-            InputMode::EditingTaskName | InputMode::EditingTaskDescription
+            InputMode::EditingTaskName | InputMode::MultilineEditDescription
         ) {
             "Edit Task"
         } else {
@@ -483,27 +1632,54 @@ fn draw_add_task_popup<B: Backend>(f: &mut Frame<B>, app: &mut App) {
 
     let desc_input_style = if matches!(
         app.input_mode,
-        InputMode::AddingTaskDescription | InputMode::EditingTaskDescription
+        InputMode::AddingTaskDescription | InputMode::MultilineEditDescription
     ) {
         ui_style::title_style(Accent::Tasks)
     } else {
         ui_style::muted_style()
     };
 
-    let desc_input = Paragraph::new(app.task_description_input.as_ref())
-        .style(desc_input_style)
-        .block(ui_style::popup_block("Task Description", Accent::Tasks))
-        .wrap(Wrap { trim: true });
-    f.render_widget(desc_input, popup_layout[3]);
+    let desc_block = ui_style::popup_block("Task Description", Accent::Tasks);
+    let desc_input = if app.input_mode == InputMode::MultilineEditDescription {
+        let lines: Vec<Spans> = app
+            .ml_editor_lines
+            .iter()
+            .map(|line| Spans::from(line.as_str()))
+            .collect();
+        Paragraph::new(lines).wrap(Wrap { trim: false })
+    } else {
+        Paragraph::new(app.task_description_input.as_ref()).wrap(Wrap { trim: true })
+    };
+    f.render_widget(
+        desc_input.style(desc_input_style).block(desc_block),
+        popup_layout[3],
+    );
+
+    let url_input_style = if app.input_mode == InputMode::AddingTaskUrl {
+        ui_style::title_style(Accent::Tasks)
+    } else {
+        ui_style::muted_style()
+    };
+    let url_input = Paragraph::new(app.task_url_input.as_ref())
+        .style(url_input_style)
+        .block(ui_style::popup_block("Task URL (optional)", Accent::Tasks));
+    f.render_widget(url_input, popup_layout[4]);
 
     let instructions = match app.input_mode {
-        InputMode::AddingTaskName => "Enter task name and press Enter to continue. (Esc to cancel)",
+        InputMode::AddingTaskName => {
+            "Enter task name and press Enter or Tab to continue. (Esc to cancel)"
+        }
         InputMode::AddingTaskDescription => {
-            "Enter task description and press Enter to save. (Tab to edit name, Esc to cancel)"
+            "Enter task description and press Enter to continue. (Tab to edit name, Esc to cancel)"
+        }
+        InputMode::AddingTaskUrl => {
+            "Enter an http(s) URL or leave empty, then press Enter to save. (Esc to cancel)"
         }
-        InputMode::EditingTaskName => "Edit task name and press Enter to continue. (Esc to cancel)",
-        InputMode::EditingTaskDescription => {
-            "Edit task description and press Enter to save. (Tab to edit name, Esc to cancel)"
+        InputMode::EditingTaskName => {
+            "Edit task name and press Enter or Tab to continue. (Esc to cancel)"
+        }
+        InputMode::MultilineEditDescription => {
+            "Edit the description; Enter adds a line, Ctrl+S saves. (Tab to edit name, Esc to cancel)"
         }
         _ => "",
     };
@@ -520,12 +1696,12 @@ fn draw_add_task_popup<B: Backend>(f: &mut Frame<B>, app: &mut App) {
     let feedback = Paragraph::new(feedback_text)
         .style(feedback_style)
         .block(ui_style::popup_block("Feedback", Accent::Tasks));
-    f.render_widget(feedback, popup_layout[4]);
+    f.render_widget(feedback, popup_layout[5]);
 
     let instructions_text = Paragraph::new(instructions)
         .style(ui_style::body_style())
         .block(ui_style::popup_block("Instructions", Accent::Tasks));
-    f.render_widget(instructions_text, popup_layout[5]);
+    f.render_widget(instructions_text, popup_layout[6]);
 
     if matches!(
         app.input_mode,
@@ -535,19 +1711,52 @@ fn draw_add_task_popup<B: Backend>(f: &mut Frame<B>, app: &mut App) {
             popup_layout[1].x + app.task_name_input.len() as u16 + 1,
             popup_layout[1].y + 1,
         );
-    } else if matches!(
-        app.input_mode,
-        InputMode::AddingTaskDescription | InputMode::EditingTaskDescription
-    ) {
+    } else if app.input_mode == InputMode::MultilineEditDescription {
+        let area = popup_layout[3];
+        let (col, row) = multiline_cursor(
+            &app.ml_editor_lines,
+            app.ml_editor_cursor,
+            area.width.saturating_sub(2),
+        );
+        f.set_cursor(
+            area.x + 1 + col,
+            area.y + 1 + row.min(area.height.saturating_sub(3)),
+        );
+    } else if app.input_mode == InputMode::AddingTaskDescription {
+        let area = popup_layout[3];
+        let (col, row) =
+            compute_wrapped_cursor(&app.task_description_input, area.width.saturating_sub(2));
+        f.set_cursor(
+            area.x + 1 + col,
+            area.y + 1 + row.min(area.height.saturating_sub(3)),
+        );
+    } else if app.input_mode == InputMode::AddingTaskUrl {
         f.set_cursor(
-            popup_layout[3].x + app.task_description_input.len() as u16 + 1,
-            popup_layout[3].y + 1,
+            popup_layout[4].x + app.task_url_input.len() as u16 + 1,
+            popup_layout[4].y + 1,
         );
     }
 }
 
+/// Screen column and row of the editor cursor once each line is wrapped to
+/// `width` columns.
+fn multiline_cursor(lines: &[String], (row, col): (usize, usize), width: u16) -> (u16, u16) {
+    let rows_above: u16 = lines[..row]
+        .iter()
+        .map(|line| match compute_wrapped_cursor(line, width) {
+            // A line that exactly fills its last row leaves the cursor on
+            // the next one.
+            (0, rows) if rows > 0 => rows,
+            (_, rows) => rows + 1,
+        })
+        .sum();
+    let before_cursor: String = lines[row].chars().take(col).collect();
+    let (col, wrapped_row) = compute_wrapped_cursor(&before_cursor, width);
+    (col, rows_above + wrapped_row)
+}
+
 fn draw_delete_popup<B: Backend>(f: &mut Frame<B>, app: &mut App) {
-    let task_name = if let Some(task) = app.tasks.get(app.selected) {
+    let task_name = if let Some(task) = app.current_task() {
         &task.name
     } else {
         "Unknown Task"
@@ -558,11 +1767,21 @@ fn draw_delete_popup<B: Backend>(f: &mut Frame<B>, app: &mut App) {
         Accent::Tasks,
         "Delete Task",
         "Delete Confirmation",
-        &format!("Are you sure you want to delete \"{}\"?", task_name),
+        &delete_confirmation_message(task_name, app.delete_subtask_count),
         "Press [Y] to confirm deletion or [N] to cancel",
     );
 }
 
+fn delete_confirmation_message(task_name: &str, subtask_count: usize) -> String {
+    if subtask_count == 0 {
+        format!("Are you sure you want to delete \"{task_name}\"?")
+    } else {
+        format!(
+            "This task has {subtask_count} subtask(s). Deleting it will also delete all subtasks. Confirm? (y/n)"
+        )
+    }
+}
+
 fn build_help_line(
     title: &'static str,
     key: &'static str,
@@ -577,7 +1796,12 @@ fn build_help_line(
     ])
 }
 
-pub fn get_help_text() -> Vec<Spans<'static>> {
+/// Every Normal-mode shortcut; the Enter and Space lines follow `enter_action`.
+pub fn get_help_text(enter_action: EnterAction) -> Vec<Spans<'static>> {
+    let (expand_key, toggle_key) = match enter_action {
+        EnterAction::ExpandTask => ("Enter", "Space or 't'"),
+        EnterAction::ToggleComplete => ("Space", "Enter or 't'"),
+    };
     vec![
         Spans::from(Span::styled(
             "Help - Available Operations",
@@ -592,27 +1816,131 @@ pub fn get_help_text() -> Vec<Spans<'static>> {
         build_help_line(
             "Filter Tasks:",
             "'/'",
-            "filter live. Supports status:done, topic:work, fav:true, quoted phrases, and -negation.",
+            "filter live. Supports status:done, topic:work, fav:true, source:csv, quoted phrases, and -negation.",
+        ),
+        build_help_line(
+            "Search Tasks:",
+            "'F'",
+            "full-text search names and descriptions; built-in topics search everywhere.",
+        ),
+        build_help_line(
+            "Search Topics:",
+            "Ctrl+F",
+            "find a topic by name or description and switch to its tab.",
+        ),
+        build_help_line("Task Presets:", "'P'", "open saved preset filters for quick reuse."),
+        build_help_line("Copy Task Name:", "'y'", "copy the selected task name to the clipboard."),
+        build_help_line(
+            "Clipboard History:",
+            "Ctrl+Y",
+            "pick an earlier yank; pastes into the open input or jumps to that task.",
         ),
-        build_help_line("Task Presets:", "'p'", "open saved preset filters for quick reuse."),
+        build_help_line("Paste Task:", "'p'", "start a new task named from the clipboard."),
         build_help_line(
             "Edit Task:",
             "'e'",
-            "opens the same two-field form used for task creation.",
+            "edit the name, then the description in a multi-line editor (Ctrl+S saves).",
+        ),
+        build_help_line("Rename Task:", "'R'", "change only the task name in a small popup."),
+        build_help_line("Toggle Complete:", toggle_key, "to mark a task complete/incomplete."),
+        build_help_line("View Task:", "'v'", "show every field of the task full-screen."),
+        build_help_line("Recent Tasks:", "r", "jump to one of the ten most recently touched tasks."),
+        build_help_line(
+            "Archive:",
+            "Ctrl+R",
+            "archived tasks; u restores one, Shift+D deletes it for good.",
         ),
-        build_help_line("Toggle Complete:", "'t'", "to mark a task complete/incomplete."),
+        build_help_line("Due Soon:", "Ctrl+U", "list open tasks due in the next few days."),
+        build_help_line("Topic Statistics:", "'i'", "task counts and activity for this topic."),
         build_help_line("Toggle Favourite:", "'f'", "to mark/unmark as favourite."),
+        build_help_line("Toggle Pin:", "Ctrl+P", "keep the task at the top of the list."),
+        build_help_line(
+            "Completion Bell:",
+            "Ctrl+B",
+            "toggle the terminal bell on completion (TASK_MANAGER_NO_BELL=1 mutes it).",
+        ),
+        build_help_line("Open Task URL:", "'o'", "open the selected task's link in the browser."),
+        build_help_line(
+            "Sort Tasks:",
+            "'s' / 'S'",
+            "cycle sort mode / flip direction (saved per topic).",
+        ),
+        build_help_line(
+            "Completion Filter:",
+            "'c'",
+            "cycle all/open/done tasks (saved per topic).",
+        ),
         build_help_line("Delete Task:", "'d'", "to delete the selected task."),
-        build_help_line("Expand/Collapse Task:", "Enter", "to toggle details."),
-        build_help_line("Navigate Tasks:", "Up/Down or j/k", "to move between tasks."),
+        build_help_line(
+            "Delete Several:",
+            "Shift+D",
+            "check tasks with Space ('a' all, 'n' none), then Enter deletes them.",
+        ),
+        build_help_line("Expand/Collapse Task:", expand_key, "to toggle details."),
+        build_help_line("Compact View:", "'z'", "show two tasks per row, names only."),
+        build_help_line(
+            "Navigate Tasks:",
+            "Up/Down or j/k",
+            "to move between tasks; past the first or last row turns the page.",
+        ),
+        build_help_line("Turn Page:", "Ctrl+PageUp/PageDown", "jump a full page of tasks."),
+        build_help_line(
+            "Jump:",
+            "gg / G / 10G",
+            "to the first, last, or numbered task in the list.",
+        ),
         build_help_line("Switch Topics:", "Left/Right or h/l", "to change topics."),
+        build_help_line(
+            "Topic Sidebar:",
+            "h / l, then j / k",
+            "with the sidebar layout, focus the topics or tasks; j/k then change topic.",
+        ),
+        build_help_line(
+            "Reorder Topics:",
+            "Shift+Left/Right",
+            "move the current topic tab left or right.",
+        ),
+        build_help_line(
+            "Topic Order:",
+            "Ctrl+O",
+            "order the tabs manually, by name, or by creation date.",
+        ),
+        build_help_line(
+            "Reorder Tasks:",
+            "Shift+Up/Down",
+            "move the selected task while the topic uses manual sort.",
+        ),
         build_help_line("Add Topic:", "'N'", "to add a new topic."),
+        build_help_line("Add Subtask:", "Ctrl+S", "to add a subtask to the selected task."),
+        build_help_line(
+            "Link Task:",
+            "Ctrl+K",
+            "to mark the selected task as blocking, blocked by or related to another.",
+        ),
+        build_help_line(
+            "Merge Topic:",
+            "'M' or Ctrl+M",
+            "move the current topic's tasks into another topic and remove it.",
+        ),
         build_help_line(
             "Delete Topic:",
             "'X'",
             "to delete the current topic (Favourites is protected).",
         ),
-        build_help_line("Scroll Logs:", "PageUp/PageDown", "to scroll logs."),
+        build_help_line(
+            "Scroll Logs:",
+            "PageUp/PageDown",
+            "to scroll logs, or the mouse wheel over the log panel.",
+        ),
+        build_help_line("Suspend:", "Ctrl+Z", "return to the shell; resume with `fg`."),
+        build_help_line("Log Level:", "Ctrl+L", "cycle the minimum level shown in the log panel."),
+        build_help_line(
+            "Log Search:",
+            "Ctrl+/",
+            "filter the log panel by text; Esc clears the search.",
+        ),
+        build_help_line("Open Log File:", "Ctrl+E", "view the full log in $VISUAL/$EDITOR."),
+        build_help_line("Vacuum Database:", "Ctrl+V", "compact the database file."),
         build_help_line(
             "Open Favourites/Completed:",
             "Shift+W",
@@ -634,11 +1962,41 @@ pub fn get_help_text() -> Vec<Spans<'static>> {
             "toggle complete/favourite, delete, or expand in popup.",
         ),
         build_help_line("Close Popup:", "Esc", "close the Favourites/Completed window."),
-        build_help_line("Toggle Help:", "'H'", "to show/hide help."),
-        build_help_line("Quit:", "'q'", "to exit the application."),
+        build_help_line("Toggle Help:", "'H' or '?'", "to show/hide help."),
+        build_help_line(
+            "Quit:",
+            "'q' / Ctrl+Q",
+            "to exit; asks first when a form has unsaved input.",
+        ),
     ]
 }
 
+/// A "Topic Aliases" section listing each alias and its topic, or nothing
+/// when no topic has one.
+fn topic_alias_help_lines(app: &App) -> Vec<Spans<'static>> {
+    let aliases: Vec<Span> = app
+        .topics
+        .iter()
+        .filter_map(|topic| Some((topic.alias_char()?, topic.name.clone())))
+        .flat_map(|(alias, name)| {
+            [
+                Span::raw(" "),
+                Span::styled(format!("'{alias}'"), ui_style::title_style(Accent::Tasks)),
+                Span::styled(format!(" {name}"), ui_style::body_style()),
+            ]
+        })
+        .collect();
+    if aliases.is_empty() {
+        return Vec::new();
+    }
+    let mut line = vec![Span::styled(
+        "Topic Aliases:",
+        ui_style::info_style().add_modifier(Modifier::BOLD),
+    )];
+    line.extend(aliases);
+    vec![Spans::from(""), Spans::from(line)]
+}
+
 fn draw_special_topics_popup<B: Backend>(f: &mut Frame<B>, app: &mut App) {
     let size = f.size();
     let popup_area = ui_style::popup_rect(PopupSize::Full, size);
@@ -702,8 +2060,8 @@ fn draw_special_topics_popup<B: Backend>(f: &mut Frame<B>, app: &mut App) {
                                 task.id,
                                 if task.completed { "Yes" } else { "No" },
                                 if task.favourite { "Yes" } else { "No" },
-                                task.created_at,
-                                task.updated_at
+                                format_age(&task.created_at),
+                                format_age(&task.updated_at)
                             ),
                             ui_style::muted_style(),
                         )),
@@ -872,3 +2230,352 @@ fn draw_save_task_preset_popup<B: Backend>(f: &mut Frame<B>, app: &mut App) {
         app.preset_form_message.is_some(),
     );
 }
+
+/// `R`: a single name field pre-filled with the task's current name.
+fn draw_rename_task_popup<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let size = f.size();
+    let feedback = app
+        .task_form_message
+        .as_deref()
+        .unwrap_or("Enter to rename, Esc to cancel");
+    widgets::draw_text_input_popup(
+        f,
+        size,
+        PopupSize::Compact,
+        Accent::Tasks,
+        "Rename",
+        "Rename Task",
+        "Task Name",
+        app.rename_input.as_str(),
+        feedback,
+        app.task_form_message.is_some(),
+    );
+}
+
+/// A one-key field for the selected topic's jump alias.
+fn draw_topic_alias_popup<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let size = f.size();
+    let topic_name = app
+        .current_topic()
+        .map(|topic| topic.name.as_str())
+        .unwrap_or_default();
+    let feedback = app
+        .topic_setting_message
+        .as_deref()
+        .unwrap_or("Enter to save (empty clears), Esc to cancel");
+    widgets::draw_text_input_popup(
+        f,
+        size,
+        PopupSize::Compact,
+        Accent::Tasks,
+        "Alias",
+        &format!("Alias for {topic_name}"),
+        "Key",
+        app.topic_setting_input.as_str(),
+        feedback,
+        app.topic_setting_message.is_some(),
+    );
+}
+
+/// A number field for the most tasks the selected topic may hold.
+fn draw_topic_task_limit_popup<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let size = f.size();
+    let topic_name = app
+        .current_topic()
+        .map(|topic| topic.name.as_str())
+        .unwrap_or_default();
+    let feedback = app
+        .topic_setting_message
+        .as_deref()
+        .unwrap_or("Enter to save (empty removes the limit), Esc to cancel");
+    widgets::draw_text_input_popup(
+        f,
+        size,
+        PopupSize::Compact,
+        Accent::Tasks,
+        "Task Limit",
+        &format!("Task limit for {topic_name}"),
+        "Tasks",
+        app.topic_setting_input.as_str(),
+        feedback,
+        app.topic_setting_message.is_some(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        compact_cell_text, compact_row_groups, delete_confirmation_message, draw_ui, snippet_spans,
+        task_list_title, topic_tab_regions, AsciiSymbols,
+    };
+    use crate::config::LayoutStyle;
+    use crate::task_manager::app::App;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use tui::{backend::TestBackend, layout::Rect, text::Spans, Terminal};
+
+    fn temp_db_path(prefix: &str) -> PathBuf {
+        let unique = format!(
+            "{}_{}_{}",
+            prefix,
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system clock before unix epoch")
+                .as_nanos()
+        );
+        std::env::temp_dir().join(format!("task_manager_cli_task_draw_{unique}.db"))
+    }
+
+    #[test]
+    fn task_list_title_shows_truncated_topic_description() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let db_path = temp_db_path("list_title");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+
+        assert!(task_list_title(&app, 0).starts_with("Tasks — All tasks [shown 0 / total 0]"));
+        app.topics[app.selected_topic].description.clear();
+        assert!(task_list_title(&app, 0).starts_with("Tasks [shown 0 / total 0]"));
+
+        app.topics[app.selected_topic].description =
+            "Everything that needs doing before the quarterly planning review".to_string();
+        let title = task_list_title(&app, 0);
+        assert!(title.starts_with("Tasks — Everything that needs doing before the… [shown"));
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 40))?;
+        terminal.draw(|f| draw_ui(f, &mut app))?;
+        assert!(!app.terminal_too_small);
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn task_list_title_shows_the_topic_task_limit() -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("list_title_limit");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        app.add_task_with_details("One", "")?;
+        assert!(!task_list_title(&app, 1).contains(" | 1/"));
+
+        app.set_topic_task_limit(Some(5))?;
+        assert!(task_list_title(&app, 1).ends_with(" | 1/5 tasks"));
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn small_terminal_suspends_normal_rendering() -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("small_terminal");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 20))?;
+        terminal.draw(|f| draw_ui(f, &mut app))?;
+        assert!(app.terminal_too_small);
+
+        terminal.backend_mut().resize(100, 30);
+        terminal.resize(Rect::new(0, 0, 100, 30))?;
+        terminal.draw(|f| draw_ui(f, &mut app))?;
+        assert!(!app.terminal_too_small);
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn topic_tab_regions_follow_tabs_padding_and_dividers() {
+        let titles = vec![Spans::from("ab"), Spans::from("cde"), Spans::from("f")];
+        let regions = topic_tab_regions(Rect::new(0, 0, 12, 3), &titles);
+        assert_eq!(regions, vec![Rect::new(2, 1, 2, 1), Rect::new(7, 1, 3, 1),]);
+    }
+
+    #[test]
+    fn hovering_a_topic_tab_tracks_the_topic() -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("topic_tooltip");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 40))?;
+        terminal.draw(|f| draw_ui(f, &mut app))?;
+        assert_eq!(app.layout_regions.topic_tabs.len(), app.topics.len());
+
+        let tab = app.layout_regions.topic_tabs[0];
+        app.on_mouse_moved(tab.x + 1, tab.y);
+        assert_eq!(app.hovered_topic_idx, Some(0));
+        assert_eq!(app.mouse_column, tab.x + 1);
+        terminal.draw(|f| draw_ui(f, &mut app))?;
+
+        app.on_mouse_moved(tab.x + 1, tab.y + 5);
+        assert_eq!(app.hovered_topic_idx, None);
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn sidebar_layout_lists_topics_left_of_the_tasks() -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("sidebar_layout");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        app.config.layout = LayoutStyle::SidebarLeft;
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 40))?;
+        terminal.draw(|f| draw_ui(f, &mut app))?;
+        let [tabs, tasks, ..] = app.layout_regions.chunks;
+        assert_eq!(tabs.height, 0);
+        assert!(app.layout_regions.topic_tabs.is_empty());
+        assert_eq!((tasks.x, tasks.y), (20, 0));
+
+        let buffer = terminal.backend().buffer();
+        let row: String = (0..tasks.x)
+            .map(|x| buffer.get(x, 1).symbol.as_str())
+            .collect();
+        assert!(row.contains(&app.topics[0].name), "{row:?}");
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn mouse_wheel_scrolls_logs_only_over_the_log_panel() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let db_path = temp_db_path("log_wheel");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 40))?;
+        terminal.draw(|f| draw_ui(f, &mut app))?;
+        let [_, tasks, _, _, logs] = app.layout_regions.chunks;
+        assert_eq!(logs.y + logs.height, 40);
+
+        app.on_mouse_scroll(tasks.x + 1, tasks.y + 1, true);
+        assert_eq!(app.log_offset, 0);
+        assert!(!app.log_panel_focused);
+
+        app.on_mouse_scroll(logs.x + 1, logs.y + 1, true);
+        app.on_mouse_scroll(logs.x + 1, logs.y + 1, true);
+        app.on_mouse_scroll(logs.x + 1, logs.y + 1, false);
+        assert_eq!(app.log_offset, 1);
+        assert!(app.log_panel_focused);
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn compact_cells_are_marked_cut_and_padded() {
+        assert_eq!(
+            compact_cell_text("Buy milk", false, 14, false),
+            "○ Buy milk    "
+        );
+        assert_eq!(
+            compact_cell_text("Buy milk", true, 14, true),
+            "[X] Buy milk  "
+        );
+        assert_eq!(
+            compact_cell_text("Renew passport", true, 12, false),
+            "✓ Renew p…  "
+        );
+    }
+
+    #[test]
+    fn compact_rows_pair_tasks_but_keep_expanded_ones_alone(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("compact_rows");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        app.add_topic("Errands")?;
+        app.selected_topic = app
+            .topics
+            .iter()
+            .position(|topic| topic.name == "Errands")
+            .expect("topic should exist");
+        for name in ["One", "Two", "Three", "Four", "Five"] {
+            app.add_task_with_details(name, "")?;
+        }
+        let indices: Vec<usize> = (0..app.tasks.len()).collect();
+        assert_eq!(
+            compact_row_groups(&app, &indices),
+            vec![vec![0, 1], vec![2, 3], vec![4]]
+        );
+
+        app.expanded.insert(app.tasks[1].id);
+        assert_eq!(
+            compact_row_groups(&app, &indices),
+            vec![vec![0], vec![1], vec![2, 3], vec![4]]
+        );
+
+        app.toggle_compact_mode();
+        assert!(app.compact_mode);
+        let mut terminal = Terminal::new(TestBackend::new(120, 40))?;
+        terminal.draw(|f| draw_ui(f, &mut app))?;
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn snippet_spans_highlight_marked_matches() {
+        let spans = snippet_spans("…the <b>budget</b> draft", tui::style::Style::default());
+        let parts: Vec<(&str, bool)> = spans
+            .0
+            .iter()
+            .map(|span| {
+                (
+                    span.content.as_ref(),
+                    span.style.fg == Some(tui::style::Color::Yellow),
+                )
+            })
+            .collect();
+        assert_eq!(
+            parts,
+            vec![("…the ", false), ("budget", true), (" draft", false)]
+        );
+    }
+
+    #[test]
+    fn delete_confirmation_mentions_subtasks_when_there_are_any() {
+        assert_eq!(
+            delete_confirmation_message("Report", 0),
+            "Are you sure you want to delete \"Report\"?"
+        );
+        assert_eq!(
+            delete_confirmation_message("Report", 3),
+            "This task has 3 subtask(s). Deleting it will also delete all subtasks. Confirm? (y/n)"
+        );
+    }
+
+    #[test]
+    fn ascii_pass_replaces_borders_and_swatches() {
+        let area = Rect::new(0, 0, 3, 1);
+        let mut buffer = tui::buffer::Buffer::empty(area);
+        for (x, symbol) in ["┌", "─", "█"].into_iter().enumerate() {
+            buffer.get_mut(x as u16, 0).set_symbol(symbol);
+        }
+        tui::widgets::Widget::render(AsciiSymbols, area, &mut buffer);
+        let symbols: Vec<&str> = (0..3).map(|x| buffer.get(x, 0).symbol.as_str()).collect();
+        assert_eq!(symbols, ["+", "-", "#"]);
+    }
+
+    #[test]
+    fn ascii_mode_draws_only_ascii() -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("ascii_mode_draw");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        app.config.ascii_mode = true;
+        app.add_task_with_details("Pinned report", "")?;
+        app.toggle_pin()?;
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 30))?;
+        terminal.draw(|f| draw_ui(f, &mut app))?;
+        let buffer = terminal.backend().buffer();
+        assert!(buffer.content().iter().all(|cell| cell.symbol.is_ascii()));
+
+        drop(app);
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+}
@@ -1,14 +1,46 @@
 use crate::common::command_palette::{visible_commands, PaletteCommand};
+use crate::config::{EnterAction, LayoutStyle};
+use crate::error::AppError;
 use crate::task_manager::app::{App, InputMode};
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 
 pub enum UiAction {
     Continue,
     Exit,
+    Suspend,
+    OpenLogFile,
 }
 
-fn log_ui_error(app: &mut App, context: &str, error: &dyn std::error::Error) {
-    app.add_log("ERROR", &format!("{context}: {error}"));
+fn log_ui_error(app: &mut App, context: &str, error: &AppError) {
+    match error {
+        AppError::ProtectedTopic(name) => app.add_log(
+            "WARN",
+            &format!("'{name}' is a built-in topic and cannot be changed"),
+        ),
+        AppError::DuplicateTopicName(name) => {
+            app.add_log("WARN", &format!("A topic named '{name}' already exists"))
+        }
+        // The limit is the user's own setting, so show it without the
+        // "Failed to ..." framing.
+        AppError::TopicTaskLimitReached(..) => {
+            let message = error.to_string();
+            app.add_log("WARN", &message);
+            app.show_error_popup(message);
+        }
+        _ => {
+            let message = format!("{context}: {error}");
+            app.add_log("ERROR", &message);
+            app.show_error_popup(message);
+        }
+    }
+}
+
+fn exit_action(app: &mut App) -> UiAction {
+    if app.request_exit() {
+        UiAction::Exit
+    } else {
+        UiAction::Continue
+    }
 }
 
 fn task_palette_commands(app: &App) -> Vec<PaletteCommand> {
@@ -35,7 +67,7 @@ fn task_palette_commands(app: &App) -> Vec<PaletteCommand> {
             },
             PaletteCommand {
                 id: "special_presets",
-                shortcut: "p",
+                shortcut: "P",
                 group: "Special",
                 label: "Open Special Presets",
                 description: "Apply or manage saved special-task filters.",
@@ -43,7 +75,7 @@ fn task_palette_commands(app: &App) -> Vec<PaletteCommand> {
             },
             PaletteCommand {
                 id: "help",
-                shortcut: "H",
+                shortcut: "H / ?",
                 group: "General",
                 label: "Open Help",
                 description: "Show task manager shortcuts and modes.",
@@ -59,6 +91,23 @@ fn task_palette_commands(app: &App) -> Vec<PaletteCommand> {
                 description: "Create a new task in the current topic.",
                 keywords: "new task create a",
             },
+            PaletteCommand {
+                id: "add_subtask",
+                shortcut: "Ctrl+S",
+                group: "Create",
+                label: "Add Subtask",
+                description: "Add a checklist item to the selected task.",
+                keywords: "subtask child checklist step",
+            },
+            PaletteCommand {
+                id: "add_link",
+                shortcut: "Ctrl+K",
+                group: "Create",
+                label: "Link Task",
+                description:
+                    "Mark the selected task as blocking, blocked by or related to another.",
+                keywords: "link dependency blocks blocked relates",
+            },
             PaletteCommand {
                 id: "add_topic",
                 shortcut: "N",
@@ -75,6 +124,30 @@ fn task_palette_commands(app: &App) -> Vec<PaletteCommand> {
                 description: "Rename or update the selected task.",
                 keywords: "edit rename update selected",
             },
+            PaletteCommand {
+                id: "rename_task",
+                shortcut: "R",
+                group: "Edit",
+                label: "Rename Task",
+                description: "Change only the selected task's name.",
+                keywords: "rename title name selected",
+            },
+            PaletteCommand {
+                id: "yank_task",
+                shortcut: "y",
+                group: "Edit",
+                label: "Copy Task Name",
+                description: "Copy the selected task name to the clipboard.",
+                keywords: "copy yank clipboard name",
+            },
+            PaletteCommand {
+                id: "paste_task",
+                shortcut: "p",
+                group: "Create",
+                label: "Paste as New Task",
+                description: "Start a new task named from the clipboard.",
+                keywords: "paste clipboard new task",
+            },
             PaletteCommand {
                 id: "delete_task",
                 shortcut: "d",
@@ -83,6 +156,14 @@ fn task_palette_commands(app: &App) -> Vec<PaletteCommand> {
                 description: "Delete the selected task.",
                 keywords: "delete remove task",
             },
+            PaletteCommand {
+                id: "open_url",
+                shortcut: "o",
+                group: "State",
+                label: "Open Task URL",
+                description: "Open the selected task's link in the browser.",
+                keywords: "open url link browser web",
+            },
             PaletteCommand {
                 id: "toggle_done",
                 shortcut: "t",
@@ -99,6 +180,14 @@ fn task_palette_commands(app: &App) -> Vec<PaletteCommand> {
                 description: "Star or unstar the selected task.",
                 keywords: "favorite favourite star f",
             },
+            PaletteCommand {
+                id: "toggle_pin",
+                shortcut: "Ctrl+P",
+                group: "State",
+                label: "Toggle Pin",
+                description: "Keep the selected task at the top of the list.",
+                keywords: "pin unpin sticky top",
+            },
             PaletteCommand {
                 id: "filter_tasks",
                 shortcut: "/",
@@ -107,9 +196,113 @@ fn task_palette_commands(app: &App) -> Vec<PaletteCommand> {
                 description: "Search tasks by status, topic, or favourite.",
                 keywords: "filter search status topic fav",
             },
+            PaletteCommand {
+                id: "search_tasks",
+                shortcut: "F",
+                group: "Discover",
+                label: "Search Tasks",
+                description: "Full-text search over task names and descriptions.",
+                keywords: "search find full text fts grep",
+            },
+            PaletteCommand {
+                id: "search_topics",
+                shortcut: "Ctrl+F",
+                group: "Discover",
+                label: "Search Topics",
+                description: "Find a topic tab by name or description and switch to it.",
+                keywords: "topic tab find jump switch",
+            },
+            PaletteCommand {
+                id: "view_task",
+                shortcut: "v",
+                group: "Discover",
+                label: "View Task",
+                description: "Read every field of the selected task full-screen.",
+                keywords: "view details open read show inspect",
+            },
+            PaletteCommand {
+                id: "topic_stats",
+                shortcut: "i",
+                group: "Discover",
+                label: "Topic Statistics",
+                description: "Task counts and recent activity for this topic.",
+                keywords: "stats statistics info topic summary overdue",
+            },
+            PaletteCommand {
+                id: "recent_tasks",
+                shortcut: "r",
+                group: "Discover",
+                label: "Recent Tasks",
+                description: "Jump to one of the ten most recently touched tasks.",
+                keywords: "recent recently touched updated resume history",
+            },
+            PaletteCommand {
+                id: "archived_tasks",
+                shortcut: "Ctrl+R",
+                group: "Discover",
+                label: "Archived Tasks",
+                description: "Restore or permanently delete archived tasks.",
+                keywords: "archive archived restore unarchive trash deleted",
+            },
+            PaletteCommand {
+                id: "due_soon",
+                shortcut: "Ctrl+U",
+                group: "Discover",
+                label: "Due Soon",
+                description: "List open tasks due in the next few days.",
+                keywords: "due soon deadline upcoming overdue",
+            },
+            PaletteCommand {
+                id: "cycle_sort",
+                shortcut: "s",
+                group: "Discover",
+                label: "Cycle Sort Mode",
+                description: "Sort the topic by created, name, updated, or status.",
+                keywords: "sort order created name updated status",
+            },
+            PaletteCommand {
+                id: "toggle_sort_direction",
+                shortcut: "S",
+                group: "Discover",
+                label: "Toggle Sort Direction",
+                description: "Switch between ascending and descending order.",
+                keywords: "sort direction ascending descending reverse",
+            },
+            PaletteCommand {
+                id: "cycle_topic_order",
+                shortcut: "Ctrl+O",
+                group: "Discover",
+                label: "Cycle Topic Order",
+                description: "Order topic tabs manually, by name, or by creation date.",
+                keywords: "topic tab order sort name created manual",
+            },
+            PaletteCommand {
+                id: "reorder_topics_by_name",
+                shortcut: "",
+                group: "Edit",
+                label: "Reorder Topics by Name",
+                description: "Rewrite the manual topic order alphabetically.",
+                keywords: "topic tab order sort alphabetical name manual",
+            },
+            PaletteCommand {
+                id: "cycle_completion_filter",
+                shortcut: "c",
+                group: "Discover",
+                label: "Cycle Completion Filter",
+                description: "Show all, open, or done tasks in this topic.",
+                keywords: "show open done completed all hide",
+            },
+            PaletteCommand {
+                id: "toggle_compact",
+                shortcut: "z",
+                group: "Discover",
+                label: "Toggle Compact View",
+                description: "Show two tasks per row, names only.",
+                keywords: "compact dense view layout columns",
+            },
             PaletteCommand {
                 id: "open_presets",
-                shortcut: "p",
+                shortcut: "P",
                 group: "Discover",
                 label: "Open Presets",
                 description: "Apply or manage saved task filters.",
@@ -123,9 +316,49 @@ fn task_palette_commands(app: &App) -> Vec<PaletteCommand> {
                 description: "Browse favourites and completed tasks.",
                 keywords: "special favourites completed popup w",
             },
+            PaletteCommand {
+                id: "merge_topic",
+                shortcut: "M",
+                group: "Edit",
+                label: "Merge Topic",
+                description: "Move this topic's tasks into another topic and delete it.",
+                keywords: "merge combine topics move join",
+            },
+            PaletteCommand {
+                id: "topic_alias",
+                shortcut: "",
+                group: "Edit",
+                label: "Set Topic Alias",
+                description: "Pick a key that jumps straight to this topic.",
+                keywords: "alias shortcut jump key topic",
+            },
+            PaletteCommand {
+                id: "topic_task_limit",
+                shortcut: "",
+                group: "Edit",
+                label: "Set Topic Task Limit",
+                description: "Cap how many tasks this topic may hold.",
+                keywords: "limit cap max maximum wip tasks topic",
+            },
+            PaletteCommand {
+                id: "toggle_bell",
+                shortcut: "Ctrl+B",
+                group: "General",
+                label: "Toggle Completion Bell",
+                description: "Ring the terminal bell when a task is completed.",
+                keywords: "sound bell beep notification mute",
+            },
+            PaletteCommand {
+                id: "vacuum_database",
+                shortcut: "Ctrl+V",
+                group: "General",
+                label: "Vacuum Database",
+                description: "Compact the database file after many deletes.",
+                keywords: "vacuum compact maintenance database shrink",
+            },
             PaletteCommand {
                 id: "help",
-                shortcut: "H",
+                shortcut: "H / ?",
                 group: "General",
                 label: "Open Help",
                 description: "Show task manager shortcuts and modes.",
@@ -143,18 +376,35 @@ pub(crate) fn visible_task_palette_commands(app: &App) -> Vec<PaletteCommand> {
     )
 }
 
-fn execute_task_palette_command(
-    app: &mut App,
-    command_id: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
+fn execute_task_palette_command(app: &mut App, command_id: &str) -> Result<(), AppError> {
     match command_id {
         "add_task" => app.begin_add_task(),
+        "add_subtask" => app.begin_add_subtask(),
+        "add_link" => app.begin_add_link(),
         "add_topic" => app.begin_add_topic(),
         "edit_task" => app.begin_edit_task(),
-        "delete_task" => app.begin_delete_task(),
+        "rename_task" => app.begin_rename_task(),
+        "delete_task" => app.begin_delete_task()?,
+        "yank_task" => app.yank_selected_task(),
+        "open_url" => app.open_selected_task_url(),
+        "paste_task" => app.paste_task_name(),
         "toggle_done" => app.toggle_task()?,
         "toggle_favourite" => app.toggle_favourite()?,
+        "toggle_pin" => app.toggle_pin()?,
         "filter_tasks" => app.begin_task_filter(),
+        "view_task" => app.begin_view_task(),
+        "recent_tasks" => app.begin_recent()?,
+        "archived_tasks" => app.begin_archive()?,
+        "due_soon" => app.begin_due_soon()?,
+        "search_tasks" => app.begin_search(),
+        "search_topics" => app.begin_topic_search()?,
+        "cycle_sort" => app.cycle_sort_mode()?,
+        "toggle_sort_direction" => app.toggle_sort_direction()?,
+        "cycle_topic_order" => app.cycle_topic_order()?,
+        "reorder_topics_by_name" => app.reorder_topics_by_name()?,
+        "cycle_completion_filter" => app.cycle_completion_filter()?,
+        "toggle_compact" => app.toggle_compact_mode(),
+        "vacuum_database" => app.vacuum_database()?,
         "open_presets" => app.begin_task_presets(),
         "open_special" => {
             app.input_mode = InputMode::ViewingSpecialTopics;
@@ -164,6 +414,11 @@ fn execute_task_palette_command(
         "close_special" => app.input_mode = InputMode::Normal,
         "filter_special" => app.begin_special_task_filter(),
         "special_presets" => app.begin_special_task_presets(),
+        "toggle_bell" => app.toggle_sound()?,
+        "merge_topic" => app.begin_merge_topic()?,
+        "topic_alias" => app.begin_set_topic_alias(),
+        "topic_task_limit" => app.begin_set_topic_task_limit(),
+        "topic_stats" => app.begin_topic_stats()?,
         "help" => app.input_mode = InputMode::Help,
         _ => {}
     }
@@ -171,66 +426,284 @@ fn execute_task_palette_command(
     Ok(())
 }
 
-pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<UiAction, Box<dyn std::error::Error>> {
+/// Vim-style `gg`, `G` and count-prefixed jumps. Returns `true` when the key
+/// was consumed; any other key clears the pending sequence and count.
+fn handle_jump_key(app: &mut App, key: KeyEvent) -> bool {
+    let pending = app.take_pending_key();
+    match key.code {
+        KeyCode::Char('g') if pending == Some(KeyCode::Char('g')) => {
+            let row = app.count_prefix.take().unwrap_or(1);
+            app.jump_to_row(row);
+        }
+        KeyCode::Char('g') => app.set_pending_key(KeyCode::Char('g')),
+        KeyCode::Char('G') => match app.count_prefix.take() {
+            Some(row) => app.jump_to_row(row),
+            None => app.jump_to_last(),
+        },
+        KeyCode::Char(c @ '0'..='9')
+            if pending.is_none()
+                && !key.modifiers.contains(KeyModifiers::CONTROL)
+                && (c != '0' || app.count_prefix.is_some()) =>
+        {
+            app.push_count_digit(c.to_digit(10).unwrap_or(0));
+        }
+        _ => {
+            app.count_prefix = None;
+            return false;
+        }
+    }
+    true
+}
+
+pub fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    match mouse.kind {
+        MouseEventKind::Moved => app.on_mouse_moved(mouse.column, mouse.row),
+        MouseEventKind::ScrollUp => app.on_mouse_scroll(mouse.column, mouse.row, true),
+        MouseEventKind::ScrollDown => app.on_mouse_scroll(mouse.column, mouse.row, false),
+        _ => {}
+    }
+}
+
+pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<UiAction, AppError> {
+    if key.code == KeyCode::Char('z') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        return Ok(UiAction::Suspend);
+    }
+    if app.input_mode == InputMode::Normal && handle_jump_key(app, key) {
+        return Ok(UiAction::Continue);
+    }
+    // Ctrl+Y opens the yank history wherever it can paste something.
+    if key.code == KeyCode::Char('y')
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+        && matches!(
+            app.input_mode,
+            InputMode::Normal
+                | InputMode::AddingTaskName
+                | InputMode::EditingTaskName
+                | InputMode::RenamingTask
+                | InputMode::AddingTaskDescription
+                | InputMode::AddingTaskUrl
+                | InputMode::AddingTopic
+                | InputMode::AddingTopicDescription
+                | InputMode::AddingSubtask
+        )
+    {
+        app.begin_clipboard_picker();
+        return Ok(UiAction::Continue);
+    }
+    // With `enter_action: toggle_complete`, Enter and Space swap roles.
+    let key = match key.code {
+        KeyCode::Enter | KeyCode::Char(' ')
+            if app.input_mode == InputMode::Normal
+                && app.config.enter_action == EnterAction::ToggleComplete =>
+        {
+            let code = if key.code == KeyCode::Enter {
+                KeyCode::Char(' ')
+            } else {
+                KeyCode::Enter
+            };
+            KeyEvent::new(code, key.modifiers)
+        }
+        _ => key,
+    };
+
     match app.input_mode {
         InputMode::Normal => match key.code {
-            KeyCode::Char('q') => return Ok(UiAction::Exit),
+            KeyCode::Char('q') => return Ok(exit_action(app)),
             KeyCode::Char(':') => app.begin_command_palette(),
-            KeyCode::Char('p') => app.begin_task_presets(),
+            KeyCode::Char('P') => app.begin_task_presets(),
+            KeyCode::Char('y') => app.yank_selected_task(),
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Err(e) = app.toggle_pin() {
+                    log_ui_error(app, "Failed to toggle pin", &e);
+                }
+            }
+            KeyCode::Char('p') => app.paste_task_name(),
+            // Terminals commonly report Ctrl+/ as Ctrl+7.
+            KeyCode::Char('/') | KeyCode::Char('7')
+                if key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                app.begin_log_search()
+            }
             KeyCode::Char('/') => app.begin_task_filter(),
+            KeyCode::Char('F') => app.begin_search(),
+            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Err(e) = app.begin_topic_search() {
+                    log_ui_error(app, "Failed to search topics", &e);
+                }
+            }
+            KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Err(e) = app.vacuum_database() {
+                    log_ui_error(app, "Failed to vacuum database", &e);
+                }
+            }
+            KeyCode::Char('v') => app.begin_view_task(),
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Err(e) = app.begin_due_soon() {
+                    log_ui_error(app, "Failed to load tasks due soon", &e);
+                }
+            }
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Err(e) = app.begin_archive() {
+                    log_ui_error(app, "Failed to load archived tasks", &e);
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Err(e) = app.begin_recent() {
+                    log_ui_error(app, "Failed to load recent tasks", &e);
+                }
+            }
+            KeyCode::Char('i') => {
+                if let Err(e) = app.begin_topic_stats() {
+                    log_ui_error(app, "Failed to load topic statistics", &e);
+                }
+            }
             KeyCode::Char('W') => {
                 app.input_mode = InputMode::ViewingSpecialTopics;
                 app.special_tab_selected = 0;
                 if let Err(e) = app.load_special_tasks() {
                     app.input_mode = InputMode::Normal;
-                    log_ui_error(app, "Failed to load special tasks", e.as_ref());
+                    log_ui_error(app, "Failed to load special tasks", &e);
                 }
             }
             KeyCode::Char('a') => app.begin_add_task(),
-            KeyCode::Char('d') => app.begin_delete_task(),
+            KeyCode::Char('d') => {
+                if let Err(e) = app.begin_delete_task() {
+                    log_ui_error(app, "Failed to load subtasks", &e);
+                }
+            }
+            KeyCode::Char('D') => app.begin_delete_multiple(),
+            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(UiAction::OpenLogFile)
+            }
             KeyCode::Char('e') => app.begin_edit_task(),
+            KeyCode::Char('R') => app.begin_rename_task(),
             KeyCode::Char('f') => {
                 if let Err(e) = app.toggle_favourite() {
-                    log_ui_error(app, "Failed to toggle favourite", e.as_ref());
+                    log_ui_error(app, "Failed to toggle favourite", &e);
+                }
+            }
+            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.cycle_log_level()
+            }
+            KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Err(e) = app.toggle_sound() {
+                    log_ui_error(app, "Failed to save config", &e);
                 }
             }
-            KeyCode::Char('H') => app.input_mode = InputMode::Help,
+            KeyCode::Char('H') | KeyCode::Char('?') => app.input_mode = InputMode::Help,
             KeyCode::Char('t') => {
                 if let Err(e) = app.toggle_task() {
-                    log_ui_error(app, "Failed to toggle task", e.as_ref());
+                    log_ui_error(app, "Failed to toggle task", &e);
+                }
+            }
+            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Err(e) = app.cycle_topic_order() {
+                    log_ui_error(app, "Failed to change topic order", &e);
+                }
+            }
+            KeyCode::Char('o') => app.open_selected_task_url(),
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.begin_add_subtask()
+            }
+            KeyCode::Char('s') => {
+                if let Err(e) = app.cycle_sort_mode() {
+                    log_ui_error(app, "Failed to change sort mode", &e);
+                }
+            }
+            KeyCode::Char('S') => {
+                if let Err(e) = app.toggle_sort_direction() {
+                    log_ui_error(app, "Failed to change sort direction", &e);
+                }
+            }
+            KeyCode::Char('z') => app.toggle_compact_mode(),
+            KeyCode::Char('c') => {
+                if let Err(e) = app.cycle_completion_filter() {
+                    log_ui_error(app, "Failed to change completion filter", &e);
+                }
+            }
+            KeyCode::Char(' ') => {
+                if let Err(e) = app.toggle_task() {
+                    log_ui_error(app, "Failed to toggle task", &e);
                 }
             }
             KeyCode::Enter => {
-                if let Some(task) = app.tasks.get(app.selected) {
-                    if app.expanded.contains(&task.id) {
-                        app.expanded.remove(&task.id);
-                    } else {
-                        app.expanded.insert(task.id);
+                if let Some(task_id) = app.current_task_id() {
+                    if !app.expanded.remove(&task_id) {
+                        app.expanded.insert(task_id);
                     }
                 }
             }
-            KeyCode::Down | KeyCode::Char('j') => app.move_selection_down(),
-            KeyCode::Up | KeyCode::Char('k') => app.move_selection_up(),
+            KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                if let Err(e) = app.move_selected_task(1) {
+                    log_ui_error(app, "Failed to move task", &e);
+                }
+            }
+            KeyCode::Up if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                if let Err(e) = app.move_selected_task(-1) {
+                    log_ui_error(app, "Failed to move task", &e);
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') if app.sidebar_has_focus() => {
+                if let Err(e) = app.step_topic(1) {
+                    log_ui_error(app, "Failed to load tasks", &e);
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k')
+                if app.sidebar_has_focus() && !key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                if let Err(e) = app.step_topic(-1) {
+                    log_ui_error(app, "Failed to load tasks", &e);
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Err(e) = app.move_down_or_next_page() {
+                    log_ui_error(app, "Failed to load the next page", &e);
+                }
+            }
+            KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.begin_add_link()
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Err(e) = app.move_up_or_previous_page() {
+                    log_ui_error(app, "Failed to load the previous page", &e);
+                }
+            }
+            KeyCode::Left if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                if let Err(e) = app.move_selected_topic(-1) {
+                    log_ui_error(app, "Failed to move topic", &e);
+                }
+            }
+            KeyCode::Right if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                if let Err(e) = app.move_selected_topic(1) {
+                    log_ui_error(app, "Failed to move topic", &e);
+                }
+            }
+            KeyCode::Left | KeyCode::Char('h') if app.config.layout == LayoutStyle::SidebarLeft => {
+                app.sidebar_focused = true;
+            }
+            KeyCode::Right | KeyCode::Char('l')
+                if app.config.layout == LayoutStyle::SidebarLeft =>
+            {
+                app.sidebar_focused = false;
+            }
             KeyCode::Left | KeyCode::Char('h') => {
-                if app.selected_topic > 0 {
-                    app.selected_topic -= 1;
-                    if let Err(e) = app.load_tasks() {
-                        app.selected_topic += 1;
-                        log_ui_error(app, "Failed to load tasks", e.as_ref());
-                    } else {
-                        app.selected = 0;
-                    }
+                if let Err(e) = app.step_topic(-1) {
+                    log_ui_error(app, "Failed to load tasks", &e);
                 }
             }
             KeyCode::Right | KeyCode::Char('l') => {
-                if app.selected_topic < app.topics.len().saturating_sub(1) {
-                    app.selected_topic += 1;
-                    if let Err(e) = app.load_tasks() {
-                        app.selected_topic -= 1;
-                        log_ui_error(app, "Failed to load tasks", e.as_ref());
-                    } else {
-                        app.selected = 0;
-                    }
+                if let Err(e) = app.step_topic(1) {
+                    log_ui_error(app, "Failed to load tasks", &e);
+                }
+            }
+            KeyCode::PageDown if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Err(e) = app.next_page() {
+                    log_ui_error(app, "Failed to load the next page", &e);
+                }
+            }
+            KeyCode::PageUp if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Err(e) = app.previous_page() {
+                    log_ui_error(app, "Failed to load the previous page", &e);
                 }
             }
             KeyCode::PageUp => app.log_offset += 1,
@@ -239,14 +712,36 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<UiAction, Box<dyn std:
                     app.log_offset -= 1;
                 }
             }
+            // Most terminals send Ctrl+M as Enter, so `M` works everywhere.
+            KeyCode::Char('m') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Err(e) = app.begin_merge_topic() {
+                    log_ui_error(app, "Failed to merge topic", &e);
+                }
+            }
+            KeyCode::Char('M') => {
+                if let Err(e) = app.begin_merge_topic() {
+                    log_ui_error(app, "Failed to merge topic", &e);
+                }
+            }
             KeyCode::Char('N') => app.begin_add_topic(),
             KeyCode::Char('X') => {
                 if !app.current_topic_is_special() {
                     if let Err(e) = app.delete_topic() {
-                        log_ui_error(app, "Failed to delete topic", e.as_ref());
+                        log_ui_error(app, "Failed to delete topic", &e);
                     }
                 }
             }
+            // Only keys with no binding above reach here, so an alias never
+            // shadows a command.
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                if let Err(e) = app.jump_to_topic_alias(c) {
+                    log_ui_error(app, "Failed to load tasks", &e);
+                }
+            }
             _ => {}
         },
         InputMode::CommandPalette => match key.code {
@@ -258,7 +753,7 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<UiAction, Box<dyn std:
                 {
                     app.close_command_palette();
                     if let Err(e) = execute_task_palette_command(app, command.id) {
-                        log_ui_error(app, "Failed to execute palette command", e.as_ref());
+                        log_ui_error(app, "Failed to execute palette command", &e);
                     }
                 } else {
                     app.close_command_palette();
@@ -287,109 +782,334 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<UiAction, Box<dyn std:
         },
         InputMode::Filtering => match key.code {
             KeyCode::Esc => {
-                app.clear_task_filter();
+                if let Err(e) = app.clear_task_filter() {
+                    log_ui_error(app, "Failed to load tasks", &e);
+                }
                 app.input_mode = InputMode::Normal;
             }
             KeyCode::Enter => app.input_mode = InputMode::Normal,
-            KeyCode::Backspace => app.pop_task_filter_char(),
-            KeyCode::Char(c) => app.append_task_filter_char(c),
+            KeyCode::Backspace => {
+                if let Err(e) = app.pop_task_filter_char() {
+                    log_ui_error(app, "Failed to load tasks", &e);
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Err(e) = app.append_task_filter_char(c) {
+                    log_ui_error(app, "Failed to load tasks", &e);
+                }
+            }
             _ => {}
         },
-        InputMode::FilteringSpecial => match key.code {
-            KeyCode::Esc => {
-                app.clear_special_task_filter();
-                app.input_mode = InputMode::ViewingSpecialTopics;
+        InputMode::ViewingTask => match key.code {
+            KeyCode::Esc | KeyCode::Char('v') => app.input_mode = InputMode::Normal,
+            KeyCode::Char('e') => app.begin_edit_task(),
+            KeyCode::Char('d') => {
+                if let Err(e) = app.begin_delete_task() {
+                    log_ui_error(app, "Failed to load subtasks", &e);
+                }
+            }
+            KeyCode::Char('t') => {
+                if let Err(e) = app.toggle_task() {
+                    log_ui_error(app, "Failed to toggle task", &e);
+                }
+            }
+            KeyCode::Char('f') => {
+                if let Err(e) = app.toggle_favourite() {
+                    log_ui_error(app, "Failed to toggle favourite", &e);
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Err(e) = app.move_down_or_next_page() {
+                    log_ui_error(app, "Failed to load the next page", &e);
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Err(e) = app.move_up_or_previous_page() {
+                    log_ui_error(app, "Failed to load the previous page", &e);
+                }
             }
-            KeyCode::Enter => app.input_mode = InputMode::ViewingSpecialTopics,
-            KeyCode::Backspace => app.pop_special_task_filter_char(),
-            KeyCode::Char(c) => app.append_special_task_filter_char(c),
             _ => {}
         },
-        InputMode::PresetFilters => match key.code {
+        InputMode::MergingTopic => match key.code {
             KeyCode::Esc => app.input_mode = InputMode::Normal,
-            KeyCode::Char('S') => app.begin_save_task_preset(),
-            KeyCode::Char('x') => {
-                if let Err(e) = app.delete_selected_task_preset() {
-                    log_ui_error(app, "Failed to delete task preset", e.as_ref());
+            KeyCode::Enter => {
+                if let Err(e) = app.confirm_merge_topic() {
+                    log_ui_error(app, "Failed to merge topic", &e);
                 }
             }
-            KeyCode::Enter => {
-                app.apply_selected_task_preset();
-                app.input_mode = InputMode::Normal;
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.merge_target_selected = app.merge_target_selected.saturating_sub(1);
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                let len = app.all_task_filter_presets().len();
-                app.move_preset_down(len);
+            KeyCode::Down | KeyCode::Char('j')
+                if app.merge_target_selected + 1 < app.merge_candidates().len() =>
+            {
+                app.merge_target_selected += 1;
             }
-            KeyCode::Up | KeyCode::Char('k') => app.move_preset_up(),
             _ => {}
         },
-        InputMode::PresetSpecialFilters => match key.code {
-            KeyCode::Esc => app.input_mode = InputMode::ViewingSpecialTopics,
-            KeyCode::Char('S') => app.begin_save_special_task_preset(),
-            KeyCode::Char('x') => {
-                if let Err(e) = app.delete_selected_task_preset() {
-                    log_ui_error(app, "Failed to delete special task preset", e.as_ref());
+        InputMode::AddingLink => match key.code {
+            KeyCode::Esc => app.cancel_add_link(),
+            KeyCode::Enter => {
+                if let Err(e) = app.add_selected_link() {
+                    log_ui_error(app, "Failed to link task", &e);
+                    app.cancel_add_link();
                 }
             }
+            KeyCode::Tab => app.cycle_link_type(),
+            KeyCode::Up | KeyCode::Char('k') => app.move_link_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => app.move_link_selection(1),
+            _ => {}
+        },
+        InputMode::ViewingDueSoon => match key.code {
+            KeyCode::Esc => app.close_due_soon(),
             KeyCode::Enter => {
-                app.apply_selected_special_task_preset();
-                app.input_mode = InputMode::ViewingSpecialTopics;
-            }
-            KeyCode::Down | KeyCode::Char('j') => {
-                let len = app.all_task_filter_presets().len();
-                app.move_preset_down(len);
+                if let Err(e) = app.open_selected_due_task() {
+                    log_ui_error(app, "Failed to open task", &e);
+                }
             }
-            KeyCode::Up | KeyCode::Char('k') => app.move_preset_up(),
+            KeyCode::Up | KeyCode::Char('k') => app.move_due_soon_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => app.move_due_soon_selection(1),
             _ => {}
         },
-        InputMode::SavingPreset | InputMode::SavingSpecialPreset => match key.code {
-            KeyCode::Esc => {
-                let special = matches!(app.input_mode, InputMode::SavingSpecialPreset);
-                app.clear_preset_form();
-                app.input_mode = if special {
-                    InputMode::PresetSpecialFilters
-                } else {
-                    InputMode::PresetFilters
-                };
-            }
+        InputMode::ViewingRecent => match key.code {
+            KeyCode::Esc => app.close_recent(),
             KeyCode::Enter => {
-                let special = matches!(app.input_mode, InputMode::SavingSpecialPreset);
-                if let Err(e) = app.save_named_task_preset(special) {
-                    app.preset_form_message = Some(e.to_string());
-                    log_ui_error(app, "Failed to save task preset", e.as_ref());
-                } else {
-                    app.clear_preset_form();
-                    app.input_mode = if special {
-                        InputMode::PresetSpecialFilters
-                    } else {
-                        InputMode::PresetFilters
-                    };
+                if let Err(e) = app.open_selected_recent_task() {
+                    log_ui_error(app, "Failed to open task", &e);
                 }
             }
-            KeyCode::Backspace => {
-                app.preset_form_message = None;
-                app.preset_name_input.pop();
+            KeyCode::Up | KeyCode::Char('k') => app.move_recent_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => app.move_recent_selection(1),
+            _ => {}
+        },
+        InputMode::ViewingArchivedTasks => match key.code {
+            KeyCode::Esc => app.close_archive(),
+            KeyCode::Up | KeyCode::Char('k') => app.move_archive_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => app.move_archive_selection(1),
+            KeyCode::Char('u') => {
+                if let Err(e) = app.unarchive_selected_task() {
+                    log_ui_error(app, "Failed to restore task", &e);
+                }
             }
-            KeyCode::Char(c) => {
-                app.preset_form_message = None;
-                app.preset_name_input.push(c);
+            KeyCode::Char('D') => {
+                if let Err(e) = app.purge_selected_archived_task() {
+                    log_ui_error(app, "Failed to delete task", &e);
+                }
             }
             _ => {}
         },
-        InputMode::AddingTaskName | InputMode::EditingTaskName => match key.code {
+        InputMode::SearchingLogs => match key.code {
+            KeyCode::Esc => app.clear_log_search(),
+            KeyCode::Enter => app.input_mode = InputMode::Normal,
+            KeyCode::Backspace => app.pop_log_search_char(),
+            KeyCode::PageUp => app.log_offset += 1,
+            KeyCode::PageDown => app.log_offset = app.log_offset.saturating_sub(1),
+            KeyCode::Char(c) => app.push_log_search_char(c),
+            _ => {}
+        },
+        InputMode::Searching => match key.code {
+            KeyCode::Esc => app.close_search(),
+            KeyCode::Enter => {
+                if let Err(e) = app.open_selected_search_result() {
+                    log_ui_error(app, "Failed to open search result", &e);
+                }
+            }
+            KeyCode::Up => app.move_search_selection(-1),
+            KeyCode::Down => app.move_search_selection(1),
+            KeyCode::Backspace => {
+                app.search_query.pop();
+                if let Err(e) = app.run_search() {
+                    log_ui_error(app, "Search failed", &e);
+                }
+            }
+            KeyCode::Char(c) => {
+                app.search_query.push(c);
+                app.search_selected = 0;
+                if let Err(e) = app.run_search() {
+                    log_ui_error(app, "Search failed", &e);
+                }
+            }
+            _ => {}
+        },
+        InputMode::ChoosingClipboard => match key.code {
+            KeyCode::Esc => app.close_clipboard_picker(),
+            KeyCode::Enter => {
+                if let Err(e) = app.choose_clipboard_entry() {
+                    log_ui_error(app, "Failed to use clipboard entry", &e);
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => app.move_clipboard_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => app.move_clipboard_selection(1),
+            _ => {}
+        },
+        InputMode::RenamingTask => match key.code {
+            KeyCode::Esc => app.cancel_rename_task(),
+            KeyCode::Enter => {
+                if let Err(e) = app.submit_rename_task() {
+                    log_ui_error(app, "Failed to rename task", &e);
+                }
+            }
+            KeyCode::Backspace => {
+                app.clear_task_form_message();
+                app.rename_input.pop();
+            }
+            KeyCode::Char(c) => {
+                app.clear_task_form_message();
+                app.rename_input.push(c);
+            }
+            _ => {}
+        },
+        InputMode::SettingTopicAlias | InputMode::SettingTopicTaskLimit => match key.code {
+            KeyCode::Esc => app.cancel_topic_setting(),
+            KeyCode::Enter if app.input_mode == InputMode::SettingTopicAlias => {
+                if let Err(e) = app.submit_topic_alias() {
+                    log_ui_error(app, "Failed to set topic alias", &e);
+                }
+            }
+            KeyCode::Enter => {
+                if let Err(e) = app.submit_topic_task_limit() {
+                    log_ui_error(app, "Failed to set topic task limit", &e);
+                }
+            }
+            KeyCode::Backspace => {
+                app.topic_setting_message = None;
+                app.topic_setting_input.pop();
+            }
+            KeyCode::Char(c) => {
+                app.topic_setting_message = None;
+                app.topic_setting_input.push(c);
+            }
+            _ => {}
+        },
+        InputMode::SearchingTopics => match key.code {
+            KeyCode::Esc => app.close_topic_search(),
+            KeyCode::Enter => {
+                if let Err(e) = app.open_selected_topic_search_result() {
+                    log_ui_error(app, "Failed to open topic", &e);
+                }
+            }
+            KeyCode::Up => app.move_topic_search_selection(-1),
+            KeyCode::Down => app.move_topic_search_selection(1),
+            KeyCode::Backspace => {
+                app.topic_search_query.pop();
+                if let Err(e) = app.run_topic_search() {
+                    log_ui_error(app, "Failed to search topics", &e);
+                }
+            }
+            KeyCode::Char(c) => {
+                app.topic_search_query.push(c);
+                app.topic_search_selected = 0;
+                if let Err(e) = app.run_topic_search() {
+                    log_ui_error(app, "Failed to search topics", &e);
+                }
+            }
+            _ => {}
+        },
+        InputMode::FilteringSpecial => match key.code {
+            KeyCode::Esc => {
+                app.clear_special_task_filter();
+                app.input_mode = InputMode::ViewingSpecialTopics;
+            }
+            KeyCode::Enter => app.input_mode = InputMode::ViewingSpecialTopics,
+            KeyCode::Backspace => app.pop_special_task_filter_char(),
+            KeyCode::Char(c) => app.append_special_task_filter_char(c),
+            _ => {}
+        },
+        InputMode::PresetFilters => match key.code {
+            KeyCode::Esc => app.input_mode = InputMode::Normal,
+            KeyCode::Char('S') => app.begin_save_task_preset(),
+            KeyCode::Char('x') => {
+                if let Err(e) = app.delete_selected_task_preset() {
+                    log_ui_error(app, "Failed to delete task preset", &e);
+                }
+            }
+            KeyCode::Enter => {
+                if let Err(e) = app.apply_selected_task_preset() {
+                    log_ui_error(app, "Failed to apply task preset", &e);
+                }
+                app.input_mode = InputMode::Normal;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let len = app.all_task_filter_presets().len();
+                app.move_preset_down(len);
+            }
+            KeyCode::Up | KeyCode::Char('k') => app.move_preset_up(),
+            _ => {}
+        },
+        InputMode::PresetSpecialFilters => match key.code {
+            KeyCode::Esc => app.input_mode = InputMode::ViewingSpecialTopics,
+            KeyCode::Char('S') => app.begin_save_special_task_preset(),
+            KeyCode::Char('x') => {
+                if let Err(e) = app.delete_selected_task_preset() {
+                    log_ui_error(app, "Failed to delete special task preset", &e);
+                }
+            }
+            KeyCode::Enter => {
+                app.apply_selected_special_task_preset();
+                app.input_mode = InputMode::ViewingSpecialTopics;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let len = app.all_task_filter_presets().len();
+                app.move_preset_down(len);
+            }
+            KeyCode::Up | KeyCode::Char('k') => app.move_preset_up(),
+            _ => {}
+        },
+        InputMode::SavingPreset | InputMode::SavingSpecialPreset => match key.code {
+            KeyCode::Esc => {
+                let special = matches!(app.input_mode, InputMode::SavingSpecialPreset);
+                app.clear_preset_form();
+                app.input_mode = if special {
+                    InputMode::PresetSpecialFilters
+                } else {
+                    InputMode::PresetFilters
+                };
+            }
+            KeyCode::Enter => {
+                let special = matches!(app.input_mode, InputMode::SavingSpecialPreset);
+                if let Err(e) = app.save_named_task_preset(special) {
+                    app.preset_form_message = Some(e.to_string());
+                    log_ui_error(app, "Failed to save task preset", &e);
+                } else {
+                    app.clear_preset_form();
+                    app.input_mode = if special {
+                        InputMode::PresetSpecialFilters
+                    } else {
+                        InputMode::PresetFilters
+                    };
+                }
+            }
+            KeyCode::Backspace => {
+                app.preset_form_message = None;
+                app.preset_name_input.pop();
+            }
+            KeyCode::Char(c) => {
+                app.preset_form_message = None;
+                app.preset_name_input.push(c);
+            }
+            _ => {}
+        },
+        InputMode::AddingTaskName | InputMode::EditingTaskName => match key.code {
             KeyCode::Enter => {
                 if !app.task_name_input.trim().is_empty() {
                     app.clear_task_form_message();
-                    app.input_mode = if app.input_mode == InputMode::AddingTaskName {
-                        InputMode::AddingTaskDescription
+                    if app.input_mode == InputMode::AddingTaskName {
+                        app.input_mode = InputMode::AddingTaskDescription;
                     } else {
-                        InputMode::EditingTaskDescription
-                    };
+                        app.begin_multiline_description();
+                    }
                 } else {
                     app.set_task_form_message("Task name cannot be empty");
                 }
             }
+            KeyCode::Tab => {
+                app.clear_task_form_message();
+                if app.input_mode == InputMode::AddingTaskName {
+                    app.input_mode = InputMode::AddingTaskDescription;
+                } else {
+                    app.begin_multiline_description();
+                }
+            }
             KeyCode::Esc => {
                 if app.input_mode == InputMode::AddingTaskName {
                     app.cancel_add_task();
@@ -398,6 +1118,12 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<UiAction, Box<dyn std:
                     app.input_mode = InputMode::Normal;
                 }
             }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.copy_task_description();
+            }
+            KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(exit_action(app))
+            }
             KeyCode::Char(c) => {
                 app.clear_task_form_message();
                 app.task_name_input.push(c);
@@ -408,83 +1134,156 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<UiAction, Box<dyn std:
             }
             _ => {}
         },
-        InputMode::AddingTaskDescription | InputMode::EditingTaskDescription => match key.code {
+        InputMode::AddingTaskDescription => match key.code {
             KeyCode::Enter => {
-                if !app.task_name_input.trim().is_empty() {
-                    let name_clone = app.task_name_input.clone();
-                    let desc_clone = app.task_description_input.clone();
-                    let result = if app.input_mode == InputMode::AddingTaskDescription {
-                        app.add_task_with_details(&name_clone, &desc_clone)
-                    } else {
-                        app.edit_task(&name_clone, &desc_clone)
-                    };
-                    if let Err(e) = result {
-                        app.set_task_form_message(e.to_string());
-                        log_ui_error(
-                            app,
-                            if app.input_mode == InputMode::AddingTaskDescription {
-                                "Failed to add task"
-                            } else {
-                                "Failed to edit task"
-                            },
-                            e.as_ref(),
-                        );
-                    } else {
-                        app.add_log(
-                            "INFO",
-                            if app.input_mode == InputMode::AddingTaskDescription {
-                                "Task saved"
-                            } else {
-                                "Task updated"
-                            },
-                        );
-                        app.reset_task_inputs();
-                        app.input_mode = InputMode::Normal;
-                    }
+                if app.task_name_input.trim().is_empty() {
+                    app.set_task_form_message("Task name cannot be empty");
                 } else {
+                    app.clear_task_form_message();
+                    app.input_mode = InputMode::AddingTaskUrl;
+                }
+            }
+            KeyCode::Esc => app.cancel_add_task(),
+            KeyCode::Tab => {
+                app.clear_task_form_message();
+                app.input_mode = InputMode::AddingTaskName;
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.copy_task_description();
+            }
+            KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(exit_action(app))
+            }
+            KeyCode::Char(c) => {
+                app.clear_task_form_message();
+                app.task_description_input.push(c);
+            }
+            KeyCode::Backspace => {
+                app.clear_task_form_message();
+                app.task_description_input.pop();
+            }
+            _ => {}
+        },
+        InputMode::MultilineEditDescription => match key.code {
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.sync_multiline_description();
+                let name_clone = app.task_name_input.clone();
+                let desc_clone = app.task_description_input.clone();
+                if name_clone.trim().is_empty() {
                     app.set_task_form_message("Task name cannot be empty");
+                } else if let Err(e) = app.edit_task(&name_clone, &desc_clone) {
+                    app.set_task_form_message(e.to_string());
+                    log_ui_error(app, "Failed to edit task", &e);
+                } else {
+                    app.add_log("INFO", "Task updated");
+                    app.reset_task_inputs();
+                    app.input_mode = InputMode::Normal;
                 }
             }
             KeyCode::Esc => {
-                if app.input_mode == InputMode::AddingTaskDescription {
-                    app.cancel_add_task();
+                app.reset_task_inputs();
+                app.input_mode = InputMode::Normal;
+            }
+            KeyCode::Tab => {
+                app.clear_task_form_message();
+                app.sync_multiline_description();
+                app.input_mode = InputMode::EditingTaskName;
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.sync_multiline_description();
+                app.copy_task_description();
+            }
+            KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(exit_action(app))
+            }
+            KeyCode::Enter => app.ml_insert_newline(),
+            KeyCode::Left => app.ml_move_horizontal(-1),
+            KeyCode::Right => app.ml_move_horizontal(1),
+            KeyCode::Up => app.ml_move_vertical(-1),
+            KeyCode::Down => app.ml_move_vertical(1),
+            KeyCode::Char(c) => {
+                app.clear_task_form_message();
+                app.ml_insert_char(c);
+            }
+            KeyCode::Backspace => {
+                app.clear_task_form_message();
+                app.ml_backspace();
+            }
+            _ => {}
+        },
+        InputMode::AddingTaskUrl => match key.code {
+            KeyCode::Enter => {
+                let name_clone = app.task_name_input.clone();
+                let desc_clone = app.task_description_input.clone();
+                let url_clone = app.task_url_input.clone();
+                if let Err(e) = app.add_task_with_url(&name_clone, &desc_clone, &url_clone) {
+                    app.set_task_form_message(e.to_string());
+                    log_ui_error(app, "Failed to add task", &e);
                 } else {
+                    app.add_log("INFO", "Task saved");
                     app.reset_task_inputs();
                     app.input_mode = InputMode::Normal;
                 }
             }
+            KeyCode::Esc => app.cancel_add_task(),
             KeyCode::Tab => {
                 app.clear_task_form_message();
-                app.input_mode = if app.input_mode == InputMode::AddingTaskDescription {
-                    InputMode::AddingTaskName
-                } else {
-                    InputMode::EditingTaskName
-                };
+                app.input_mode = InputMode::AddingTaskName;
+            }
+            KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(exit_action(app))
             }
             KeyCode::Char(c) => {
                 app.clear_task_form_message();
-                app.task_description_input.push(c);
+                app.task_url_input.push(c);
             }
             KeyCode::Backspace => {
                 app.clear_task_form_message();
-                app.task_description_input.pop();
+                app.task_url_input.pop();
             }
             _ => {}
         },
-        InputMode::DeleteTask => match key.code {
+        InputMode::DeleteTask | InputMode::ConfirmingDeleteTask => match key.code {
             KeyCode::Char('y') => {
                 if let Err(e) = app.delete_task() {
-                    log_ui_error(app, "Failed to delete task", e.as_ref());
+                    log_ui_error(app, "Failed to delete task", &e);
                 }
                 app.input_mode = InputMode::Normal;
             }
             KeyCode::Char('n') | KeyCode::Esc => app.input_mode = InputMode::Normal,
             _ => {}
         },
+        InputMode::DeletingMultipleTasks => match key.code {
+            KeyCode::Esc => app.close_delete_multiple(),
+            KeyCode::Enter => app.request_delete_marked(),
+            KeyCode::Char(' ') => app.toggle_delete_mark(),
+            KeyCode::Char('a') => app.mark_all_for_delete(),
+            KeyCode::Char('n') => app.clear_delete_marks(),
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Err(e) = app.move_down_or_next_page() {
+                    log_ui_error(app, "Failed to load the next page", &e);
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Err(e) = app.move_up_or_previous_page() {
+                    log_ui_error(app, "Failed to load the previous page", &e);
+                }
+            }
+            _ => {}
+        },
+        InputMode::ConfirmingDeleteMultipleTasks => match key.code {
+            KeyCode::Char('y') => {
+                if let Err(e) = app.delete_marked_tasks() {
+                    log_ui_error(app, "Failed to delete tasks", &e);
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => app.cancel_delete_marked(),
+            _ => {}
+        },
         InputMode::DeleteSpecialTask => match key.code {
             KeyCode::Char('y') => {
                 if let Err(e) = app.delete_special_task() {
-                    log_ui_error(app, "Failed to delete task", e.as_ref());
+                    log_ui_error(app, "Failed to delete task", &e);
                 }
                 app.input_mode = InputMode::ViewingSpecialTopics;
             }
@@ -492,26 +1291,65 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<UiAction, Box<dyn std:
             _ => {}
         },
         InputMode::AddingTopic => match key.code {
+            KeyCode::Enter => app.begin_topic_description(),
+            KeyCode::Esc => app.cancel_add_topic(),
+            KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(exit_action(app))
+            }
+            KeyCode::Char(c) => app.input.push(c),
+            KeyCode::Backspace => {
+                app.input.pop();
+            }
+            _ => {}
+        },
+        InputMode::AddingTopicDescription => match key.code {
             KeyCode::Enter => {
-                if !app.input.is_empty() {
-                    let input_clone = app.input.clone();
-                    if let Err(e) = app.add_topic(&input_clone) {
-                        log_ui_error(app, "Failed to add topic", e.as_ref());
-                    } else {
-                        app.add_log("INFO", &format!("Added topic: {}", app.input));
-                    }
+                if let Err(e) = app.submit_new_topic() {
+                    log_ui_error(app, "Failed to add topic", &e);
+                }
+            }
+            KeyCode::Esc => app.cancel_add_topic(),
+            KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(exit_action(app))
+            }
+            KeyCode::Char(c) => app.input.push(c),
+            KeyCode::Backspace => {
+                app.input.pop();
+            }
+            _ => {}
+        },
+        InputMode::AddingSubtask => match key.code {
+            KeyCode::Enter => {
+                let name = std::mem::take(&mut app.input);
+                if let Err(e) = app.add_subtask(&name) {
+                    log_ui_error(app, "Failed to add subtask", &e);
                 }
                 app.input_mode = InputMode::Normal;
             }
-            KeyCode::Esc => app.input_mode = InputMode::Normal,
+            KeyCode::Esc => {
+                app.input.clear();
+                app.input_mode = InputMode::Normal;
+            }
+            KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(exit_action(app))
+            }
             KeyCode::Char(c) => app.input.push(c),
             KeyCode::Backspace => {
                 app.input.pop();
             }
             _ => {}
         },
+        InputMode::ShowingError => app.dismiss_error_popup(),
+        InputMode::ViewingTopicStats => app.close_topic_stats(),
+        InputMode::ConfirmingExit => match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => return Ok(UiAction::Exit),
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.cancel_exit(),
+            _ => {}
+        },
         InputMode::Help => match key.code {
-            KeyCode::Esc | KeyCode::Char('H') => app.input_mode = InputMode::Normal,
+            KeyCode::Esc | KeyCode::Char('H') | KeyCode::Char('?') => {
+                app.input_mode = InputMode::Normal
+            }
             KeyCode::Char(':') => app.begin_command_palette(),
             _ => {}
         },
@@ -523,7 +1361,7 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<UiAction, Box<dyn std:
                     app.special_task_selected = 0;
                     if let Err(e) = app.load_special_tasks() {
                         app.special_tab_selected += 1;
-                        log_ui_error(app, "Failed to load special tasks", e.as_ref());
+                        log_ui_error(app, "Failed to load special tasks", &e);
                     }
                 }
             }
@@ -533,7 +1371,7 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<UiAction, Box<dyn std:
                     app.special_task_selected = 0;
                     if let Err(e) = app.load_special_tasks() {
                         app.special_tab_selected -= 1;
-                        log_ui_error(app, "Failed to load special tasks", e.as_ref());
+                        log_ui_error(app, "Failed to load special tasks", &e);
                     }
                 }
             }
@@ -554,16 +1392,16 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<UiAction, Box<dyn std:
             }
             KeyCode::Char('t') => {
                 if let Err(e) = app.toggle_special_task() {
-                    log_ui_error(app, "Failed to toggle task", e.as_ref());
+                    log_ui_error(app, "Failed to toggle task", &e);
                 }
             }
             KeyCode::Char('f') => {
                 if let Err(e) = app.toggle_special_favourite() {
-                    log_ui_error(app, "Failed to toggle favourite", e.as_ref());
+                    log_ui_error(app, "Failed to toggle favourite", &e);
                 }
             }
             KeyCode::Char('d') => app.begin_delete_special_task(),
-            KeyCode::Char('p') => app.begin_special_task_presets(),
+            KeyCode::Char('P') => app.begin_special_task_presets(),
             KeyCode::Char('/') => app.begin_special_task_filter(),
             KeyCode::Esc => app.input_mode = InputMode::Normal,
             _ => {}
@@ -575,11 +1413,12 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<UiAction, Box<dyn std:
 
 #[cfg(test)]
 mod tests {
-    use super::handle_key;
-    use crate::task_manager::app::{App, InputMode};
+    use super::{execute_task_palette_command, handle_key};
+    use crate::config::LayoutStyle;
+    use crate::task_manager::app::{App, Clipboard, InputMode};
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
     use std::path::PathBuf;
-    use std::time::{SystemTime, UNIX_EPOCH};
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
     fn temp_db_path(prefix: &str) -> PathBuf {
         let unique = format!(
@@ -594,6 +1433,407 @@ mod tests {
         std::env::temp_dir().join(format!("task_manager_cli_task_ui_{unique}.db"))
     }
 
+    #[test]
+    fn question_mark_opens_and_closes_help() -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("help_alias");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+
+        let question_mark = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT);
+        handle_key(&mut app, question_mark)?;
+        assert_eq!(app.input_mode, InputMode::Help);
+        handle_key(&mut app, question_mark)?;
+        assert_eq!(app.input_mode, InputMode::Normal);
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn shift_p_opens_presets_in_normal_and_special_views() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let db_path = temp_db_path("presets_key");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        let shift_p = KeyEvent::new(KeyCode::Char('P'), KeyModifiers::SHIFT);
+
+        handle_key(&mut app, shift_p)?;
+        assert_eq!(app.input_mode, InputMode::PresetFilters);
+
+        app.input_mode = InputMode::ViewingSpecialTopics;
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE),
+        )?;
+        assert_eq!(app.input_mode, InputMode::ViewingSpecialTopics);
+        handle_key(&mut app, shift_p)?;
+        assert_eq!(app.input_mode, InputMode::PresetSpecialFilters);
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn enter_action_swaps_enter_and_space() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::config::EnterAction;
+
+        let db_path = temp_db_path("enter_action");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        app.config.sound_enabled = false;
+        app.add_task_with_details("Ship it", "")?;
+        let task_id = app.tasks[0].id;
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let space = KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE);
+
+        handle_key(&mut app, enter)?;
+        assert!(app.expanded.contains(&task_id));
+        handle_key(&mut app, space)?;
+        assert!(app.tasks[0].completed);
+
+        app.config.enter_action = EnterAction::ToggleComplete;
+        handle_key(&mut app, enter)?;
+        assert!(!app.tasks[0].completed);
+        handle_key(&mut app, space)?;
+        assert!(!app.expanded.contains(&task_id));
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    /// Keeps yanks in the app's internal clipboard only.
+    struct DiscardingClipboard;
+
+    impl Clipboard for DiscardingClipboard {
+        fn copy_text(&mut self, _text: &str) -> Result<(), arboard::Error> {
+            Ok(())
+        }
+
+        fn paste_text(&mut self) -> Result<String, arboard::Error> {
+            Ok(String::new())
+        }
+    }
+
+    #[test]
+    fn clipboard_picker_pastes_into_inputs_and_jumps_to_tasks(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("clipboard_history");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        app.system_clipboard = Box::new(DiscardingClipboard);
+        for name in ["Alpha report", "Beta launch", "Gamma review"] {
+            app.add_task_with_details(name, "")?;
+        }
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        let ctrl_y = key(KeyCode::Char('y'), KeyModifiers::CONTROL);
+        let enter = key(KeyCode::Enter, KeyModifiers::NONE);
+
+        for index in [0, 1, 0] {
+            app.selected = index;
+            handle_key(&mut app, key(KeyCode::Char('y'), KeyModifiers::NONE))?;
+        }
+        assert_eq!(app.clipboard_history, vec!["Alpha report", "Beta launch"]);
+
+        handle_key(&mut app, ctrl_y)?;
+        assert_eq!(app.input_mode, InputMode::ChoosingClipboard);
+        handle_key(&mut app, key(KeyCode::Char('j'), KeyModifiers::NONE))?;
+        handle_key(&mut app, enter)?;
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(app.clipboard, "Beta launch");
+        assert_eq!(app.tasks[app.selected].name, "Beta launch");
+
+        app.begin_add_task();
+        handle_key(&mut app, key(KeyCode::Char('x'), KeyModifiers::NONE))?;
+        handle_key(&mut app, ctrl_y)?;
+        handle_key(&mut app, enter)?;
+        assert_eq!(app.input_mode, InputMode::AddingTaskName);
+        assert_eq!(app.task_name_input, "xAlpha report");
+
+        handle_key(&mut app, ctrl_y)?;
+        handle_key(&mut app, key(KeyCode::Esc, KeyModifiers::NONE))?;
+        assert_eq!(app.input_mode, InputMode::AddingTaskName);
+        assert_eq!(app.task_name_input, "xAlpha report");
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn rename_popup_changes_only_the_name() -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("rename_task");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        app.add_task_with_details("Draft", "Keep this description")?;
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        handle_key(&mut app, key(KeyCode::Char('R')))?;
+        assert_eq!(app.input_mode, InputMode::RenamingTask);
+        assert_eq!(app.rename_input, "Draft");
+
+        for _ in 0.."Draft".len() {
+            handle_key(&mut app, key(KeyCode::Backspace))?;
+        }
+        handle_key(&mut app, key(KeyCode::Enter))?;
+        assert_eq!(app.input_mode, InputMode::RenamingTask);
+        assert_eq!(
+            app.task_form_message.as_deref(),
+            Some("Task name cannot be empty")
+        );
+
+        for c in "Final".chars() {
+            handle_key(&mut app, key(KeyCode::Char(c)))?;
+        }
+        assert_eq!(app.task_form_message, None);
+        handle_key(&mut app, key(KeyCode::Enter))?;
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(app.tasks[app.selected].name, "Final");
+        assert_eq!(app.tasks[app.selected].description, "Keep this description");
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn sidebar_layout_moves_focus_with_h_l_and_topics_with_j_k(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("sidebar_keys");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        app.config.layout = LayoutStyle::SidebarLeft;
+        app.add_topic("Work")?;
+        app.add_topic("Home")?;
+        app.selected_topic = 0;
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        // Without focus, `l` and `j` stay on the task list.
+        handle_key(&mut app, key(KeyCode::Char('l')))?;
+        handle_key(&mut app, key(KeyCode::Char('j')))?;
+        assert_eq!(app.selected_topic, 0);
+
+        handle_key(&mut app, key(KeyCode::Char('h')))?;
+        assert!(app.sidebar_has_focus());
+        handle_key(&mut app, key(KeyCode::Char('j')))?;
+        handle_key(&mut app, key(KeyCode::Char('j')))?;
+        handle_key(&mut app, key(KeyCode::Char('k')))?;
+        assert_eq!(app.selected_topic, 1);
+
+        handle_key(&mut app, key(KeyCode::Char('l')))?;
+        assert!(!app.sidebar_has_focus());
+        assert_eq!(app.selected_topic, 1);
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn topic_task_limit_is_set_from_the_palette() -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("topic_task_limit_popup");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        app.add_topic("Work")?;
+        app.selected_topic = app
+            .topics
+            .iter()
+            .position(|topic| topic.name == "Work")
+            .expect("topic should exist");
+        let press = |app: &mut App, code: KeyCode| {
+            handle_key(app, KeyEvent::new(code, KeyModifiers::NONE)).map(|_| ())
+        };
+
+        execute_task_palette_command(&mut app, "topic_task_limit")?;
+        assert_eq!(app.input_mode, InputMode::SettingTopicTaskLimit);
+        press(&mut app, KeyCode::Char('x'))?;
+        press(&mut app, KeyCode::Enter)?;
+        assert_eq!(app.input_mode, InputMode::SettingTopicTaskLimit);
+        assert!(app.topic_setting_message.is_some());
+        press(&mut app, KeyCode::Backspace)?;
+        press(&mut app, KeyCode::Char('0'))?;
+        press(&mut app, KeyCode::Enter)?;
+        assert_eq!(app.input_mode, InputMode::SettingTopicTaskLimit);
+        assert!(app.topic_setting_message.is_some());
+        press(&mut app, KeyCode::Backspace)?;
+        press(&mut app, KeyCode::Char('3'))?;
+        press(&mut app, KeyCode::Enter)?;
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(app.topics[app.selected_topic].task_limit, Some(3));
+
+        app.begin_set_topic_task_limit();
+        assert_eq!(app.topic_setting_input, "3");
+        press(&mut app, KeyCode::Backspace)?;
+        press(&mut app, KeyCode::Enter)?;
+        assert_eq!(app.topics[app.selected_topic].task_limit, None);
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn topic_alias_jumps_only_on_unbound_keys() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::common::logs::LogLevel;
+
+        let db_path = temp_db_path("topic_alias");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        app.add_topic("Work")?;
+        app.add_topic("Home")?;
+        let position = |app: &App, name: &str| {
+            app.topics
+                .iter()
+                .position(|topic| topic.name == name)
+                .expect("topic should exist")
+        };
+
+        let press = |app: &mut App, code: KeyCode| {
+            handle_key(app, KeyEvent::new(code, KeyModifiers::NONE)).map(|_| ())
+        };
+        app.selected_topic = position(&app, "Work");
+        app.begin_set_topic_alias();
+        assert_eq!(app.input_mode, InputMode::SettingTopicAlias);
+        press(&mut app, KeyCode::Char('w'))?;
+        press(&mut app, KeyCode::Char('x'))?;
+        press(&mut app, KeyCode::Enter)?;
+        assert_eq!(app.input_mode, InputMode::SettingTopicAlias);
+        assert!(app.topic_setting_message.is_some());
+        press(&mut app, KeyCode::Backspace)?;
+        press(&mut app, KeyCode::Enter)?;
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(app.topics[position(&app, "Work")].alias_char(), Some('w'));
+        app.selected_topic = position(&app, "Home");
+        // `a` adds a task, so it is refused as an alias, inline.
+        assert!(app.set_topic_alias(Some('a')).is_err());
+        app.begin_set_topic_alias();
+        press(&mut app, KeyCode::Char('a'))?;
+        press(&mut app, KeyCode::Enter)?;
+        assert_eq!(app.input_mode, InputMode::SettingTopicAlias);
+        assert_eq!(
+            app.topic_setting_message.as_deref(),
+            Some("'a' is already a Normal mode key")
+        );
+        app.cancel_topic_setting();
+        assert_eq!(app.topics[position(&app, "Home")].alias_char(), None);
+
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE),
+        )?;
+        assert_eq!(app.topics[app.selected_topic].name, "Work");
+
+        app.selected_topic = position(&app, "Home");
+        app.set_topic_alias(Some('w'))?;
+        assert_eq!(
+            app.topic_alias_conflicts(),
+            vec![('w', vec!["Work".to_string(), "Home".to_string()])]
+        );
+        let conflict_warnings = |app: &App| {
+            app.logs
+                .iter()
+                .filter(|entry| {
+                    entry.level == LogLevel::Warn && entry.message.contains("Alias 'w'")
+                })
+                .count()
+        };
+        assert_eq!(conflict_warnings(&app), 1);
+        app.load_topics()?;
+        assert_eq!(conflict_warnings(&app), 1);
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE),
+        )?;
+        assert_eq!(app.topics[app.selected_topic].name, "Work");
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn edit_popup_tabs_between_fields_and_saves_both() -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("edit_two_fields");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        app.add_task_with_details("Draft", "old notes")?;
+        let press = |app: &mut App, code: KeyCode| {
+            handle_key(app, KeyEvent::new(code, KeyModifiers::NONE)).map(|_| ())
+        };
+
+        press(&mut app, KeyCode::Char('e'))?;
+        assert_eq!(app.input_mode, InputMode::EditingTaskName);
+        assert_eq!(app.task_description_input, "old notes");
+        press(&mut app, KeyCode::Char('!'))?;
+        press(&mut app, KeyCode::Tab)?;
+        assert_eq!(app.input_mode, InputMode::MultilineEditDescription);
+        press(&mut app, KeyCode::Backspace)?;
+        press(&mut app, KeyCode::Tab)?;
+        assert_eq!(app.input_mode, InputMode::EditingTaskName);
+        press(&mut app, KeyCode::Tab)?;
+        assert_eq!(app.input_mode, InputMode::MultilineEditDescription);
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
+        )?;
+
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(app.tasks[0].name, "Draft!");
+        assert_eq!(app.tasks[0].description, "old note");
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn multiline_description_editor_moves_splits_and_joins_lines(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("multiline_editor");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        app.add_task_with_details("Draft", "héllo world")?;
+        let press = |app: &mut App, code: KeyCode| {
+            handle_key(app, KeyEvent::new(code, KeyModifiers::NONE)).map(|_| ())
+        };
+
+        press(&mut app, KeyCode::Char('e'))?;
+        press(&mut app, KeyCode::Enter)?;
+        assert_eq!(app.input_mode, InputMode::MultilineEditDescription);
+        assert_eq!(app.ml_editor_lines, vec!["héllo world"]);
+        assert_eq!(app.ml_editor_cursor, (0, 11));
+
+        for _ in 0..6 {
+            press(&mut app, KeyCode::Left)?;
+        }
+        press(&mut app, KeyCode::Enter)?;
+        assert_eq!(app.ml_editor_lines, vec!["héllo", " world"]);
+        assert_eq!(app.ml_editor_cursor, (1, 0));
+        press(&mut app, KeyCode::Right)?;
+        press(&mut app, KeyCode::Backspace)?;
+        press(&mut app, KeyCode::Up)?;
+        assert_eq!(app.ml_editor_cursor, (0, 0));
+        press(&mut app, KeyCode::Down)?;
+        press(&mut app, KeyCode::Char('!'))?;
+        assert_eq!(app.ml_editor_lines, vec!["héllo", "!world"]);
+
+        press(&mut app, KeyCode::Left)?;
+        press(&mut app, KeyCode::Backspace)?;
+        assert_eq!(app.ml_editor_lines, vec!["héllo!world"]);
+        assert_eq!(app.ml_editor_cursor, (0, 5));
+        press(&mut app, KeyCode::Enter)?;
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
+        )?;
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(app.tasks[0].description, "héllo\n!world");
+
+        press(&mut app, KeyCode::Char('e'))?;
+        press(&mut app, KeyCode::Tab)?;
+        assert_eq!(app.ml_editor_lines, vec!["héllo", "!world"]);
+        press(&mut app, KeyCode::Char('x'))?;
+        press(&mut app, KeyCode::Esc)?;
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(app.tasks[0].description, "héllo\n!world");
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
     #[test]
     fn command_palette_treats_j_and_k_as_query_text() -> Result<(), Box<dyn std::error::Error>> {
         let db_path = temp_db_path("palette_jk");
@@ -616,4 +1856,106 @@ mod tests {
         let _ = std::fs::remove_file(db_path);
         Ok(())
     }
+
+    #[test]
+    fn gg_g_and_count_prefix_jump_within_the_task_list() -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("vim_jumps");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        for name in ["one", "two", "three", "four"] {
+            app.add_task_with_details(name, "")?;
+        }
+        let press = |app: &mut App, c: char| {
+            handle_key(app, KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)).map(|_| ())
+        };
+        let selected_name = |app: &App| app.tasks[app.selected].name.clone();
+
+        press(&mut app, 'G')?;
+        assert_eq!(selected_name(&app), "four");
+
+        press(&mut app, 'g')?;
+        assert_eq!(
+            app.pending_key.map(|(code, _)| code),
+            Some(KeyCode::Char('g'))
+        );
+        press(&mut app, 'g')?;
+        assert_eq!(selected_name(&app), "one");
+        assert_eq!(app.pending_key, None);
+
+        press(&mut app, '2')?;
+        press(&mut app, 'G')?;
+        assert_eq!(selected_name(&app), "two");
+
+        press(&mut app, '1')?;
+        press(&mut app, '0')?;
+        press(&mut app, 'g')?;
+        press(&mut app, 'g')?;
+        assert_eq!(selected_name(&app), "four");
+        assert_eq!(app.count_prefix, None);
+
+        // A tick between the two presses does not cancel the sequence...
+        press(&mut app, 'G')?;
+        press(&mut app, 'g')?;
+        app.on_tick();
+        press(&mut app, 'g')?;
+        assert_eq!(selected_name(&app), "one");
+
+        // ...but a first `g` pressed too long ago does.
+        press(&mut app, 'G')?;
+        let long_ago = Instant::now()
+            .checked_sub(Duration::from_secs(5))
+            .expect("clock should be past five seconds");
+        app.pending_key = Some((KeyCode::Char('g'), long_ago));
+        press(&mut app, 'g')?;
+        assert_eq!(selected_name(&app), "four");
+        assert_eq!(
+            app.pending_key.map(|(code, _)| code),
+            Some(KeyCode::Char('g'))
+        );
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
+
+    #[test]
+    fn ctrl_slash_searches_logs_and_esc_clears() -> Result<(), Box<dyn std::error::Error>> {
+        let db_path = temp_db_path("log_search");
+        let db_path_str = db_path.to_string_lossy().to_string();
+        let mut app = App::new(&db_path_str)?;
+        app.logs.clear();
+        app.add_log("INFO", "Saved task Alpha");
+        app.add_log("WARN", "Could not reach server");
+        app.add_log("INFO", "saved task beta");
+
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('7'), KeyModifiers::CONTROL),
+        )?;
+        assert_eq!(app.input_mode, InputMode::SearchingLogs);
+        assert_eq!(app.count_prefix, None);
+        for c in "SAVED".chars() {
+            handle_key(
+                &mut app,
+                KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+            )?;
+        }
+        assert_eq!(app.log_filtered, vec![0, 2]);
+        assert_eq!(app.visible_logs().len(), 2);
+
+        app.add_log("INFO", "Unrelated");
+        assert_eq!(app.visible_logs().len(), 2);
+
+        // A query of only spaces filters nothing.
+        app.log_search_query = "  ".to_string();
+        app.add_log("INFO", "Another");
+        assert_eq!(app.visible_logs().len(), 5);
+
+        handle_key(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))?;
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(app.log_search_query.is_empty());
+        assert_eq!(app.visible_logs().len(), 5);
+
+        let _ = std::fs::remove_file(db_path);
+        Ok(())
+    }
 }
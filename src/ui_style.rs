@@ -33,6 +33,40 @@ pub fn accent_color(accent: Accent) -> Color {
     }
 }
 
+/// Parses a user-supplied colour name (`red`, `light-blue`) or `#rrggbb` hex.
+pub fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim().to_lowercase().replace(['-', '_', ' '], "");
+    if let Some(hex) = value.strip_prefix('#') {
+        // Only ASCII hex digits, so the byte slices below stay on char boundaries.
+        if hex.len() != 6 || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return None;
+        }
+        let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+        let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+        let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+        return Some(Color::Rgb(r, g, b));
+    }
+    Some(match value.as_str() {
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return None,
+    })
+}
+
 pub fn surface_block<'a>(title: &'a str, accent: Accent) -> Block<'a> {
     Block::default()
         .borders(Borders::ALL)
@@ -0,0 +1,155 @@
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
+
+/// Timestamp format used for `created_at` / `updated_at` columns.
+pub const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Format of the `due_date` column.
+pub const DUE_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Coarse, human-readable age such as `"3 days ago"`.
+pub fn format_duration(d: Duration) -> String {
+    let seconds = d.num_seconds();
+    let (count, unit) = if seconds < 60 {
+        return "just now".to_string();
+    } else if seconds < 60 * 60 {
+        (d.num_minutes(), "minute")
+    } else if seconds < 24 * 60 * 60 {
+        (d.num_hours(), "hour")
+    } else if d.num_days() < 30 {
+        (d.num_days(), "day")
+    } else if d.num_days() < 365 {
+        (d.num_days() / 30, "month")
+    } else {
+        (d.num_days() / 365, "year")
+    };
+
+    format!(
+        "{} {}{} ago",
+        count,
+        unit,
+        if count == 1 { "" } else { "s" }
+    )
+}
+
+/// Age of a stored local timestamp relative to now; unparseable values are
+/// returned unchanged.
+pub fn format_age(timestamp: &str) -> String {
+    NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+        .ok()
+        .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+        .map(|then| format_duration(Local::now().signed_duration_since(then)))
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+/// A stored local timestamp as ISO-8601 with its UTC offset, e.g.
+/// `2026-10-15T09:30:00+02:00`; unparseable values are returned unchanged.
+pub fn to_iso8601(timestamp: &str) -> String {
+    NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+        .ok()
+        .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+        .map(|local| local.to_rfc3339())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+/// `"due today"`, `"due in 3 days"` or `"2 days overdue"` relative to `today`;
+/// unparseable values are returned unchanged.
+pub fn format_due(due_date: &str, today: NaiveDate) -> String {
+    let Ok(due) = NaiveDate::parse_from_str(due_date, DUE_DATE_FORMAT) else {
+        return due_date.to_string();
+    };
+    let days = (due - today).num_days();
+    let plural = |n: i64| if n == 1 { "" } else { "s" };
+    match days {
+        0 => "due today".to_string(),
+        d if d > 0 => format!("due in {} day{}", d, plural(d)),
+        d => format!("{} day{} overdue", -d, plural(-d)),
+    }
+}
+
+/// File size such as `"512 B"`, `"1.5 KB"` or `"2.0 MB"` (powers of 1024).
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Number of whitespace-separated words in `s`.
+pub fn word_count(s: &str) -> usize {
+    s.split_whitespace().count()
+}
+
+/// Whole minutes needed to read `words` at 200 words per minute, rounded up.
+pub fn reading_minutes(words: usize) -> usize {
+    words.div_ceil(200)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        format_age, format_bytes, format_due, format_duration, reading_minutes, word_count,
+    };
+    use chrono::{Duration, NaiveDate};
+
+    #[test]
+    fn format_duration_picks_the_largest_whole_unit() {
+        let cases = [
+            (Duration::seconds(-5), "just now"),
+            (Duration::seconds(59), "just now"),
+            (Duration::seconds(60), "1 minute ago"),
+            (Duration::minutes(59), "59 minutes ago"),
+            (Duration::hours(1), "1 hour ago"),
+            (Duration::hours(23), "23 hours ago"),
+            (Duration::days(3), "3 days ago"),
+            (Duration::days(29), "29 days ago"),
+            (Duration::days(45), "1 month ago"),
+            (Duration::days(364), "12 months ago"),
+            (Duration::days(365), "1 year ago"),
+            (Duration::days(800), "2 years ago"),
+        ];
+        for (duration, expected) in cases {
+            assert_eq!(format_duration(duration), expected, "{duration:?}");
+        }
+    }
+
+    #[test]
+    fn format_age_falls_back_to_the_raw_value() {
+        assert_eq!(format_age("not a date"), "not a date");
+        assert!(format_age("2001-02-03 04:05:06").ends_with("years ago"));
+    }
+
+    #[test]
+    fn word_count_splits_on_unicode_whitespace() {
+        assert_eq!(word_count(""), 0);
+        assert_eq!(word_count("  one\ttwo\n three\u{3000}four  "), 4);
+        assert_eq!(reading_minutes(0), 0);
+        assert_eq!(reading_minutes(1), 1);
+        assert_eq!(reading_minutes(200), 1);
+        assert_eq!(reading_minutes(201), 2);
+    }
+
+    #[test]
+    fn format_due_counts_days_from_today() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 15).expect("valid date");
+        assert_eq!(format_due("2026-10-15", today), "due today");
+        assert_eq!(format_due("2026-10-16", today), "due in 1 day");
+        assert_eq!(format_due("2026-10-22", today), "due in 7 days");
+        assert_eq!(format_due("2026-10-13", today), "2 days overdue");
+        assert_eq!(format_due("next week", today), "next week");
+    }
+
+    #[test]
+    fn format_bytes_uses_binary_units() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+}
@@ -0,0 +1,157 @@
+//! `task_manager_cli watch-db`: polls the task table and prints a line for
+//! every task created, changed or deleted since the previous poll.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::Write,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::db::task_manager::models::{Task, TopicOrder};
+use crate::db::task_manager::operations::DbOperations;
+use crate::error::AppError;
+
+/// Default `--interval`, in milliseconds.
+pub const DEFAULT_INTERVAL_MS: u64 = 500;
+
+/// How often a sleeping poll loop checks whether it was asked to stop.
+const STOP_CHECK: Duration = Duration::from_millis(50);
+
+/// One line of `watch-db` output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskChange {
+    Created {
+        id: i32,
+        name: String,
+        topic: String,
+    },
+    Updated {
+        id: i32,
+        field: &'static str,
+    },
+    Deleted {
+        id: i32,
+    },
+}
+
+impl fmt::Display for TaskChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskChange::Created { id, name, topic } => {
+                write!(f, "[CREATED] id={id} name={name:?} topic={topic:?}")
+            }
+            TaskChange::Updated { id, field } => write!(f, "[UPDATED] id={id} field={field}"),
+            TaskChange::Deleted { id } => write!(f, "[DELETED] id={id}"),
+        }
+    }
+}
+
+/// The task table as of the last poll, keyed by id.
+pub type Snapshot = BTreeMap<i32, Task>;
+
+pub fn snapshot(db_ops: &DbOperations) -> Result<Snapshot, AppError> {
+    Ok(db_ops
+        .load_all_tasks()?
+        .into_iter()
+        .map(|task| (task.id, task))
+        .collect())
+}
+
+/// Names of the fields that differ between two versions of a task. Not
+/// every edit bumps `updated_at` (URLs, due dates and pins don't), so the
+/// fields themselves are compared.
+fn changed_fields(old: &Task, new: &Task) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    let mut check = |changed: bool, field| {
+        if changed {
+            fields.push(field);
+        }
+    };
+    check(old.name != new.name, "name");
+    check(old.description != new.description, "description");
+    check(old.topic_id != new.topic_id, "topic");
+    check(old.completed != new.completed, "completed");
+    check(old.favourite != new.favourite, "favourite");
+    check(old.pinned != new.pinned, "pinned");
+    check(old.url != new.url, "url");
+    check(old.due_date != new.due_date, "due_date");
+    check(old.sort_weight != new.sort_weight, "sort_weight");
+    check(old.archived != new.archived, "archived");
+    fields
+}
+
+/// Changes from `old` to `new`, in id order. `topic_name` resolves the topic
+/// of created tasks.
+pub fn diff(old: &Snapshot, new: &Snapshot, topic_name: impl Fn(i32) -> String) -> Vec<TaskChange> {
+    let mut changes = Vec::new();
+    for (&id, task) in new {
+        match old.get(&id) {
+            None => changes.push(TaskChange::Created {
+                id,
+                name: task.name.clone(),
+                topic: topic_name(task.topic_id),
+            }),
+            Some(previous) => changes.extend(
+                changed_fields(previous, task)
+                    .into_iter()
+                    .map(|field| TaskChange::Updated { id, field }),
+            ),
+        }
+    }
+    changes.extend(
+        old.keys()
+            .filter(|id| !new.contains_key(id))
+            .map(|&id| TaskChange::Deleted { id }),
+    );
+    changes.sort_by_key(|change| match change {
+        TaskChange::Created { id, .. }
+        | TaskChange::Updated { id, .. }
+        | TaskChange::Deleted { id } => *id,
+    });
+    changes
+}
+
+/// Polls every `interval` until `stop` is set, printing each change to
+/// `out` as it is seen. Tasks that exist when watching starts are not
+/// reported.
+pub fn run(
+    db_ops: &DbOperations,
+    out: &mut impl Write,
+    interval: Duration,
+    stop: &AtomicBool,
+) -> Result<i32, AppError> {
+    let mut previous = snapshot(db_ops)?;
+    while !stop.load(Ordering::Relaxed) {
+        let deadline = Instant::now() + interval;
+        while !stop.load(Ordering::Relaxed) && Instant::now() < deadline {
+            thread::sleep(STOP_CHECK.min(deadline.saturating_duration_since(Instant::now())));
+        }
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let current = snapshot(db_ops)?;
+        // Topic names are only needed to report new tasks.
+        let topics = if current.keys().any(|id| !previous.contains_key(id)) {
+            db_ops.load_topics(TopicOrder::Manual)?
+        } else {
+            Vec::new()
+        };
+        let topic_name = |topic_id: i32| {
+            topics
+                .iter()
+                .find(|topic| topic.id == topic_id)
+                .map(|topic| topic.name.clone())
+                .unwrap_or_default()
+        };
+        for change in diff(&previous, &current, topic_name) {
+            writeln!(out, "{change}")?;
+        }
+        out.flush()?;
+        previous = current;
+    }
+    Ok(0)
+}
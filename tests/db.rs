@@ -1,4 +1,20 @@
 #[path = "db/app_lifecycle.rs"]
 mod app_lifecycle;
+#[path = "db/bench.rs"]
+mod bench;
 #[path = "common/mod.rs"]
 mod common;
+#[path = "db/doctor.rs"]
+mod doctor;
+#[path = "db/errors.rs"]
+mod errors;
+#[path = "db/migrate.rs"]
+mod migrate;
+#[path = "db/reset.rs"]
+mod reset;
+#[path = "db/slow_query.rs"]
+mod slow_query;
+#[path = "db/topics.rs"]
+mod topics;
+#[path = "db/watch.rs"]
+mod watch;
@@ -65,3 +65,80 @@ fn notes_app_supports_basic_note_lifecycle() -> Result<(), Box<dyn std::error::E
     let _ = std::fs::remove_dir_all(notes_root);
     Ok(())
 }
+
+#[test]
+fn count_tasks_by_topic_groups_in_one_query() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("task_counts");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    let app = TaskManagerApp::new(&db_path_str)?;
+    let work = app.db_ops.add_topic("Work", "")?;
+    let home = app.db_ops.add_topic("Home", "")?;
+    let empty = app.db_ops.add_topic("Empty", "")?;
+    app.db_ops.add_task(work.id, "Report", "")?;
+    app.db_ops.add_task(work.id, "Review", "")?;
+    app.db_ops.add_task(home.id, "Laundry", "")?;
+
+    let counts = app.db_ops.count_tasks_by_topic()?;
+    assert_eq!(counts.get(&work.id), Some(&2));
+    assert_eq!(counts.get(&home.id), Some(&1));
+    assert_eq!(counts.get(&empty.id), None);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn task_links_are_read_from_both_sides_and_warn_on_completion(
+) -> Result<(), Box<dyn std::error::Error>> {
+    use task_manager_cli::db::task_manager::models::LinkType;
+
+    let db_path = temp_db_path("task_links");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = TaskManagerApp::new(&db_path_str)?;
+    app.add_topic("Release")?;
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Release")
+        .expect("topic should exist");
+    app.add_task_with_details("Ship", "")?;
+    app.add_task_with_details("Write changelog", "")?;
+    let ship = app.tasks.iter().find(|t| t.name == "Ship").unwrap().id;
+    let changelog = app
+        .tasks
+        .iter()
+        .find(|t| t.name == "Write changelog")
+        .unwrap()
+        .id;
+
+    assert!(app.db_ops.add_link(ship, ship, LinkType::Blocks).is_err());
+    let link = app.db_ops.add_link(changelog, ship, LinkType::Blocks)?;
+    assert_eq!(app.db_ops.load_links(ship)?.len(), 1);
+    app.load_tasks()?;
+
+    let ship_links = &app.task_links[&ship];
+    assert_eq!(ship_links[0].link_type, LinkType::BlockedBy);
+    assert_eq!(ship_links[0].name, "Write changelog");
+    assert_eq!(app.task_links[&changelog][0].link_type, LinkType::Blocks);
+
+    app.selected = app.tasks.iter().position(|t| t.id == ship).unwrap();
+    app.toggle_task()?;
+    assert!(app.tasks.iter().find(|t| t.id == ship).unwrap().completed);
+    assert!(app
+        .logs
+        .iter()
+        .any(|entry| entry.message.contains("still blocked by: Write changelog")));
+
+    assert_eq!(app.db_ops.remove_link(link.id)?, 1);
+    assert!(app.db_ops.load_links(ship)?.is_empty());
+
+    // Deleting either end removes the link row with it.
+    let link = app.db_ops.add_link(changelog, ship, LinkType::Blocks)?;
+    app.db_ops.delete_task(changelog)?;
+    assert!(app.db_ops.load_links(ship)?.is_empty());
+    assert_eq!(app.db_ops.remove_link(link.id)?, 0);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
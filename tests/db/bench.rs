@@ -0,0 +1,32 @@
+use task_manager_cli::bench::run_bench;
+use task_manager_cli::cli;
+
+#[test]
+fn bench_times_every_operation_once_per_task() -> Result<(), Box<dyn std::error::Error>> {
+    let timings = run_bench(20)?;
+
+    let names: Vec<&str> = timings.iter().map(|op| op.name).collect();
+    assert_eq!(names, ["insert", "load", "toggle", "delete"]);
+    for op in &timings {
+        assert_eq!(op.samples.len(), 20);
+        assert!(op.min() <= op.average() && op.average() <= op.max());
+    }
+    Ok(())
+}
+
+#[test]
+fn bench_command_prints_a_table_and_checks_the_insert_limit(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut out = Vec::new();
+    assert_eq!(cli::bench(&mut out, 5, Some(60_000.0))?, 0);
+    let output = String::from_utf8(out)?;
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].starts_with("operation"));
+    assert!(lines[1].starts_with("insert"));
+
+    let mut out = Vec::new();
+    assert_eq!(cli::bench(&mut out, 5, Some(-1.0))?, 1);
+    assert!(String::from_utf8(out)?.contains("over the -1 ms limit"));
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use super::common::temp_db_path;
+use task_manager_cli::cli;
+use task_manager_cli::doctor::{self, CheckStatus};
+use task_manager_cli::task_manager::app::App;
+
+#[test]
+fn doctor_fails_without_a_database() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("doctor_missing");
+
+    let mut out = Vec::new();
+    assert_eq!(cli::doctor(&db_path, &mut out)?, 1);
+    let output = String::from_utf8(out)?;
+    assert!(output.starts_with("[FAIL]  database readable"));
+    assert!(output.ends_with("0 ok, 0 warning(s), 1 failed\n"));
+    assert!(!db_path.exists());
+    Ok(())
+}
+
+#[test]
+fn doctor_checks_report_database_problems() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("doctor_checks");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+
+    assert_eq!(doctor::check_db_readable(&db_path).status, CheckStatus::Ok);
+    assert_eq!(doctor::check_db_writable(&db_path).status, CheckStatus::Ok);
+    assert_eq!(doctor::check_pool(&app.db_ops).status, CheckStatus::Ok);
+    assert_eq!(
+        doctor::check_schema_version(&app.db_ops).message,
+        "up to date"
+    );
+    assert_eq!(
+        doctor::check_orphan_tasks(&app.db_ops).status,
+        CheckStatus::Ok
+    );
+    assert_eq!(
+        doctor::check_unnamed_tasks(&app.db_ops).status,
+        CheckStatus::Ok
+    );
+
+    let topic = app.db_ops.add_topic("Work", "")?;
+    app.db_ops.add_task(topic.id, "  ", "")?;
+    let unnamed = doctor::check_unnamed_tasks(&app.db_ops);
+    assert_eq!(unnamed.status, CheckStatus::Warn);
+    assert_eq!(unnamed.message, "1 task(s) have no name");
+
+    let config_path = db_path.with_extension("json");
+    assert_eq!(doctor::check_config(&config_path).status, CheckStatus::Ok);
+    std::fs::write(&config_path, "{ not json")?;
+    let config = doctor::check_config(&config_path);
+    assert_eq!(config.status, CheckStatus::Fail);
+    assert!(config.to_string().starts_with("[FAIL]  config file"));
+
+    let log_file = db_path.with_extension("logs").join("app.log");
+    assert_eq!(doctor::check_log_dir(&log_file).status, CheckStatus::Ok);
+    assert!(log_file.exists());
+
+    drop(app);
+    let _ = std::fs::remove_dir_all(db_path.with_extension("logs"));
+    let _ = std::fs::remove_file(config_path);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use super::common::temp_db_path;
+use task_manager_cli::error::AppError;
+use task_manager_cli::task_manager::app::App;
+
+#[test]
+fn deleting_default_topic_reports_protected_topic() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("protected_topic");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Default")
+        .expect("default topic should exist");
+
+    assert!(matches!(
+        app.delete_topic(),
+        Err(AppError::ProtectedTopic(name)) if name == "Default"
+    ));
+    assert!(app.topics.iter().any(|topic| topic.name == "Default"));
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn missing_records_report_not_found() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("not_found_errors");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+
+    assert!(matches!(
+        app.db_ops.toggle_task_completion(9999),
+        Err(AppError::TaskNotFound(9999))
+    ));
+    assert!(matches!(
+        app.db_ops.delete_topic(9999),
+        Err(AppError::TopicNotFound(9999))
+    ));
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn duplicate_topic_names_are_rejected_by_the_database() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("duplicate_topic");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    app.add_topic("Work")?;
+    assert!(matches!(
+        app.add_topic("Work"),
+        Err(AppError::DuplicateTopicName(name)) if name == "Work"
+    ));
+    assert!(matches!(
+        app.db_ops.add_topic("Work", ""),
+        Err(AppError::Database(_))
+    ));
+    assert!(matches!(
+        app.db_ops.add_topic("WORK", ""),
+        Err(AppError::Database(_))
+    ));
+    assert_eq!(
+        app.topics
+            .iter()
+            .filter(|topic| topic.name == "Work")
+            .count(),
+        1
+    );
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn second_instance_on_the_same_database_is_refused() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("instance_lock");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    let first = App::new(&db_path_str)?;
+    assert!(matches!(
+        App::new(&db_path_str),
+        Err(AppError::AlreadyRunning)
+    ));
+
+    drop(first);
+    assert!(App::new(&db_path_str).is_ok());
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use super::common::temp_db_path;
+use task_manager_cli::cli;
+use task_manager_cli::db::task_manager::models::SortMode;
+use task_manager_cli::task_manager::app::App;
+
+#[test]
+fn migrate_only_lists_applied_migrations_once() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("migrate_only");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    let mut out = Vec::new();
+    assert_eq!(cli::migrate_only(&db_path_str, &mut out, false)?, 0);
+    let output = String::from_utf8(out)?;
+    let applied: Vec<&str> = output
+        .lines()
+        .filter(|line| line.starts_with("Applied "))
+        .collect();
+    assert!(applied.len() > 1);
+    assert!(applied.iter().any(|line| line.contains("099000")));
+    assert!(output.ends_with("Migrations applied successfully\n"));
+
+    let mut out = Vec::new();
+    assert_eq!(cli::migrate_only(&db_path_str, &mut out, false)?, 0);
+    assert_eq!(String::from_utf8(out)?, "Migrations applied successfully\n");
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn quiet_migrate_only_prints_just_the_result() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("migrate_only_quiet");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    let mut out = Vec::new();
+    assert_eq!(cli::migrate_only(&db_path_str, &mut out, true)?, 0);
+    assert_eq!(String::from_utf8(out)?, "Migrations applied successfully\n");
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn maintain_vacuums_and_reports_sizes() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("maintain");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    let mut out = Vec::new();
+    assert_eq!(cli::maintain(&app.db_ops, &mut out, &db_path_str)?, 0);
+    let output = String::from_utf8(out)?;
+    assert!(output.contains("Size before: "));
+    assert!(output.contains("Size after: "));
+
+    app.vacuum_database()?;
+    assert!(app
+        .logs
+        .iter()
+        .any(|entry| entry.message.starts_with("Vacuumed database in ")));
+
+    drop(app);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn app_logs_applied_migrations_and_check_reports_pending() -> Result<(), Box<dyn std::error::Error>>
+{
+    let db_path = temp_db_path("migration_status");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    let app = App::new(&db_path_str)?;
+    let logged = |app: &App, prefix: &str| {
+        app.logs
+            .iter()
+            .any(|entry| entry.message.starts_with(prefix))
+    };
+    assert!(logged(&app, "Applied migration "));
+    assert!(!logged(&app, "Database schema up to date"));
+    drop(app);
+
+    let mut app = App::new(&db_path_str)?;
+    assert!(logged(&app, "Database schema up to date"));
+    assert_eq!(app.db_ops.pending_migration_count()?, 0);
+    app.add_task_with_details("Open", "")?;
+    app.add_task_with_details("Done", "")?;
+    app.selected = app.tasks.iter().position(|t| t.name == "Done").unwrap();
+    app.toggle_task()?;
+    let mut out = Vec::new();
+    cli::check(&app.db_ops, &mut out, &db_path_str)?;
+    let out = String::from_utf8(out)?;
+    assert!(out.contains("Migrations: up to date\n"));
+    assert!(out.contains("Tasks: 2 (1 pending)\n"));
+
+    drop(app);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn topic_task_list_query_uses_an_index() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("explain_load_tasks");
+    let app = App::new(&db_path.to_string_lossy())?;
+
+    let work = app.db_ops.add_topic("Work", "")?;
+    let plan = app
+        .db_ops
+        .explain_task_list_query(&work, SortMode::Created, true)?;
+    assert!(plan.contains("USING INDEX idx_task_topic"), "{plan}");
+    assert!(
+        !plan.lines().any(|line| line.starts_with("SCAN task")),
+        "{plan}"
+    );
+
+    let plan = app
+        .db_ops
+        .explain_query("SELECT * FROM task WHERE favourite = 1")?;
+    assert!(plan.contains("USING INDEX idx_task_favourite"), "{plan}");
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
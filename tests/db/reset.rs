@@ -0,0 +1,31 @@
+use super::common::temp_db_path;
+use task_manager_cli::cli;
+use task_manager_cli::task_manager::app::App;
+
+#[test]
+fn reset_db_requires_exact_confirmation_and_recreates_the_schema(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("reset_db");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    {
+        let mut app = App::new(&db_path_str)?;
+        app.add_topic("Work")?;
+    }
+
+    let mut out = Vec::new();
+    assert_eq!(cli::reset_db(&db_path_str, &mut out, None)?, 1);
+    assert_eq!(cli::reset_db(&db_path_str, &mut out, Some("true"))?, 1);
+    assert!(App::new(&db_path_str)?
+        .topics
+        .iter()
+        .any(|topic| topic.name == "Work"));
+
+    assert_eq!(cli::reset_db(&db_path_str, &mut out, Some("yes"))?, 0);
+    assert_eq!(String::from_utf8(out)?, "Database reset successfully.\n");
+    let app = App::new(&db_path_str)?;
+    assert!(!app.topics.iter().any(|topic| topic.name == "Work"));
+    assert!(app.topics.iter().any(|topic| topic.name == "Default"));
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use super::common::temp_db_path;
+use slog::{o, Drain};
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use task_manager_cli::db::task_manager::models::TopicOrder;
+use task_manager_cli::db::task_manager::operations::DbOperations;
+use task_manager_cli::task_manager::app::App;
+
+/// Collects JSON log lines so the test can read them back.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .map_err(|_| io::Error::other("log buffer poisoned"))?
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn zero_threshold_logs_every_query_as_slow() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("slow_query");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+    let buffer = SharedBuffer::default();
+    let drain = Mutex::new(slog_json::Json::default(buffer.clone())).fuse();
+    let logger = slog::Logger::root(drain, o!());
+
+    let db_ops = DbOperations::new(app.db_ops.pool.clone(), logger)
+        .with_slow_query_threshold(Duration::from_millis(0));
+    db_ops.load_topics(TopicOrder::Manual)?;
+
+    let logged = String::from_utf8(buffer.0.lock().map_err(|e| e.to_string())?.clone())?;
+    let record: serde_json::Value = logged
+        .lines()
+        .map(serde_json::from_str)
+        .find(|record: &Result<serde_json::Value, _>| {
+            record
+                .as_ref()
+                .is_ok_and(|record| record["msg"] == "Slow query detected")
+        })
+        .expect("a slow query warning should be logged")?;
+    assert_eq!(record["method"], "load_topics");
+    assert_eq!(record["level"], "WARN");
+    assert!(record["elapsed_ms"].is_u64());
+
+    let quiet_buffer = SharedBuffer::default();
+    let quiet_logger = slog::Logger::root(
+        Mutex::new(slog_json::Json::default(quiet_buffer.clone())).fuse(),
+        o!(),
+    );
+    DbOperations::new(app.db_ops.pool.clone(), quiet_logger)
+        .with_slow_query_threshold(Duration::from_secs(60))
+        .load_topics(TopicOrder::Manual)?;
+    assert!(quiet_buffer.0.lock().map_err(|e| e.to_string())?.is_empty());
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use task_manager_cli::db::run_migrations;
+use task_manager_cli::db::task_manager::models::TopicOrder;
+use task_manager_cli::db::task_manager::operations::DbOperations;
+
+#[test]
+fn get_or_create_topic_creates_a_topic_only_once() -> Result<(), Box<dyn std::error::Error>> {
+    // One connection: every `:memory:` connection is its own database.
+    let pool = Pool::builder()
+        .max_size(1)
+        .build(ConnectionManager::<SqliteConnection>::new(":memory:"))?;
+    run_migrations(&mut *pool.get()?)?;
+    let db_ops = DbOperations::new(pool, slog::Logger::root(slog::Discard, slog::o!()));
+
+    let created = db_ops.get_or_create_topic("Imported")?;
+    let found = db_ops.get_or_create_topic("Imported")?;
+    let folded = db_ops.get_or_create_topic("imported")?;
+
+    assert_eq!(created.id, found.id);
+    assert_eq!(created.id, folded.id);
+    let named: Vec<_> = db_ops
+        .load_topics(TopicOrder::Manual)?
+        .into_iter()
+        .filter(|topic| topic.name == "Imported")
+        .collect();
+    assert_eq!(named.len(), 1);
+    Ok(())
+}
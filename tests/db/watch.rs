@@ -0,0 +1,42 @@
+use super::common::temp_db_path;
+use task_manager_cli::task_manager::app::App;
+use task_manager_cli::watch::{diff, snapshot};
+
+#[test]
+fn watch_diff_reports_created_updated_and_deleted_tasks() -> Result<(), Box<dyn std::error::Error>>
+{
+    let db_path = temp_db_path("watch_db");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+    let topic = app.db_ops.add_topic("Work", "")?;
+    let kept = app.db_ops.add_task(topic.id, "Kept", "")?;
+    let removed = app.db_ops.add_task(topic.id, "Removed", "")?;
+    let before = snapshot(&app.db_ops)?;
+
+    let added = app.db_ops.add_task(topic.id, "Say \"hi\"", "")?;
+    app.db_ops
+        .set_task_url(kept.id, Some("https://example.com"))?;
+    app.db_ops.delete_task(removed.id)?;
+    let after = snapshot(&app.db_ops)?;
+
+    let lines: Vec<String> = diff(&before, &after, |_| "Work".to_string())
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            format!("[UPDATED] id={} field=url", kept.id),
+            format!("[DELETED] id={}", removed.id),
+            format!(
+                "[CREATED] id={} name=\"Say \\\"hi\\\"\" topic=\"Work\"",
+                added.id
+            ),
+        ]
+    );
+    assert!(diff(&after, &after, |_| String::new()).is_empty());
+
+    drop(app);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
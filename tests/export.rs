@@ -0,0 +1,6 @@
+#[path = "common/mod.rs"]
+mod common;
+#[path = "export/export.rs"]
+mod export;
+#[path = "export/import.rs"]
+mod import;
@@ -0,0 +1,222 @@
+use super::common::temp_db_path;
+use task_manager_cli::cli::{self, FileFormat};
+use task_manager_cli::export::{
+    export_records, generate_digest, parse_csv, parse_json, task_to_vtodo, to_csv, to_json,
+};
+use task_manager_cli::task_manager::app::App;
+
+#[test]
+fn exported_tasks_parse_back_as_import_records() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("export_round_trip");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+
+    let mut out = Vec::new();
+    cli::add(
+        &app.db_ops,
+        &mut out,
+        "Work",
+        "Write report, v2",
+        "Say \"hi\"",
+        None,
+    )?;
+    cli::add(&app.db_ops, &mut out, "Default", "Inbox zero", "", None)?;
+    let added = String::from_utf8(out)?;
+    assert!(added.contains("to Work: Write report, v2"));
+
+    let records = export_records(&app.db_ops)?;
+    assert_eq!(records.len(), 2);
+
+    let from_csv = parse_csv(&to_csv(&records))?;
+    let from_json = parse_json(&to_json(&records)?)?;
+    for mut parsed in [from_csv, from_json] {
+        // Source line numbers differ between formats; compare the content only.
+        parsed
+            .iter_mut()
+            .zip(&records)
+            .for_each(|(parsed, record)| parsed.line = record.line);
+        assert_eq!(parsed, records);
+    }
+
+    let mut out = Vec::new();
+    cli::export_tasks(&app.db_ops, &mut out, Some(FileFormat::Csv), None)?;
+    assert!(String::from_utf8(out)?
+        .starts_with("topic,name,description,completed,favourite\nWork,\"Write report, v2\""));
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn complete_and_delete_report_missing_tasks() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("cli_complete_delete");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+    let mut out = Vec::new();
+
+    let default_id = app
+        .topics
+        .iter()
+        .find(|topic| topic.name == "Default")
+        .map(|topic| topic.id)
+        .expect("default topic should exist");
+    let id = app.db_ops.add_task(default_id, "Ship it", "")?.id;
+
+    assert_eq!(cli::complete(&app.db_ops, &mut out, id)?, 0);
+    assert!(app.db_ops.find_task(id)?.is_some_and(|task| task.completed));
+    assert_eq!(cli::delete(&app.db_ops, &mut out, id)?, 0);
+    assert!(cli::delete(&app.db_ops, &mut out, id).is_err());
+    assert!(cli::complete(&app.db_ops, &mut out, id).is_err());
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn digest_groups_tasks_by_todays_activity() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("digest");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+    let topic = app.db_ops.add_topic("Work", "")?;
+    let today = chrono::Local::now().date_naive();
+    let yesterday = today - chrono::Duration::days(1);
+
+    app.db_ops.add_task(topic.id, "Fresh", "")?;
+    let done = app.db_ops.add_task(topic.id, "Shipped", "")?;
+    app.db_ops.toggle_task_completion(done.id)?;
+    let late = app.db_ops.add_task(topic.id, "Late report", "")?;
+    app.db_ops
+        .set_task_due_date(late.id, Some(&yesterday.format("%Y-%m-%d").to_string()))?;
+    let due = app.db_ops.add_task(topic.id, "Call bank", "")?;
+    app.db_ops
+        .set_task_due_date(due.id, Some(&today.format("%Y-%m-%d").to_string()))?;
+
+    let digest = generate_digest(&app.db_ops, today)?;
+    assert!(digest.starts_with(&format!("# Task digest for {}\n", today.format("%Y-%m-%d"))));
+    assert!(digest.contains("## Created today (4)"));
+    assert!(digest.contains("## Completed today (1)\n\n- Shipped (Work)\n"));
+    assert!(digest.contains(&format!(
+        "## Overdue (1)\n\n- Late report (Work) — due {}\n",
+        yesterday.format("%Y-%m-%d")
+    )));
+    assert!(digest.contains("## Due today (1)\n\n- Call bank (Work)"));
+
+    let empty = generate_digest(&app.db_ops, today + chrono::Duration::days(30))?;
+    assert!(empty.contains("## Created today (0)\n\n_None_\n"));
+
+    let output = db_path.with_extension("md");
+    let mut out = Vec::new();
+    assert_eq!(cli::digest(&app.db_ops, &mut out, &output, today)?, 0);
+    assert_eq!(std::fs::read_to_string(&output)?, digest);
+
+    drop(app);
+    let _ = std::fs::remove_file(output);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn completion_topic_listing_reads_the_database_without_locking(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("completion_topics");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    let mut out = Vec::new();
+    assert_eq!(cli::list_topics(&db_path_str, &mut out)?, 0);
+    assert!(out.is_empty());
+
+    let app = App::new(&db_path_str)?;
+    app.db_ops.add_topic("Work", "")?;
+    let mut out = Vec::new();
+    assert_eq!(cli::list_topics(&db_path_str, &mut out)?, 0);
+    let names = String::from_utf8(out)?;
+    assert!(names.lines().any(|name| name == "Work"));
+    assert!(names.lines().any(|name| name == "Default"));
+
+    let mut script = Vec::new();
+    assert_eq!(cli::completions(cli::Shell::Bash, &mut script)?, 0);
+    assert!(String::from_utf8(script)?.contains("completions --list-topics"));
+
+    drop(app);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn export_watch_writes_the_initial_export_and_stops_when_asked(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("export_watch");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+    let topic = app.db_ops.add_topic("Work", "")?;
+    app.db_ops.add_task(topic.id, "Watched", "")?;
+
+    let output = db_path.with_extension("json");
+    let stop = std::sync::atomic::AtomicBool::new(true);
+    let mut out = Vec::new();
+    assert_eq!(
+        cli::export_watch(&app.db_ops, &mut out, None, &output, &db_path, &stop, false)?,
+        0
+    );
+    assert!(String::from_utf8(out)?.starts_with("Exported 1 task(s) to "));
+    let records = parse_json(&std::fs::read_to_string(&output)?)?;
+    assert_eq!(records[0].name, "Watched");
+
+    drop(app);
+    let _ = std::fs::remove_file(output);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn ics_export_writes_a_vtodo_per_task_with_a_due_date() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("export_ics");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+    let topic = app.db_ops.add_topic("Work", "")?;
+
+    app.db_ops.add_task(topic.id, "No deadline", "")?;
+    let report = app
+        .db_ops
+        .add_task(topic.id, "Report; draft, v2", "Line one\nLine two")?;
+    app.db_ops
+        .set_task_due_date(report.id, Some("2026-10-20"))?;
+    let filed = app.db_ops.add_task(topic.id, "File taxes", "")?;
+    app.db_ops.set_task_due_date(filed.id, Some("2026-10-18"))?;
+    let filed = app.db_ops.toggle_task_completion(filed.id)?;
+
+    let vtodo = task_to_vtodo(&filed);
+    assert!(vtodo.starts_with("BEGIN:VTODO\r\n"));
+    assert!(vtodo.ends_with("END:VTODO\r\n"));
+    assert!(vtodo.contains(&format!("UID:task-{}@task_manager_cli\r\n", filed.id)));
+    assert!(vtodo.contains("DUE;VALUE=DATE:20261018\r\n"));
+    assert!(vtodo.contains("STATUS:COMPLETED\r\n"));
+    let stamp = vtodo
+        .lines()
+        .find_map(|line| line.strip_prefix("DTSTAMP:"))
+        .expect("DTSTAMP should be set");
+    assert_eq!(stamp.len(), "20261018T120000Z".len());
+    assert!(stamp.ends_with('Z'));
+
+    let output = db_path.with_extension("ics");
+    let mut out = Vec::new();
+    assert_eq!(cli::export_ics(&app.db_ops, &mut out, &output)?, 0);
+    assert!(String::from_utf8(out)?.starts_with("Exported 2 task(s) with due dates"));
+
+    let ics = std::fs::read_to_string(&output)?;
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert_eq!(ics.matches("BEGIN:VTODO").count(), 2);
+    assert!(!ics.contains("No deadline"));
+    assert!(ics.contains("SUMMARY:Report\\; draft\\, v2\r\n"));
+    assert!(ics.contains("DESCRIPTION:Line one\\nLine two\r\n"));
+    assert!(ics.contains("STATUS:NEEDS-ACTION\r\n"));
+    // Soonest due date first.
+    assert!(ics.find("File taxes") < ics.find("Report"));
+    assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+
+    drop(app);
+    let _ = std::fs::remove_file(output);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
@@ -0,0 +1,378 @@
+use super::common::temp_db_path;
+use std::collections::HashSet;
+use task_manager_cli::cli;
+use task_manager_cli::db::task_manager::models::{MergeStrategy, TaskSource, TopicOrder};
+use task_manager_cli::error::AppError;
+use task_manager_cli::export::org::{import_org, parse_org, OrgStatus};
+use task_manager_cli::export::todoist::{import_todoist, parse_todoist, plan_todoist_import};
+use task_manager_cli::export::{import_records, parse_csv, parse_json, validate_import};
+use task_manager_cli::task_manager::app::App;
+
+#[test]
+fn csv_import_parses_quoted_fields_and_reports_line_numbers(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let records = parse_csv(
+        "topic,name,description,completed\n\
+         Work,\"Write report, draft\",\"Say \"\"hi\"\"\",yes\n\
+         \n\
+         Work,,missing name,no\n\
+         Favourites,Starred,,no\n",
+    )?;
+
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].name, "Write report, draft");
+    assert_eq!(records[0].description, "Say \"hi\"");
+    assert!(records[0].completed);
+
+    let errors = validate_import(&records);
+    let lines: Vec<usize> = errors.iter().map(|error| error.line).collect();
+    assert_eq!(lines, vec![4, 5]);
+    assert!(errors[0].to_string().starts_with("line 4:"));
+    Ok(())
+}
+
+#[test]
+fn dry_run_import_reports_summary_without_writing() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("import_dry_run");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+    let records = parse_json(
+        r#"[{"topic": "Work", "name": "Plan sprint"}, {"topic": "Work", "name": "Review PRs", "favourite": true}]"#,
+    )?;
+    assert!(validate_import(&records).is_empty());
+
+    let preview = import_records(
+        &app.db_ops,
+        &records,
+        MergeStrategy::default(),
+        TaskSource::ImportJson,
+        true,
+    )?;
+    assert!(!app
+        .db_ops
+        .load_topics(TopicOrder::Manual)?
+        .iter()
+        .any(|topic| topic.name == "Work"));
+
+    let imported = import_records(
+        &app.db_ops,
+        &records,
+        MergeStrategy::default(),
+        TaskSource::ImportJson,
+        false,
+    )?;
+    assert_eq!(preview.tasks, imported.tasks);
+    assert_eq!(preview.new_topics, vec!["Work".to_string()]);
+    assert!(preview
+        .to_string()
+        .starts_with("Would import 2 task(s) into 1 topic(s) (1 new)"));
+    assert!(imported.to_string().starts_with("Imported 2 task(s)"));
+
+    let work = app
+        .db_ops
+        .load_topics(TopicOrder::Manual)?
+        .into_iter()
+        .find(|topic| topic.name == "Work")
+        .expect("work topic should be created");
+    let tasks = app.db_ops.load_tasks(&work)?;
+    assert_eq!(tasks.len(), 2);
+    assert!(tasks[1].favourite);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn merge_strategies_resolve_existing_task_names() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("import_merge");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+    let topic = app.db_ops.add_topic("Work", "")?;
+    app.db_ops.add_task(topic.id, "Plan sprint", "old")?;
+    let records = parse_json(
+        r#"[{"topic": "Work", "name": "Plan sprint", "description": "new", "completed": true},
+            {"topic": "Work", "name": "Review PRs"}]"#,
+    )?;
+
+    let preview = import_records(
+        &app.db_ops,
+        &records,
+        MergeStrategy::Skip,
+        TaskSource::ImportJson,
+        true,
+    )?;
+    assert_eq!((preview.result.inserted, preview.result.skipped), (1, 1));
+    let skipped = import_records(
+        &app.db_ops,
+        &records,
+        MergeStrategy::Skip,
+        TaskSource::ImportJson,
+        false,
+    )?;
+    assert_eq!(skipped.result, preview.result);
+    assert!(skipped
+        .to_string()
+        .contains("inserted 1, updated 0, skipped 1"));
+
+    let overwritten = app.db_ops.import_with_strategy(
+        &records,
+        topic.id,
+        MergeStrategy::Overwrite,
+        TaskSource::ImportJson,
+    )?;
+    assert_eq!((overwritten.inserted, overwritten.updated), (0, 2));
+    let tasks = app.db_ops.load_tasks(&topic)?;
+    assert_eq!(tasks.len(), 2);
+    let plan = tasks
+        .iter()
+        .find(|task| task.name == "Plan sprint")
+        .expect("Plan sprint should be imported");
+    assert_eq!(plan.description, "new");
+    assert!(plan.completed);
+
+    let duplicated = app.db_ops.import_with_strategy(
+        &records,
+        topic.id,
+        MergeStrategy::Duplicate,
+        TaskSource::ImportJson,
+    )?;
+    assert_eq!(duplicated.inserted, 2);
+    assert_eq!(app.db_ops.load_tasks(&topic)?.len(), 4);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+const TODOIST_FIXTURE: &str = r#"{
+    "projects": [{"id": "1", "name": "Inbox"}],
+    "tasks": [
+        {"content": "Buy milk", "description": "2 litres", "completed": false, "labels": ["errands"], "priority": 1},
+        {"content": "File taxes", "checked": true, "labels": []},
+        {"content": "Buy milk", "description": "again", "completed": false},
+        {"content": "   ", "completed": false}
+    ]
+}"#;
+
+#[test]
+fn todoist_plan_skips_blank_and_repeated_names() -> Result<(), Box<dyn std::error::Error>> {
+    let export = parse_todoist(TODOIST_FIXTURE)?;
+    assert_eq!(export.tasks.len(), 4);
+    assert!(export.tasks[1].completed);
+
+    let (tasks, skipped) = plan_todoist_import(&export, HashSet::new());
+    let names: Vec<&str> = tasks.iter().map(|task| task.content.as_str()).collect();
+    assert_eq!(names, vec!["Buy milk", "File taxes"]);
+    assert_eq!(skipped, 2);
+    Ok(())
+}
+
+#[test]
+fn todoist_import_inserts_into_topic_and_skips_existing() -> Result<(), Box<dyn std::error::Error>>
+{
+    let db_path = temp_db_path("import_todoist");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let fixture_path = db_path.with_extension("todoist.json");
+    std::fs::write(&fixture_path, TODOIST_FIXTURE)?;
+    let app = App::new(&db_path_str)?;
+    let inbox = app.db_ops.add_topic("Inbox", "")?;
+    app.db_ops.add_task(inbox.id, "File taxes", "")?;
+
+    let preview = import_todoist(&app.db_ops, &fixture_path, inbox.id, true)?;
+    assert_eq!((preview.inserted, preview.skipped), (1, 3));
+    assert_eq!(app.db_ops.load_tasks(&inbox)?.len(), 1);
+
+    let summary = import_todoist(&app.db_ops, &fixture_path, inbox.id, false)?;
+    assert_eq!((summary.inserted, summary.skipped), (1, 3));
+    assert!(summary
+        .to_string()
+        .starts_with("Imported 1 task(s) from Todoist (3 skipped as duplicates)"));
+    let tasks = app.db_ops.load_tasks(&inbox)?;
+    assert_eq!(tasks.len(), 2);
+    assert!(tasks
+        .iter()
+        .any(|task| task.name == "Buy milk" && task.description == "2 litres"));
+
+    let _ = std::fs::remove_file(fixture_path);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+const ORG_FIXTURE: &str = "#+TITLE: Chores
+Preamble text is ignored.
+
+* TODO Clean the garage :home:weekend:
+Sort the tools first.
+Then sweep.
+** DONE Sort tools
+** TODO Sweep floor
+* DONE Pay rent
+* Plan holiday :travel:
+* TODO File taxes
+";
+
+#[test]
+fn org_parser_reads_status_body_tags_and_subtasks() {
+    let tasks = parse_org(ORG_FIXTURE);
+    let names: Vec<&str> = tasks.iter().map(|task| task.name.as_str()).collect();
+    assert_eq!(
+        names,
+        ["Clean the garage", "Pay rent", "Plan holiday", "File taxes"]
+    );
+
+    let garage = &tasks[0];
+    assert_eq!(garage.status, OrgStatus::Todo);
+    assert_eq!(garage.body, "Sort the tools first.\nThen sweep.");
+    assert_eq!(garage.tags, ["home", "weekend"]);
+    assert_eq!(garage.subtasks.len(), 2);
+    assert!(garage.subtasks[0].completed());
+    assert!(!garage.subtasks[1].completed());
+
+    assert_eq!(tasks[1].status, OrgStatus::Done);
+    assert!(tasks[1].body.is_empty());
+    assert_eq!(tasks[2].status, OrgStatus::Todo);
+    assert_eq!(tasks[2].tags, ["travel"]);
+}
+
+#[test]
+fn org_import_inserts_tasks_with_subtasks_and_skips_existing(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("import_org");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let fixture_path = db_path.with_extension("org");
+    std::fs::write(&fixture_path, ORG_FIXTURE)?;
+    let app = App::new(&db_path_str)?;
+    let inbox = app.db_ops.add_topic("Inbox", "")?;
+    app.db_ops.add_task(inbox.id, "File taxes", "")?;
+
+    let preview = import_org(&app.db_ops, &fixture_path, inbox.id, true)?;
+    assert_eq!(
+        (preview.inserted, preview.subtasks, preview.skipped),
+        (3, 2, 1)
+    );
+    assert_eq!(app.db_ops.load_tasks(&inbox)?.len(), 1);
+
+    let summary = import_org(&app.db_ops, &fixture_path, inbox.id, false)?;
+    assert!(summary
+        .to_string()
+        .starts_with("Imported 3 task(s) with 2 subtask(s) from Org (1 skipped as duplicates)"));
+    let tasks = app.db_ops.load_tasks(&inbox)?;
+    assert_eq!(tasks.len(), 4);
+    assert!(tasks
+        .iter()
+        .any(|task| task.name == "Pay rent" && task.completed));
+    let garage = tasks
+        .iter()
+        .find(|task| task.name == "Clean the garage")
+        .expect("garage task imported");
+    let subtasks = app.db_ops.load_subtasks(garage.id)?;
+    let subtasks: Vec<(&str, bool)> = subtasks
+        .iter()
+        .map(|subtask| (subtask.name.as_str(), subtask.completed))
+        .collect();
+    assert_eq!(subtasks, [("Sort tools", true), ("Sweep floor", false)]);
+
+    let _ = std::fs::remove_file(fixture_path);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn org_import_command_creates_its_topic_once() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("import_org_topic");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let fixture_path = db_path.with_extension("org");
+    std::fs::write(&fixture_path, ORG_FIXTURE)?;
+    let app = App::new(&db_path_str)?;
+    let chores = |app: &App| -> Result<usize, AppError> {
+        Ok(app
+            .db_ops
+            .load_topics(TopicOrder::Manual)?
+            .iter()
+            .filter(|topic| topic.name == "Chores")
+            .count())
+    };
+
+    let mut out = Vec::new();
+    cli::import_org(&app.db_ops, &mut out, &fixture_path, "Chores", true)?;
+    assert!(String::from_utf8(out)?.starts_with("Would create topic Chores"));
+    assert_eq!(chores(&app)?, 0);
+
+    cli::import_org(&app.db_ops, &mut Vec::new(), &fixture_path, "Chores", false)?;
+    cli::import_org(&app.db_ops, &mut Vec::new(), &fixture_path, "Chores", false)?;
+    assert_eq!(chores(&app)?, 1);
+
+    let _ = std::fs::remove_file(fixture_path);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn cli_dry_run_import_neither_creates_nor_writes_the_database(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("import_dry_run_cli");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let file_path = db_path.with_extension("json");
+    std::fs::write(&file_path, r#"[{"topic": "Work", "name": "Plan sprint"}]"#)?;
+    let dry_run = |out: &mut Vec<u8>| {
+        cli::import(
+            &db_path_str,
+            out,
+            &file_path,
+            None,
+            None,
+            MergeStrategy::default(),
+            true,
+        )
+    };
+
+    let mut out = Vec::new();
+    assert_eq!(dry_run(&mut out)?, 0);
+    assert!(!db_path.exists());
+    assert!(String::from_utf8(out)?.starts_with("Would import 1 task(s) into 1 topic(s) (1 new)"));
+
+    let app = App::new(&db_path_str)?;
+    let mut out = Vec::new();
+    assert_eq!(dry_run(&mut out)?, 0);
+    assert!(String::from_utf8(out)?.starts_with("Would import 1 task(s) into 1 topic(s) (1 new)"));
+    assert!(!app
+        .db_ops
+        .load_topics(TopicOrder::Manual)?
+        .iter()
+        .any(|topic| topic.name == "Work"));
+
+    let _ = std::fs::remove_file(file_path);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn cli_import_refuses_a_topic_for_json_and_csv() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("import_topic_refused");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let file_path = db_path.with_extension("csv");
+    std::fs::write(&file_path, "topic,name\nWork,Plan sprint\n")?;
+
+    let result = cli::import(
+        &db_path_str,
+        &mut Vec::new(),
+        &file_path,
+        None,
+        Some("Inbox"),
+        MergeStrategy::default(),
+        false,
+    );
+    assert!(matches!(result, Err(AppError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidInput));
+    assert!(!db_path.exists());
+
+    let _ = std::fs::remove_file(file_path);
+    Ok(())
+}
+
+#[test]
+fn malformed_import_files_report_a_parse_error() {
+    assert!(matches!(
+        parse_json("[{\"name\": \"Unclosed\""),
+        Err(AppError::Parse(_))
+    ));
+    assert!(matches!(parse_todoist("not json"), Err(AppError::Parse(_))));
+}
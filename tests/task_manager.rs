@@ -1,8 +1,24 @@
+#[path = "task_manager/archive.rs"]
+mod archive;
 #[path = "common/mod.rs"]
 mod common;
+#[path = "task_manager/config.rs"]
+mod config;
 #[path = "task_manager/filters.rs"]
 mod filters;
 #[path = "task_manager/forms.rs"]
 mod forms;
+#[path = "task_manager/metrics.rs"]
+mod metrics;
 #[path = "task_manager/presets.rs"]
 mod presets;
+#[path = "task_manager/repl.rs"]
+mod repl;
+#[path = "task_manager/search.rs"]
+mod search;
+#[path = "task_manager/sorting.rs"]
+mod sorting;
+#[path = "task_manager/topics.rs"]
+mod topics;
+#[path = "task_manager/worker.rs"]
+mod worker;
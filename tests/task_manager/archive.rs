@@ -0,0 +1,131 @@
+use super::common::temp_db_path;
+use task_manager_cli::task_manager::app::{App, InputMode};
+
+fn task_names(app: &App) -> Vec<&str> {
+    app.tasks.iter().map(|task| task.name.as_str()).collect()
+}
+
+#[test]
+fn soft_delete_archives_tasks_until_restored_or_purged() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("soft_delete_archive");
+    let mut app = App::new(&db_path.to_string_lossy())?;
+    app.add_topic("Work")?;
+    app.selected_topic = app.topics.iter().position(|t| t.name == "Work").unwrap();
+    app.load_tasks()?;
+    app.add_task_with_details("Old report", "budget")?;
+    app.add_task_with_details("Standup", "")?;
+    let work_id = app.tasks[0].topic_id;
+
+    app.config.soft_delete = true;
+    app.selected = 0;
+    app.delete_task()?;
+    assert_eq!(task_names(&app), vec!["Standup"]);
+    let archived = app.db_ops.load_archived_tasks(Some(work_id))?;
+    assert_eq!(archived.len(), 1);
+    assert!(archived[0].archived);
+    assert!(app
+        .db_ops
+        .fts_search_with_snippets("budget", None)?
+        .is_empty());
+
+    app.begin_archive()?;
+    assert_eq!(app.input_mode, InputMode::ViewingArchivedTasks);
+    assert_eq!(app.archived_tasks.len(), 1);
+    app.unarchive_selected_task()?;
+    assert!(app.archived_tasks.is_empty());
+    assert_eq!(task_names(&app), vec!["Old report", "Standup"]);
+    app.close_archive();
+
+    app.selected = 1;
+    app.delete_task()?;
+    app.begin_archive()?;
+    let standup_id = app.archived_tasks[0].id;
+    app.purge_selected_archived_task()?;
+    assert!(app.archived_tasks.is_empty());
+    assert!(app.db_ops.find_task(standup_id)?.is_none());
+
+    app.config.soft_delete = false;
+    app.close_archive();
+    app.selected = 0;
+    app.delete_task()?;
+    assert!(app.tasks.is_empty());
+    assert!(app.db_ops.load_archived_tasks(None)?.is_empty());
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn mass_delete_removes_only_checked_tasks() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("mass_delete");
+    let mut app = App::new(&db_path.to_string_lossy())?;
+    app.add_topic("Work")?;
+    app.selected_topic = app.topics.iter().position(|t| t.name == "Work").unwrap();
+    app.load_tasks()?;
+    for name in ["Alpha", "Beta", "Gamma", "Delta"] {
+        app.add_task_with_details(name, "")?;
+    }
+    app.selected_tasks.insert(app.tasks[3].id);
+
+    app.begin_delete_multiple();
+    assert_eq!(app.input_mode, InputMode::DeletingMultipleTasks);
+    assert_eq!(app.selected_tasks.len(), 1);
+    app.selected = 1;
+    app.toggle_delete_mark();
+    app.request_delete_marked();
+    assert_eq!(app.input_mode, InputMode::ConfirmingDeleteMultipleTasks);
+    app.cancel_delete_marked();
+    assert_eq!(app.input_mode, InputMode::DeletingMultipleTasks);
+
+    app.mark_all_for_delete();
+    app.clear_delete_marks();
+    app.request_delete_marked();
+    assert_eq!(app.input_mode, InputMode::DeletingMultipleTasks);
+
+    app.selected = 1;
+    app.toggle_delete_mark();
+    app.selected = 3;
+    app.toggle_delete_mark();
+    app.request_delete_marked();
+    app.delete_marked_tasks()?;
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(task_names(&app), vec!["Alpha", "Gamma"]);
+    assert!(app.selected_tasks.is_empty());
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn failed_mass_delete_keeps_every_task_and_its_mark() -> Result<(), Box<dyn std::error::Error>> {
+    use diesel::RunQueryDsl;
+
+    let db_path = temp_db_path("mass_delete_rollback");
+    let mut app = App::new(&db_path.to_string_lossy())?;
+    app.add_topic("Work")?;
+    app.selected_topic = app.topics.iter().position(|t| t.name == "Work").unwrap();
+    app.load_tasks()?;
+    for name in ["Alpha", "Beta", "Gamma"] {
+        app.add_task_with_details(name, "")?;
+    }
+    app.db_ops.add_subtask(app.tasks[0].id, "Alpha step")?;
+    diesel::sql_query(
+        "CREATE TRIGGER keep_gamma BEFORE DELETE ON task WHEN OLD.name = 'Gamma' \
+         BEGIN SELECT RAISE(ABORT, 'Gamma is kept'); END",
+    )
+    .execute(&mut app.db_ops.pool.get()?)?;
+
+    app.begin_delete_multiple();
+    app.mark_all_for_delete();
+    app.request_delete_marked();
+    assert!(app.delete_marked_tasks().is_err());
+    app.load_tasks()?;
+    assert_eq!(task_names(&app), vec!["Alpha", "Beta", "Gamma"]);
+    let alpha_id = app.tasks[0].id;
+    let subtasks = app.db_ops.load_subtasks_for(&[alpha_id])?;
+    assert_eq!(subtasks.get(&alpha_id).map_or(0, Vec::len), 1);
+    assert_eq!(app.selected_tasks.len(), 3);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
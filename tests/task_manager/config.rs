@@ -0,0 +1,166 @@
+use super::common::temp_notes_root;
+use task_manager_cli::config::{check_startup_paths, ConfigError};
+use task_manager_cli::task_manager::app::App;
+
+#[test]
+fn completion_bell_toggle_persists_to_config() -> Result<(), Box<dyn std::error::Error>> {
+    // A private directory so the config file is not shared with other tests.
+    let dir = temp_notes_root("task_config");
+    std::fs::create_dir_all(&dir)?;
+    let db_path_str = dir.join("tasks.db").to_string_lossy().to_string();
+
+    {
+        let mut app = App::new(&db_path_str)?;
+        let initial = app.config.sound_enabled;
+        app.toggle_sound()?;
+        assert_eq!(app.config.sound_enabled, !initial);
+        app.toggle_sound()?;
+        app.toggle_sound()?;
+    }
+
+    assert!(dir.join("task_manager_config.json").exists());
+    let app = App::new(&db_path_str)?;
+    if std::env::var("TASK_MANAGER_NO_BELL").as_deref() != Ok("1") {
+        assert!(!app.config.sound_enabled);
+    }
+
+    let _ = std::fs::remove_dir_all(dir);
+    Ok(())
+}
+
+#[test]
+fn saving_a_toggle_leaves_runtime_overrides_out_of_the_file(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = temp_notes_root("config_overrides");
+    std::fs::create_dir_all(&dir)?;
+    let db_path_str = dir.join("tasks.db").to_string_lossy().to_string();
+
+    let mut app = App::new(&db_path_str)?;
+    // What TASK_MANAGER_ASCII and TASK_MANAGER_TICK_RATE_MS would set.
+    app.config.ascii_mode = true;
+    app.config.tick_rate_ms = 50;
+    app.cycle_topic_order()?;
+    app.toggle_sound()?;
+
+    let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(
+        dir.join("task_manager_config.json"),
+    )?)?;
+    assert_eq!(saved["ascii_mode"], false);
+    assert_ne!(saved["tick_rate_ms"], 50);
+    assert_eq!(saved["sound_enabled"], app.config.sound_enabled);
+    assert_eq!(
+        saved["topic_order"],
+        serde_json::to_value(app.config.topic_order)?
+    );
+
+    let _ = std::fs::remove_dir_all(dir);
+    Ok(())
+}
+
+#[test]
+fn topic_order_cycles_persists_and_reorders_manual_positions(
+) -> Result<(), Box<dyn std::error::Error>> {
+    use task_manager_cli::db::task_manager::models::TopicOrder;
+
+    let dir = temp_notes_root("topic_order");
+    std::fs::create_dir_all(&dir)?;
+    let db_path_str = dir.join("tasks.db").to_string_lossy().to_string();
+    let names =
+        |app: &App| -> Vec<String> { app.topics.iter().map(|topic| topic.name.clone()).collect() };
+
+    {
+        let mut app = App::new(&db_path_str)?;
+        app.add_topic("work")?;
+        app.add_topic("Home")?;
+        app.add_topic("Errands")?;
+        app.selected_topic = 1;
+        assert_eq!(names(&app), vec!["Default", "work", "Home", "Errands"]);
+
+        app.cycle_topic_order()?;
+        assert_eq!(app.config.topic_order, TopicOrder::Name);
+        assert_eq!(names(&app), vec!["Default", "Errands", "Home", "work"]);
+        assert_eq!(app.topics[app.selected_topic].name, "work");
+        // Positions only matter in manual order, so neither can change them.
+        app.move_selected_topic(-1)?;
+        app.reorder_topics_by_name()?;
+        assert_eq!(names(&app), vec!["Default", "Errands", "Home", "work"]);
+
+        app.cycle_topic_order()?;
+        assert_eq!(app.config.topic_order, TopicOrder::Created);
+        assert_eq!(names(&app), vec!["Default", "work", "Home", "Errands"]);
+    }
+
+    let mut app = App::new(&db_path_str)?;
+    assert_eq!(app.config.topic_order, TopicOrder::Created);
+    app.cycle_topic_order()?;
+    assert_eq!(app.config.topic_order, TopicOrder::Manual);
+    assert_eq!(names(&app), vec!["Default", "work", "Home", "Errands"]);
+    app.reorder_topics_by_name()?;
+    assert_eq!(names(&app), vec!["Default", "Errands", "Home", "work"]);
+
+    let _ = std::fs::remove_dir_all(dir);
+    Ok(())
+}
+
+#[test]
+fn startup_paths_report_every_problem() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = temp_notes_root("startup_paths");
+    std::fs::create_dir_all(&dir)?;
+    let log_file = dir.join("logs/app.log");
+    assert_eq!(
+        check_startup_paths(&dir.join("db"), "tasks.db", &log_file),
+        Ok(())
+    );
+    assert!(!dir.join("db").exists());
+    // Checking writes nothing, not even a probe file.
+    assert!(std::fs::read_dir(&dir)?.next().is_none());
+
+    // A regular file where a directory should be can never be written into.
+    let blocker = dir.join("blocker");
+    std::fs::write(&blocker, "")?;
+    std::fs::write(
+        dir.join("task_manager_config.json"),
+        r#"{"tick_rate_ms": 5}"#,
+    )?;
+    let errors = check_startup_paths(&dir, "nested/tasks.db", &blocker.join("app.log"))
+        .expect_err("paths should be rejected");
+    assert_eq!(
+        errors,
+        vec![
+            ConfigError::DbFilenameContainsPathSep("nested/tasks.db".to_string()),
+            ConfigError::LogDirNotWritable(blocker.clone()),
+            ConfigError::TickRateOutOfRange(5),
+        ]
+    );
+    assert!(errors[0]
+        .to_string()
+        .ends_with("put the directory in TASK_MANAGER_DB_DIR instead."));
+
+    let errors = check_startup_paths(&blocker.join("db"), "tasks.db", &log_file)
+        .expect_err("db dir under a file should be rejected");
+    assert_eq!(
+        errors,
+        vec![ConfigError::DbDirNotWritable(blocker.join("db"))]
+    );
+
+    let _ = std::fs::remove_dir_all(dir);
+    Ok(())
+}
+
+#[test]
+fn only_database_commands_run_the_startup_checks() {
+    use clap::Parser;
+    use task_manager_cli::cli::{Cli, Command};
+
+    let command = |args: &[&str]| {
+        Cli::try_parse_from(args)
+            .expect("arguments should parse")
+            .command
+            .unwrap_or(Command::Tui)
+    };
+    assert!(command(&["tm"]).needs_preflight());
+    assert!(command(&["tm", "list"]).needs_preflight());
+    assert!(!command(&["tm", "completions", "bash"]).needs_preflight());
+    assert!(!command(&["tm", "bench"]).needs_preflight());
+    assert!(!command(&["tm", "doctor"]).needs_preflight());
+}
@@ -140,3 +140,166 @@ fn command_palette_round_trips_mode_and_query() -> Result<(), Box<dyn std::error
     let _ = std::fs::remove_file(db_path);
     Ok(())
 }
+
+#[test]
+fn log_min_level_hides_lower_severity_entries() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("log_min_level");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    app.logs.clear();
+
+    app.add_log("INFO", "saved");
+    app.add_log("WARN", "nothing selected");
+    app.add_log("ERROR", "write failed");
+    assert_eq!(app.visible_logs().len(), 3);
+
+    app.cycle_log_level();
+    app.cycle_log_level();
+    let messages: Vec<&str> = app
+        .visible_logs()
+        .iter()
+        .map(|entry| entry.message.as_str())
+        .collect();
+    assert_eq!(messages, vec!["nothing selected", "write failed"]);
+    assert!(app.logs[2].to_string().ends_with("[ERROR] write failed"));
+
+    app.cycle_log_level();
+    app.cycle_log_level();
+    assert_eq!(app.visible_logs().len(), 3);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn completed_tasks_follow_open_ones_behind_a_separator() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("completed_separator");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Default")
+        .expect("default topic should exist");
+    app.add_task_with_details("Alpha", "Done first")?;
+    app.add_task_with_details("Beta", "Still open")?;
+    app.add_task_with_details("Gamma", "Still open")?;
+    let first = app.filtered_task_indices();
+    assert_eq!(app.completed_separator_position(&first), None);
+
+    app.selected = 0;
+    app.toggle_task()?;
+
+    let filtered = app.filtered_task_indices();
+    assert!(app.tasks[filtered[0]].name == "Beta" && app.tasks[filtered[2]].name == "Alpha");
+    assert_eq!(app.completed_separator_position(&filtered), Some(2));
+
+    app.selected = filtered[1];
+    app.move_selection_down();
+    assert_eq!(app.tasks[app.selected].name, "Alpha");
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn task_filter_matches_in_memory_and_in_the_database() -> Result<(), Box<dyn std::error::Error>> {
+    use diesel::prelude::*;
+    use task_manager_cli::db::schema::task;
+    use task_manager_cli::db::task_manager::models::{CompletionFilter, Task};
+    use task_manager_cli::task_manager::app::TaskFilter;
+
+    let db_path = temp_db_path("task_filter_struct");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+
+    let work = app.db_ops.add_topic("Work", "")?;
+    app.db_ops.add_task(work.id, "Report", "")?;
+    let talk = app.db_ops.add_task(work.id, "Talk", "")?;
+    let rent = app.db_ops.add_task(work.id, "Rent", "")?;
+    app.db_ops.toggle_task_completion(talk.id)?;
+    app.db_ops.toggle_task_completion(rent.id)?;
+    app.db_ops.pin_task(rent.id)?;
+
+    let filter = TaskFilter {
+        completion: CompletionFilter::Open,
+    };
+    assert_eq!(filter.summary(), "Open");
+
+    let mut conn = app.db_ops.pool.get()?;
+    let query = || task::table.filter(task::topic_id.eq(work.id)).into_boxed();
+    let all: Vec<Task> = query().order_by(task::id).load(&mut conn)?;
+    let in_memory: Vec<&str> = all
+        .iter()
+        .filter(|task| filter.matches(task))
+        .map(|task| task.name.as_str())
+        .collect();
+    let from_db: Vec<String> = filter
+        .filter_query(query())
+        .order_by(task::id)
+        .select(task::name)
+        .load(&mut conn)?;
+    assert_eq!(in_memory, vec!["Report", "Rent"]);
+    assert_eq!(from_db, in_memory);
+
+    drop(conn);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn task_source_is_recorded_and_filterable() -> Result<(), Box<dyn std::error::Error>> {
+    use task_manager_cli::db::task_manager::models::{MergeStrategy, TaskSource};
+    use task_manager_cli::error::AppError;
+    use task_manager_cli::export::{import_records, parse_csv};
+
+    let db_path = temp_db_path("task_source_filter");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Default")
+        .expect("default topic should exist");
+
+    app.add_task_with_details("Typed", "")?;
+    let topic_id = app.topics[app.selected_topic].id;
+    app.db_ops
+        .add_task_from(topic_id, "Scripted", "", TaskSource::Cli)?;
+    let records = parse_csv("topic,name\nWork,Imported\n")?;
+    import_records(
+        &app.db_ops,
+        &records,
+        MergeStrategy::default(),
+        TaskSource::ImportCsv,
+        false,
+    )?;
+
+    let names = |app: &App| -> Vec<String> { app.tasks.iter().map(|t| t.name.clone()).collect() };
+    let filter_by = |app: &mut App, query: &str| -> Result<(), AppError> {
+        app.clear_task_filter()?;
+        for c in query.chars() {
+            app.append_task_filter_char(c)?;
+        }
+        Ok(())
+    };
+    filter_by(&mut app, "source:tui")?;
+    assert_eq!(names(&app), vec!["Typed"]);
+    filter_by(&mut app, "source:CLI")?;
+    assert_eq!(names(&app), vec!["Scripted"]);
+    filter_by(&mut app, "-source:cli")?;
+    assert_eq!(names(&app), vec!["Typed", "Imported"]);
+    assert_eq!(app.filtered_task_indices().len(), 2);
+    for query in ["source:csv", "source:import_csv"] {
+        filter_by(&mut app, query)?;
+        assert_eq!(names(&app), vec!["Imported"]);
+    }
+    filter_by(&mut app, "source:nowhere")?;
+    assert!(app.tasks.is_empty());
+    filter_by(&mut app, "source:org")?;
+    assert!(app.tasks.is_empty());
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
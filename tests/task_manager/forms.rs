@@ -1,6 +1,7 @@
 use super::common::temp_db_path;
+use std::sync::{Arc, Mutex};
 use task_manager_cli::db::task_manager::models::Topic;
-use task_manager_cli::task_manager::app::{App, InputMode};
+use task_manager_cli::task_manager::app::{App, Clipboard, InputMode};
 
 #[test]
 fn begin_add_task_requires_regular_topic() -> Result<(), Box<dyn std::error::Error>> {
@@ -14,6 +15,10 @@ fn begin_add_task_requires_regular_topic() -> Result<(), Box<dyn std::error::Err
         description: String::new(),
         created_at: String::new(),
         updated_at: String::new(),
+        color: None,
+        position: 0,
+        alias: None,
+        task_limit: None,
     });
     app.selected_topic = app.topics.len() - 1;
     app.begin_add_task();
@@ -22,7 +27,7 @@ fn begin_add_task_requires_regular_topic() -> Result<(), Box<dyn std::error::Err
     assert!(app
         .logs
         .last()
-        .is_some_and(|entry| entry.contains("regular topic")));
+        .is_some_and(|entry| entry.message.contains("regular topic")));
 
     let _ = std::fs::remove_file(db_path);
     Ok(())
@@ -77,3 +82,264 @@ fn reset_task_inputs_clears_inline_feedback() -> Result<(), Box<dyn std::error::
     let _ = std::fs::remove_file(db_path);
     Ok(())
 }
+
+/// Stands in for the desktop clipboard; the test keeps a handle on the text.
+struct FakeClipboard(Arc<Mutex<String>>);
+
+impl Clipboard for FakeClipboard {
+    fn copy_text(&mut self, text: &str) -> Result<(), arboard::Error> {
+        *self.0.lock().unwrap() = text.to_string();
+        Ok(())
+    }
+
+    fn paste_text(&mut self) -> Result<String, arboard::Error> {
+        Ok(self.0.lock().unwrap().clone())
+    }
+}
+
+#[test]
+fn yanked_task_name_prefills_pasted_task() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("clipboard_round_trip");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Default")
+        .expect("default topic should exist");
+    let copied = Arc::new(Mutex::new(String::new()));
+    app.system_clipboard = Box::new(FakeClipboard(Arc::clone(&copied)));
+    app.add_task_with_details("Renew passport", "")?;
+    app.selected = 0;
+
+    app.yank_selected_task();
+    assert_eq!(app.clipboard, "Renew passport");
+    assert_eq!(*copied.lock().unwrap(), "Renew passport");
+
+    *copied.lock().unwrap() = "Book flights\nand hotel".to_string();
+    app.paste_task_name();
+    assert_eq!(app.input_mode, InputMode::AddingTaskName);
+    assert_eq!(app.task_name_input, "Book flights");
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn exit_with_unsaved_task_input_requires_confirmation() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("confirm_exit");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Default")
+        .expect("default topic should exist");
+    assert!(app.request_exit());
+
+    app.begin_add_task();
+    app.task_name_input = "Half typed".to_string();
+    app.input_mode = InputMode::AddingTaskDescription;
+    assert!(!app.request_exit());
+    assert_eq!(app.input_mode, InputMode::ConfirmingExit);
+
+    app.cancel_exit();
+    assert_eq!(app.input_mode, InputMode::AddingTaskDescription);
+    assert_eq!(app.task_name_input, "Half typed");
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn task_url_is_validated_and_stored() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("task_url");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Default")
+        .expect("default topic should exist");
+
+    assert!(app
+        .add_task_with_url("Docs", "", "ftp://example.com")
+        .is_err());
+    assert!(app.tasks.is_empty());
+
+    app.add_task_with_url("Docs", "", " https://example.com/docs ")?;
+    app.add_task_with_details("Plain", "")?;
+    assert_eq!(
+        app.tasks[0].url.as_deref(),
+        Some("https://example.com/docs")
+    );
+    assert_eq!(app.tasks[1].url, None);
+
+    app.selected = 1;
+    app.open_selected_task_url();
+    assert!(app
+        .logs
+        .last()
+        .is_some_and(|entry| entry.message.contains("No URL set for this task")));
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn begin_view_task_requires_a_selected_task() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("view_task");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    app.add_topic("Reading")?;
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Reading")
+        .expect("topic should exist");
+    app.load_tasks()?;
+
+    app.begin_view_task();
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert!(app
+        .logs
+        .last()
+        .is_some_and(|entry| entry.message.contains("No task selected to view")));
+
+    app.add_task_with_details("Long article", "Several paragraphs")?;
+    app.begin_view_task();
+    assert_eq!(app.input_mode, InputMode::ViewingTask);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn subtasks_are_added_in_order_and_report_progress() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("subtasks");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    app.add_topic("Chores")?;
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Chores")
+        .expect("topic should exist");
+    app.load_tasks()?;
+
+    app.begin_add_subtask();
+    assert_eq!(app.input_mode, InputMode::Normal);
+
+    app.add_task_with_details("Clean kitchen", "")?;
+    app.begin_add_subtask();
+    assert_eq!(app.input_mode, InputMode::AddingSubtask);
+    assert!(app.add_subtask("   ").is_err());
+    app.add_subtask("Dishes")?;
+    app.add_subtask("Floor")?;
+
+    let task_id = app.tasks[app.selected].id;
+    let subtasks = app.db_ops.load_subtasks(task_id)?;
+    let names: Vec<&str> = subtasks.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["Dishes", "Floor"]);
+    assert_eq!(app.subtask_progress(task_id), (0, 2));
+
+    app.db_ops.toggle_subtask(subtasks[0].id)?;
+    app.load_tasks()?;
+    assert_eq!(app.subtask_progress(task_id), (1, 2));
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn add_link_popup_links_the_selected_task() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("add_link");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    app.add_topic("Garden")?;
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Garden")
+        .expect("topic should exist");
+    app.add_task_with_details("Buy seeds", "")?;
+
+    app.begin_add_link();
+    assert_eq!(app.input_mode, InputMode::Normal);
+
+    app.add_task_with_details("Plant seeds", "")?;
+    app.begin_add_link();
+    assert_eq!(app.input_mode, InputMode::AddingLink);
+    assert_eq!(app.link_candidates().len(), 1);
+    app.cycle_link_type();
+    app.add_selected_link()?;
+    assert_eq!(app.input_mode, InputMode::Normal);
+
+    let source = app.tasks[app.selected].id;
+    assert_eq!(app.task_links[&source].len(), 1);
+    assert_eq!(app.db_ops.load_links(source)?[0].link_type, "blocked_by");
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn error_popup_is_dismissed_back_to_normal_mode() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("error_popup");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    app.show_error_popup("Failed to delete task: database is locked".to_string());
+    assert_eq!(app.input_mode, InputMode::ShowingError);
+    assert_eq!(
+        app.error_popup.as_deref(),
+        Some("Failed to delete task: database is locked")
+    );
+
+    app.dismiss_error_popup();
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert!(app.error_popup.is_none());
+
+    drop(app);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn deleting_a_task_with_subtasks_confirms_and_removes_them(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("delete_with_subtasks");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    app.add_topic("Chores")?;
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Chores")
+        .expect("topic should exist");
+    app.load_tasks()?;
+
+    app.add_task_with_details("Plain task", "")?;
+    app.begin_delete_task()?;
+    assert_eq!(app.input_mode, InputMode::DeleteTask);
+    app.input_mode = InputMode::Normal;
+
+    app.add_task_with_details("Clean kitchen", "")?;
+    app.add_subtask("Dishes")?;
+    app.add_subtask("Floor")?;
+    let task_id = app.tasks[app.selected].id;
+
+    app.begin_delete_task()?;
+    assert_eq!(app.input_mode, InputMode::ConfirmingDeleteTask);
+    assert_eq!(app.delete_subtask_count, 2);
+
+    app.delete_task()?;
+    assert!(app.db_ops.load_subtasks(task_id)?.is_empty());
+    assert!(app.db_ops.find_task(task_id)?.is_none());
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
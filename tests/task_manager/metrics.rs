@@ -0,0 +1,153 @@
+use super::common::temp_db_path;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use task_manager_cli::db::task_manager::models::TopicOrder;
+use task_manager_cli::task_manager::app::App;
+use task_manager_cli::task_manager::metrics::MetricsServer;
+
+fn get(server: &MetricsServer, path: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let mut stream = TcpStream::connect(server.local_addr())?;
+    write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n")?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    Ok(response)
+}
+
+#[test]
+fn metrics_endpoint_reports_task_and_topic_counts() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("metrics");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    app.add_topic("Ops")?;
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Ops")
+        .expect("topic should exist");
+    app.add_task_with_details("Rotate keys", "")?;
+    app.add_task_with_details("Renew certificate", "")?;
+    app.toggle_task()?;
+
+    let server = MetricsServer::start(0, app.db_ops.clone())?;
+    let response = get(&server, "/metrics")?;
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("\ntask_manager_total_tasks 2\n"));
+    assert!(response.contains("\ntask_manager_completed_tasks 1\n"));
+    assert!(response.contains(&format!(
+        "\ntask_manager_total_topics {}\n",
+        app.db_ops.load_topics(TopicOrder::Manual)?.len()
+    )));
+    assert!(response.contains("# TYPE task_manager_db_pool_connections_active gauge"));
+
+    assert!(get(&server, "/")?.starts_with("HTTP/1.1 404"));
+    server.shutdown();
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn stats_command_summarises_the_whole_database() -> Result<(), Box<dyn std::error::Error>> {
+    use task_manager_cli::cli::{self, OutputFormat};
+
+    let db_path = temp_db_path("global_stats");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+    let work = app.db_ops.add_topic("Work", "")?;
+    let done = app.db_ops.add_task(work.id, "Ship release", "")?;
+    app.db_ops.toggle_task_completion(done.id)?;
+    let late = app.db_ops.add_task(work.id, "File report", "")?;
+    app.db_ops.set_task_due_date(late.id, Some("2000-01-01"))?;
+    app.db_ops.add_task(work.id, "Plan sprint", "")?;
+    let archived = app.db_ops.add_task(work.id, "Old idea", "")?;
+    app.db_ops.archive_task(archived.id)?;
+
+    let stats = app.db_ops.compute_global_stats()?;
+    assert_eq!(stats.total_tasks, 3);
+    assert_eq!(stats.completed_tasks, 1);
+    // Default and Work; the Favourites and Completed views are not counted.
+    assert_eq!(stats.total_topics, 2);
+    assert_eq!(stats.overdue_tasks, 1);
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    assert_eq!(stats.most_productive_day, Some((today.clone(), 3)));
+    assert_eq!(
+        stats
+            .most_recent_topic
+            .as_ref()
+            .map(|(name, _)| name.as_str()),
+        Some("Work")
+    );
+
+    let mut out = Vec::new();
+    assert_eq!(cli::stats(&app.db_ops, &mut out, OutputFormat::Table)?, 0);
+    let table = String::from_utf8(out)?;
+    assert!(table.contains("Completed                    33.3%"));
+    assert!(table.contains("Average tasks per topic      1.5"));
+    assert!(table.contains(&format!("{today} (3 created)")));
+
+    let mut out = Vec::new();
+    cli::stats(&app.db_ops, &mut out, OutputFormat::Json)?;
+    let json: serde_json::Value = serde_json::from_slice(&out)?;
+    assert_eq!(json["total_tasks"], 3);
+    assert_eq!(json["completion_percentage"], 33.3);
+    assert_eq!(json["most_productive_day"]["date"], today.as_str());
+    let updated_at = json["most_recently_updated_topic"]["updated_at"]
+        .as_str()
+        .unwrap_or_default();
+    assert!(chrono::DateTime::parse_from_rfc3339(updated_at).is_ok());
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn list_command_heads_tasks_with_a_topic_summary() -> Result<(), Box<dyn std::error::Error>> {
+    use task_manager_cli::cli::{self, ListFormat};
+
+    let db_path = temp_db_path("list_summary");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+    let work = app.db_ops.add_topic("Work", "")?;
+    let done = app.db_ops.add_task(work.id, "Ship release", "")?;
+    app.db_ops.toggle_task_completion(done.id)?;
+    app.db_ops.add_task(work.id, "Plan sprint", "")?;
+    app.db_ops.add_task(work.id, "File report", "")?;
+    let other = app.db_ops.add_topic("Home", "")?;
+    app.db_ops.add_task(other.id, "Water plants", "")?;
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+    let summary = app.db_ops.topic_summary(Some(work.id))?;
+    assert_eq!(
+        (summary.total, summary.completed, summary.pending()),
+        (3, 1, 2)
+    );
+    assert_eq!(summary.oldest_pending.as_deref(), Some(today.as_str()));
+    assert_eq!(app.db_ops.topic_summary(None)?.total, 4);
+
+    let mut out = Vec::new();
+    assert_eq!(
+        cli::list(&app.db_ops, &mut out, Some("Work"), ListFormat::Text)?,
+        0
+    );
+    let text = String::from_utf8(out)?;
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines[0],
+        format!("Work: 3 task(s), 1 completed, 2 pending, oldest pending {today}")
+    );
+    assert!(!lines[1].is_empty() && lines[1].chars().all(|c| c == '─'));
+    assert_eq!(lines.len(), 5);
+
+    let mut out = Vec::new();
+    cli::list(&app.db_ops, &mut out, Some("Work"), ListFormat::Json)?;
+    let json: serde_json::Value = serde_json::from_slice(&out)?;
+    assert_eq!(json["meta"]["total"], 3);
+    assert_eq!(json["meta"]["completed"], 1);
+    assert_eq!(json["meta"]["pending"], 2);
+    assert_eq!(json["meta"]["oldest_pending"], today.as_str());
+    assert_eq!(json["tasks"].as_array().map(Vec::len), Some(3));
+
+    drop(app);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
@@ -17,7 +17,7 @@ fn applying_task_preset_sets_filter_and_selection() -> Result<(), Box<dyn std::e
     app.selected = 1;
 
     app.preset_selected = 0;
-    app.apply_selected_task_preset();
+    app.apply_selected_task_preset()?;
 
     assert_eq!(app.task_filter, "status:open");
     assert_eq!(app.filtered_task_indices(), vec![0, 1]);
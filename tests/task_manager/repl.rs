@@ -0,0 +1,150 @@
+use super::common::temp_db_path;
+use task_manager_cli::repl::{self, parse_command, ParseError, ReplCommand};
+use task_manager_cli::task_manager::app::App;
+
+#[test]
+fn repl_commands_parse_with_quoted_words() {
+    assert_eq!(
+        parse_command("add Work \"Write report\" 'due Friday'\n"),
+        Ok(ReplCommand::Add {
+            topic: "Work".to_string(),
+            name: "Write report".to_string(),
+            description: "due Friday".to_string(),
+        })
+    );
+    assert_eq!(parse_command("list"), Ok(ReplCommand::List { topic: None }));
+    assert_eq!(parse_command("done 7"), Ok(ReplCommand::Done(7)));
+    assert_eq!(parse_command("   "), Err(ParseError::Empty));
+    assert_eq!(
+        parse_command("delete seven"),
+        Err(ParseError::InvalidId("seven".to_string()))
+    );
+    assert_eq!(
+        parse_command("add Work"),
+        Err(ParseError::MissingArgument {
+            command: "add",
+            argument: "task name",
+        })
+    );
+    assert_eq!(
+        parse_command("add Work \"Write"),
+        Err(ParseError::UnterminatedQuote)
+    );
+    assert_eq!(
+        parse_command("fly"),
+        Err(ParseError::UnknownCommand("fly".to_string()))
+    );
+}
+
+#[test]
+fn repl_runs_commands_until_exit() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("repl");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let app = App::new(&db_path_str)?;
+
+    let input = "add Work \"Write report\"\n\nfavourite 1\ndone 1\nbogus\ndelete 99\ndone 99\n\
+                 list Nowhere\nadd Work \"\"\nexit\nlist\n";
+    let mut out = Vec::new();
+    assert_eq!(
+        repl::run(&app.db_ops, input.as_bytes(), &mut out, false)?,
+        0
+    );
+
+    let output = String::from_utf8(out)?;
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "Added task 1 to Work: Write report",
+            "Favourited task 1: Write report",
+            "Completed task 1: Write report",
+            "error: unknown command 'bogus'; try 'help'",
+            "error: task 99 not found",
+            "error: task 99 not found",
+            "error: unknown topic: Nowhere",
+            "error: Task name cannot be empty",
+        ]
+    );
+
+    drop(app);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn repl_completes_commands_and_topic_names() {
+    let topics = vec!["Work".to_string(), "Weekend plans".to_string()];
+    assert_eq!(
+        repl::complete("fa", &topics),
+        (0, vec!["favourite".to_string()])
+    );
+    assert_eq!(
+        repl::complete("d", &topics),
+        (0, vec!["done".to_string(), "delete".to_string()])
+    );
+    assert_eq!(
+        repl::complete("add w", &topics),
+        (4, vec!["Work".to_string(), "\"Weekend plans\"".to_string()])
+    );
+    assert_eq!(
+        repl::complete("list \"Weekend p", &topics),
+        (5, vec!["\"Weekend plans\"".to_string()])
+    );
+    assert!(repl::complete("done W", &topics).1.is_empty());
+    assert!(repl::complete("add Work W", &topics).1.is_empty());
+}
+
+#[test]
+fn line_editor_tab_completes_and_recalls_history() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use task_manager_cli::repl::{Edit, LineEditor};
+
+    let topics = vec!["Work".to_string(), "Weekend".to_string()];
+    let mut editor = LineEditor::new();
+    let press = |editor: &mut LineEditor, code: KeyCode| {
+        editor.handle_key(KeyEvent::new(code, KeyModifiers::NONE), &topics)
+    };
+    let type_text = |editor: &mut LineEditor, text: &str| {
+        for c in text.chars() {
+            press(editor, KeyCode::Char(c));
+        }
+    };
+
+    type_text(&mut editor, "li");
+    assert_eq!(press(&mut editor, KeyCode::Tab), Edit::Continue);
+    assert_eq!(editor.line(), "list ");
+    type_text(&mut editor, "w");
+    assert_eq!(
+        press(&mut editor, KeyCode::Tab),
+        Edit::Candidates(vec!["Work".to_string(), "Weekend".to_string()])
+    );
+    type_text(&mut editor, "o");
+    press(&mut editor, KeyCode::Tab);
+    assert_eq!(
+        press(&mut editor, KeyCode::Enter),
+        Edit::Submit("list Work ".to_string())
+    );
+    assert_eq!(editor.line(), "");
+
+    type_text(&mut editor, "topcs");
+    press(&mut editor, KeyCode::Left);
+    press(&mut editor, KeyCode::Left);
+    type_text(&mut editor, "i");
+    assert_eq!((editor.line().as_str(), editor.cursor()), ("topics", 4));
+    press(&mut editor, KeyCode::Enter);
+
+    press(&mut editor, KeyCode::Up);
+    assert_eq!(editor.line(), "topics");
+    press(&mut editor, KeyCode::Up);
+    assert_eq!(editor.line(), "list Work ");
+    press(&mut editor, KeyCode::Down);
+    press(&mut editor, KeyCode::Down);
+    assert_eq!(editor.line(), "");
+    assert_eq!(
+        editor.handle_key(
+            KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL),
+            &topics
+        ),
+        Edit::Eof
+    );
+}
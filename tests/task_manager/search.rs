@@ -0,0 +1,268 @@
+use super::common::temp_db_path;
+use task_manager_cli::task_manager::app::{App, InputMode};
+
+fn select_topic(app: &mut App, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == name)
+        .expect("topic should exist");
+    app.load_tasks()?;
+    Ok(())
+}
+
+#[test]
+fn fts_search_returns_highlighted_snippets_scoped_to_topic(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("fts_search_snippets");
+    let mut app = App::new(&db_path.to_string_lossy())?;
+
+    app.add_topic("Work")?;
+    app.add_topic("Home")?;
+    select_topic(&mut app, "Work")?;
+    app.add_task_with_details("Quarterly report", "Draft the budget summary for finance")?;
+    app.add_task_with_details("Standup notes", "")?;
+    select_topic(&mut app, "Home")?;
+    app.add_task_with_details("Plan budget", "Groceries and rent")?;
+
+    let work_id = app.topics.iter().find(|t| t.name == "Work").unwrap().id;
+    let hits = app.db_ops.fts_search_with_snippets("budg", Some(work_id))?;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].0.name, "Quarterly report");
+    assert!(
+        hits[0].1.contains("<b>budget</b>"),
+        "snippet: {}",
+        hits[0].1
+    );
+
+    let all = app.db_ops.fts_search_with_snippets("budget", None)?;
+    assert_eq!(all.len(), 2);
+    assert!(app.db_ops.fts_search_with_snippets("  ", None)?.is_empty());
+    assert!(app
+        .db_ops
+        .fts_search_with_snippets("\"unbalanced", None)?
+        .is_empty());
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn fts_index_follows_task_edits_and_deletes() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("fts_search_sync");
+    let mut app = App::new(&db_path.to_string_lossy())?;
+
+    app.add_task_with_details("Renew passport", "")?;
+    app.selected = app
+        .tasks
+        .iter()
+        .position(|task| task.name == "Renew passport")
+        .unwrap();
+    app.edit_task("Renew licence", "at the post office")?;
+
+    assert!(app
+        .db_ops
+        .fts_search_with_snippets("passport", None)?
+        .is_empty());
+    assert_eq!(app.db_ops.fts_search_with_snippets("post", None)?.len(), 1);
+
+    app.delete_task()?;
+    assert!(app
+        .db_ops
+        .fts_search_with_snippets("licence", None)?
+        .is_empty());
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn search_popup_focuses_the_selected_task() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("fts_search_focus");
+    let mut app = App::new(&db_path.to_string_lossy())?;
+
+    app.add_topic("Errands")?;
+    select_topic(&mut app, "Errands")?;
+    app.add_task_with_details("Buy stamps", "")?;
+    app.add_task_with_details("Collect parcel", "from the depot")?;
+    select_topic(&mut app, "Default")?;
+
+    app.begin_search();
+    assert_eq!(app.input_mode, InputMode::Searching);
+    app.search_query = "depot".to_string();
+    app.run_search()?;
+    assert_eq!(app.search_results.len(), 1);
+
+    app.open_selected_search_result()?;
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(app.topics[app.selected_topic].name, "Errands");
+    assert_eq!(app.tasks[app.selected].name, "Collect parcel");
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn due_soon_lists_open_tasks_by_due_date_and_opens_them() -> Result<(), Box<dyn std::error::Error>>
+{
+    let db_path = temp_db_path("due_soon");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    let today = chrono::Local::now().date_naive();
+    let in_days = |days: i64| {
+        (today + chrono::Duration::days(days))
+            .format("%Y-%m-%d")
+            .to_string()
+    };
+
+    let work = app.db_ops.add_topic("Work", "")?;
+    let home = app.db_ops.add_topic("Home", "")?;
+    let report = app.db_ops.add_task(work.id, "Report", "")?;
+    let rent = app.db_ops.add_task(home.id, "Rent", "")?;
+    let late = app.db_ops.add_task(home.id, "Library books", "")?;
+    let far = app.db_ops.add_task(work.id, "Conference talk", "")?;
+    let done = app.db_ops.add_task(work.id, "Expenses", "")?;
+    app.db_ops.add_task(work.id, "Someday", "")?;
+    app.db_ops.set_task_due_date(report.id, Some(&in_days(3)))?;
+    app.db_ops.set_task_due_date(rent.id, Some(&in_days(0)))?;
+    app.db_ops.set_task_due_date(late.id, Some(&in_days(-2)))?;
+    app.db_ops.set_task_due_date(far.id, Some(&in_days(30)))?;
+    app.db_ops.set_task_due_date(done.id, Some(&in_days(1)))?;
+    app.db_ops.toggle_task_completion(done.id)?;
+    app.load_topics()?;
+
+    app.begin_due_soon()?;
+    assert_eq!(app.input_mode, InputMode::ViewingDueSoon);
+    let names: Vec<&str> = app
+        .due_soon_tasks
+        .iter()
+        .map(|task| task.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Library books", "Rent", "Report"]);
+
+    app.move_due_soon_selection(-1);
+    app.open_selected_due_task()?;
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(app.topics[app.selected_topic].name, "Work");
+    assert_eq!(app.tasks[app.selected].name, "Report");
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn window_title_tracks_open_tasks_due_today() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("window_title");
+    let mut app = App::new(&db_path.to_string_lossy())?;
+    let today = chrono::Local::now()
+        .date_naive()
+        .format("%Y-%m-%d")
+        .to_string();
+
+    assert_eq!(app.window_title_update()?, Some("Task Manager".to_string()));
+    assert_eq!(app.window_title_update()?, None);
+
+    let work = app.db_ops.add_topic("Work", "")?;
+    let report = app.db_ops.add_task(work.id, "Report", "")?;
+    let rent = app.db_ops.add_task(work.id, "Rent", "")?;
+    app.db_ops.set_task_due_date(report.id, Some(&today))?;
+    app.db_ops.set_task_due_date(rent.id, Some(&today))?;
+    assert_eq!(app.db_ops.count_tasks_due_today()?, 2);
+    assert_eq!(
+        app.window_title_update()?,
+        Some("Task Manager — 2 due today".to_string())
+    );
+
+    app.db_ops.toggle_task_completion(rent.id)?;
+    app.db_ops.archive_task(report.id)?;
+    assert_eq!(app.window_title_update()?, Some("Task Manager".to_string()));
+    assert_eq!(app.last_title_count, Some(0));
+
+    // After a failed count the title waits for the retry time.
+    app.db_ops.toggle_task_completion(rent.id)?;
+    app.title_retry_at = Some(std::time::Instant::now() + std::time::Duration::from_secs(60));
+    assert_eq!(app.window_title_update()?, None);
+    app.title_retry_at = Some(std::time::Instant::now());
+    assert_eq!(
+        app.window_title_update()?,
+        Some("Task Manager — 1 due today".to_string())
+    );
+    assert_eq!(app.title_retry_at, None);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn tasks_due_within_a_day_are_logged_and_flash_the_log_panel(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("due_within_day");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    assert!(!app.log_panel_alert_active());
+
+    let today = chrono::Local::now().date_naive();
+    let work = app.db_ops.add_topic("Work", "")?;
+    let rent = app.db_ops.add_task(work.id, "Rent", "")?;
+    let talk = app.db_ops.add_task(work.id, "Conference talk", "")?;
+    app.db_ops
+        .set_task_due_date(rent.id, Some(&today.format("%Y-%m-%d").to_string()))?;
+    let later = today + chrono::Duration::days(10);
+    app.db_ops
+        .set_task_due_date(talk.id, Some(&later.format("%Y-%m-%d").to_string()))?;
+
+    app.check_due_within_day()?;
+    let warnings: Vec<&str> = app
+        .logs
+        .iter()
+        .map(|entry| entry.message.as_str())
+        .filter(|message| message.contains("is due within 24 hours"))
+        .collect();
+    assert_eq!(warnings, vec!["Task 'Rent' is due within 24 hours!"]);
+    assert!(app.log_panel_alert_active());
+    assert!(app.next_due_check_at > std::time::Instant::now());
+
+    drop(app);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn recent_tasks_are_cached_until_reload_and_open_in_their_topic(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("recent_tasks");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    let work = app.db_ops.add_topic("Work", "")?;
+    let home = app.db_ops.add_topic("Home", "")?;
+    app.db_ops.add_task(work.id, "Report", "")?;
+    app.db_ops.add_task(home.id, "Rent", "")?;
+    app.load_topics()?;
+    select_topic(&mut app, "Home")?;
+
+    let names = |app: &mut App| -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        Ok(app
+            .recent_tasks()?
+            .iter()
+            .map(|(task, topic)| format!("{}/{}", topic.name, task.name))
+            .collect())
+    };
+    assert_eq!(names(&mut app)?, vec!["Home/Rent", "Work/Report"]);
+
+    app.db_ops.add_task(home.id, "Groceries", "")?;
+    assert_eq!(names(&mut app)?.len(), 2);
+    app.load_tasks()?;
+    assert_eq!(names(&mut app)?[0], "Home/Groceries");
+
+    app.begin_recent()?;
+    assert_eq!(app.input_mode, InputMode::ViewingRecent);
+    app.move_recent_selection(2);
+    app.open_selected_recent_task()?;
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(app.topics[app.selected_topic].name, "Work");
+    assert_eq!(app.tasks[app.selected].name, "Report");
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
@@ -0,0 +1,332 @@
+use super::common::temp_db_path;
+use task_manager_cli::db::task_manager::models::{CompletionFilter, NewTask, SortMode};
+use task_manager_cli::error::AppError;
+use task_manager_cli::task_manager::app::App;
+
+fn select_topic(app: &mut App, name: &str) -> Result<(), AppError> {
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == name)
+        .expect("topic should exist");
+    app.load_tasks()
+}
+
+#[test]
+fn sort_mode_orders_tasks_and_persists_per_topic() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("topic_sort_persist");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    {
+        let mut app = App::new(&db_path_str)?;
+        app.add_topic("Work")?;
+        select_topic(&mut app, "Work")?;
+        app.add_task_with_details("Charlie", "")?;
+        app.add_task_with_details("Alpha", "")?;
+        app.add_task_with_details("Bravo", "")?;
+
+        app.cycle_sort_mode()?;
+        assert_eq!(app.sort_mode, SortMode::Name);
+        let names: Vec<&str> = app.tasks.iter().map(|task| task.name.as_str()).collect();
+        assert_eq!(names, vec!["Alpha", "Bravo", "Charlie"]);
+
+        app.toggle_sort_direction()?;
+        let names: Vec<&str> = app.tasks.iter().map(|task| task.name.as_str()).collect();
+        assert_eq!(names, vec!["Charlie", "Bravo", "Alpha"]);
+
+        select_topic(&mut app, "Default")?;
+        assert_eq!(app.sort_mode, SortMode::Created);
+        assert!(app.sort_ascending);
+    }
+
+    let mut app = App::new(&db_path_str)?;
+    select_topic(&mut app, "Work")?;
+    assert_eq!(app.sort_mode, SortMode::Name);
+    assert!(!app.sort_ascending);
+    assert_eq!(
+        app.tasks.first().map(|task| task.name.as_str()),
+        Some("Charlie")
+    );
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn completion_filter_hides_tasks_and_survives_restart() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("topic_completion_filter");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    {
+        let mut app = App::new(&db_path_str)?;
+        app.add_topic("Home")?;
+        select_topic(&mut app, "Home")?;
+        app.add_task_with_details("Open chore", "")?;
+        app.add_task_with_details("Done chore", "")?;
+        app.selected = 1;
+        app.toggle_task()?;
+
+        app.cycle_completion_filter()?;
+        assert_eq!(app.filter.completion, CompletionFilter::Open);
+        let visible = app.filtered_task_indices();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(app.tasks[visible[0]].name, "Open chore");
+    }
+
+    let mut app = App::new(&db_path_str)?;
+    select_topic(&mut app, "Home")?;
+    assert_eq!(app.filter.completion, CompletionFilter::Open);
+    assert_eq!(app.filtered_task_indices().len(), 1);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn pinned_tasks_sort_first_and_ignore_completion_filter() -> Result<(), Box<dyn std::error::Error>>
+{
+    let db_path = temp_db_path("topic_pinned_tasks");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    let mut app = App::new(&db_path_str)?;
+    app.add_topic("Errands")?;
+    select_topic(&mut app, "Errands")?;
+    app.add_task_with_details("Alpha", "")?;
+    app.add_task_with_details("Bravo", "")?;
+    app.add_task_with_details("Charlie", "")?;
+
+    app.selected = 2;
+    app.toggle_pin()?;
+    let names: Vec<&str> = app.tasks.iter().map(|task| task.name.as_str()).collect();
+    assert_eq!(names, vec!["Charlie", "Alpha", "Bravo"]);
+    assert_eq!(app.selected, 0);
+
+    // Reversing the sort keeps the pinned task on top.
+    app.toggle_sort_direction()?;
+    let names: Vec<&str> = app.tasks.iter().map(|task| task.name.as_str()).collect();
+    assert_eq!(names, vec!["Charlie", "Bravo", "Alpha"]);
+
+    app.selected = 0;
+    app.toggle_task()?;
+    app.cycle_completion_filter()?;
+    assert_eq!(app.filter.completion, CompletionFilter::Open);
+    assert_eq!(app.filtered_task_indices().len(), 3);
+
+    let pinned_id = app.tasks[0].id;
+    app.db_ops.unpin_task(pinned_id)?;
+    app.load_tasks()?;
+    assert_eq!(app.filtered_task_indices().len(), 2);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn manual_sort_moves_tasks_by_swapping_weights() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("topic_manual_sort");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    let mut app = App::new(&db_path_str)?;
+    app.add_topic("Chores")?;
+    select_topic(&mut app, "Chores")?;
+    app.add_task_with_details("Alpha", "")?;
+    app.add_task_with_details("Bravo", "")?;
+    app.add_task_with_details("Charlie", "")?;
+    let weights: Vec<i32> = app.tasks.iter().map(|task| task.sort_weight).collect();
+    assert_eq!(weights, vec![0, 10, 20]);
+
+    app.selected = 2;
+    app.move_selected_task(-1)?;
+    assert_eq!(app.tasks[2].name, "Charlie");
+    while app.sort_mode != SortMode::Manual {
+        app.cycle_sort_mode()?;
+    }
+    app.selected = 2;
+    app.move_selected_task(-1)?;
+    let names: Vec<&str> = app.tasks.iter().map(|task| task.name.as_str()).collect();
+    assert_eq!(names, vec!["Alpha", "Charlie", "Bravo"]);
+    assert_eq!(app.selected, 1);
+
+    // A task inserted with a clashing weight triggers a renumbering.
+    let topic_id = app.tasks[0].topic_id;
+    app.db_ops.bulk_insert_tasks(&[NewTask {
+        topic_id,
+        name: "Delta",
+        description: "",
+        completed: false,
+        favourite: false,
+        pinned: false,
+        created_at: "2026-10-15 09:00:00",
+        updated_at: "2026-10-15 09:00:00",
+        sort_weight: 20,
+        source: "tui",
+        url: None,
+    }])?;
+    app.load_tasks()?;
+    app.selected = 3;
+    app.move_selected_task(-1)?;
+    let names: Vec<&str> = app.tasks.iter().map(|task| task.name.as_str()).collect();
+    assert_eq!(names, vec!["Alpha", "Charlie", "Delta", "Bravo"]);
+    let weights: Vec<i32> = app.tasks.iter().map(|task| task.sort_weight).collect();
+    assert_eq!(weights, vec![0, 10, 20, 30]);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn task_list_pages_with_selection_and_focus() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("task_list_paging");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    let mut app = App::new(&db_path_str)?;
+    assert_eq!(app.page_size, 50);
+    app.page_size = 2;
+    app.add_topic("Work")?;
+    select_topic(&mut app, "Work")?;
+    for name in ["One", "Two", "Three", "Four", "Five"] {
+        app.add_task_with_details(name, "")?;
+    }
+    let names = |app: &App| -> Vec<String> { app.tasks.iter().map(|t| t.name.clone()).collect() };
+    assert_eq!(app.page_count(), 3);
+    assert_eq!(names(&app), vec!["One", "Two"]);
+    let first_id = app.tasks[0].id;
+
+    app.move_down_or_next_page()?;
+    assert_eq!(app.selected, 1);
+    app.move_down_or_next_page()?;
+    assert_eq!((app.page_offset, app.selected), (1, 0));
+    assert_eq!(names(&app), vec!["Three", "Four"]);
+
+    app.move_up_or_previous_page()?;
+    assert_eq!((app.page_offset, app.selected), (0, 1));
+    assert_eq!(app.tasks[app.selected].name, "Two");
+
+    assert!(app.next_page()?);
+    assert!(app.next_page()?);
+    assert_eq!(names(&app), vec!["Five"]);
+    assert!(!app.next_page()?);
+    app.move_down_or_next_page()?;
+    assert_eq!((app.page_offset, app.selected), (2, 0));
+
+    assert!(app.select_task_by_id(first_id)?);
+    assert_eq!((app.page_offset, app.selected), (0, 0));
+
+    select_topic(&mut app, "Default")?;
+    assert_eq!(app.page_offset, 0);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn task_list_pages_over_filtered_tasks() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("task_list_filtered_paging");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    let mut app = App::new(&db_path_str)?;
+    app.page_size = 2;
+    app.add_topic("Work")?;
+    select_topic(&mut app, "Work")?;
+    for name in ["Apple one", "Kiwi", "APPLE two", "Plum", "Apple three"] {
+        app.add_task_with_details(name, "")?;
+    }
+    let names = |app: &App| -> Vec<String> { app.tasks.iter().map(|t| t.name.clone()).collect() };
+    assert_eq!((app.task_total, app.page_count()), (5, 3));
+
+    app.next_page()?;
+    for c in "apple".chars() {
+        app.append_task_filter_char(c)?;
+    }
+    assert_eq!(app.page_offset, 0);
+    assert_eq!((app.task_total, app.page_count()), (3, 2));
+    assert_eq!(names(&app), vec!["Apple one", "APPLE two"]);
+    app.move_down_or_next_page()?;
+    app.move_down_or_next_page()?;
+    assert_eq!((app.page_offset, app.selected), (1, 0));
+    assert_eq!(names(&app), vec!["Apple three"]);
+
+    app.clear_task_filter()?;
+    for c in "-apple".chars() {
+        app.append_task_filter_char(c)?;
+    }
+    assert_eq!(names(&app), vec!["Kiwi", "Plum"]);
+    let plum_id = app.tasks[1].id;
+
+    app.clear_task_filter()?;
+    app.db_ops.toggle_task_completion(plum_id)?;
+    for c in "status:done".chars() {
+        app.append_task_filter_char(c)?;
+    }
+    assert_eq!((app.task_total, names(&app)), (1, vec!["Plum".to_string()]));
+    assert!(app.select_task_by_id(plum_id)?);
+    assert_eq!((app.page_offset, app.selected), (0, 0));
+
+    app.pop_task_filter_char()?;
+    app.pop_task_filter_char()?;
+    app.pop_task_filter_char()?;
+    app.pop_task_filter_char()?;
+    assert_eq!((app.task_total, app.page_count()), (0, 1));
+    assert!(app.tasks.is_empty());
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn completed_tasks_follow_open_ones_across_pages() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("task_list_completed_paging");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    let mut app = App::new(&db_path_str)?;
+    app.page_size = 2;
+    app.add_topic("Work")?;
+    select_topic(&mut app, "Work")?;
+    for name in ["One", "Two", "Three", "Four"] {
+        app.add_task_with_details(name, "")?;
+    }
+    let done: Vec<i32> = app.tasks.iter().map(|task| task.id).collect();
+    for id in done {
+        app.db_ops.toggle_task_completion(id)?;
+    }
+    app.load_tasks()?;
+    let names = |app: &App| -> Vec<String> { app.tasks.iter().map(|t| t.name.clone()).collect() };
+    assert_eq!(names(&app), vec!["Three", "Four"]);
+    app.next_page()?;
+    assert_eq!(names(&app), vec!["One", "Two"]);
+    assert!(app.tasks.iter().all(|task| task.completed));
+
+    while app.sort_mode != SortMode::Completed {
+        app.cycle_sort_mode()?;
+    }
+    app.toggle_sort_direction()?;
+    app.page_offset = 0;
+    app.load_tasks()?;
+    assert!(app.tasks.iter().all(|task| task.completed));
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn task_filter_folds_non_ascii_case_in_sql() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("task_list_unicode_filter");
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    let mut app = App::new(&db_path_str)?;
+    app.page_size = 2;
+    app.add_topic("Work")?;
+    select_topic(&mut app, "Work")?;
+    for name in ["Über plan", "Kiwi", "Plum", "ÜBER review"] {
+        app.add_task_with_details(name, "")?;
+    }
+    for c in "über".chars() {
+        app.append_task_filter_char(c)?;
+    }
+    let names: Vec<String> = app.tasks.iter().map(|t| t.name.clone()).collect();
+    assert_eq!(app.task_total, 2);
+    assert_eq!(names, vec!["Über plan", "ÜBER review"]);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
@@ -0,0 +1,439 @@
+use super::common::temp_db_path;
+use task_manager_cli::error::AppError;
+use task_manager_cli::task_manager::app::{App, InputMode};
+
+#[test]
+fn update_topic_sets_fields_and_rejects_duplicate_names() -> Result<(), Box<dyn std::error::Error>>
+{
+    let db_path = temp_db_path("update_topic");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    app.add_topic("Work")?;
+    app.add_topic("Home")?;
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Work")
+        .expect("work topic should exist");
+
+    app.update_topic(Some("Office"), Some("Day job"), Some("light-blue"))?;
+    let topic = &app.topics[app.selected_topic];
+    assert_eq!(topic.name, "Office");
+    assert_eq!(topic.description, "Day job");
+    assert_eq!(topic.color.as_deref(), Some("light-blue"));
+
+    assert!(matches!(
+        app.update_topic(Some("home"), None, None),
+        Err(AppError::DuplicateTopicName(name)) if name == "home"
+    ));
+    assert!(app.update_topic(None, None, Some("not-a-colour")).is_err());
+    assert!(app.update_topic(None, None, Some("#aé1234")).is_err());
+    assert!(app.update_topic(None, None, Some("#+f+f+f")).is_err());
+    assert_eq!(app.topics[app.selected_topic].name, "Office");
+
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Default")
+        .expect("default topic should exist");
+    assert!(matches!(
+        app.update_topic(Some("Everything"), None, None),
+        Err(AppError::ProtectedTopic(_))
+    ));
+    app.update_topic(None, None, Some("green"))?;
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn moving_topics_reorders_tabs_and_keeps_builtins_grouped() -> Result<(), Box<dyn std::error::Error>>
+{
+    let db_path = temp_db_path("move_topic");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let names =
+        |app: &App| -> Vec<String> { app.topics.iter().map(|topic| topic.name.clone()).collect() };
+
+    {
+        let mut app = App::new(&db_path_str)?;
+        app.add_topic("Work")?;
+        app.add_topic("Home")?;
+        assert_eq!(names(&app), vec!["Default", "Work", "Home"]);
+
+        app.selected_topic = 2;
+        app.move_selected_topic(-1)?;
+        assert_eq!(names(&app), vec!["Default", "Home", "Work"]);
+        assert_eq!(app.topics[app.selected_topic].name, "Home");
+
+        // A user topic cannot cross into the built-in group.
+        app.move_selected_topic(-1)?;
+        assert_eq!(names(&app), vec!["Default", "Home", "Work"]);
+        assert_eq!(app.selected_topic, 1);
+
+        app.selected_topic = 0;
+        app.move_selected_topic(1)?;
+        assert_eq!(app.topics[0].name, "Default");
+    }
+
+    let app = App::new(&db_path_str)?;
+    assert_eq!(names(&app), vec!["Default", "Home", "Work"]);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn merging_topics_moves_tasks_and_removes_the_source() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("merge_topic");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    let select = |app: &mut App, name: &str| -> Result<(), AppError> {
+        app.selected_topic = app
+            .topics
+            .iter()
+            .position(|topic| topic.name == name)
+            .expect("topic should exist");
+        app.load_tasks()
+    };
+
+    app.add_topic("Inbox")?;
+    app.add_topic("Work")?;
+    select(&mut app, "Inbox")?;
+    app.add_task_with_details("Reply to Sam", "")?;
+    app.add_task_with_details("File receipts", "")?;
+
+    app.begin_merge_topic()?;
+    let candidates: Vec<&str> = app
+        .merge_candidates()
+        .iter()
+        .map(|topic| topic.name.as_str())
+        .collect();
+    assert_eq!(candidates, vec!["Work"]);
+    app.confirm_merge_topic()?;
+
+    assert!(!app.topics.iter().any(|topic| topic.name == "Inbox"));
+    assert_eq!(app.topics[app.selected_topic].name, "Work");
+    assert_eq!(app.tasks.len(), 2);
+    assert!(app
+        .logs
+        .iter()
+        .any(|entry| entry.message.contains("2 task(s) moved")));
+
+    let default_id = app.topics.iter().find(|t| t.name == "Default").unwrap().id;
+    let work_id = app.topics[app.selected_topic].id;
+    assert!(matches!(
+        app.db_ops.merge_topics(work_id, default_id),
+        Err(AppError::ProtectedTopic(name)) if name == "Default"
+    ));
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn topic_search_matches_name_or_description_and_switches_tab(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("topic_search");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    app.db_ops.add_topic("Work", "Day job")?;
+    let home = app.db_ops.add_topic("Home", "")?;
+    app.db_ops.add_topic("100%_done", "")?;
+    app.load_topics()?;
+
+    let names = |topics: &[task_manager_cli::db::task_manager::models::Topic]| -> Vec<String> {
+        topics.iter().map(|topic| topic.name.clone()).collect()
+    };
+    assert_eq!(names(&app.db_ops.search_topics("JOB")?), vec!["Work"]);
+    assert_eq!(names(&app.db_ops.search_topics("%")?), vec!["100%_done"]);
+    assert!(app.db_ops.search_topics("missing")?.is_empty());
+
+    app.begin_topic_search()?;
+    assert_eq!(app.input_mode, InputMode::SearchingTopics);
+    assert!(!names(&app.topic_search_results).contains(&"Favourites".to_string()));
+    assert_eq!(app.topic_search_results.len(), app.topics.len());
+
+    app.topic_search_query.push_str("ho");
+    app.run_topic_search()?;
+    assert_eq!(names(&app.topic_search_results), vec!["Home"]);
+    app.open_selected_topic_search_result()?;
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(app.topics[app.selected_topic].id, home.id);
+    assert!(app.topic_search_query.is_empty());
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn topic_task_limit_refuses_new_tasks_once_reached() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("topic_task_limit");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    app.add_topic("Work")?;
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Work")
+        .expect("work topic should exist");
+    let work_id = app.topics[app.selected_topic].id;
+
+    assert!(app.set_topic_task_limit(Some(0)).is_err());
+    app.set_topic_task_limit(Some(2))?;
+    assert_eq!(app.topics[app.selected_topic].task_limit, Some(2));
+
+    app.add_task_with_details("One", "")?;
+    app.add_task_with_details("Two", "")?;
+    assert!(matches!(
+        app.add_task_with_details("Three", ""),
+        Err(AppError::TopicTaskLimitReached(name, 2)) if name == "Work"
+    ));
+    assert_eq!(app.db_ops.count_tasks_in_topic(work_id)?, 2);
+
+    app.set_topic_task_limit(None)?;
+    app.add_task_with_details("Three", "")?;
+    assert_eq!(app.db_ops.count_tasks_in_topic(work_id)?, 3);
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn topic_creation_collects_an_optional_description() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("topic_description_step");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    app.begin_add_topic();
+    app.input = "Garden".to_string();
+    app.begin_topic_description();
+    assert_eq!(app.input_mode, InputMode::AddingTopicDescription);
+    app.input = "Beds and borders".to_string();
+    app.submit_new_topic()?;
+    assert_eq!(app.input_mode, InputMode::Normal);
+    let garden = app
+        .topics
+        .iter()
+        .find(|topic| topic.name == "Garden")
+        .expect("garden topic should exist");
+    assert_eq!(garden.description, "Beds and borders");
+
+    app.begin_add_topic();
+    app.input = "Errands".to_string();
+    app.begin_topic_description();
+    app.submit_new_topic()?;
+    let errands = app
+        .topics
+        .iter()
+        .find(|topic| topic.name == "Errands")
+        .expect("errands topic should exist");
+    assert_eq!(errands.description, "");
+
+    app.begin_add_topic();
+    app.input = "Abandoned".to_string();
+    app.begin_topic_description();
+    app.cancel_add_topic();
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert!(!app.topics.iter().any(|topic| topic.name == "Abandoned"));
+
+    drop(app);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn topic_stats_count_tasks_and_recent_activity() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("topic_stats");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    let work = app.db_ops.add_topic("Work", "Day job")?;
+    let other = app.db_ops.add_topic("Home", "")?;
+    app.db_ops.add_task(other.id, "Elsewhere", "")?;
+    let report = app.db_ops.add_task(work.id, "Report", "")?;
+    let late = app.db_ops.add_task(work.id, "Late invoice", "")?;
+    let standup = app.db_ops.add_task(work.id, "Standup notes", "")?;
+    app.db_ops.toggle_task_completion(report.id)?;
+    let yesterday = chrono::Local::now().date_naive() - chrono::Duration::days(1);
+    app.db_ops
+        .set_task_due_date(late.id, Some(&yesterday.format("%Y-%m-%d").to_string()))?;
+
+    let stats = app.db_ops.topic_stats(work.id)?;
+    assert_eq!(stats.topic.description, "Day job");
+    assert_eq!(
+        (
+            stats.total,
+            stats.completed,
+            stats.in_progress,
+            stats.overdue
+        ),
+        (3, 1, 2, 1)
+    );
+    assert_eq!(stats.latest_created.as_deref(), Some("Standup notes"));
+    assert_eq!(stats.latest_completed.as_deref(), Some("Report"));
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    assert_eq!(stats.most_active_day, Some((today, 3)));
+
+    app.load_topics()?;
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Work")
+        .expect("work topic should exist");
+    app.begin_topic_stats()?;
+    assert_eq!(app.input_mode, InputMode::ViewingTopicStats);
+    assert_eq!(app.topic_stats.as_ref().map(|stats| stats.total), Some(3));
+    app.close_topic_stats();
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert!(app.topic_stats.is_none());
+
+    // Archived (soft-deleted) tasks no longer count.
+    app.db_ops.archive_task(standup.id)?;
+    let stats = app.db_ops.topic_stats(work.id)?;
+    assert_eq!((stats.total, stats.in_progress), (2, 1));
+    assert_eq!(stats.latest_created.as_deref(), Some("Late invoice"));
+    assert_eq!(app.db_ops.count_tasks()?, (3, 1));
+
+    drop(app);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn list_topics_prints_a_table_json_or_csv() -> Result<(), Box<dyn std::error::Error>> {
+    use task_manager_cli::cli::{self, OutputFormat};
+
+    let db_path = temp_db_path("list_topics_formats");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    app.add_topic("Work")?;
+    let work = app.topics.iter().position(|topic| topic.name == "Work");
+    app.selected_topic = work.expect("work topic should exist");
+    app.update_topic(None, Some(&"x".repeat(50)), None)?;
+
+    let mut out = Vec::new();
+    assert_eq!(cli::topics(&app.db_ops, &mut out, OutputFormat::Table)?, 0);
+    let table = String::from_utf8(out)?;
+    let mut lines = table.lines();
+    assert_eq!(lines.next(), Some("ID  Name        Description"));
+    let work_line = lines
+        .find(|line| line.contains("Work"))
+        .expect("work row should be listed");
+    assert!(work_line.ends_with(&format!("Work        {}...", "x".repeat(37))));
+
+    let mut out = Vec::new();
+    cli::topics(&app.db_ops, &mut out, OutputFormat::Json)?;
+    let json: serde_json::Value = serde_json::from_slice(&out)?;
+    let topics = json["topics"].as_array().expect("topics array");
+    assert!(topics.iter().any(|topic| topic["name"] == "Work"));
+
+    let mut out = Vec::new();
+    cli::topics(&app.db_ops, &mut out, OutputFormat::Csv)?;
+    let csv = String::from_utf8(out)?;
+    assert!(csv.starts_with("id,name,description\n"));
+    assert!(csv.contains(",Work,xxxx"));
+
+    drop(app);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn list_topics_binary_prints_topics_and_fails_cleanly() -> Result<(), Box<dyn std::error::Error>> {
+    use std::process::Command;
+
+    let dir = temp_db_path("list_topics_cli");
+    std::fs::create_dir_all(&dir)?;
+    let mut app = App::new(&dir.join("tasks.db").to_string_lossy())?;
+    app.add_topic("Errands")?;
+    drop(app);
+
+    let run = |db_dir: &std::path::Path| {
+        Command::new(env!("CARGO_BIN_EXE_task_manager_cli"))
+            .args(["list-topics", "--format", "csv"])
+            .current_dir(&dir)
+            .env("TASK_MANAGER_DB_DIR", db_dir)
+            .env("TASK_MANAGER_DB_FILENAME", "tasks.db")
+            .output()
+    };
+    let output = run(&dir)?;
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.starts_with("id,name,description\n"));
+    assert!(stdout.lines().any(|line| line.ends_with(",Errands,")));
+
+    std::fs::create_dir_all(dir.join("broken"))?;
+    std::fs::write(dir.join("broken").join("tasks.db"), "not a database")?;
+    let output = run(&dir.join("broken"))?;
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)?.contains("cannot open database"));
+
+    let _ = std::fs::remove_dir_all(dir);
+    Ok(())
+}
+
+#[test]
+fn overdue_counts_track_open_tasks_past_their_due_date() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("overdue_badges");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    let work = app.db_ops.add_topic("Work", "")?;
+    let home = app.db_ops.add_topic("Home", "")?;
+    let late = app.db_ops.add_task(work.id, "File report", "")?;
+    app.db_ops.set_task_due_date(late.id, Some("2000-01-01"))?;
+    let done = app.db_ops.add_task(work.id, "Old chore", "")?;
+    app.db_ops.set_task_due_date(done.id, Some("2000-01-01"))?;
+    app.db_ops.toggle_task_completion(done.id)?;
+    let future = app.db_ops.add_task(home.id, "Renew passport", "")?;
+    app.db_ops
+        .set_task_due_date(future.id, Some("2999-01-01"))?;
+
+    app.load_topics()?;
+    let topic = |name: &str| {
+        app.topics
+            .iter()
+            .find(|topic| topic.name == name)
+            .cloned()
+            .expect("topic should exist")
+    };
+    assert_eq!(app.topic_overdue_count(&topic("Work")), 1);
+    assert_eq!(app.topic_overdue_count(&topic("Home")), 0);
+    assert_eq!(app.topic_overdue_count(&topic("Default")), 1);
+
+    app.db_ops.toggle_task_completion(late.id)?;
+    app.load_tasks()?;
+    assert!(app.overdue_counts.is_empty());
+
+    drop(app);
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn current_task_and_topic_follow_the_selection() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("current_selection");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    assert_eq!(
+        app.current_topic().map(|topic| topic.name.as_str()),
+        Some("Default")
+    );
+    assert!(app.current_task().is_none());
+
+    app.add_task_with_details("Water plants", "")?;
+    let task = app.current_task().expect("new task should be selected");
+    assert_eq!(task.name, "Water plants");
+    assert_eq!(app.current_task_id(), Some(task.id));
+
+    app.selected = app.tasks.len();
+    assert_eq!(app.current_task_id(), None);
+    app.topics.clear();
+    assert!(app.current_topic().is_none());
+    assert!(!app.current_topic_is_special());
+    app.add_task_with_details("Nowhere to go", "")?;
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
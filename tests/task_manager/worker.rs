@@ -0,0 +1,131 @@
+use std::time::{Duration, Instant};
+
+use super::common::temp_db_path;
+use task_manager_cli::task_manager::app::App;
+
+/// Polls the worker until `done` holds, giving up after two seconds.
+fn poll_until(app: &mut App, done: impl Fn(&App) -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline {
+        app.poll_db_events();
+        if done(app) {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    false
+}
+
+#[test]
+fn worker_loads_topics_and_applies_toggles() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("db_worker");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+
+    app.add_topic("Work")?;
+    let work = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Work")
+        .expect("work topic should exist");
+    app.selected_topic = work;
+    app.load_tasks()?;
+    app.add_task_with_details("Write report", "")?;
+    app.selected_topic = 0;
+    app.load_tasks()?;
+
+    app.start_db_worker()?;
+    app.step_topic(1)?;
+    assert_eq!(app.selected_topic, work);
+    assert!(app.tasks.is_empty(), "the old topic's tasks are not kept");
+    assert!(poll_until(&mut app, |app| app
+        .tasks
+        .iter()
+        .any(|task| task.name == "Write report")));
+    assert_eq!(app.tasks.len(), 1);
+
+    app.selected = 0;
+    app.toggle_task()?;
+    assert!(
+        !app.tasks[0].completed,
+        "toggle should not block on the write"
+    );
+    assert!(poll_until(&mut app, |app| app
+        .tasks
+        .first()
+        .is_some_and(|task| task.completed)));
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn failed_topic_load_on_the_worker_returns_to_the_old_topic(
+) -> Result<(), Box<dyn std::error::Error>> {
+    use diesel::RunQueryDsl;
+
+    let db_path = temp_db_path("db_worker_rollback");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    app.add_topic("Work")?;
+    app.selected_topic = 0;
+    app.load_tasks()?;
+
+    app.start_db_worker()?;
+    diesel::sql_query("ALTER TABLE topic_settings RENAME TO topic_settings_gone")
+        .execute(&mut app.db_ops.pool.get()?)?;
+    app.step_topic(1)?;
+    assert_eq!(app.selected_topic, 1);
+    assert!(poll_until(&mut app, |app| app.selected_topic == 0));
+    assert_eq!(app.topic_switch_from, None);
+    assert!(app
+        .logs
+        .iter()
+        .any(|entry| entry.message.starts_with("Database error")));
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}
+
+#[test]
+fn task_writes_and_search_run_on_the_worker() -> Result<(), Box<dyn std::error::Error>> {
+    let db_path = temp_db_path("db_worker_writes");
+    let db_path_str = db_path.to_string_lossy().to_string();
+    let mut app = App::new(&db_path_str)?;
+    app.add_topic("Work")?;
+    app.selected_topic = app
+        .topics
+        .iter()
+        .position(|topic| topic.name == "Work")
+        .expect("work topic should exist");
+    app.load_tasks()?;
+    app.start_db_worker()?;
+    let names = |app: &App| -> Vec<String> { app.tasks.iter().map(|t| t.name.clone()).collect() };
+
+    for name in ["Alpha", "Bravo", "Charlie"] {
+        app.add_task_with_details(name, "")?;
+    }
+    assert!(app.tasks.is_empty(), "adding should not block on the write");
+    assert!(poll_until(&mut app, |app| app.tasks.len() == 3));
+
+    app.selected = 0;
+    app.rename_input = "Delta".to_string();
+    app.submit_rename_task()?;
+    assert!(poll_until(&mut app, |app| names(app)
+        == vec!["Delta", "Bravo", "Charlie"]));
+
+    app.search_query = "bravo".to_string();
+    app.run_search()?;
+    assert!(poll_until(&mut app, |app| app.search_results.len() == 1));
+    assert_eq!(app.search_results[0].0.name, "Bravo");
+
+    app.selected_tasks
+        .extend(app.tasks[1..].iter().map(|task| task.id));
+    app.delete_marked_tasks()?;
+    assert_eq!(app.tasks.len(), 3, "deleting should not block on the write");
+    assert!(poll_until(&mut app, |app| names(app) == vec!["Delta"]));
+    assert!(app.selected_tasks.is_empty());
+
+    let _ = std::fs::remove_file(db_path);
+    Ok(())
+}